@@ -2,6 +2,7 @@
 use codec::{Decode, Encode, MaxEncodedLen};
 use composable_support::collections::vec::bounded::BiBoundedVec;
 use scale_info::TypeInfo;
+use sp_runtime::DispatchResult;
 use sp_std::vec::Vec;
 
 use crate::currency::Exponent;
@@ -24,6 +25,14 @@ impl BasicAssetMetadata {
 	}
 }
 
+/// Sets the metadata of assets created by a `CurrencyFactory`.
+pub trait MutateMetadata {
+	type AssetId;
+
+	/// Fails if the asset does not exist.
+	fn set_metadata(asset_id: Self::AssetId, metadata: BasicAssetMetadata) -> DispatchResult;
+}
+
 #[derive(Decode, Encode, Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Asset<ForeignId> {
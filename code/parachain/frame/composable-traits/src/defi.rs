@@ -264,10 +264,12 @@ pub trait SellEngine<Configuration>: DeFiEngine {
 	type OrderId: OrderIdLike;
 	/// sell base asset for price given or higher
 	/// - `from_to` - account requesting sell
+	/// - `reserve_price` - price below which engine does not sell, must not be above `order` price
 	fn ask(
 		from_to: &Self::AccountId,
 		order: Sell<Self::MayBeAssetId, Self::Balance>,
 		configuration: Configuration,
+		reserve_price: Option<LiftedFixedBalance>,
 	) -> Result<Self::OrderId, DispatchError>;
 	/// take order. get not found error if order never existed or was removed.
 	/// - `take.limit` - for `sell` order it is maximal value are you to pay for `base` in `quote`
@@ -280,6 +282,10 @@ pub trait SellEngine<Configuration>: DeFiEngine {
 		order_id: Self::OrderId,
 		take: Take<Self::Balance>,
 	) -> Result<(), DispatchError>;
+	/// amount of `base` not sold yet, `None` if order was sold out or removed
+	fn remaining(order_id: Self::OrderId) -> Option<Self::Balance>;
+	/// remove order, amount of `base` not sold yet is returned to account requested sell
+	fn cancel(order_id: Self::OrderId) -> Result<(), DispatchError>;
 }
 
 pub trait DeFiComposableConfig: frame_system::Config {
@@ -338,6 +338,138 @@ where
 	pub assets: BTreeMap<AssetId, Balance>,
 }
 
+/// [`Amm`] for tests of pallets using DEX.
+#[cfg(feature = "test-utils")]
+pub mod fake {
+	use super::*;
+	use frame_support::traits::tokens::fungibles::Mutate;
+	use sp_runtime::traits::Saturating;
+	use sp_std::marker::PhantomData;
+
+	const NOT_SUPPORTED: DispatchError = DispatchError::Other("not supported by DexFake");
+
+	/// Swaps by `Price` of `quote` for one `base`, minting `quote` out of thin air.
+	/// Any pool exists, everything else is not supported.
+	pub struct DexFake<Assets, Price, AccountId>(PhantomData<(Assets, Price, AccountId)>);
+
+	impl<Assets, Price, AccountId> Amm for DexFake<Assets, Price, AccountId>
+	where
+		Assets: Mutate<AccountId>,
+		Price: Get<Assets::Balance>,
+	{
+		type AssetId = Assets::AssetId;
+		type Balance = Assets::Balance;
+		type AccountId = AccountId;
+		type PoolId = CurrencyPair<Assets::AssetId>;
+
+		fn pool_exists(_pool_id: Self::PoolId) -> bool {
+			true
+		}
+
+		fn assets(
+			_pool_id: Self::PoolId,
+		) -> Result<BTreeMap<Self::AssetId, Permill>, DispatchError> {
+			Err(NOT_SUPPORTED)
+		}
+
+		fn lp_token(_pool_id: Self::PoolId) -> Result<Self::AssetId, DispatchError> {
+			Err(NOT_SUPPORTED)
+		}
+
+		fn redeemable_assets_for_lp_tokens(
+			_pool_id: Self::PoolId,
+			_lp_amount: Self::Balance,
+			_min_expected_amounts: BTreeMap<Self::AssetId, Self::Balance>,
+		) -> Result<RedeemableAssets<Self::AssetId, Self::Balance>, DispatchError>
+		where
+			Self::AssetId: Ord,
+		{
+			Err(NOT_SUPPORTED)
+		}
+
+		fn simulate_add_liquidity(
+			_who: &Self::AccountId,
+			_pool_id: Self::PoolId,
+			_amounts: BTreeMap<Self::AssetId, Self::Balance>,
+		) -> Result<Self::Balance, DispatchError>
+		where
+			Self::AssetId: Ord,
+		{
+			Err(NOT_SUPPORTED)
+		}
+
+		fn simulate_remove_liquidity(
+			_who: &Self::AccountId,
+			_pool_id: Self::PoolId,
+			_lp_amount: Self::Balance,
+			_min_expected_amounts: BTreeMap<Self::AssetId, Self::Balance>,
+		) -> Result<RemoveLiquiditySimulationResult<Self::AssetId, Self::Balance>, DispatchError>
+		where
+			Self::AssetId: Ord,
+		{
+			Err(NOT_SUPPORTED)
+		}
+
+		fn spot_price(
+			_pool_id: Self::PoolId,
+			base_asset: AssetAmount<Self::AssetId, Self::Balance>,
+			quote_asset_id: Self::AssetId,
+		) -> Result<SwapResult<Self::AssetId, Self::Balance>, DispatchError> {
+			Ok(SwapResult::new(
+				quote_asset_id,
+				base_asset.amount.saturating_mul(Price::get()),
+				quote_asset_id,
+				Self::Balance::default(),
+			))
+		}
+
+		fn do_buy(
+			_who: &Self::AccountId,
+			_pool_id: Self::PoolId,
+			_in_asset_id: Self::AssetId,
+			_out_asset: AssetAmount<Self::AssetId, Self::Balance>,
+			_keep_alive: bool,
+		) -> Result<SwapResult<Self::AssetId, Self::Balance>, DispatchError> {
+			Err(NOT_SUPPORTED)
+		}
+
+		fn add_liquidity(
+			_who: &Self::AccountId,
+			_pool_id: Self::PoolId,
+			_assets: BTreeMap<Self::AssetId, Self::Balance>,
+			_min_mint_amount: Self::Balance,
+			_keep_alive: bool,
+		) -> Result<(), DispatchError> {
+			Err(NOT_SUPPORTED)
+		}
+
+		fn remove_liquidity(
+			_who: &Self::AccountId,
+			_pool_id: Self::PoolId,
+			_lp_amount: Self::Balance,
+			_min_receive: BTreeMap<Self::AssetId, Self::Balance>,
+		) -> Result<(), DispatchError> {
+			Err(NOT_SUPPORTED)
+		}
+
+		fn do_swap(
+			who: &Self::AccountId,
+			pool_id: Self::PoolId,
+			in_asset: AssetAmount<Self::AssetId, Self::Balance>,
+			min_receive: AssetAmount<Self::AssetId, Self::Balance>,
+			_keep_alive: bool,
+		) -> Result<SwapResult<Self::AssetId, Self::Balance>, DispatchError> {
+			let result = Self::spot_price(pool_id, in_asset, min_receive.asset_id)?;
+			if result.value.amount < min_receive.amount {
+				return Err(DispatchError::Other("slippage exceeded"))
+			}
+			Assets::burn_from(in_asset.asset_id, who, in_asset.amount)?;
+			Assets::mint_into(min_receive.asset_id, who, result.value.amount)?;
+			Ok(result)
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::dex::{Fee, FeeConfig};
@@ -1,6 +1,10 @@
 //! Traits used in the implementation of the Mosaic pallet.
 
-use frame_support::dispatch::DispatchResultWithPostInfo;
+use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::vec::Vec;
 
 /// Trait containing the business logic relevant to managing the Relayer of the Mosaic pallet.
 pub trait RelayerInterface {
@@ -45,9 +49,26 @@ pub trait RelayerInterface {
 		decay: Self::BudgetPenaltyDecayer,
 	);
 
+	/// Sets the Relayer's budget of a specific asset for _incoming_ transactions from a specific
+	/// network. This budget is used instead of the asset wide budget for that network.
+	fn set_network_budget(
+		asset_id: Self::AssetId,
+		network_id: Self::NetworkId,
+		amount: Self::Balance,
+		decay: Self::BudgetPenaltyDecayer,
+	);
+
 	/// Sets the supported networks and maximum transaction sizes accepted by the Relayer.
 	fn set_network(network_id: Self::NetworkId, network_info: Self::NetworkInfo);
 
+	/// Sets or removes the maximum transaction sizes accepted by the Relayer for a specific asset
+	/// on a specific network, overriding the network wide limits.
+	fn set_asset_network(
+		asset_id: Self::AssetId,
+		network_id: Self::NetworkId,
+		network_info: Option<Self::NetworkInfo>,
+	);
+
 	/// Sets the current Relayer configuration
 	fn set_relayer(relayer: Self::AccountId);
 
@@ -58,6 +79,7 @@ pub trait RelayerInterface {
 	/// `lock_time` blocks have expired.
 	fn timelocked_mint(
 		asset_id: Self::AssetId,
+		network_id: Self::NetworkId,
 		current_block: Self::BlockNumber,
 		to: Self::AccountId,
 		amount: Self::Balance,
@@ -105,3 +127,48 @@ pub trait Claim {
 		now: Self::BlockNumber,
 	) -> DispatchResultWithPostInfo;
 }
+
+/// A transfer of an account which is still locked in the pallet.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct PendingTransfer<AssetId, Balance, BlockNumber> {
+	pub asset_id: AssetId,
+	pub amount: Balance,
+	/// The first block at which the transfer can be claimed.
+	pub unlock_block: BlockNumber,
+}
+
+/// The pending incoming and outgoing transfers of an account.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct PendingTransfers<AssetId, Balance, BlockNumber> {
+	/// Minted transfers, claimable by the account once unlocked.
+	pub incoming: Vec<PendingTransfer<AssetId, Balance, BlockNumber>>,
+	/// Transfers not yet accepted by the Relayer, reclaimable by the account once unlocked.
+	pub outgoing: Vec<PendingTransfer<AssetId, Balance, BlockNumber>>,
+}
+
+/// The minting capacity of the Relayer for an asset.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct BudgetStatus<AssetId, NetworkId, Balance> {
+	pub asset_id: AssetId,
+	/// The network the budget is restricted to, `None` for the asset wide budget.
+	pub network_id: Option<NetworkId>,
+	pub budget: Balance,
+	/// The penalty, decayed up to the current block.
+	pub penalty: Balance,
+	/// The amount that can currently be minted, `budget - penalty`.
+	pub remaining: Balance,
+}
+
+/// The current Relayer, and the scheduled rotation if any.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct RelayerStatus<AccountId, BlockNumber> {
+	pub current: AccountId,
+	/// The account the Relayer rotates to.
+	pub next: Option<AccountId>,
+	/// The block at which the Relayer rotates to `next`.
+	pub ttl: Option<BlockNumber>,
+}
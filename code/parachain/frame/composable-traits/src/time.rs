@@ -26,6 +26,8 @@ pub const MS_PER_YEAR_NAIVE: DurationSeconds = SECONDS_PER_YEAR_NAIVE * 1000;
 pub enum TimeReleaseFunction {
 	LinearDecrease(LinearDecrease),
 	StairstepExponentialDecrease(StairstepExponentialDecrease),
+	ContinuousExponentialDecrease(ContinuousExponentialDecrease),
+	PiecewiseLinearDecrease(PiecewiseLinearDecrease),
 }
 
 impl Default for TimeReleaseFunction {
@@ -48,3 +50,22 @@ pub struct StairstepExponentialDecrease {
 	// Drop per unit of `step`.
 	pub cut: Permill,
 }
+
+/// Smooth exponential decay, without the steps of [`StairstepExponentialDecrease`].
+#[derive(Default, Decode, Encode, MaxEncodedLen, Clone, Debug, PartialEq, Eq, TypeInfo)]
+pub struct ContinuousExponentialDecrease {
+	/// Seconds it takes for the amount to halve
+	pub half_life: DurationSeconds,
+}
+
+/// Maximal number of points of a [`PiecewiseLinearDecrease`].
+pub type MaxPiecewiseLinearPoints = ConstU32<16>;
+
+/// Linear decrease between points, starting from the full amount at zero seconds.
+#[derive(Default, Decode, Encode, MaxEncodedLen, Clone, Debug, PartialEq, Eq, TypeInfo)]
+pub struct PiecewiseLinearDecrease {
+	/// Seconds after start and the part of the amount left at that time.
+	/// Seconds must be increasing and parts must not increase.
+	/// The amount stays at the last part after the last point.
+	pub points: BoundedVec<(DurationSeconds, Permill), MaxPiecewiseLinearPoints>,
+}
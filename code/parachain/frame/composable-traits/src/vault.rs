@@ -5,6 +5,8 @@ use frame_support::{
 	sp_std::{collections::btree_map::BTreeMap, fmt::Debug},
 };
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 use crate::defi::Rate;
 
@@ -28,6 +30,7 @@ pub enum FundsAvailability<Balance> {
 }
 
 #[derive(Copy, Clone, Encode, Decode, Debug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Deposit<Balance, BlockNumber> {
 	/// Indicates that the vault has deposited an amount large enough to forever be exempt from
 	/// rent payments.
@@ -52,6 +55,36 @@ impl<Balance, BlockNumber> Deposit<Balance, BlockNumber> {
 	}
 }
 
+/// Rent status of a vault, as seen at a given block.
+#[derive(Copy, Clone, Encode, Decode, Debug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RentStatus<Balance, BlockNumber> {
+	/// The deposit currently held for the vault.
+	pub deposit: Deposit<Balance, BlockNumber>,
+	/// Whether the vault has been tombstoned.
+	pub tombstoned: bool,
+	/// Number of blocks until the rent is exhausted and the vault can be tombstoned. `None` if the
+	/// vault is rent exempt or already tombstoned.
+	pub blocks_until_eviction: Option<BlockNumber>,
+	/// Earliest number of blocks until the vault can be deleted. `None` if the vault is rent
+	/// exempt.
+	pub blocks_until_deletion: Option<BlockNumber>,
+}
+
+/// Overview of the allocation & balances at a single strategy of a vault.
+#[derive(Copy, Clone, Encode, Decode, MaxEncodedLen, Default, Debug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct StrategyOverview<Balance> {
+	// The allocation of this strategy
+	pub allocation: Perquintill,
+	/// The reported balance of the strategy
+	pub balance: Balance,
+	/// Sum of all withdrawn funds.
+	pub lifetime_withdrawn: Balance,
+	/// Sum of all deposited funds.
+	pub lifetime_deposited: Balance,
+}
+
 #[derive(Clone, Encode, Decode, Default, Debug, PartialEq, Eq, TypeInfo)]
 pub struct VaultConfig<AccountId, CurrencyId>
 where
@@ -0,0 +1,574 @@
+//! IBC support for CosmWasm contracts.
+//!
+//! A contract whose code exports every IBC entrypoint is bound to the port `wasm.<address>`.
+//! [`Router`] is plugged into `pallet-ibc` and forwards the callbacks of such ports to the
+//! contract, while the packets sent by a contract go out through [`Config::IbcHandler`].
+
+use crate::{
+	runtimes::wasmi::{CosmwasmVM, CosmwasmVMError, InitialStorageMutability},
+	AccountIdOf, CodeIdToInfo, Config, EntryPoint, Error, Pallet,
+};
+use ::ibc::{
+	applications::transfer::{msgs::transfer::MsgTransfer, Amount, PrefixedCoin, PrefixedDenom},
+	core::{
+		ics04_channel::{
+			channel::{ChannelEnd, Counterparty, Order},
+			error::Error as Ics04Error,
+			msgs::acknowledgement::Acknowledgement,
+			packet::Packet,
+			Version,
+		},
+		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+		ics26_routing::context::{
+			Acknowledgement as GenericAcknowledgement, Module, ModuleId, ModuleOutputBuilder,
+			OnRecvPacketAck,
+		},
+	},
+	signer::Signer,
+	timestamp::Timestamp as IbcTimestamp,
+	Height,
+};
+use alloc::{
+	boxed::Box,
+	format,
+	string::{String, ToString},
+};
+use core::{fmt::Formatter, marker::PhantomData, str::FromStr};
+use cosmwasm_minimal_std::{
+	ibc::{
+		Ibc3ChannelOpenResponse, IbcAcknowledgement, IbcChannel, IbcChannelCloseMsg,
+		IbcChannelConnectMsg, IbcChannelOpenMsg, IbcEndpoint, IbcOrder, IbcPacket, IbcPacketAckMsg,
+		IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcTimeout, IbcTimeoutBlock,
+	},
+	Addr, Binary as CosmwasmBinary, Coin, Timestamp,
+};
+use cosmwasm_vm::{
+	executor::ibc::{
+		IbcChannelCloseInput, IbcChannelConnectInput, IbcChannelOpenInput, IbcPacketAckInput,
+		IbcPacketReceiveInput, IbcPacketTimeoutInput,
+	},
+	system::{cosmwasm_system_entrypoint, CosmwasmCallVM, CosmwasmCodeId},
+	vm::VmErrorOf,
+};
+use cosmwasm_vm_wasmi::WasmiVM;
+use frame_support::{traits::Get, weights::Weight};
+use ibc_primitives::SendPacketData;
+use ibc_trait::{CallbackWeight, IbcTrait, ModuleRouter};
+use serde::Serialize;
+use sp_std::vec::Vec;
+
+pub const MODULE_ID: &str = "PalletCosmwasm";
+pub const PORT_PREFIX: &str = "wasm.";
+
+/// Acknowledgement written when a contract fails to process a packet, following wasmd.
+pub(crate) const ACK_ERR: &[u8] = br#"{"error":"contract failed to process the packet"}"#;
+
+impl<T: Config> Pallet<T> {
+	/// Port bound by the contract, if its code is IBC capable.
+	pub(crate) fn do_contract_ibc_port(
+		contract: &AccountIdOf<T>,
+		code_id: CosmwasmCodeId,
+	) -> Option<String> {
+		CodeIdToInfo::<T>::get(code_id)
+			.filter(|info| info.ibc_capable)
+			.map(|_| format!("{}{}", PORT_PREFIX, Self::account_to_cosmwasm_addr(contract.clone())))
+	}
+
+	/// Contract bound to the port, if any.
+	pub(crate) fn do_port_contract(port_id: &str) -> Option<AccountIdOf<T>> {
+		let contract =
+			Self::cosmwasm_addr_to_account(port_id.strip_prefix(PORT_PREFIX)?.into()).ok()?;
+		let info = Self::contract_info(&contract).ok()?;
+		CodeIdToInfo::<T>::get(info.code_id)
+			.filter(|code| code.ibc_capable)
+			.map(|_| contract)
+	}
+
+	/// Call an IBC entrypoint of a contract, the contract being its own sender.
+	pub(crate) fn do_ibc_entrypoint<I>(
+		contract: AccountIdOf<T>,
+		entrypoint: EntryPoint,
+		message: Vec<u8>,
+	) -> Result<Option<CosmwasmBinary>, CosmwasmVMError<T>>
+	where
+		for<'x> WasmiVM<CosmwasmVM<'x, T>>: CosmwasmCallVM<I>,
+		for<'x> VmErrorOf<WasmiVM<CosmwasmVM<'x, T>>>: Into<CosmwasmVMError<T>>,
+	{
+		let mut shared = Self::do_create_vm_shared(
+			T::IbcCallbackGas::get(),
+			InitialStorageMutability::ReadWrite,
+		);
+		let info = Self::contract_info(&contract)?;
+		Self::do_extrinsic_dispatch(
+			&mut shared,
+			entrypoint,
+			contract.clone(),
+			contract,
+			info,
+			Default::default(),
+			|vm| cosmwasm_system_entrypoint::<I, _>(vm, &message).map_err(Into::into),
+		)
+	}
+
+	/// Send a packet from the port bound by the executing contract.
+	pub(crate) fn do_ibc_send_packet(
+		vm: &mut CosmwasmVM<T>,
+		channel_id: String,
+		data: CosmwasmBinary,
+		timeout: IbcTimeout,
+	) -> Result<(), CosmwasmVMError<T>> {
+		let port_id =
+			Self::do_contract_ibc_port(vm.contract_address.as_ref(), vm.contract_info.code_id)
+				.ok_or(Error::<T>::ContractNotIbcCapable)?;
+		let (revision_number, timeout_height) = match timeout.block() {
+			Some(IbcTimeoutBlock { revision, height }) => (Some(revision), height),
+			None => (None, 0),
+		};
+		T::IbcHandler::send_packet(SendPacketData {
+			data: data.into(),
+			revision_number,
+			timeout_height,
+			timeout_timestamp: timeout.timestamp().map(|t| t.nanos()).unwrap_or_default(),
+			port_id: port_id.into_bytes(),
+			channel_id: channel_id.into_bytes(),
+		})
+		.map_err(|e| {
+			log::debug!(target: "runtime::contracts", "do_ibc_send_packet: {:?}", e);
+			Error::<T>::IbcDispatchFailed.into()
+		})
+	}
+
+	/// Transfer funds of the executing contract over ICS-20, the contract being the sender.
+	pub(crate) fn do_ibc_transfer(
+		vm: &mut CosmwasmVM<T>,
+		channel_id: String,
+		to_address: String,
+		amount: Coin,
+		timeout: IbcTimeout,
+	) -> Result<(), CosmwasmVMError<T>> {
+		let sender = Self::account_to_cosmwasm_addr(vm.contract_address.as_ref().clone());
+		let msg = to_msg_transfer(sender, channel_id, to_address, amount, timeout)
+			.ok_or(Error::<T>::IbcDispatchFailed)?;
+		T::IbcTransfer::send_transfer(msg, None).map_err(|e| {
+			log::debug!(target: "runtime::contracts", "do_ibc_transfer: {:?}", e);
+			Error::<T>::IbcDispatchFailed.into()
+		})
+	}
+
+	/// Close a channel of the port bound by the executing contract.
+	pub(crate) fn do_ibc_close_channel(
+		vm: &mut CosmwasmVM<T>,
+		channel_id: String,
+	) -> Result<(), CosmwasmVMError<T>> {
+		let port_id =
+			Self::do_contract_ibc_port(vm.contract_address.as_ref(), vm.contract_info.code_id)
+				.ok_or(Error::<T>::ContractNotIbcCapable)?;
+		let port_id = PortId::from_str(&port_id).map_err(|_| Error::<T>::IbcDispatchFailed)?;
+		let channel_id =
+			ChannelId::from_str(&channel_id).map_err(|_| Error::<T>::IbcDispatchFailed)?;
+		T::IbcHandler::close_channel(port_id, channel_id).map_err(|e| {
+			log::debug!(target: "runtime::contracts", "do_ibc_close_channel: {:?}", e);
+			Error::<T>::IbcDispatchFailed.into()
+		})
+	}
+}
+
+/// ICS-20 transfer out of the `transfer` port, `None` if any identifier is malformed.
+pub(crate) fn to_msg_transfer(
+	sender: String,
+	channel_id: String,
+	to_address: String,
+	amount: Coin,
+	timeout: IbcTimeout,
+) -> Option<MsgTransfer<PrefixedCoin>> {
+	let (revision_number, timeout_height) = match timeout.block() {
+		Some(IbcTimeoutBlock { revision, height }) => (revision, height),
+		None => (0, 0),
+	};
+	Some(MsgTransfer {
+		source_port: PortId::transfer(),
+		source_channel: ChannelId::from_str(&channel_id).ok()?,
+		token: PrefixedCoin {
+			denom: PrefixedDenom::from_str(&amount.denom).ok()?,
+			amount: Amount::from_str(&amount.amount.to_string()).ok()?,
+		},
+		sender: Signer::from_str(&sender).ok()?,
+		receiver: Signer::from_str(&to_address).ok()?,
+		timeout_height: Height::new(revision_number, timeout_height),
+		timeout_timestamp: IbcTimestamp::from_nanoseconds(
+			timeout.timestamp().map(|t| t.nanos()).unwrap_or_default(),
+		)
+		.ok()?,
+	})
+}
+
+fn to_ibc_endpoint(port_id: &PortId, channel_id: &ChannelId) -> IbcEndpoint {
+	IbcEndpoint { port_id: port_id.to_string(), channel_id: channel_id.to_string() }
+}
+
+fn app_error(message: &str) -> Ics04Error {
+	Ics04Error::app_module(format!("[pallet-cosmwasm]: {}", message))
+}
+
+fn to_ibc_channel(
+	order: Order,
+	connection_hops: &[ConnectionId],
+	port_id: &PortId,
+	channel_id: &ChannelId,
+	counterparty: &Counterparty,
+	version: &Version,
+) -> Result<IbcChannel, Ics04Error> {
+	let order = match order {
+		Order::Unordered => IbcOrder::Unordered,
+		Order::Ordered => IbcOrder::Ordered,
+		Order::None => return Err(app_error("channel order is not supported")),
+	};
+	let counterparty_channel_id =
+		counterparty.channel_id().map(ToString::to_string).unwrap_or_default();
+	let connection_id =
+		connection_hops.get(0).ok_or_else(|| app_error("channel has no connection"))?;
+	Ok(IbcChannel::new(
+		to_ibc_endpoint(port_id, channel_id),
+		IbcEndpoint {
+			port_id: counterparty.port_id().to_string(),
+			channel_id: counterparty_channel_id,
+		},
+		order,
+		version.to_string(),
+		connection_id.to_string(),
+	))
+}
+
+fn to_ibc_channel_from_end<T: Config>(
+	port_id: &PortId,
+	channel_id: &ChannelId,
+) -> Result<IbcChannel, Ics04Error> {
+	let end: ChannelEnd = T::IbcHandler::channel_end(port_id, channel_id)
+		.map_err(|_| app_error("channel not found"))?;
+	to_ibc_channel(
+		*end.ordering(),
+		end.connection_hops(),
+		port_id,
+		channel_id,
+		end.counterparty(),
+		end.version(),
+	)
+}
+
+fn to_ibc_packet(packet: &Packet) -> IbcPacket {
+	let block = (!packet.timeout_height.is_zero()).then(|| IbcTimeoutBlock {
+		revision: packet.timeout_height.revision_number,
+		height: packet.timeout_height.revision_height,
+	});
+	let timestamp = Timestamp::from_nanos(packet.timeout_timestamp.nanoseconds());
+	let timeout = match block {
+		Some(block) if timestamp.nanos() != 0 => IbcTimeout::with_both(block, timestamp),
+		Some(block) => IbcTimeout::with_block(block),
+		None => IbcTimeout::with_timestamp(timestamp),
+	};
+	IbcPacket::new(
+		packet.data.clone(),
+		to_ibc_endpoint(&packet.source_port, &packet.source_channel),
+		to_ibc_endpoint(&packet.destination_port, &packet.destination_channel),
+		packet.sequence.into(),
+		timeout,
+	)
+}
+
+fn to_relayer_addr(relayer: &Signer) -> Addr {
+	Addr::unchecked(relayer.to_string())
+}
+
+/// Routes the IBC callbacks of the `wasm.<address>` ports to the bound contracts.
+#[derive(Clone)]
+pub struct Router<T: Config>(PhantomData<T>);
+
+impl<T: Config> Default for Router<T> {
+	fn default() -> Self {
+		Self(PhantomData::default())
+	}
+}
+
+impl<T: Config> core::fmt::Debug for Router<T> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		write!(f, "pallet-cosmwasm")
+	}
+}
+
+impl<T: Config> Router<T> {
+	fn contract(port_id: &PortId) -> Result<AccountIdOf<T>, Ics04Error> {
+		Pallet::<T>::do_port_contract(port_id.as_str())
+			.ok_or_else(|| app_error("no contract bound to the port"))
+	}
+
+	fn call<I, M: Serialize>(
+		port_id: &PortId,
+		entrypoint: EntryPoint,
+		message: &M,
+	) -> Result<Option<CosmwasmBinary>, Ics04Error>
+	where
+		for<'x> WasmiVM<CosmwasmVM<'x, T>>: CosmwasmCallVM<I>,
+		for<'x> VmErrorOf<WasmiVM<CosmwasmVM<'x, T>>>: Into<CosmwasmVMError<T>>,
+	{
+		let contract = Self::contract(port_id)?;
+		let message =
+			serde_json::to_vec(message).map_err(|_| app_error("failed to encode the message"))?;
+		Pallet::<T>::do_ibc_entrypoint::<I>(contract, entrypoint, message).map_err(|e| {
+			log::debug!(target: "runtime::contracts", "ibc callback: {:?}", e);
+			app_error("contract execution failed")
+		})
+	}
+}
+
+pub struct ContractAcknowledgement(Vec<u8>);
+
+impl AsRef<[u8]> for ContractAcknowledgement {
+	fn as_ref(&self) -> &[u8] {
+		self.0.as_slice()
+	}
+}
+
+impl GenericAcknowledgement for ContractAcknowledgement {}
+
+impl<T: Config + Send + Sync> Module for Router<T> {
+	fn on_chan_open_init(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		order: Order,
+		connection_hops: &[ConnectionId],
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty: &Counterparty,
+		version: &Version,
+	) -> Result<(), Ics04Error> {
+		let channel =
+			to_ibc_channel(order, connection_hops, port_id, channel_id, counterparty, version)?;
+		Self::call::<IbcChannelOpenInput>(
+			port_id,
+			EntryPoint::IbcChannelOpen,
+			&IbcChannelOpenMsg::new_init(channel),
+		)
+		.map(|_| ())
+	}
+
+	fn on_chan_open_try(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		order: Order,
+		connection_hops: &[ConnectionId],
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty: &Counterparty,
+		version: &Version,
+		counterparty_version: &Version,
+	) -> Result<Version, Ics04Error> {
+		let channel =
+			to_ibc_channel(order, connection_hops, port_id, channel_id, counterparty, version)?;
+		let response = Self::call::<IbcChannelOpenInput>(
+			port_id,
+			EntryPoint::IbcChannelOpen,
+			&IbcChannelOpenMsg::new_try(channel, counterparty_version.to_string()),
+		)?;
+		// A contract may override the version, otherwise the counterparty one is accepted.
+		Ok(response
+			.and_then(|data| serde_json::from_slice::<Ibc3ChannelOpenResponse>(&data).ok())
+			.map(|response| Version::new(response.version))
+			.unwrap_or_else(|| counterparty_version.clone()))
+	}
+
+	fn on_chan_open_ack(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty_version: &Version,
+	) -> Result<(), Ics04Error> {
+		let channel = to_ibc_channel_from_end::<T>(port_id, channel_id)?;
+		Self::call::<IbcChannelConnectInput<_>>(
+			port_id,
+			EntryPoint::IbcChannelConnect,
+			&IbcChannelConnectMsg::new_ack(channel, counterparty_version.to_string()),
+		)
+		.map(|_| ())
+	}
+
+	fn on_chan_open_confirm(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+	) -> Result<(), Ics04Error> {
+		let channel = to_ibc_channel_from_end::<T>(port_id, channel_id)?;
+		Self::call::<IbcChannelConnectInput<_>>(
+			port_id,
+			EntryPoint::IbcChannelConnect,
+			&IbcChannelConnectMsg::new_confirm(channel),
+		)
+		.map(|_| ())
+	}
+
+	fn on_chan_close_init(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+	) -> Result<(), Ics04Error> {
+		let channel = to_ibc_channel_from_end::<T>(port_id, channel_id)?;
+		Self::call::<IbcChannelCloseInput<_>>(
+			port_id,
+			EntryPoint::IbcChannelClose,
+			&IbcChannelCloseMsg::new_init(channel),
+		)
+		.map(|_| ())
+	}
+
+	fn on_chan_close_confirm(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+	) -> Result<(), Ics04Error> {
+		let channel = to_ibc_channel_from_end::<T>(port_id, channel_id)?;
+		Self::call::<IbcChannelCloseInput<_>>(
+			port_id,
+			EntryPoint::IbcChannelClose,
+			&IbcChannelCloseMsg::new_confirm(channel),
+		)
+		.map(|_| ())
+	}
+
+	fn on_recv_packet(
+		&self,
+		_output: &mut ModuleOutputBuilder,
+		packet: &Packet,
+		relayer: &Signer,
+	) -> OnRecvPacketAck {
+		let result = Self::call::<IbcPacketReceiveInput<_>>(
+			&packet.destination_port,
+			EntryPoint::IbcPacketReceive,
+			&IbcPacketReceiveMsg::new(to_ibc_packet(packet), to_relayer_addr(relayer)),
+		);
+		let ack: Vec<u8> = match result {
+			Ok(data) => data.map(Into::into).unwrap_or_default(),
+			// The contract changes are reverted, the failure is still acknowledged so that the
+			// sender is notified instead of waiting for the packet to time out.
+			Err(e) => {
+				log::debug!(target: "runtime::contracts", "on_recv_packet: {:?}", e);
+				ACK_ERR.to_vec()
+			},
+		};
+		let packet = packet.clone();
+		OnRecvPacketAck::Successful(
+			Box::new(ContractAcknowledgement(ack.clone())),
+			Box::new(move |_ctx| {
+				T::IbcHandler::write_acknowledgement(&packet, ack).map_err(|e| format!("{:?}", e))
+			}),
+		)
+	}
+
+	fn on_acknowledgement_packet(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		packet: &Packet,
+		acknowledgement: &Acknowledgement,
+		relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		Self::call::<IbcPacketAckInput<_>>(
+			&packet.source_port,
+			EntryPoint::IbcPacketAck,
+			&IbcPacketAckMsg::new(
+				IbcAcknowledgement::new(acknowledgement.as_ref().to_vec()),
+				to_ibc_packet(packet),
+				to_relayer_addr(relayer),
+			),
+		)
+		.map(|_| ())
+	}
+
+	fn on_timeout_packet(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		packet: &Packet,
+		relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		Self::call::<IbcPacketTimeoutInput<_>>(
+			&packet.source_port,
+			EntryPoint::IbcPacketTimeout,
+			&IbcPacketTimeoutMsg::new(to_ibc_packet(packet), to_relayer_addr(relayer)),
+		)
+		.map(|_| ())
+	}
+}
+
+impl<T: Config + Send + Sync> ModuleRouter for Router<T> {
+	fn get_route_mut(&mut self, module_id: &ModuleId) -> Option<&mut dyn Module> {
+		Self::has_route(module_id).then(|| self as &mut dyn Module)
+	}
+
+	fn has_route(module_id: &ModuleId) -> bool {
+		module_id.to_string() == MODULE_ID
+	}
+
+	fn lookup_module_by_port(port_id: &PortId) -> Option<ModuleId> {
+		Pallet::<T>::do_port_contract(port_id.as_str())
+			.and_then(|_| ModuleId::from_str(MODULE_ID).ok())
+	}
+
+	fn get_weight(port_id: &PortId) -> Option<Box<dyn CallbackWeight>> {
+		port_id
+			.as_str()
+			.starts_with(PORT_PREFIX)
+			.then(|| Box::new(WeightHandler::<T>::default()) as Box<dyn CallbackWeight>)
+	}
+}
+
+/// The callbacks are bounded by the gas given to the contract.
+pub struct WeightHandler<T: Config>(PhantomData<T>);
+
+impl<T: Config> Default for WeightHandler<T> {
+	fn default() -> Self {
+		Self(PhantomData::default())
+	}
+}
+
+impl<T: Config> CallbackWeight for WeightHandler<T> {
+	fn on_chan_open_init(&self) -> Weight {
+		T::IbcCallbackGas::get()
+	}
+
+	fn on_chan_open_try(&self) -> Weight {
+		T::IbcCallbackGas::get()
+	}
+
+	fn on_chan_open_ack(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Weight {
+		T::IbcCallbackGas::get()
+	}
+
+	fn on_chan_open_confirm(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Weight {
+		T::IbcCallbackGas::get()
+	}
+
+	fn on_chan_close_init(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Weight {
+		T::IbcCallbackGas::get()
+	}
+
+	fn on_chan_close_confirm(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Weight {
+		T::IbcCallbackGas::get()
+	}
+
+	fn on_recv_packet(&self, _packet: &Packet) -> Weight {
+		T::IbcCallbackGas::get()
+	}
+
+	fn on_acknowledgement_packet(
+		&self,
+		_packet: &Packet,
+		_acknowledgement: &Acknowledgement,
+	) -> Weight {
+		T::IbcCallbackGas::get()
+	}
+
+	fn on_timeout_packet(&self, _packet: &Packet) -> Weight {
+		T::IbcCallbackGas::get()
+	}
+}
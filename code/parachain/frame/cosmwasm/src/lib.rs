@@ -39,7 +39,9 @@ extern crate alloc;
 
 pub use pallet::*;
 
+pub mod ibc;
 pub mod instrument;
+pub mod migrations;
 pub mod runtimes;
 pub mod types;
 pub mod weights;
@@ -92,8 +94,13 @@ pub mod pallet {
 	pub use cosmwasm_minimal_std::{QueryRequest, QueryResponse};
 	use cosmwasm_vm::{
 		executor::{
-			cosmwasm_call, AllocateInput, AsFunctionName, DeallocateInput, ExecuteInput,
-			InstantiateInput, MigrateInput, QueryInput, ReplyInput,
+			cosmwasm_call,
+			ibc::{
+				IbcChannelCloseInput, IbcChannelConnectInput, IbcChannelOpenInput,
+				IbcPacketAckInput, IbcPacketReceiveInput, IbcPacketTimeoutInput,
+			},
+			AllocateInput, AsFunctionName, DeallocateInput, ExecuteInput, InstantiateInput,
+			MigrateInput, QueryInput, ReplyInput,
 		},
 		memory::PointerOf,
 		system::{cosmwasm_system_query, CosmwasmCodeId, CosmwasmContractMeta},
@@ -111,7 +118,10 @@ pub mod pallet {
 		},
 		transactional, BoundedBTreeMap, PalletId, StorageHasher, Twox64Concat,
 	};
-	use frame_system::{ensure_signed, pallet_prelude::OriginFor};
+	use frame_system::{
+		ensure_signed,
+		pallet_prelude::{BlockNumberFor, OriginFor},
+	};
 	use sp_core::{crypto::UncheckedFrom, ecdsa, ed25519};
 	use sp_runtime::traits::{Convert, Hash, MaybeDisplay, SaturatedConversion};
 	use sp_std::vec::Vec;
@@ -149,6 +159,12 @@ pub mod pallet {
 		Reply,
 		Sudo,
 		Query,
+		IbcChannelOpen,
+		IbcChannelConnect,
+		IbcChannelClose,
+		IbcPacketReceive,
+		IbcPacketAck,
+		IbcPacketTimeout,
 	}
 
 	#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
@@ -228,6 +244,8 @@ pub mod pallet {
 		IteratorIdOverflow,
 		IteratorNotFound,
 		NotAuthorized,
+		ContractNotIbcCapable,
+		IbcDispatchFailed,
 	}
 
 	#[pallet::config]
@@ -352,14 +370,35 @@ pub mod pallet {
 		/// Source of time.
 		type UnixTime: UnixTime;
 
+		/// Handler used to send the packets of IBC capable contracts.
+		type IbcHandler: ibc_trait::IbcTrait;
+
+		/// Sends the ICS-20 transfers of contracts, applying the checks of the transfer
+		/// application.
+		type IbcTransfer: ibc_trait::IbcTransfer;
+
+		/// Gas available to a contract while executing an IBC callback.
+		#[pallet::constant]
+		type IbcCallbackGas: Get<u64>;
+
 		/// Weight implementation.
 		type WeightInfo: WeightInfo;
 	}
 
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::migrate_to_v1::<T>()
+		}
+	}
+
 	/// A mapping from an original code id to the original code, untouched by instrumentation.
 	#[pallet::storage]
 	pub(crate) type PristineCode<T: Config> =
@@ -665,6 +704,7 @@ pub mod pallet {
 
 		/// Wrapper around [`Pallet::<T>::cosmwasm_call`] for extrinsics.
 		/// It's purpose is converting the input and emit events based on the result.
+		/// The data returned by the contract is forwarded to the caller.
 		pub(crate) fn do_extrinsic_dispatch<F>(
 			shared: &mut CosmwasmVMShared,
 			entrypoint: EntryPoint,
@@ -673,7 +713,7 @@ pub mod pallet {
 			info: ContractInfoOf<T>,
 			funds: FundsOf<T>,
 			call: F,
-		) -> Result<(), CosmwasmVMError<T>>
+		) -> Result<Option<CosmwasmBinary>, CosmwasmVMError<T>>
 		where
 			F: for<'x> FnOnce(
 				&'x mut WasmiVM<CosmwasmVM<'x, T>>,
@@ -703,8 +743,9 @@ pub mod pallet {
 					Self::deposit_event(Event::<T>::Executed {
 						contract,
 						entrypoint,
-						data: data.map(Into::into),
+						data: data.clone().map(Into::into),
 					});
+					data
 				},
 			)
 		}
@@ -898,6 +939,38 @@ pub mod pallet {
 				// extern "C" fn reply(env_ptr: u32, msg_ptr: u32) -> u32;
 				&[parity_wasm::elements::ValueType::I32, parity_wasm::elements::ValueType::I32],
 			),
+			// IBC exports, a contract exporting all of them is bound to a port.
+			// extern "C" fn ibc_*(env_ptr: u32, msg_ptr: u32) -> u32;
+			(
+				ExportRequirement::Optional,
+				IbcChannelOpenInput::NAME,
+				&[parity_wasm::elements::ValueType::I32, parity_wasm::elements::ValueType::I32],
+			),
+			(
+				ExportRequirement::Optional,
+				IbcChannelConnectInput::<VmMessageCustomOf<CosmwasmVM<T>>>::NAME,
+				&[parity_wasm::elements::ValueType::I32, parity_wasm::elements::ValueType::I32],
+			),
+			(
+				ExportRequirement::Optional,
+				IbcChannelCloseInput::<VmMessageCustomOf<CosmwasmVM<T>>>::NAME,
+				&[parity_wasm::elements::ValueType::I32, parity_wasm::elements::ValueType::I32],
+			),
+			(
+				ExportRequirement::Optional,
+				IbcPacketReceiveInput::<VmMessageCustomOf<CosmwasmVM<T>>>::NAME,
+				&[parity_wasm::elements::ValueType::I32, parity_wasm::elements::ValueType::I32],
+			),
+			(
+				ExportRequirement::Optional,
+				IbcPacketAckInput::<VmMessageCustomOf<CosmwasmVM<T>>>::NAME,
+				&[parity_wasm::elements::ValueType::I32, parity_wasm::elements::ValueType::I32],
+			),
+			(
+				ExportRequirement::Optional,
+				IbcPacketTimeoutInput::<VmMessageCustomOf<CosmwasmVM<T>>>::NAME,
+				&[parity_wasm::elements::ValueType::I32, parity_wasm::elements::ValueType::I32],
+			),
 		];
 
 		/// Default module from where a CosmWasm import functions.
@@ -992,6 +1065,15 @@ pub mod pallet {
 			})
 		}
 
+		/// Whether the module exports every IBC entrypoint.
+		pub(crate) fn do_check_ibc_capability(module: &parity_wasm::elements::Module) -> bool {
+			let exports = module.export_section().map(|es| es.entries()).unwrap_or(&[]);
+			Self::V1_EXPORTS
+				.iter()
+				.filter(|(_, name, _)| name.starts_with("ibc_"))
+				.all(|(_, name, _)| exports.iter().any(|export| &export.field() == name))
+		}
+
 		pub(crate) fn do_load_module(
 			code: &ContractCodeOf<T>,
 		) -> Result<parity_wasm::elements::Module, Error<T>> {
@@ -1010,6 +1092,7 @@ pub mod pallet {
 			T::NativeAsset::reserve(who, deposit.saturated_into())
 				.map_err(|_| Error::<T>::NotEnoughFundsForUpload)?;
 			let module = Self::do_load_module(&code)?;
+			let ibc_capable = Self::do_check_ibc_capability(&module);
 			let instrumented_code = Self::do_instrument_code(module)?;
 			let code_id = CurrentCodeId::<T>::increment()?;
 			CodeHashToId::<T>::insert(code_hash, code_id);
@@ -1022,6 +1105,7 @@ pub mod pallet {
 					pristine_code_hash: code_hash,
 					instrumentation_version: Self::INSTRUMENTATION_VERSION,
 					refcount: 0,
+					ibc_capable,
 				},
 			);
 			Self::deposit_event(Event::<T>::Uploaded { code_hash, code_id });
@@ -1495,8 +1579,7 @@ pub mod pallet {
 				creator: CosmwasmAccount::<T>::new(info.instantiator.clone()).into(),
 				admin: info.admin.map(|admin| CosmwasmAccount::<T>::new(admin).into()),
 				pinned,
-				// TODO(hussein-aitlahcen): IBC
-				ibc_port: None,
+				ibc_port: Self::do_contract_ibc_port(&address, code_id),
 			})
 		}
 
@@ -0,0 +1,44 @@
+use crate::{pallet::*, types::CodeInfo};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{Get, StorageVersion},
+	weights::Weight,
+};
+
+/// [`CodeInfo`] layout before it tracked `ibc_capable`
+#[derive(Encode, Decode)]
+pub struct CodeInfoV0<AccountId, Hash> {
+	pub creator: AccountId,
+	pub pristine_code_hash: Hash,
+	pub instrumentation_version: u16,
+	pub refcount: u32,
+}
+
+/// Adds `ibc_capable` to stored codes, inspecting the exports of their pristine code.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if StorageVersion::get::<Pallet<T>>() >= 1 {
+		return T::DbWeight::get().reads(1)
+	}
+
+	let mut translated = 0_u64;
+	CodeIdToInfo::<T>::translate::<CodeInfoV0<AccountIdOf<T>, CodeHashOf<T>>, _>(
+		|code_id, info| {
+			translated += 1;
+			let ibc_capable = PristineCode::<T>::get(code_id)
+				.and_then(|code| Pallet::<T>::do_load_module(&code).ok())
+				.map(|module| Pallet::<T>::do_check_ibc_capability(&module))
+				.unwrap_or(false);
+			Some(CodeInfo {
+				creator: info.creator,
+				pristine_code_hash: info.pristine_code_hash,
+				instrumentation_version: info.instrumentation_version,
+				refcount: info.refcount,
+				ibc_capable,
+			})
+		},
+	);
+	StorageVersion::new(1).put::<Pallet<T>>();
+	log::info!("migrated {} cosmwasm codes to v1", translated);
+
+	T::DbWeight::get().reads_writes(translated.saturating_mul(2) + 1, translated + 1)
+}
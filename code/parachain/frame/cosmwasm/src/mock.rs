@@ -1,5 +1,15 @@
 use crate::*;
 
+use ::ibc::{
+	applications::transfer::{msgs::transfer::MsgTransfer, PrefixedCoin},
+	core::{
+		ics04_channel::{
+			channel::ChannelEnd, msgs::acknowledgement::Acknowledgement, packet::Packet,
+		},
+		ics24_host::identifier::{ChannelId, PortId},
+		ics26_routing::context::ModuleOutputBuilder,
+	},
+};
 use composable_traits::{
 	currency::{CurrencyFactory, RangeId},
 	xcm::assets::XcmAssetLocation,
@@ -218,6 +228,106 @@ parameter_types! {
 	pub const CodeStorageByteDeposit: u32 = 1;
 	pub const ContractStorageByteReadPrice: u32 = 1;
 	pub const ContractStorageByteWritePrice: u32 = 1;
+	pub const IbcCallbackGas: u64 = 100_000_000;
+}
+
+thread_local! {
+	/// Acknowledgements written through [`MockIbc`].
+	pub static ACKNOWLEDGEMENTS: core::cell::RefCell<Vec<Vec<u8>>> = Default::default();
+	/// ICS-20 transfers sent through [`MockIbc`].
+	pub static TRANSFERS: core::cell::RefCell<Vec<MsgTransfer<PrefixedCoin>>> = Default::default();
+	/// Channels closed through [`MockIbc`].
+	pub static CLOSED_CHANNELS: core::cell::RefCell<Vec<(PortId, ChannelId)>> = Default::default();
+}
+
+/// Records what is dispatched to IBC, channels are unknown to it.
+pub struct MockIbc;
+impl ibc_trait::IbcTransfer for MockIbc {
+	fn send_transfer(
+		msg: MsgTransfer<PrefixedCoin>,
+		_: Option<alloc::string::String>,
+	) -> Result<(), ibc_trait::Error> {
+		TRANSFERS.with(|transfers| transfers.borrow_mut().push(msg));
+		Ok(())
+	}
+}
+
+impl ibc_trait::IbcTrait for MockIbc {
+	fn client_revision_number(_: Vec<u8>, _: Vec<u8>) -> Result<u64, ibc_trait::Error> {
+		Err(ibc_trait::Error::Other)
+	}
+
+	fn send_packet(_: ibc_primitives::SendPacketData) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::SendPacketError)
+	}
+
+	fn open_channel(_: PortId, _: ChannelEnd) -> Result<ChannelId, ibc_trait::Error> {
+		Err(ibc_trait::Error::ChannelInitError)
+	}
+
+	fn close_channel(port_id: PortId, channel_id: ChannelId) -> Result<(), ibc_trait::Error> {
+		CLOSED_CHANNELS.with(|closed| closed.borrow_mut().push((port_id, channel_id)));
+		Ok(())
+	}
+
+	fn channel_end(_: &PortId, _: &ChannelId) -> Result<ChannelEnd, ibc_trait::Error> {
+		Err(ibc_trait::Error::ChannelOrPortError)
+	}
+
+	fn next_sequence_send(_: &PortId, _: &ChannelId) -> Result<u64, ibc_trait::Error> {
+		Ok(1)
+	}
+
+	fn write_acknowledgement(_: &Packet, ack: Vec<u8>) -> Result<(), ibc_trait::Error> {
+		ACKNOWLEDGEMENTS.with(|acks| acks.borrow_mut().push(ack));
+		Ok(())
+	}
+
+	fn send_transfer(
+		_: MsgTransfer<PrefixedCoin>,
+		_: Option<alloc::string::String>,
+	) -> Result<(), ibc_trait::Error> {
+		// contracts transfer through `IbcTransfer`
+		Err(ibc_trait::Error::SendTransferError)
+	}
+
+	fn on_receive_packet(_: &mut ModuleOutputBuilder, _: &Packet) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::ReceivePacketError)
+	}
+
+	fn on_ack_packet(
+		_: &mut ModuleOutputBuilder,
+		_: &Packet,
+		_: &Acknowledgement,
+	) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::AcknowledgementError)
+	}
+
+	fn on_timeout_packet(_: &mut ModuleOutputBuilder, _: &Packet) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::TimeoutError)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_client() -> Result<::ibc::core::ics24_host::identifier::ClientId, ibc_trait::Error> {
+		Err(ibc_trait::Error::ClientIdError)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_connection(
+		_: ::ibc::core::ics24_host::identifier::ClientId,
+		_: ::ibc::core::ics24_host::identifier::ConnectionId,
+	) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::ConnectionIdError)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_channel(
+		_: ::ibc::core::ics24_host::identifier::PortId,
+		_: ::ibc::core::ics24_host::identifier::ChannelId,
+		_: ::ibc::core::ics24_host::identifier::ConnectionId,
+	) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::ChannelInitError)
+	}
 }
 
 impl Config for Test {
@@ -250,6 +360,9 @@ impl Config for Test {
 	type UnixTime = Timestamp;
 	// TODO: proper weights
 	type WeightInfo = ();
+	type IbcHandler = MockIbc;
+	type IbcTransfer = MockIbc;
+	type IbcCallbackGas = IbcCallbackGas;
 }
 
 // Build genesis storage according to the mock runtime.
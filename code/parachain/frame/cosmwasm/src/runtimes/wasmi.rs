@@ -3,7 +3,9 @@ use crate::{
 	runtimes::abstraction::GasOutcome, weights::WeightInfo, Config, ContractInfoOf, Pallet,
 };
 use alloc::string::String;
-use cosmwasm_minimal_std::{Coin, ContractInfoResponse, Empty, Env, MessageInfo};
+use cosmwasm_minimal_std::{
+	ibc::IbcTimeout, Binary, Coin, ContractInfoResponse, Empty, Env, MessageInfo,
+};
 use cosmwasm_vm::{
 	executor::ExecutorError,
 	has::Has,
@@ -436,6 +438,32 @@ impl<'a, T: Config> VMBase for CosmwasmVM<'a, T> {
 		Err(CosmwasmVMError::Unsupported)
 	}
 
+	fn ibc_transfer(
+		&mut self,
+		channel_id: String,
+		to_address: String,
+		amount: Coin,
+		timeout: IbcTimeout,
+	) -> Result<(), Self::Error> {
+		log::debug!(target: "runtime::contracts", "ibc_transfer");
+		Pallet::<T>::do_ibc_transfer(self, channel_id, to_address, amount, timeout)
+	}
+
+	fn ibc_send_packet(
+		&mut self,
+		channel_id: String,
+		data: Binary,
+		timeout: IbcTimeout,
+	) -> Result<(), Self::Error> {
+		log::debug!(target: "runtime::contracts", "ibc_send_packet");
+		Pallet::<T>::do_ibc_send_packet(self, channel_id, data, timeout)
+	}
+
+	fn ibc_close_channel(&mut self, channel_id: String) -> Result<(), Self::Error> {
+		log::debug!(target: "runtime::contracts", "ibc_close_channel");
+		Pallet::<T>::do_ibc_close_channel(self, channel_id)
+	}
+
 	fn query_info(&mut self, address: Self::Address) -> Result<ContractInfoResponse, Self::Error> {
 		log::debug!(target: "runtime::contracts", "query_info");
 		Pallet::<T>::do_query_info(self, address.into_inner())
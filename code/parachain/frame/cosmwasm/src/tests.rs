@@ -1,6 +1,312 @@
-use crate::mock::*;
+use crate::{
+	ibc::{to_msg_transfer, Router, ACK_ERR},
+	migrations::CodeInfoV0,
+	mock::*,
+	types::{CodeInfo, ContractInfo},
+	CodeIdToInfo, ContractToInfo, Pallet, PristineCode,
+};
+use ::ibc::{
+	core::{
+		ics04_channel::{
+			channel::{Counterparty, Order},
+			packet::Packet,
+			Version,
+		},
+		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+		ics26_routing::context::{AsAnyMut, Module, ModuleOutputBuilder, OnRecvPacketAck},
+	},
+	signer::Signer,
+	timestamp::Timestamp as IbcTimestamp,
+	Height,
+};
+use core::str::FromStr;
+use cosmwasm_minimal_std::{
+	ibc::{IbcTimeout, IbcTimeoutBlock},
+	Coin, Timestamp,
+};
+use frame_support::traits::{Hooks, StorageVersion};
+use ibc_trait::ModuleRouter;
+use parity_wasm::{
+	builder,
+	elements::{Instruction, Instructions},
+};
+use sp_core::H256;
+use sp_runtime::AccountId32;
+
+const IBC_EXPORTS: [&str; 6] = [
+	"ibc_channel_open",
+	"ibc_channel_connect",
+	"ibc_channel_close",
+	"ibc_packet_receive",
+	"ibc_packet_ack",
+	"ibc_packet_timeout",
+];
+
+const CONTRACT: AccountId32 = AccountId32::new([7; 32]);
+
+fn code_exporting(names: &[&str]) -> Vec<u8> {
+	let module = names
+		.iter()
+		.enumerate()
+		.fold(builder::module(), |module, (index, name)| {
+			module
+				.function()
+				.signature()
+				.build()
+				.body()
+				.with_instructions(Instructions::new(vec![Instruction::End]))
+				.build()
+				.build()
+				.export()
+				.field(name)
+				.internal()
+				.func(index as u32)
+				.build()
+		})
+		.build();
+	parity_wasm::serialize(module).unwrap()
+}
+
+fn insert_contract(ibc_capable: bool) {
+	CodeIdToInfo::<Test>::insert(
+		1,
+		CodeInfo {
+			creator: CONTRACT,
+			pristine_code_hash: H256::zero(),
+			instrumentation_version: 1,
+			refcount: 1,
+			ibc_capable,
+		},
+	);
+	ContractToInfo::<Test>::insert(
+		CONTRACT,
+		ContractInfo {
+			code_id: 1,
+			trie_id: Default::default(),
+			instantiator: CONTRACT,
+			admin: None,
+			label: Default::default(),
+		},
+	);
+}
+
+fn contract_port() -> PortId {
+	PortId::from_str(&format!("wasm.0x{}", hex::encode(CONTRACT))).unwrap()
+}
+
+fn packet(destination_port: PortId) -> Packet {
+	Packet {
+		sequence: 1u64.into(),
+		source_port: PortId::transfer(),
+		source_channel: ChannelId::new(0),
+		destination_port,
+		destination_channel: ChannelId::new(1),
+		data: b"{}".to_vec(),
+		timeout_height: Height::new(0, 100),
+		timeout_timestamp: IbcTimestamp::none(),
+	}
+}
 
 #[test]
 fn works() {
 	new_test_ext().execute_with(|| {})
 }
+
+#[test]
+fn ibc_capability_requires_every_ibc_export() {
+	new_test_ext().execute_with(|| {
+		let code = code_exporting(&IBC_EXPORTS).try_into().unwrap();
+		let module = Pallet::<Test>::do_load_module(&code).unwrap();
+		assert!(Pallet::<Test>::do_check_ibc_capability(&module));
+
+		let code = code_exporting(&IBC_EXPORTS[..5]).try_into().unwrap();
+		let module = Pallet::<Test>::do_load_module(&code).unwrap();
+		assert!(!Pallet::<Test>::do_check_ibc_capability(&module));
+	})
+}
+
+#[test]
+fn migrate_codes_without_ibc_capability() {
+	new_test_ext().execute_with(|| {
+		let legacy = |refcount| CodeInfoV0 {
+			creator: CONTRACT,
+			pristine_code_hash: H256::zero(),
+			instrumentation_version: 1,
+			refcount,
+		};
+		frame_support::storage::unhashed::put(&CodeIdToInfo::<Test>::hashed_key_for(1), &legacy(1));
+		frame_support::storage::unhashed::put(&CodeIdToInfo::<Test>::hashed_key_for(2), &legacy(2));
+		PristineCode::<Test>::insert(
+			1,
+			frame_support::BoundedVec::try_from(code_exporting(&IBC_EXPORTS)).unwrap(),
+		);
+		PristineCode::<Test>::insert(
+			2,
+			frame_support::BoundedVec::try_from(code_exporting(&[])).unwrap(),
+		);
+		StorageVersion::new(0).put::<Pallet<Test>>();
+
+		Pallet::<Test>::on_runtime_upgrade();
+
+		let info = CodeIdToInfo::<Test>::get(1).unwrap();
+		assert_eq!(info.refcount, 1);
+		assert!(info.ibc_capable);
+		let info = CodeIdToInfo::<Test>::get(2).unwrap();
+		assert_eq!(info.refcount, 2);
+		assert!(!info.ibc_capable);
+		assert_eq!(StorageVersion::get::<Pallet<Test>>(), crate::pallet::STORAGE_VERSION);
+	})
+}
+
+#[test]
+fn only_ibc_capable_contracts_are_bound_to_a_port() {
+	new_test_ext().execute_with(|| {
+		assert!(Router::<Test>::lookup_module_by_port(&contract_port()).is_none());
+
+		insert_contract(false);
+		assert!(Router::<Test>::lookup_module_by_port(&contract_port()).is_none());
+		assert_eq!(Pallet::<Test>::do_contract_ibc_port(&CONTRACT, 1), None);
+
+		insert_contract(true);
+		assert!(Router::<Test>::lookup_module_by_port(&contract_port()).is_some());
+		assert_eq!(
+			Pallet::<Test>::do_contract_ibc_port(&CONTRACT, 1),
+			Some(contract_port().to_string())
+		);
+		assert!(Router::<Test>::lookup_module_by_port(&PortId::transfer()).is_none());
+	})
+}
+
+#[test]
+fn channel_callbacks_fail_without_bound_contract() {
+	new_test_ext().execute_with(|| {
+		let mut router = Router::<Test>::default();
+		let mut output = ModuleOutputBuilder::new();
+		let port_id = contract_port();
+		let channel_id = ChannelId::new(0);
+		let counterparty = Counterparty::new(PortId::transfer(), None);
+		let version = Version::new("ics20-1".to_string());
+		let hops = [ConnectionId::new(0)];
+
+		assert!(router
+			.on_chan_open_init(
+				&mut output,
+				Order::Unordered,
+				&hops,
+				&port_id,
+				&channel_id,
+				&counterparty,
+				&version,
+			)
+			.is_err());
+		assert!(router
+			.on_chan_open_try(
+				&mut output,
+				Order::Unordered,
+				&hops,
+				&port_id,
+				&channel_id,
+				&counterparty,
+				&version,
+				&version,
+			)
+			.is_err());
+		assert!(router.on_chan_open_ack(&mut output, &port_id, &channel_id, &version).is_err());
+		assert!(router.on_chan_open_confirm(&mut output, &port_id, &channel_id).is_err());
+		assert!(router.on_chan_close_init(&mut output, &port_id, &channel_id).is_err());
+		assert!(router.on_chan_close_confirm(&mut output, &port_id, &channel_id).is_err());
+	})
+}
+
+#[test]
+fn channel_order_none_is_rejected() {
+	new_test_ext().execute_with(|| {
+		insert_contract(true);
+		let mut output = ModuleOutputBuilder::new();
+		assert!(Router::<Test>::default()
+			.on_chan_open_init(
+				&mut output,
+				Order::None,
+				&[ConnectionId::new(0)],
+				&contract_port(),
+				&ChannelId::new(0),
+				&Counterparty::new(PortId::transfer(), None),
+				&Version::new("ics20-1".to_string()),
+			)
+			.is_err());
+	})
+}
+
+#[test]
+fn failed_packet_receive_is_acknowledged_as_failed() {
+	new_test_ext().execute_with(|| {
+		let mut router = Router::<Test>::default();
+		let mut output = ModuleOutputBuilder::new();
+		let relayer = Signer::from_str("relayer").unwrap();
+
+		match router.on_recv_packet(&mut output, &packet(contract_port()), &relayer) {
+			OnRecvPacketAck::Successful(ack, write_fn) => {
+				assert_eq!(ack.as_ref(), ACK_ERR);
+				write_fn(router.as_any_mut()).unwrap();
+			},
+			_ => panic!("failed packets are acknowledged"),
+		}
+		ACKNOWLEDGEMENTS.with(|acks| {
+			assert_eq!(
+				acks.borrow().as_slice(),
+				[br#"{"error":"contract failed to process the packet"}"#.to_vec()]
+			)
+		});
+	})
+}
+
+#[test]
+fn packet_callbacks_fail_without_bound_contract() {
+	new_test_ext().execute_with(|| {
+		let mut router = Router::<Test>::default();
+		let mut output = ModuleOutputBuilder::new();
+		let relayer = Signer::from_str("relayer").unwrap();
+		let mut packet = packet(PortId::transfer());
+		packet.source_port = contract_port();
+
+		assert!(router
+			.on_acknowledgement_packet(&mut output, &packet, &b"{}".to_vec().into(), &relayer)
+			.is_err());
+		assert!(router.on_timeout_packet(&mut output, &packet, &relayer).is_err());
+	})
+}
+
+#[test]
+fn contract_transfer_is_sent_from_the_contract() {
+	let sender = format!("0x{}", hex::encode(CONTRACT));
+	let timeout = IbcTimeout::with_both(
+		IbcTimeoutBlock { revision: 1, height: 100 },
+		Timestamp::from_nanos(1_000),
+	);
+	let msg = to_msg_transfer(
+		sender.clone(),
+		"channel-0".to_string(),
+		"bob".to_string(),
+		Coin { denom: "transfer/channel-0/uatom".to_string(), amount: 42u128.into() },
+		timeout,
+	)
+	.unwrap();
+
+	assert_eq!(msg.source_port, PortId::transfer());
+	assert_eq!(msg.source_channel, ChannelId::new(0));
+	assert_eq!(msg.sender.to_string(), sender);
+	assert_eq!(msg.receiver.to_string(), "bob");
+	assert_eq!(msg.token.denom.to_string(), "transfer/channel-0/uatom");
+	assert_eq!(msg.token.amount.to_string(), "42");
+	assert_eq!(msg.timeout_height, Height::new(1, 100));
+	assert_eq!(msg.timeout_timestamp.nanoseconds(), 1_000);
+
+	assert!(to_msg_transfer(
+		sender,
+		"not a channel".to_string(),
+		"bob".to_string(),
+		Coin { denom: "1".to_string(), amount: 42u128.into() },
+		IbcTimeout::with_timestamp(Timestamp::from_nanos(1_000)),
+	)
+	.is_none());
+}
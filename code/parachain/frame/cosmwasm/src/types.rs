@@ -13,6 +13,8 @@ pub struct CodeInfo<AccountId, Hash> {
 	pub instrumentation_version: u16,
 	/// Number of contract referencing this code.
 	pub refcount: u32,
+	/// Whether the code exports the IBC entrypoints.
+	pub ibc_capable: bool,
 }
 
 /// Contract metadata.
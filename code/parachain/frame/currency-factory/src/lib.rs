@@ -54,7 +54,7 @@ pub mod pallet {
 		weights::WeightInfo,
 	};
 	use composable_traits::{
-		assets::BasicAssetMetadata,
+		assets::{BasicAssetMetadata, MutateMetadata},
 		currency::{
 			AssetExistentialDepositInspect, AssetIdLike, BalanceLike, CurrencyFactory, Exponent,
 			LocalAssets,
@@ -160,11 +160,20 @@ pub mod pallet {
 			metadata: BasicAssetMetadata,
 		) -> DispatchResultWithPostInfo {
 			T::AddOrigin::ensure_origin(origin)?;
+			<Self as MutateMetadata>::set_metadata(asset_id, metadata)?;
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> MutateMetadata for Pallet<T> {
+		type AssetId = T::AssetId;
+
+		fn set_metadata(asset_id: T::AssetId, metadata: BasicAssetMetadata) -> DispatchResult {
 			if AssetEd::<T>::get(asset_id).is_some() {
 				// note: if will decide to build route on symbol, than better to make second map
 				// from symbol to asset to check unique
 				AssetMetadata::<T>::insert(asset_id, metadata);
-				Ok(().into())
+				Ok(())
 			} else {
 				Err(Error::<T>::AssetNotFound.into())
 			}
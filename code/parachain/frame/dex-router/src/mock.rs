@@ -178,6 +178,8 @@ parameter_types! {
 	pub const PbloStakeFinancialNftCollectionId: CurrencyId = 1002;
 	// TODO(benluelo): Use a better value here?
 	pub const TreasuryAccountId: AccountId = 123_456_789_u128;
+	pub const StakingRewardsGaugeEpochLength: BlockNumber = 100;
+	pub const MaxStakingRewardsGauges: u32 = 10;
 }
 
 impl pallet_staking_rewards::Config for Test {
@@ -205,6 +207,8 @@ impl pallet_staking_rewards::Config for Test {
 	type LockId = StakingRewardsLockId;
 	type TreasuryAccount = TreasuryAccountId;
 	type ExistentialDeposits = ExistentialDeposits;
+	type GaugeEpochLength = StakingRewardsGaugeEpochLength;
+	type MaxGauges = MaxStakingRewardsGauges;
 }
 
 parameter_types! {
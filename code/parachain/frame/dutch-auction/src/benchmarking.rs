@@ -86,7 +86,8 @@ benchmarks! {
 		}: _(
 			caller,
 			sell,
-			<_>::default()
+			<_>::default(),
+			None
 		)
 	take {
 		let sell = sell_identity::<T>();
@@ -96,7 +97,7 @@ benchmarks! {
 		mint_native_tokens::<T>(&account_id);
 		<T as pallet::Config>::MultiCurrency::mint_into(sell.pair.base, &account_id, amount).unwrap();
 		<T as pallet::Config>::MultiCurrency::mint_into(sell.pair.quote, &account_id, amount).unwrap();
-		DutchAuction::<T>::ask(caller.clone().into(), sell, <_>::default()).unwrap();
+		DutchAuction::<T>::ask(caller.clone().into(), sell, <_>::default(), None).unwrap();
 		let order_id = OrdersIndex::<T>::get();
 		let take_order = take_identity::<T>();
 		DutchAuction::<T>::take(caller.clone().into(), order_id, take_order.clone()).unwrap();
@@ -112,7 +113,7 @@ benchmarks! {
 		let amount: BalanceOf<T> = 1_000_000_000_000_u64.into();
 		mint_native_tokens::<T>(&account_id);
 		<T as pallet::Config>::MultiCurrency::mint_into(sell.pair.base, &account_id, amount).unwrap();
-		DutchAuction::<T>::ask(caller.clone().into(), sell, <_>::default()).unwrap();
+		DutchAuction::<T>::ask(caller.clone().into(), sell, <_>::default(), None).unwrap();
 		let order_id = OrdersIndex::<T>::get();
 		}: _(
 			caller,
@@ -145,7 +146,7 @@ benchmarks! {
 		mint_native_tokens::<T>(&account_id);
 		<T as pallet::Config>::MultiCurrency::mint_into(sell.pair.base, &account_id, amount).unwrap();
 		<T as pallet::Config>::MultiCurrency::mint_into(sell.pair.quote, &account_id, amount).unwrap();
-		DutchAuction::<T>::ask(caller.clone().into(), sell, <_>::default()).unwrap();
+		DutchAuction::<T>::ask(caller.clone().into(), sell, <_>::default(), None).unwrap();
 		let order_id = OrdersIndex::<T>::get();
 		let take_order = take_identity::<T>();
 		DutchAuction::<T>::take(caller.into(), order_id, take_order).unwrap();
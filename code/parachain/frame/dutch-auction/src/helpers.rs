@@ -1,8 +1,11 @@
 use crate::{math::*, prelude::*, support::DefiMultiReservableCurrency, types::*};
 pub use crate::{pallet::*, weights::WeightInfo};
-use composable_support::abstractions::utils::increment::Increment;
+use composable_support::{
+	abstractions::utils::increment::Increment,
+	math::safe::{SafeAdd, SafeSub},
+};
 use composable_traits::{
-	defi::{DeFiComposableConfig, Sell, SellEngine, Take},
+	defi::{DeFiComposableConfig, LiftedFixedBalance, Sell, SellEngine, Take},
 	time::TimeReleaseFunction,
 	xcm::XcmSellInitialResponseTransact,
 };
@@ -11,77 +14,172 @@ use frame_support::{
 	transactional,
 };
 use orml_traits::MultiReservableCurrency;
-use sp_runtime::{traits::AccountIdConversion, DispatchError};
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating},
+	DispatchError,
+};
 use sp_std::convert::TryInto;
 use xcm::latest::{prelude::*, MultiAsset, WeightLimit::Unlimited};
 
 impl<T: Config> Pallet<T> {
+	/// Settles all takes of the block against the order at one uniform clearing price.
+	/// Takes are filled from highest to lowest limit, first come first served for same limit, and
+	/// the last filled take may be filled partially. Takes with limit below the auction price are
+	/// not filled. Clearing price is the auction price if the order is not sold out, otherwise it
+	/// is the limit of the lowest filled take. Unfilled remainder of the order stays in auction.
 	#[transactional]
 	pub fn take_order(
 		order_id: <T as Config>::OrderId,
 		mut takes: Vec<TakeOf<T>>,
 	) -> Result<(), DispatchError> {
 		<SellOrders<T>>::try_mutate_exists(order_id, |order_item| {
-			if let Some(crate::types::SellOrder {
-				order,
-				context: _,
-				from_to: ref seller,
-				configuration: _,
-				total_amount_received,
-			}) = order_item
-			{
-				let mut amount_received = T::Balance::zero();
-				// users payed N * WEIGHT before, we here pay N * (log N - 1) * Weight. We can
-				// retain pure N by first served principle so, not highest price.
-				takes.sort_by(|a, b| b.take.limit.cmp(&a.take.limit));
-				// calculate real price
-				for take in takes {
-					let quote_amount = take.take.quote_limit_amount()?;
-					// TODO: what to do with orders which nobody ever takes? some kind of dust
-					// orders
-					if order.take.amount == T::Balance::zero() {
-						// bidder was unlucky because order was sol out
-						T::MultiCurrency::unreserve(order.pair.quote, &take.from_to, quote_amount);
-					} else {
-						let take_amount = take.take.amount.min(order.take.amount);
-						order.take.amount -= take_amount;
-						let real_quote_amount = take.take.quote_amount(take_amount)?;
-
-						T::MultiCurrency::exchange_reserved(
-							order.pair.base,
-							seller,
-							take_amount,
-							order.pair.quote,
-							&take.from_to,
-							real_quote_amount,
-						)?;
-						if real_quote_amount < quote_amount {
-							T::MultiCurrency::unreserve(
-								order.pair.quote,
-								&take.from_to,
-								quote_amount - real_quote_amount,
-							);
-						}
-						amount_received += real_quote_amount;
+			let sell = match order_item {
+				Some(sell) => sell,
+				None => return Err(Error::<T>::TakeOrderDidNotHappen.into()),
+			};
+			let auction_price = Self::current_price(sell)?;
+			// users payed N * WEIGHT before, we here pay N * (log N - 1) * Weight.
+			// sort is stable, so earlier takes win within same limit
+			takes.sort_by(|a, b| b.take.limit.cmp(&a.take.limit));
+
+			let mut remaining = sell.order.take.amount;
+			let mut clearing_price = auction_price;
+			let mut amounts = Vec::with_capacity(takes.len());
+			for take in takes.iter() {
+				let amount = if take.take.limit < auction_price {
+					T::Balance::zero()
+				} else {
+					take.take.amount.min(remaining)
+				};
+				if !amount.is_zero() {
+					remaining = remaining.safe_sub(&amount)?;
+					if remaining.is_zero() {
+						clearing_price = take.take.limit;
 					}
 				}
+				amounts.push(amount);
+			}
 
-				*total_amount_received += amount_received;
-
-				if order.take.amount == T::Balance::zero() {
-					Self::callback_xcm(order, seller, order_id, *total_amount_received)?;
-					*order_item = None;
-					Self::deposit_event(Event::OrderRemoved { order_id });
+			let mut fills = Vec::with_capacity(takes.len());
+			let mut amount_received = T::Balance::zero();
+			for (take, amount) in takes.into_iter().zip(amounts) {
+				let reserved_quote_amount = take.take.quote_limit_amount()?;
+				let quote_amount = Take::new(amount, clearing_price).quote_limit_amount()?;
+				if !amount.is_zero() {
+					T::MultiCurrency::exchange_reserved(
+						sell.order.pair.base,
+						&sell.from_to,
+						amount,
+						sell.order.pair.quote,
+						&take.from_to,
+						quote_amount,
+					)?;
+					amount_received = amount_received.safe_add(&quote_amount)?;
+					fills.push(TakeFill { from_to: take.from_to.clone(), amount, quote_amount });
 				}
-
-				if amount_received > T::Balance::zero() {
-					return Ok(())
+				let unused_quote_amount = reserved_quote_amount.saturating_sub(quote_amount);
+				if !unused_quote_amount.is_zero() {
+					T::MultiCurrency::unreserve(
+						sell.order.pair.quote,
+						&take.from_to,
+						unused_quote_amount,
+					);
 				}
 			}
-			Err(Error::<T>::TakeOrderDidNotHappen.into())
+
+			sell.order.take.amount = remaining;
+			sell.total_amount_received = sell.total_amount_received.safe_add(&amount_received)?;
+
+			if !fills.is_empty() {
+				Self::deposit_event(Event::OrderSettled { order_id, clearing_price, fills });
+			}
+
+			if remaining.is_zero() {
+				Self::callback_xcm(
+					&sell.order,
+					&sell.from_to,
+					order_id,
+					sell.total_amount_received,
+				)?;
+				*order_item = None;
+				Self::deposit_event(Event::OrderRemoved { order_id });
+			}
+
+			Ok(())
 		})
 	}
 
+	pub fn do_ask(
+		from_to: &T::AccountId,
+		order: Sell<T::MayBeAssetId, T::Balance>,
+		configuration: TimeReleaseFunction,
+		reserve_price: Option<LiftedFixedBalance>,
+	) -> Result<T::OrderId, DispatchError> {
+		ensure!(order.is_valid(), Error::<T>::OrderParametersIsInvalid,);
+		if let Some(reserve_price) = reserve_price {
+			ensure!(reserve_price <= order.take.limit, Error::<T>::ReservePriceAboveInitialPrice);
+		}
+		let order_id = <OrdersIndex<T>>::increment();
+		let treasury = &T::PalletId::get().into_account_truncating();
+		let deposit = T::PositionExistentialDeposit::get();
+		<T::NativeCurrency as NativeTransfer<T::AccountId>>::transfer(
+			from_to, treasury, deposit, true,
+		)?;
+
+		let now = T::UnixTime::now().as_secs();
+		let order = SellOf::<T> {
+			from_to: from_to.clone(),
+			configuration,
+			order,
+			reserve_price,
+			context: EDContext::<T::Balance> { added_at: now, deposit },
+			total_amount_received: T::Balance::zero(),
+		};
+
+		T::MultiCurrency::reserve(order.order.pair.base, from_to, order.order.take.amount)?;
+		SellOrders::<T>::insert(order_id, order);
+
+		Ok(order_id)
+	}
+
+	/// removes order, returning not sold `base` and deposit to seller
+	pub fn remove_order(order_id: T::OrderId, order: SellOf<T>) -> Result<(), DispatchError> {
+		let treasury = &T::PalletId::get().into_account_truncating();
+		T::MultiCurrency::unreserve(order.order.pair.base, &order.from_to, order.order.take.amount);
+		if let Some(takes) = <Takes<T>>::take(order_id) {
+			Self::unreserve_takes(order.order.pair.quote, takes);
+		}
+		<T::NativeCurrency as NativeTransfer<T::AccountId>>::transfer(
+			treasury,
+			&order.from_to,
+			order.context.deposit,
+			false,
+		)?;
+
+		<SellOrders<T>>::remove(order_id);
+		Self::deposit_event(Event::OrderRemoved { order_id });
+		Ok(())
+	}
+
+	/// returns `quote` reserved for `takes` to takers
+	pub fn unreserve_takes(quote: T::MayBeAssetId, takes: Vec<TakeOf<T>>) {
+		for take in takes {
+			if let Ok(quote_amount) = take.take.quote_limit_amount() {
+				T::MultiCurrency::unreserve(quote, &take.from_to, quote_amount);
+			}
+		}
+	}
+
+	/// current auction price of the order, never below its reserve price
+	pub fn current_price(order: &SellOf<T>) -> Result<LiftedFixedBalance, DispatchError> {
+		let passed = T::UnixTime::now().as_secs().saturating_sub(order.context.added_at);
+		Ok(order.configuration.price_with_reserve(
+			order.order.take.limit,
+			passed,
+			order.reserve_price,
+		)?)
+	}
+
 	pub fn callback_xcm(
 		order: &Sell<
 			<T as DeFiComposableConfig>::MayBeAssetId,
@@ -184,28 +282,9 @@ impl<T: Config + DeFiComposableConfig> SellEngine<TimeReleaseFunction> for Palle
 		from_to: &Self::AccountId,
 		order: Sell<Self::MayBeAssetId, Self::Balance>,
 		configuration: TimeReleaseFunction,
+		reserve_price: Option<LiftedFixedBalance>,
 	) -> Result<Self::OrderId, DispatchError> {
-		ensure!(order.is_valid(), Error::<T>::OrderParametersIsInvalid,);
-		let order_id = <OrdersIndex<T>>::increment();
-		let treasury = &T::PalletId::get().into_account_truncating();
-		let deposit = T::PositionExistentialDeposit::get();
-		<T::NativeCurrency as NativeTransfer<T::AccountId>>::transfer(
-			from_to, treasury, deposit, true,
-		)?;
-
-		let now = T::UnixTime::now().as_secs();
-		let order = SellOf::<T> {
-			from_to: from_to.clone(),
-			configuration,
-			order,
-			context: EDContext::<Self::Balance> { added_at: now, deposit },
-			total_amount_received: Self::Balance::zero(),
-		};
-
-		T::MultiCurrency::reserve(order.order.pair.base, from_to, order.order.take.amount)?;
-		SellOrders::<T>::insert(order_id, order);
-
-		Ok(order_id)
+		Self::do_ask(from_to, order, configuration, reserve_price)
 	}
 
 	fn take(
@@ -216,12 +295,10 @@ impl<T: Config + DeFiComposableConfig> SellEngine<TimeReleaseFunction> for Palle
 		ensure!(take.is_valid(), Error::<T>::TakeParametersIsInvalid,);
 		let order = <SellOrders<T>>::try_get(order_id)
 			.map_err(|_x| Error::<T>::RequestedOrderDoesNotExists)?;
-		ensure!(order.order.take.limit <= take.limit, Error::<T>::TakeLimitDoesNotSatisfyOrder,);
-		let limit = order.order.take.limit;
 		// may consider storing calculation results within single block, so that finalize does
 		// not recalculates
-		let passed = T::UnixTime::now().as_secs() - order.context.added_at;
-		let _limit = order.configuration.price(limit, passed)?;
+		let price = Self::current_price(&order)?;
+		ensure!(price <= take.limit, Error::<T>::TakeLimitDoesNotSatisfyOrder,);
 		let quote_amount = take.quote_limit_amount()?;
 
 		T::MultiCurrency::reserve(order.order.pair.quote, from_to, quote_amount)?;
@@ -229,4 +306,13 @@ impl<T: Config + DeFiComposableConfig> SellEngine<TimeReleaseFunction> for Palle
 
 		Ok(())
 	}
+
+	fn remaining(order_id: Self::OrderId) -> Option<Self::Balance> {
+		<SellOrders<T>>::get(order_id).map(|order| order.order.take.amount)
+	}
+
+	fn cancel(order_id: Self::OrderId) -> Result<(), DispatchError> {
+		let order = <SellOrders<T>>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+		Self::remove_order(order_id, order)
+	}
 }
@@ -8,6 +8,14 @@
 //! Sell(ask) orders stored on chain. Sell takes deposit from seller, returned during take or
 //! liquidation. Takes live only one block.
 //!
+//! # Settlement
+//! All takes of the block are settled together at the end of the block with one uniform clearing
+//! price, so there is no gain in being first within block.
+//! Takes are filled from highest limit, the last filled take may be filled partially.
+//! If the order is not sold out, clearing price is the auction price of the block, otherwise it is
+//! the limit of the lowest filled take. Not filled amount of the order stays in auction for next
+//! blocks, not filled takes are returned to takers.
+//!
 //! # Take Sell Order
 //! Allows for best price to win during auction take. as takes are not executed immediately.
 //! When auction steps onto new value, several people will decide it worth it.
@@ -23,6 +31,7 @@
 //! # Price prediction
 //! Dutch action starts with configured price and than and other price value is f(t).
 //! So any external observer can predict what price will be on specified block.
+//! Seller may set reserve price, price does not decrease below it.
 //!
 //! # DEX
 //! Currently this dutch auction does not tries to sell on external DEX.
@@ -74,6 +83,7 @@ mod benchmarking;
 mod mock;
 
 mod helpers;
+pub mod migrations;
 mod prelude;
 mod support;
 mod types;
@@ -86,7 +96,7 @@ pub mod pallet {
 	use crate::{
 		prelude::*,
 		types::*,
-		validation::{SellValid, XcmSellRequestValid},
+		validation::{SellValid, TimeReleaseFunctionValid, XcmSellRequestValid},
 	};
 	use composable_support::{
 		abstractions::{
@@ -97,7 +107,10 @@ pub mod pallet {
 		validation::Validate,
 	};
 	use composable_traits::{
-		defi::{DeFiComposableConfig, DeFiEngine, OrderIdLike, Sell, SellEngine, Take},
+		defi::{
+			DeFiComposableConfig, DeFiEngine, LiftedFixedBalance, OrderIdLike, Sell, SellEngine,
+			Take,
+		},
 		time::TimeReleaseFunction,
 		xcm::{ConfigurationId, CumulusMethodId, XcmSellRequest},
 	};
@@ -112,7 +125,7 @@ pub mod pallet {
 		pallet_prelude::{BlockNumberFor, OriginFor},
 	};
 	use orml_traits::{MultiCurrency, MultiReservableCurrency};
-	use sp_runtime::{traits::AccountIdConversion, DispatchError};
+	use sp_runtime::DispatchError;
 	use sp_std::convert::TryInto;
 	use xcm::latest::prelude::*;
 
@@ -128,6 +141,9 @@ pub mod pallet {
 	pub type TakeOf<T> =
 		TakeOrder<<T as DeFiComposableConfig>::Balance, <T as frame_system::Config>::AccountId>;
 
+	pub type TakeFillOf<T> =
+		TakeFill<<T as DeFiComposableConfig>::Balance, <T as frame_system::Config>::AccountId>;
+
 	#[pallet::config]
 	#[pallet::disable_frame_system_supertrait_check]
 	pub trait Config: DeFiComposableConfig + frame_system::Config {
@@ -175,6 +191,12 @@ pub mod pallet {
 		OrderRemoved {
 			order_id: OrderIdOf<T>,
 		},
+		/// takes of the block were settled at same `clearing_price`
+		OrderSettled {
+			order_id: OrderIdOf<T>,
+			clearing_price: LiftedFixedBalance,
+			fills: Vec<TakeFillOf<T>>,
+		},
 		ConfigurationAdded {
 			configuration_id: ConfigurationId,
 			configuration: TimeReleaseFunction,
@@ -194,10 +216,16 @@ pub mod pallet {
 		XcmCannotDecodeRemoteParametersToLocalRepresentations,
 		XcmCannotFindLocalIdentifiersAsDecodedFromRemote,
 		XcmNotFoundConfigurationById,
+		/// reserve price cannot be higher than initial price of the order
+		ReservePriceAboveInitialPrice,
 	}
 
+	/// sell orders got `reserve_price` in version 1
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -278,25 +306,28 @@ pub mod pallet {
 			configuration: TimeReleaseFunction,
 		) -> DispatchResultWithPostInfo {
 			let _ = T::AdminOrigin::ensure_origin(origin)?;
+			let configuration = TimeReleaseFunctionValid::validate(configuration)?;
 			Configurations::<T>::insert(configuration_id, configuration.clone());
 			Self::deposit_event(Event::ConfigurationAdded { configuration_id, configuration });
 			Ok(().into())
 		}
 
 		/// sell `order` in auction with `configuration`
+		/// price never decreases below `reserve_price` if provided
 		/// some deposit is taken for storing sell order
 		#[pallet::weight(T::WeightInfo::ask())]
 		pub fn ask(
 			origin: OriginFor<T>,
 			order: Sell<T::MayBeAssetId, T::Balance>,
 			configuration: TimeReleaseFunction,
+			reserve_price: Option<LiftedFixedBalance>,
 		) -> DispatchResultWithPostInfo {
 			let who = &(ensure_signed(origin)?);
 
 			let order = SellValid::validate(order)?;
+			let configuration = TimeReleaseFunctionValid::validate(configuration)?;
 
-			let order_id =
-				<Self as SellEngine<TimeReleaseFunction>>::ask(who, order, configuration)?;
+			let order_id = Self::do_ask(who, order, configuration, reserve_price)?;
 
 			Self::deposit_event(Event::OrderAdded {
 				order_id,
@@ -306,6 +337,7 @@ pub mod pallet {
 		}
 
 		/// adds take to list, does not execute take immediately
+		/// all takes of the block are settled in the end of the block at one clearing price
 		#[pallet::weight(T::WeightInfo::take())]
 		pub fn take(
 			origin: OriginFor<T>,
@@ -328,17 +360,7 @@ pub mod pallet {
 			// we can later allow liquidate old orders(or orders with some block liquidation
 			// timeout set) using kind of account per order is possible, but may risk to
 			// pollute account system
-			let treasury = &T::PalletId::get().into_account_truncating();
-			T::MultiCurrency::unreserve(order.order.pair.base, &who, order.order.take.amount);
-			<T::NativeCurrency as NativeTransfer<T::AccountId>>::transfer(
-				treasury,
-				&order.from_to,
-				order.context.deposit,
-				false,
-			)?;
-
-			<SellOrders<T>>::remove(order_id);
-			Self::deposit_event(Event::OrderRemoved { order_id });
+			Self::remove_order(order_id, order)?;
 
 			Ok(Pays::No.into())
 		}
@@ -373,7 +395,7 @@ pub mod pallet {
 				.map_err(|_| Error::<T>::XcmCannotDecodeRemoteParametersToLocalRepresentations)?;
 
 			let order_id =
-				<Self as SellEngine<TimeReleaseFunction>>::ask(&who, order, configuration)?;
+				<Self as SellEngine<TimeReleaseFunction>>::ask(&who, order, configuration, None)?;
 			LocalOrderIdToRemote::<T>::insert(order_id, (parachain_id, request.order_id));
 
 			Self::deposit_event(Event::OrderAdded {
@@ -391,8 +413,12 @@ pub mod pallet {
 		// so we stay fast and prevent attack
 		fn on_finalize(_n: T::BlockNumber) {
 			for (order_id, takes) in <Takes<T>>::drain() {
-				if let Err(err) = Self::take_order(order_id, takes) {
+				if let Err(err) = Self::take_order(order_id, takes.clone()) {
 					log::error!("failed to take order {:?} with {:?}", order_id, err);
+					// settlement is reverted, so takes are not filled and get their funds back
+					if let Some(order) = <SellOrders<T>>::get(order_id) {
+						Self::unreserve_takes(order.order.pair.quote, takes);
+					}
 				}
 			}
 		}
@@ -400,5 +426,9 @@ pub mod pallet {
 		fn on_initialize(_n: T::BlockNumber) -> Weight {
 			T::WeightInfo::known_overhead_for_on_finalize()
 		}
+
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::migrate_to_v1::<T>()
+		}
 	}
 }
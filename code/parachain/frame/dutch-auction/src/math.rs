@@ -1,14 +1,17 @@
 //! Price function for auction with price going to minimal possible value.
-//! Linear, step-wise exponential, and continuous exponential, others, configured from MakerDao
-//! https://github.com/makerdao/dss/blob/master/src/abaci.sol
+//! Linear, step-wise exponential, continuous exponential and piecewise linear, configured from
+//! MakerDao https://github.com/makerdao/dss/blob/master/src/abaci.sol
 
-use composable_support::math::safe::{SafeDiv, SafeMul};
+use composable_support::math::safe::{SafeAdd, SafeDiv, SafeMul, SafeSub};
 use composable_traits::{
 	defi::LiftedFixedBalance,
-	time::{DurationSeconds, LinearDecrease, StairstepExponentialDecrease, TimeReleaseFunction},
+	time::{
+		ContinuousExponentialDecrease, DurationSeconds, LinearDecrease, PiecewiseLinearDecrease,
+		StairstepExponentialDecrease, TimeReleaseFunction,
+	},
 };
 use sp_runtime::{
-	traits::{Saturating, Zero},
+	traits::{One, Saturating, Zero},
 	ArithmeticError, FixedPointNumber,
 };
 
@@ -19,6 +22,17 @@ pub trait AuctionTimeCurveModel {
 		initial_price: LiftedFixedBalance,
 		duration_since_start: DurationSeconds,
 	) -> Result<LiftedFixedBalance, ArithmeticError>;
+
+	/// return current auction price, but never less than `reserve_price`
+	fn price_with_reserve(
+		&self,
+		initial_price: LiftedFixedBalance,
+		duration_since_start: DurationSeconds,
+		reserve_price: Option<LiftedFixedBalance>,
+	) -> Result<LiftedFixedBalance, ArithmeticError> {
+		let price = self.price(initial_price, duration_since_start)?;
+		Ok(reserve_price.map_or(price, |reserve_price| price.max(reserve_price)))
+	}
 }
 
 impl AuctionTimeCurveModel for TimeReleaseFunction {
@@ -31,6 +45,10 @@ impl AuctionTimeCurveModel for TimeReleaseFunction {
 			TimeReleaseFunction::LinearDecrease(x) => x.price(initial_price, duration_since_start),
 			TimeReleaseFunction::StairstepExponentialDecrease(x) =>
 				x.price(initial_price, duration_since_start),
+			TimeReleaseFunction::ContinuousExponentialDecrease(x) =>
+				x.price(initial_price, duration_since_start),
+			TimeReleaseFunction::PiecewiseLinearDecrease(x) =>
+				x.price(initial_price, duration_since_start),
 		}
 	}
 }
@@ -77,13 +95,86 @@ impl AuctionTimeCurveModel for StairstepExponentialDecrease {
 	}
 }
 
+/// ln(2) with the precision of [`LiftedFixedBalance`]
+const LN_2: LiftedFixedBalance = LiftedFixedBalance::from_inner(693_147_180_559_945_309);
+
+/// Number of Taylor series terms used to calculate `e ^ x` for `0 <= x < 1`, enough to be exact up
+/// to the precision of [`LiftedFixedBalance`]
+const EXP_SERIES_TERMS: u128 = 20;
+
+/// e ^ x, for 0 <= x < 1
+fn exp_fraction(x: LiftedFixedBalance) -> Result<LiftedFixedBalance, ArithmeticError> {
+	let mut term = LiftedFixedBalance::one();
+	let mut sum = term;
+	for n in 1..=EXP_SERIES_TERMS {
+		term = term.safe_mul(&x)?.safe_div(&LiftedFixedBalance::saturating_from_integer(n))?;
+		sum = sum.safe_add(&term)?;
+	}
+	Ok(sum)
+}
+
+/// returns: initial_price * 2 ^ (-duration_since_start / half_life)
+/// whole halvings are exact, the remainder is e ^ (-remainder * ln(2) / half_life)
+impl AuctionTimeCurveModel for ContinuousExponentialDecrease {
+	fn price(
+		&self,
+		initial_price: LiftedFixedBalance,
+		duration_since_start: DurationSeconds,
+	) -> Result<LiftedFixedBalance, ArithmeticError> {
+		let halvings = duration_since_start.safe_div(&self.half_life)?;
+		let halved_price = initial_price.safe_mul(
+			&LiftedFixedBalance::saturating_from_rational(1_u32, 2_u32)
+				.saturating_pow(halvings as usize),
+		)?;
+		let remainder = duration_since_start % self.half_life;
+		let exponent = LN_2.safe_mul(
+			&LiftedFixedBalance::checked_from_rational(remainder, self.half_life)
+				.ok_or(ArithmeticError::DivisionByZero)?,
+		)?;
+		halved_price.safe_div(&exp_fraction(exponent)?)
+	}
+}
+
+/// returns: initial_price * part, where part is interpolated linearly between the points around
+/// `duration_since_start`, starting with the whole price at zero seconds
+impl AuctionTimeCurveModel for PiecewiseLinearDecrease {
+	fn price(
+		&self,
+		initial_price: LiftedFixedBalance,
+		duration_since_start: DurationSeconds,
+	) -> Result<LiftedFixedBalance, ArithmeticError> {
+		let mut previous_time = DurationSeconds::zero();
+		let mut previous_part = LiftedFixedBalance::one();
+		for (time, part) in self.points.iter() {
+			let part: LiftedFixedBalance = (*part).into();
+			if duration_since_start < *time {
+				let progress = LiftedFixedBalance::checked_from_rational(
+					duration_since_start.safe_sub(&previous_time)?,
+					time.safe_sub(&previous_time)?,
+				)
+				.ok_or(ArithmeticError::DivisionByZero)?;
+				let part = previous_part
+					.safe_sub(&previous_part.saturating_sub(part).safe_mul(&progress)?)?;
+				return initial_price.safe_mul(&part)
+			}
+			previous_time = *time;
+			previous_part = part;
+		}
+		initial_price.safe_mul(&previous_part)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 
 	use composable_traits::{
 		defi::LiftedFixedBalance,
-		time::{DurationSeconds, LinearDecrease, StairstepExponentialDecrease, ONE_HOUR},
+		time::{
+			ContinuousExponentialDecrease, DurationSeconds, LinearDecrease,
+			PiecewiseLinearDecrease, StairstepExponentialDecrease, ONE_HOUR,
+		},
 	};
+	use frame_support::BoundedVec;
 
 	use sp_arithmetic::assert_eq_error_rate;
 	use sp_runtime::{
@@ -137,6 +228,71 @@ mod tests {
 		}
 	}
 
+	#[test]
+	pub fn test_smooth_exp_decrease() {
+		let half_life = 100;
+		let calc = ContinuousExponentialDecrease { half_life };
+		let initial_price = 4000.0;
+
+		for time in [0, 1, 50, 99, 100, 150, 333, 1000] {
+			let price: f64 = calc
+				.price(LiftedFixedBalance::from_float(initial_price), time)
+				.unwrap()
+				.to_float();
+			let expected_price = initial_price * 0.5_f64.powf(time as f64 / half_life as f64);
+			assert_eq_error_rate!(price, expected_price, 0.000_001);
+		}
+
+		assert!(calc
+			.price(LiftedFixedBalance::saturating_from_integer(4000), 100 * half_life)
+			.unwrap()
+			.is_zero());
+		assert!(ContinuousExponentialDecrease { half_life: 0 }
+			.price(LiftedFixedBalance::one(), 1)
+			.is_err());
+	}
+
+	#[test]
+	pub fn test_piecewise_linear_decrease() {
+		let calc = PiecewiseLinearDecrease {
+			points: BoundedVec::try_from(vec![
+				(100, Permill::from_percent(50)),
+				(300, Permill::from_percent(40)),
+			])
+			.unwrap(),
+		};
+		let initial_price = LiftedFixedBalance::saturating_from_integer(1000);
+		let price_at = |time| calc.price(initial_price, time).unwrap();
+
+		assert_eq!(price_at(0), initial_price);
+		assert_eq!(price_at(50), 750.into());
+		assert_eq!(price_at(100), 500.into());
+		assert_eq!(price_at(200), 450.into());
+		assert_eq!(price_at(300), 400.into());
+		assert_eq!(price_at(ONE_HOUR), 400.into());
+
+		let calc = PiecewiseLinearDecrease::default();
+		assert_eq!(calc.price(initial_price, ONE_HOUR).unwrap(), initial_price);
+	}
+
+	#[test]
+	pub fn test_price_never_goes_below_reserve() {
+		let calc = LinearDecrease { total: ONE_HOUR };
+		let initial_price = LiftedFixedBalance::saturating_from_integer(1000);
+		let reserve_price = Some(LiftedFixedBalance::saturating_from_integer(100));
+
+		assert_eq!(calc.price_with_reserve(initial_price, 0, reserve_price).unwrap(), 1000.into());
+		assert_eq!(
+			calc.price_with_reserve(initial_price, ONE_HOUR / 2, reserve_price).unwrap(),
+			500.into()
+		);
+		assert_eq!(
+			calc.price_with_reserve(initial_price, ONE_HOUR, reserve_price).unwrap(),
+			100.into()
+		);
+		assert!(calc.price_with_reserve(initial_price, ONE_HOUR, None).unwrap().is_zero());
+	}
+
 	use proptest::{prop_assert, strategy::Strategy, test_runner::TestRunner};
 
 	#[test]
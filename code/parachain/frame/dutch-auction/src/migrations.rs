@@ -0,0 +1,44 @@
+use crate::{pallet::*, prelude::*, types::*};
+use composable_traits::{defi::Sell, time::TimeReleaseFunction};
+
+/// [`SellOrder`] layout before it had `reserve_price`
+#[derive(Encode, Decode)]
+pub struct SellOrderV0<AssetId, Balance, AccountId, Context, Configuration> {
+	pub from_to: AccountId,
+	pub order: Sell<AssetId, Balance>,
+	pub configuration: Configuration,
+	pub context: Context,
+	pub total_amount_received: Balance,
+}
+
+pub type SellV0Of<T> = SellOrderV0<
+	<T as composable_traits::defi::DeFiComposableConfig>::MayBeAssetId,
+	<T as composable_traits::defi::DeFiComposableConfig>::Balance,
+	<T as frame_system::Config>::AccountId,
+	EDContext<<T as composable_traits::defi::DeFiComposableConfig>::Balance>,
+	TimeReleaseFunction,
+>;
+
+/// Adds `reserve_price` to stored sell orders, existing orders have no reserve price.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if StorageVersion::get::<Pallet<T>>() >= 1 {
+		return T::DbWeight::get().reads(1)
+	}
+
+	let mut translated = 0_u64;
+	SellOrders::<T>::translate::<SellV0Of<T>, _>(|_order_id, order| {
+		translated += 1;
+		Some(SellOrder {
+			from_to: order.from_to,
+			order: order.order,
+			reserve_price: None,
+			configuration: order.configuration,
+			context: order.context,
+			total_amount_received: order.total_amount_received,
+		})
+	});
+	StorageVersion::new(1).put::<Pallet<T>>();
+	log::info!("migrated {} sell orders to v1", translated);
+
+	T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+}
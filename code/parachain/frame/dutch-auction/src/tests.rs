@@ -2,8 +2,8 @@ use crate::{self as pallet_dutch_auction, weights::*};
 
 use crate::mock::{currency::*, runtime::*};
 use composable_traits::{
-	defi::{LiftedFixedBalance, Sell, Take},
-	time::{LinearDecrease, TimeReleaseFunction},
+	defi::{LiftedFixedBalance, Sell, SellEngine, Take},
+	time::{ContinuousExponentialDecrease, LinearDecrease, TimeReleaseFunction},
 	xcm::XcmSellRequest,
 };
 use frame_support::{
@@ -84,7 +84,7 @@ fn setup_sell() {
 		let gas = Assets::balance(PICA, &ALICE);
 		let treasury =
 			Assets::balance(PICA, &DutchAuctionPalletId::get().into_account_truncating());
-		DutchAuction::ask(Origin::signed(seller), sell, configuration).unwrap();
+		DutchAuction::ask(Origin::signed(seller), sell, configuration, None).unwrap();
 		let treasury_added =
 			Assets::balance(PICA, &DutchAuctionPalletId::get().into_account_truncating()) -
 				treasury;
@@ -117,7 +117,7 @@ fn with_immediate_exact_buy() {
 		let take_amount = 1000_u128;
 		let sell = Sell::new(BTC, USDT, sell_amount, fixed(take_amount));
 		let configuration = TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 42 });
-		DutchAuction::ask(Origin::signed(seller), sell, configuration).unwrap();
+		DutchAuction::ask(Origin::signed(seller), sell, configuration, None).unwrap();
 		let order_id = crate::OrdersIndex::<Runtime>::get();
 		let result = DutchAuction::take(Origin::signed(buyer), order_id, Take::new(1, fixed(999)));
 		assert!(!result.is_ok());
@@ -134,6 +134,51 @@ fn with_immediate_exact_buy() {
 	});
 }
 
+#[test]
+fn failed_settlement_unreserves_takes() {
+	new_test_externalities().execute_with(|| {
+		Tokens::mint_into(USDT, &BOB, 1_000_000_000_000_000_000_000).unwrap();
+		Tokens::mint_into(BTC, &ALICE, 10).unwrap();
+		let seller = AccountId::from_raw(ALICE.0);
+		let buyer = AccountId::from_raw(BOB.0);
+		let sell = Sell::new(BTC, USDT, 1, fixed(1000));
+		let configuration = TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 42 });
+		DutchAuction::ask(Origin::signed(seller), sell, configuration, None).unwrap();
+		let order_id = crate::OrdersIndex::<Runtime>::get();
+		assert_ok!(DutchAuction::take(Origin::signed(buyer), order_id, Take::new(1, fixed(1000))));
+		assert_eq!(Assets::reserved_balance(USDT, &BOB), 1000);
+
+		// seller has nothing to exchange, so settlement fails
+		Tokens::unreserve(BTC, &ALICE, 1);
+		Tokens::burn_from(BTC, &ALICE, 10).unwrap();
+		DutchAuction::on_finalize(42);
+
+		assert!(crate::SellOrders::<Runtime>::get(order_id).is_some());
+		assert_eq!(Assets::reserved_balance(USDT, &BOB), 0);
+		assert_eq!(Tokens::balance(BTC, &BOB), 0);
+	});
+}
+
+#[test]
+fn cancel_unreserves_takes() {
+	new_test_externalities().execute_with(|| {
+		Tokens::mint_into(USDT, &BOB, 1_000_000_000_000_000_000_000).unwrap();
+		Tokens::mint_into(BTC, &ALICE, 10).unwrap();
+		let seller = AccountId::from_raw(ALICE.0);
+		let buyer = AccountId::from_raw(BOB.0);
+		let sell = Sell::new(BTC, USDT, 1, fixed(1000));
+		let configuration = TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 42 });
+		DutchAuction::ask(Origin::signed(seller), sell, configuration, None).unwrap();
+		let order_id = crate::OrdersIndex::<Runtime>::get();
+		assert_ok!(DutchAuction::take(Origin::signed(buyer), order_id, Take::new(1, fixed(1000))));
+
+		assert_ok!(<DutchAuction as SellEngine<TimeReleaseFunction>>::cancel(order_id));
+
+		assert_eq!(Assets::reserved_balance(USDT, &BOB), 0);
+		assert!(crate::Takes::<Runtime>::get(order_id).is_none());
+	});
+}
+
 #[test]
 fn with_two_takes_higher_than_limit_and_not_enough_for_all() {
 	new_test_externalities().execute_with(|| {
@@ -148,7 +193,7 @@ fn with_two_takes_higher_than_limit_and_not_enough_for_all() {
 		let configuration = TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 42 });
 
 		let sell = Sell::new(BTC, USDT, sell_amount, fixed(take_amount));
-		DutchAuction::ask(Origin::signed(seller), sell, configuration).unwrap();
+		DutchAuction::ask(Origin::signed(seller), sell, configuration, None).unwrap();
 		let order_id = crate::OrdersIndex::<Runtime>::get();
 		assert_ok!(DutchAuction::take(Origin::signed(buyer), order_id, Take::new(1, fixed(1001))));
 		assert_ok!(DutchAuction::take(Origin::signed(buyer), order_id, Take::new(1, fixed(1002))));
@@ -160,6 +205,133 @@ fn with_two_takes_higher_than_limit_and_not_enough_for_all() {
 	});
 }
 
+#[test]
+fn takes_of_block_clear_at_uniform_price() {
+	new_test_externalities().execute_with(|| {
+		Tokens::mint_into(USDT, &BOB, 1_000_000).unwrap();
+		Tokens::mint_into(BTC, &ALICE, 10).unwrap();
+		let seller = AccountId::from_raw(ALICE.0);
+		let buyer = AccountId::from_raw(BOB.0);
+		let configuration = TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 42 });
+		let sell = Sell::new(BTC, USDT, 3, fixed(1000));
+		DutchAuction::ask(Origin::signed(seller), sell, configuration, None).unwrap();
+		let order_id = crate::OrdersIndex::<Runtime>::get();
+
+		assert_ok!(DutchAuction::take(Origin::signed(buyer), order_id, Take::new(2, fixed(1005))));
+		assert_ok!(DutchAuction::take(Origin::signed(buyer), order_id, Take::new(2, fixed(1010))));
+		assert_ok!(DutchAuction::take(Origin::signed(buyer), order_id, Take::new(1, fixed(1001))));
+
+		DutchAuction::on_finalize(42);
+
+		// highest take is filled fully, next one partially, the lowest is not filled
+		System::assert_has_event(Event::DutchAuction(crate::Event::OrderSettled {
+			order_id,
+			clearing_price: fixed(1005),
+			fills: vec![
+				crate::types::TakeFill { from_to: buyer, amount: 2, quote_amount: 2010 },
+				crate::types::TakeFill { from_to: buyer, amount: 1, quote_amount: 1005 },
+			],
+		}));
+		assert!(crate::SellOrders::<Runtime>::get(order_id).is_none());
+		assert_eq!(Tokens::balance(USDT, &ALICE), 3015);
+		assert_eq!(Tokens::balance(BTC, &BOB), 3);
+		assert_eq!(Assets::reserved_balance(USDT, &BOB), 0);
+		assert_eq!(Tokens::balance(USDT, &BOB), 1_000_000 - 3015);
+	});
+}
+
+#[test]
+fn unfilled_order_remainder_carries_over_to_next_block() {
+	new_test_externalities().execute_with(|| {
+		Tokens::mint_into(USDT, &BOB, 1_000_000).unwrap();
+		Tokens::mint_into(BTC, &ALICE, 10).unwrap();
+		let seller = AccountId::from_raw(ALICE.0);
+		let buyer = AccountId::from_raw(BOB.0);
+		let configuration = TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 42 });
+		let sell = Sell::new(BTC, USDT, 3, fixed(1000));
+		DutchAuction::ask(Origin::signed(seller), sell, configuration, None).unwrap();
+		let order_id = crate::OrdersIndex::<Runtime>::get();
+
+		assert_ok!(DutchAuction::take(Origin::signed(buyer), order_id, Take::new(1, fixed(1001))));
+		DutchAuction::on_finalize(42);
+
+		// not sold out, so clearing price is the auction price
+		System::assert_has_event(Event::DutchAuction(crate::Event::OrderSettled {
+			order_id,
+			clearing_price: fixed(1000),
+			fills: vec![crate::types::TakeFill { from_to: buyer, amount: 1, quote_amount: 1000 }],
+		}));
+		let order = crate::SellOrders::<Runtime>::get(order_id).expect("remainder is on sale");
+		assert_eq!(order.order.take.amount, 2);
+		assert_eq!(order.total_amount_received, 1000);
+		assert_eq!(Assets::reserved_balance(USDT, &BOB), 0);
+
+		// half of auction passed
+		Timestamp::set_timestamp(Timestamp::now() + 21 * 1000);
+		assert_ok!(DutchAuction::take(Origin::signed(buyer), order_id, Take::new(5, fixed(500))));
+		DutchAuction::on_finalize(43);
+
+		assert!(crate::SellOrders::<Runtime>::get(order_id).is_none());
+		assert_eq!(Tokens::balance(USDT, &ALICE), 2000);
+		assert_eq!(Tokens::balance(BTC, &BOB), 3);
+		assert_eq!(Assets::reserved_balance(USDT, &BOB), 0);
+	});
+}
+
+#[test]
+fn take_is_limited_by_reserve_price() {
+	new_test_externalities().execute_with(|| {
+		Tokens::mint_into(USDT, &BOB, 1_000_000_000_000_000_000_000).unwrap();
+		Tokens::mint_into(BTC, &ALICE, 10).unwrap();
+		let seller = AccountId::from_raw(ALICE.0);
+		let buyer = AccountId::from_raw(BOB.0);
+		let sell = Sell::new(BTC, USDT, 1, fixed(1000));
+		let configuration = TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 42 });
+
+		assert_noop!(
+			DutchAuction::ask(
+				Origin::signed(seller),
+				sell.clone(),
+				configuration.clone(),
+				Some(fixed(1001))
+			),
+			crate::Error::<Runtime>::ReservePriceAboveInitialPrice,
+		);
+		assert_ok!(DutchAuction::ask(
+			Origin::signed(seller),
+			sell,
+			configuration,
+			Some(fixed(600))
+		));
+		let order_id = crate::OrdersIndex::<Runtime>::get();
+
+		// linear decrease would be at 500 in the middle of auction
+		Timestamp::set_timestamp(Timestamp::now() + 21 * 1000);
+		assert_noop!(
+			DutchAuction::take(Origin::signed(buyer), order_id, Take::new(1, fixed(599))),
+			crate::Error::<Runtime>::TakeLimitDoesNotSatisfyOrder,
+		);
+		assert_ok!(DutchAuction::take(Origin::signed(buyer), order_id, Take::new(1, fixed(600))));
+	});
+}
+
+#[test]
+fn ask_with_invalid_configuration() {
+	new_test_externalities().execute_with(|| {
+		Tokens::mint_into(BTC, &ALICE, 10).unwrap();
+		let seller = AccountId::from_raw(ALICE.0);
+		let sell = Sell::new(BTC, USDT, 1, fixed(1000));
+		let configuration =
+			TimeReleaseFunction::ContinuousExponentialDecrease(ContinuousExponentialDecrease {
+				half_life: 0,
+			});
+		assert_noop!(
+			DutchAuction::ask(Origin::signed(seller), sell, configuration, None),
+			sp_runtime::DispatchError::Other("Exponential decrease with zero half life."),
+		);
+	});
+}
+
 #[test]
 fn liquidation() {
 	new_test_externalities()
@@ -168,7 +340,7 @@ fn liquidation() {
 			let seller = AccountId::from_raw(ALICE.0);
 			let sell = Sell::new(BTC, USDT, 1, fixed(1000));
 			let configuration = TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 42 });
-			DutchAuction::ask(Origin::signed(seller), sell, configuration).unwrap();
+			DutchAuction::ask(Origin::signed(seller), sell, configuration, None).unwrap();
 			let order_id = crate::OrdersIndex::<Runtime>::get();
 			let balance_before = <Balances as fungible::Inspect<_>>::balance(&ALICE);
 			DutchAuction::liquidate(Origin::signed(seller), order_id).unwrap();
@@ -184,3 +356,43 @@ fn liquidation() {
 		})
 		.unwrap();
 }
+
+#[test]
+fn migrate_sell_orders_without_reserve_price() {
+	new_test_externalities().execute_with(|| {
+		let seller = AccountId::from_raw(ALICE.0);
+		let order = Sell::new(BTC, USDT, 1, fixed(1000));
+		let configuration = TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 42 });
+		let context = crate::types::EDContext { added_at: 42, deposit: 1 };
+		let legacy = crate::migrations::SellOrderV0 {
+			from_to: seller,
+			order: order.clone(),
+			configuration: configuration.clone(),
+			context: context.clone(),
+			total_amount_received: 0,
+		};
+		frame_support::storage::unhashed::put(
+			&crate::SellOrders::<Runtime>::hashed_key_for(1),
+			&legacy,
+		);
+		frame_support::traits::StorageVersion::new(0).put::<DutchAuction>();
+
+		DutchAuction::on_runtime_upgrade();
+
+		assert_eq!(
+			crate::SellOrders::<Runtime>::get(1),
+			Some(crate::types::SellOrder {
+				from_to: seller,
+				order,
+				reserve_price: None,
+				configuration,
+				context,
+				total_amount_received: 0,
+			})
+		);
+		assert_eq!(
+			frame_support::traits::StorageVersion::get::<DutchAuction>(),
+			crate::pallet::STORAGE_VERSION
+		);
+	});
+}
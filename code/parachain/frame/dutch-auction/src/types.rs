@@ -1,7 +1,7 @@
 use crate::prelude::*;
 
 use composable_traits::{
-	defi::{Sell, Take},
+	defi::{LiftedFixedBalance, Sell, Take},
 	time::Timestamp,
 };
 
@@ -9,6 +9,8 @@ use composable_traits::{
 pub struct SellOrder<AssetId, Balance, AccountId, Context, Configuration> {
 	pub from_to: AccountId,
 	pub order: Sell<AssetId, Balance>,
+	/// price below which auction price stops decreasing
+	pub reserve_price: Option<LiftedFixedBalance>,
 	/// is take from input parameters, example continuity of order lifetime or price decay function
 	pub configuration: Configuration,
 	/// context captured when sell started, example current timestamp or ED captured
@@ -24,8 +26,18 @@ pub struct EDContext<Balance> {
 	pub deposit: Balance,
 }
 
-#[derive(Encode, Decode, MaxEncodedLen, Default, TypeInfo, PartialEq, Eq)]
+#[derive(Encode, Decode, MaxEncodedLen, Default, TypeInfo, Clone, PartialEq, Eq)]
 pub struct TakeOrder<Balance, AccountId> {
 	pub from_to: AccountId,
 	pub take: Take<Balance>,
 }
+
+/// part of the order filled for taker during settlement of the block takes
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct TakeFill<Balance, AccountId> {
+	pub from_to: AccountId,
+	/// amount of `base` received by taker
+	pub amount: Balance,
+	/// amount of `quote` paid by taker
+	pub quote_amount: Balance,
+}
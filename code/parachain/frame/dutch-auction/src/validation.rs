@@ -1,7 +1,12 @@
 use composable_support::validation::Validate;
-use composable_traits::{defi::Sell, xcm::XcmSellRequest};
+use composable_traits::{
+	defi::Sell,
+	time::{DurationSeconds, TimeReleaseFunction},
+	xcm::XcmSellRequest,
+};
 use frame_support::pallet_prelude::*;
 use scale_info::TypeInfo;
+use sp_runtime::Permill;
 
 #[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, Default)]
 pub struct XcmSellRequestValid;
@@ -25,3 +30,29 @@ impl<AssetId: PartialEq, Balance> Validate<Sell<AssetId, Balance>, SellValid> fo
 		Ok(sell)
 	}
 }
+
+#[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, Default)]
+pub struct TimeReleaseFunctionValid;
+
+impl Validate<TimeReleaseFunction, TimeReleaseFunctionValid> for TimeReleaseFunctionValid {
+	fn validate(configuration: TimeReleaseFunction) -> Result<TimeReleaseFunction, &'static str> {
+		match &configuration {
+			TimeReleaseFunction::LinearDecrease(_) => {},
+			TimeReleaseFunction::StairstepExponentialDecrease(x) =>
+				ensure!(x.step > 0, "Stairstep with zero step."),
+			TimeReleaseFunction::ContinuousExponentialDecrease(x) =>
+				ensure!(x.half_life > 0, "Exponential decrease with zero half life."),
+			TimeReleaseFunction::PiecewiseLinearDecrease(x) => {
+				let mut previous: Option<&(DurationSeconds, Permill)> = None;
+				for point in x.points.iter() {
+					if let Some(previous) = previous {
+						ensure!(previous.0 < point.0, "Piecewise linear times are not increasing.");
+						ensure!(previous.1 >= point.1, "Piecewise linear parts are increasing.");
+					}
+					previous = Some(point);
+				}
+			},
+		}
+		Ok(configuration)
+	}
+}
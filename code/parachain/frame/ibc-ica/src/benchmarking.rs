@@ -0,0 +1,217 @@
+//! Benchmarking setup for pallet-ibc-ica
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{dispatch::GetCallMetadata, traits::EnsureOrigin};
+use frame_system::RawOrigin;
+use ibc::{
+	core::ics04_channel::packet::Sequence, handler::HandlerOutputBuilder, timestamp::Timestamp,
+	Height,
+};
+use ibc_trait::IbcTrait;
+use sp_std::str::FromStr;
+
+const CONNECTION_ID: &[u8] = b"connection-0";
+const COUNTERPARTY_CONNECTION_ID: &[u8] = b"connection-1";
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+fn remark<T: Config>() -> <T as Config>::Call
+where
+	<T as Config>::Call: From<frame_system::Call<T>>,
+{
+	frame_system::Call::<T>::remark { remark: vec![0; 32] }.into()
+}
+
+fn entry_of<T: Config>(call: &<T as Config>::Call) -> CallFilterEntryOf<T> {
+	let metadata = call.get_call_metadata();
+	CallFilterEntry {
+		pallet_name: metadata.pallet_name.as_bytes().to_vec().try_into().unwrap(),
+		function_name: metadata.function_name.as_bytes().to_vec().try_into().unwrap(),
+	}
+}
+
+fn setup_connection<T: Config>() {
+	let client_id = T::IbcHandler::create_client().unwrap();
+	T::IbcHandler::create_connection(client_id, ConnectionId::from_str("connection-0").unwrap())
+		.unwrap();
+}
+
+fn metadata(address: &str) -> Metadata {
+	Metadata {
+		version: VERSION.to_string(),
+		controller_connection_id: "connection-0".to_string(),
+		host_connection_id: "connection-1".to_string(),
+		address: address.to_string(),
+		encoding: HOST_ENCODING.to_string(),
+		tx_type: TX_TYPE.to_string(),
+	}
+}
+
+fn packet(port_id: PortId, data: Vec<u8>) -> Packet {
+	Packet {
+		sequence: Sequence::from(1),
+		source_port: port_id.clone(),
+		source_channel: ChannelId::new(0),
+		destination_port: port_id,
+		destination_channel: ChannelId::new(0),
+		data,
+		timeout_height: Height::new(0, 1000),
+		timeout_timestamp: Timestamp::none(),
+	}
+}
+
+benchmarks! {
+	where_clause {
+		where <T as Config>::Call: From<frame_system::Call<T>>,
+			T: Send + Sync
+	}
+
+	register_account {
+		setup_connection::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()), CONNECTION_ID.to_vec(), COUNTERPARTY_CONNECTION_ID.to_vec())
+	verify {
+		assert!(InterchainAccounts::<T>::contains_key(&caller, CONNECTION_ID));
+	}
+
+	send_tx {
+		setup_connection::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		Pallet::<T>::register_account(
+			RawOrigin::Signed(caller.clone()).into(),
+			CONNECTION_ID.to_vec(),
+			COUNTERPARTY_CONNECTION_ID.to_vec(),
+		)
+		.unwrap();
+		InterchainAccounts::<T>::mutate(&caller, CONNECTION_ID, |account| {
+			if let Some(account) = account {
+				account.address = Some(b"cosmos1ica".to_vec());
+			}
+		});
+	}: _(RawOrigin::Signed(caller.clone()), CONNECTION_ID.to_vec(), vec![0; 256], 1000, 0)
+	verify {
+		assert_last_event::<T>(Event::TxSent { owner: caller, connection_id: CONNECTION_ID.to_vec() }.into());
+	}
+
+	allow_call {
+		let origin = T::AdminOrigin::successful_origin();
+		let entry = entry_of::<T>(&remark::<T>());
+	}: _<T::Origin>(origin, entry.clone())
+	verify {
+		assert!(AllowedCalls::<T>::contains_key(entry));
+	}
+
+	disallow_call {
+		let entry = entry_of::<T>(&remark::<T>());
+		AllowedCalls::<T>::insert(&entry, ());
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, entry.clone())
+	verify {
+		assert!(!AllowedCalls::<T>::contains_key(entry));
+	}
+
+	on_chan_open_try {
+		let mut handler = IbcHandler::<T>::default();
+		let mut output = HandlerOutputBuilder::new();
+		let port_id = PortId::from_str(HOST_PORT_ID).unwrap();
+		let counterparty = Counterparty::new(
+			PortId::from_str(&Pallet::<T>::controller_port(&whitelisted_caller())).unwrap(),
+			Some(ChannelId::new(0)),
+		);
+		let version = metadata("").encode();
+	}: {
+		handler.on_chan_open_try(
+			&mut output,
+			Order::Ordered,
+			&[ConnectionId::from_str("connection-1").unwrap()],
+			&port_id,
+			&ChannelId::new(0),
+			&counterparty,
+			&version,
+			&version,
+		)
+		.unwrap();
+	}
+	verify {
+		assert!(HostAccounts::<T>::contains_key(ChannelId::new(0).to_string().as_bytes()));
+	}
+
+	on_chan_open_ack {
+		let caller: T::AccountId = whitelisted_caller();
+		InterchainAccounts::<T>::insert(
+			&caller,
+			CONNECTION_ID,
+			InterchainAccount { channel_id: ChannelId::new(0).to_string().into_bytes(), address: None },
+		);
+		let mut handler = IbcHandler::<T>::default();
+		let mut output = HandlerOutputBuilder::new();
+		let port_id = PortId::from_str(&Pallet::<T>::controller_port(&caller)).unwrap();
+		let version = metadata("cosmos1ica").encode();
+	}: {
+		handler.on_chan_open_ack(&mut output, &port_id, &ChannelId::new(0), &version).unwrap();
+	}
+	verify {
+		assert!(InterchainAccounts::<T>::get(&caller, CONNECTION_ID).unwrap().address.is_some());
+	}
+
+	on_chan_close_confirm {
+		let caller: T::AccountId = whitelisted_caller();
+		InterchainAccounts::<T>::insert(
+			&caller,
+			CONNECTION_ID,
+			InterchainAccount {
+				channel_id: ChannelId::new(0).to_string().into_bytes(),
+				address: Some(b"cosmos1ica".to_vec()),
+			},
+		);
+		let mut handler = IbcHandler::<T>::default();
+		let mut output = HandlerOutputBuilder::new();
+		let port_id = PortId::from_str(&Pallet::<T>::controller_port(&caller)).unwrap();
+	}: {
+		handler.on_chan_close_confirm(&mut output, &port_id, &ChannelId::new(0)).unwrap();
+	}
+	verify {
+		assert!(!InterchainAccounts::<T>::contains_key(&caller, CONNECTION_ID));
+	}
+
+	on_recv_packet {
+		let account: T::AccountId = whitelisted_caller();
+		HostAccounts::<T>::insert(ChannelId::new(0).to_string().into_bytes(), &account);
+		// The calls dispatched are paid on top, an allowed call is looked up.
+		let call = remark::<T>();
+		AllowedCalls::<T>::insert(entry_of::<T>(&call), ());
+		let data = HostPacketData { calls: vec![call], memo: vec![] }.encode();
+		let packet = packet(PortId::from_str(HOST_PORT_ID).unwrap(), data);
+		let handler = IbcHandler::<T>::default();
+		let mut output = HandlerOutputBuilder::new();
+		let relayer = Signer::from_str("relayer").unwrap();
+	}: {
+		handler.on_recv_packet(&mut output, &packet, &relayer);
+	}
+
+	on_acknowledgement_packet {
+		let caller: T::AccountId = whitelisted_caller();
+		let packet = packet(PortId::from_str(&Pallet::<T>::controller_port(&caller)).unwrap(), vec![]);
+		let mut handler = IbcHandler::<T>::default();
+		let mut output = HandlerOutputBuilder::new();
+		let relayer = Signer::from_str("relayer").unwrap();
+		let acknowledgement = Acknowledgement::from(ACK_SUCCESS.to_vec());
+	}: {
+		handler.on_acknowledgement_packet(&mut output, &packet, &acknowledgement, &relayer).unwrap();
+	}
+
+	on_timeout_packet {
+		let caller: T::AccountId = whitelisted_caller();
+		let packet = packet(PortId::from_str(&Pallet::<T>::controller_port(&caller)).unwrap(), vec![]);
+		let mut handler = IbcHandler::<T>::default();
+		let mut output = HandlerOutputBuilder::new();
+		let relayer = Signer::from_str("relayer").unwrap();
+	}: {
+		handler.on_timeout_packet(&mut output, &packet, &relayer).unwrap();
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Runtime);
@@ -0,0 +1,732 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(
+	not(test),
+	deny(
+		clippy::disallowed_methods,
+		clippy::disallowed_types,
+		clippy::indexing_slicing,
+		clippy::todo,
+		clippy::unwrap_used,
+		clippy::panic
+	)
+)]
+#![deny(
+	unused_imports,
+	bad_style,
+	bare_trait_objects,
+	const_err,
+	improper_ctypes,
+	non_shorthand_field_patterns,
+	no_mangle_generic_items,
+	overflowing_literals,
+	path_statements,
+	patterns_in_fns_without_body,
+	private_in_public,
+	unconditional_recursion,
+	unused_allocation,
+	unused_comparisons,
+	unused_parens,
+	while_true,
+	trivial_casts,
+	trivial_numeric_casts,
+	unused_extern_crates
+)]
+
+//! ICS-27 interchain accounts.
+//!
+//! As a controller, a local account registers an account on a remote chain over an ordered
+//! channel bound to its own `icacontroller-<owner>` port, then sends it transactions.
+//! As a host, remote controllers open channels to the `icahost` port and get a derived local
+//! account dispatching the SCALE encoded calls they send. Only the calls allowed by governance
+//! are executed.
+
+mod weight;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, DecodeLimit, Encode};
+use composable_traits::call_filter::CallFilterEntry;
+use core::{fmt::Formatter, marker::PhantomData};
+use frame_support::{dispatch::Weight, weights::GetDispatchInfo, RuntimeDebug};
+use ibc::{
+	core::{
+		ics04_channel::{
+			channel::{Counterparty, Order},
+			error::Error as Ics04Error,
+			msgs::acknowledgement::Acknowledgement,
+			packet::Packet,
+			Version,
+		},
+		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+		ics26_routing::context::{
+			Acknowledgement as GenericAcknowledgement, Module, ModuleOutputBuilder, OnRecvPacketAck,
+		},
+	},
+	signer::Signer,
+};
+use ibc_trait::CallbackWeight;
+pub use pallet::*;
+use scale_info::{
+	prelude::{
+		format,
+		string::{String, ToString},
+	},
+	TypeInfo,
+};
+use serde::{Deserialize, Serialize};
+use sp_std::{boxed::Box, prelude::*};
+pub use weight::{SubstrateWeight, WeightInfo};
+
+pub const MODULE_ID: &str = "PalletIbcIca";
+pub const HOST_PORT_ID: &str = "icahost";
+pub const CONTROLLER_PORT_PREFIX: &str = "icacontroller-";
+pub const VERSION: &str = "ics27-1";
+/// Encoding of the packets executed by the host, a SCALE encoded [`HostPacketData`].
+pub const HOST_ENCODING: &str = "scale";
+pub const TX_TYPE: &str = "sdk_multi_msg";
+
+/// Maximum nesting of the calls decoded by the host.
+const MAX_CALL_DEPTH: u32 = 32;
+const ACK_SUCCESS: &[u8] = br#"{"result":"AQ=="}"#;
+const ACK_ERR: &[u8] = br#"{"error":"failed to execute the calls"}"#;
+
+/// ICS-27 channel version metadata, JSON encoded in the channel version.
+#[derive(Clone, PartialEq, Eq, Default, RuntimeDebug, Serialize, Deserialize)]
+pub struct Metadata {
+	pub version: String,
+	pub controller_connection_id: String,
+	pub host_connection_id: String,
+	/// Address of the interchain account, set by the host.
+	pub address: String,
+	pub encoding: String,
+	pub tx_type: String,
+}
+
+impl Metadata {
+	pub fn decode(version: &Version) -> Option<Self> {
+		serde_json::from_str::<Self>(&version.to_string())
+			.ok()
+			.filter(|metadata| metadata.version == VERSION)
+	}
+
+	pub fn encode(&self) -> Version {
+		Version::new(serde_json::to_string(self).unwrap_or_default())
+	}
+}
+
+/// Packet executed by the host on behalf of an interchain account.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct HostPacketData<Call> {
+	/// Calls dispatched in order, all of them or none.
+	pub calls: Vec<Call>,
+	pub memo: Vec<u8>,
+}
+
+/// Interchain account controlled by a local account.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct InterchainAccount {
+	/// Channel of the owner controller port.
+	pub channel_id: Vec<u8>,
+	/// Address of the account on the host, known once the channel is open.
+	pub address: Option<Vec<u8>>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		dispatch::{CallMetadata, Dispatchable, GetCallMetadata, PostDispatchInfo},
+		pallet_prelude::*,
+		transactional, PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use ibc::core::ics04_channel::channel::{ChannelEnd, State};
+	use ibc_primitives::SendPacketData;
+	use ibc_trait::{connection_id_from_bytes, port_id_from_bytes, IbcTrait};
+	use sp_runtime::traits::AccountIdConversion;
+
+	pub(crate) type CallFilterEntryOf<T> = CallFilterEntry<<T as Config>::MaxStringSize>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		type IbcHandler: IbcTrait;
+
+		/// Calls a host interchain account may dispatch, subject to [`AllowedCalls`].
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ GetCallMetadata;
+
+		/// Used to derive the host interchain accounts.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Origin allowed to update the calls executed by the host.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		#[pallet::constant]
+		type MaxStringSize: Get<u32>
+			+ TypeInfo
+			+ core::fmt::Debug
+			+ MaxEncodedLen
+			+ Copy
+			+ Clone
+			+ PartialEq
+			+ Eq;
+
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// Interchain accounts controlled by local accounts, by owner and controller connection.
+	#[pallet::storage]
+	#[pallet::getter(fn interchain_account)]
+	pub type InterchainAccounts<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		Vec<u8>,
+		InterchainAccount,
+		OptionQuery,
+	>;
+
+	/// Local accounts of the remote controllers, by host channel.
+	#[pallet::storage]
+	#[pallet::getter(fn host_account)]
+	pub type HostAccounts<T: Config> =
+		StorageMap<_, Blake2_128Concat, Vec<u8>, T::AccountId, OptionQuery>;
+
+	/// Calls the host executes, any other call fails the whole packet.
+	#[pallet::storage]
+	pub type AllowedCalls<T: Config> = StorageMap<_, Twox64Concat, CallFilterEntryOf<T>, ()>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An interchain account channel has been initialized.
+		RegistrationRequested { owner: T::AccountId, connection_id: Vec<u8>, channel_id: Vec<u8> },
+		/// The host acknowledged the interchain account.
+		AccountRegistered { owner: T::AccountId, connection_id: Vec<u8>, address: Vec<u8> },
+		/// A transaction has been sent to the interchain account.
+		TxSent { owner: T::AccountId, connection_id: Vec<u8> },
+		/// The host acknowledged a transaction.
+		TxAcknowledged {
+			owner: T::AccountId,
+			channel_id: Vec<u8>,
+			sequence: u64,
+			acknowledgement: Vec<u8>,
+		},
+		/// A transaction timed out.
+		TxTimedOut { owner: T::AccountId, channel_id: Vec<u8>, sequence: u64 },
+		/// A remote controller opened a channel to the host.
+		HostChannelOpened { channel_id: Vec<u8>, account: T::AccountId },
+		/// The host processed a packet.
+		Executed { account: T::AccountId, channel_id: Vec<u8>, sequence: u64, success: bool },
+		/// A channel has been closed.
+		ChannelClosed { port_id: Vec<u8>, channel_id: Vec<u8> },
+		/// The host now executes this call.
+		CallAllowed { entry: CallFilterEntryOf<T> },
+		/// The host no longer executes this call.
+		CallDisallowed { entry: CallFilterEntryOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Invalid params passed
+		InvalidParams,
+		/// The pallet or function name is not a valid UTF8 string.
+		InvalidString,
+		/// An interchain account is already registered on this connection.
+		AccountAlreadyRegistered,
+		/// No interchain account is registered on this connection.
+		AccountNotRegistered,
+		/// The host did not acknowledge the interchain account yet.
+		AccountNotOpen,
+		/// Error opening channel
+		ChannelInitError,
+		/// Error registering packet
+		PacketSendError,
+		/// The host packet could not be decoded.
+		DecodingError,
+		/// The call is not allowed on the host.
+		CallNotAllowed,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register an interchain account on the host chain of `connection_id`.
+		///
+		/// Opens an ordered channel from the owner controller port to the host port,
+		/// `counterparty_connection_id` being the host end of the connection. While the host has
+		/// not acknowledged the account, registering again replaces the pending channel.
+		#[pallet::weight(T::WeightInfo::register_account())]
+		pub fn register_account(
+			origin: OriginFor<T>,
+			connection_id: Vec<u8>,
+			counterparty_connection_id: Vec<u8>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			// A registration the host never acknowledged can be retried over a new channel.
+			ensure!(
+				InterchainAccounts::<T>::get(&owner, &connection_id)
+					.map_or(true, |account| account.address.is_none()),
+				Error::<T>::AccountAlreadyRegistered
+			);
+			let connection = connection_id_from_bytes(connection_id.clone())
+				.map_err(|_| Error::<T>::InvalidParams)?;
+			let counterparty_connection = connection_id_from_bytes(counterparty_connection_id)
+				.map_err(|_| Error::<T>::InvalidParams)?;
+			let metadata = Metadata {
+				version: VERSION.to_string(),
+				controller_connection_id: connection.to_string(),
+				host_connection_id: counterparty_connection.to_string(),
+				address: String::new(),
+				encoding: HOST_ENCODING.to_string(),
+				tx_type: TX_TYPE.to_string(),
+			};
+			let host_port = port_id_from_bytes(HOST_PORT_ID.as_bytes().to_vec())
+				.map_err(|_| Error::<T>::InvalidParams)?;
+			let channel_end = ChannelEnd::new(
+				State::Init,
+				Order::Ordered,
+				Counterparty::new(host_port, None),
+				vec![connection],
+				metadata.encode(),
+			);
+			let port_id = port_id_from_bytes(Self::controller_port(&owner).into_bytes())
+				.map_err(|_| Error::<T>::InvalidParams)?;
+			let channel_id = T::IbcHandler::open_channel(port_id, channel_end)
+				.map_err(|_| Error::<T>::ChannelInitError)?
+				.to_string()
+				.into_bytes();
+			InterchainAccounts::<T>::insert(
+				&owner,
+				&connection_id,
+				InterchainAccount { channel_id: channel_id.clone(), address: None },
+			);
+			Self::deposit_event(Event::<T>::RegistrationRequested {
+				owner,
+				connection_id,
+				channel_id,
+			});
+			Ok(())
+		}
+
+		/// Send a transaction to the interchain account registered on `connection_id`.
+		///
+		/// `data` is the packet data as expected by the host, e.g. an encoded
+		/// `InterchainAccountPacketData` for Cosmos SDK chains.
+		#[pallet::weight(T::WeightInfo::send_tx())]
+		pub fn send_tx(
+			origin: OriginFor<T>,
+			connection_id: Vec<u8>,
+			data: Vec<u8>,
+			timeout_height: u64,
+			timeout_timestamp: u64,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let account = InterchainAccounts::<T>::get(&owner, &connection_id)
+				.ok_or(Error::<T>::AccountNotRegistered)?;
+			ensure!(account.address.is_some(), Error::<T>::AccountNotOpen);
+			T::IbcHandler::send_packet(SendPacketData {
+				data,
+				revision_number: None,
+				timeout_height,
+				timeout_timestamp,
+				port_id: Self::controller_port(&owner).into_bytes(),
+				channel_id: account.channel_id,
+			})
+			.map_err(|_| Error::<T>::PacketSendError)?;
+			Self::deposit_event(Event::<T>::TxSent { owner, connection_id });
+			Ok(())
+		}
+
+		/// Allow the host to execute a call.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::weight(T::WeightInfo::allow_call())]
+		pub fn allow_call(origin: OriginFor<T>, entry: CallFilterEntryOf<T>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(entry.valid(), Error::<T>::InvalidString);
+			if !AllowedCalls::<T>::contains_key(&entry) {
+				AllowedCalls::<T>::insert(&entry, ());
+				Self::deposit_event(Event::<T>::CallAllowed { entry });
+			}
+			Ok(())
+		}
+
+		/// Stop the host from executing a call.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::weight(T::WeightInfo::disallow_call())]
+		pub fn disallow_call(origin: OriginFor<T>, entry: CallFilterEntryOf<T>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(entry.valid(), Error::<T>::InvalidString);
+			if AllowedCalls::<T>::contains_key(&entry) {
+				AllowedCalls::<T>::remove(&entry);
+				Self::deposit_event(Event::<T>::CallDisallowed { entry });
+			}
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Controller port of a local account, `icacontroller-<hex encoded account>`.
+		pub fn controller_port(owner: &T::AccountId) -> String {
+			format!("{}{}", CONTROLLER_PORT_PREFIX, hex::encode(owner.encode()))
+		}
+
+		/// Owner of a controller port.
+		pub fn controller_port_owner(port_id: &str) -> Option<T::AccountId> {
+			let owner = hex::decode(port_id.strip_prefix(CONTROLLER_PORT_PREFIX)?).ok()?;
+			T::AccountId::decode(&mut owner.as_slice()).ok()
+		}
+
+		/// Local account of the controller at the other end of the connection.
+		pub fn derive_host_account(
+			connection_id: &ConnectionId,
+			counterparty_port_id: &PortId,
+		) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating((
+				connection_id.as_bytes(),
+				counterparty_port_id.as_bytes(),
+			))
+		}
+
+		pub fn allowed(call: &<T as Config>::Call) -> bool {
+			let CallMetadata { function_name, pallet_name } = call.get_call_metadata();
+			match (
+				BoundedVec::try_from(pallet_name.as_bytes().to_vec()),
+				BoundedVec::try_from(function_name.as_bytes().to_vec()),
+			) {
+				(Ok(pallet_name), Ok(function_name)) =>
+					AllowedCalls::<T>::contains_key(CallFilterEntry { pallet_name, function_name }),
+				_ => false,
+			}
+		}
+
+		pub(crate) fn decode_host_packet(
+			data: &[u8],
+		) -> Result<HostPacketData<<T as Config>::Call>, Error<T>> {
+			HostPacketData::decode_with_depth_limit(MAX_CALL_DEPTH, &mut &*data)
+				.map_err(|_| Error::<T>::DecodingError)
+		}
+
+		/// Dispatch the calls of a host packet from the interchain account, reverting all of
+		/// them if any fails or is not allowed.
+		#[transactional]
+		pub(crate) fn do_execute(account: &T::AccountId, data: &[u8]) -> DispatchResult {
+			let HostPacketData { calls, .. } = Self::decode_host_packet(data)?;
+			for call in calls {
+				ensure!(Self::allowed(&call), Error::<T>::CallNotAllowed);
+				call.dispatch(frame_system::RawOrigin::Signed(account.clone()).into())
+					.map_err(|e| e.error)?;
+			}
+			Ok(())
+		}
+
+		pub(crate) fn do_deposit_event(event: Event<T>) {
+			Self::deposit_event(event)
+		}
+	}
+}
+
+#[derive(Clone)]
+pub struct IbcHandler<T: Config>(PhantomData<T>);
+
+impl<T: Config> Default for IbcHandler<T> {
+	fn default() -> Self {
+		Self(PhantomData::default())
+	}
+}
+
+pub struct IcaAcknowledgement(Vec<u8>);
+
+impl AsRef<[u8]> for IcaAcknowledgement {
+	fn as_ref(&self) -> &[u8] {
+		self.0.as_slice()
+	}
+}
+
+impl GenericAcknowledgement for IcaAcknowledgement {}
+
+impl<T: Config> core::fmt::Debug for IbcHandler<T> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		write!(f, "pallet-ibc-ica")
+	}
+}
+
+fn app_error(message: &str) -> Ics04Error {
+	Ics04Error::app_module(format!("[pallet-ibc-ica]: {}", message))
+}
+
+impl<T: Config + Send + Sync> Module for IbcHandler<T> {
+	fn on_chan_open_init(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		order: Order,
+		_connection_hops: &[ConnectionId],
+		port_id: &PortId,
+		_channel_id: &ChannelId,
+		counterparty: &Counterparty,
+		version: &Version,
+	) -> Result<(), Ics04Error> {
+		// Only controllers initiate channels, toward a host port.
+		Pallet::<T>::controller_port_owner(port_id.as_str())
+			.ok_or_else(|| app_error("channel must be initialized from a controller port"))?;
+		if order != Order::Ordered {
+			return Err(app_error("channel must be ordered"))
+		}
+		if counterparty.port_id().as_str() != HOST_PORT_ID {
+			return Err(app_error("counterparty must be a host port"))
+		}
+		Metadata::decode(version).ok_or_else(|| app_error("invalid version metadata"))?;
+		Ok(())
+	}
+
+	fn on_chan_open_try(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		order: Order,
+		connection_hops: &[ConnectionId],
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty: &Counterparty,
+		_version: &Version,
+		counterparty_version: &Version,
+	) -> Result<Version, Ics04Error> {
+		if port_id.as_str() != HOST_PORT_ID {
+			return Err(app_error("only the host port accepts channels"))
+		}
+		if order != Order::Ordered {
+			return Err(app_error("channel must be ordered"))
+		}
+		let mut metadata = Metadata::decode(counterparty_version)
+			.ok_or_else(|| app_error("invalid version metadata"))?;
+		if metadata.encoding != HOST_ENCODING {
+			return Err(app_error("unsupported encoding"))
+		}
+		let connection_id = connection_hops
+			.get(0)
+			.filter(|connection_id| connection_id.as_str() == metadata.host_connection_id)
+			.ok_or_else(|| app_error("host connection mismatch"))?;
+		let account = Pallet::<T>::derive_host_account(connection_id, counterparty.port_id());
+		let channel_id = channel_id.to_string().into_bytes();
+		HostAccounts::<T>::insert(&channel_id, &account);
+		metadata.address = format!("0x{}", hex::encode(account.encode()));
+		Pallet::<T>::do_deposit_event(Event::<T>::HostChannelOpened { channel_id, account });
+		Ok(metadata.encode())
+	}
+
+	fn on_chan_open_ack(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty_version: &Version,
+	) -> Result<(), Ics04Error> {
+		let owner = Pallet::<T>::controller_port_owner(port_id.as_str())
+			.ok_or_else(|| app_error("not a controller port"))?;
+		let metadata = Metadata::decode(counterparty_version)
+			.filter(|metadata| !metadata.address.is_empty())
+			.ok_or_else(|| app_error("invalid version metadata"))?;
+		let connection_id = metadata.controller_connection_id.into_bytes();
+		let address = metadata.address.into_bytes();
+		InterchainAccounts::<T>::try_mutate(&owner, &connection_id, |account| match account {
+			Some(account) if account.channel_id == channel_id.to_string().as_bytes() => {
+				account.address = Some(address.clone());
+				Ok(())
+			},
+			_ => Err(app_error("unknown interchain account")),
+		})?;
+		Pallet::<T>::do_deposit_event(Event::<T>::AccountRegistered {
+			owner,
+			connection_id,
+			address,
+		});
+		Ok(())
+	}
+
+	fn on_chan_open_confirm(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+	) -> Result<(), Ics04Error> {
+		Ok(())
+	}
+
+	fn on_chan_close_init(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+	) -> Result<(), Ics04Error> {
+		Err(app_error("interchain account channels cannot be closed"))
+	}
+
+	fn on_chan_close_confirm(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+	) -> Result<(), Ics04Error> {
+		let channel = channel_id.to_string().into_bytes();
+		if let Some(owner) = Pallet::<T>::controller_port_owner(port_id.as_str()) {
+			let connections = InterchainAccounts::<T>::iter_prefix(&owner)
+				.filter(|(_, account)| account.channel_id == channel)
+				.map(|(connection_id, _)| connection_id)
+				.collect::<Vec<_>>();
+			for connection_id in connections {
+				InterchainAccounts::<T>::remove(&owner, connection_id);
+			}
+		} else {
+			HostAccounts::<T>::remove(&channel);
+		}
+		Pallet::<T>::do_deposit_event(Event::<T>::ChannelClosed {
+			port_id: port_id.as_bytes().to_vec(),
+			channel_id: channel,
+		});
+		Ok(())
+	}
+
+	fn on_recv_packet(
+		&self,
+		_output: &mut ModuleOutputBuilder,
+		packet: &Packet,
+		_relayer: &Signer,
+	) -> OnRecvPacketAck {
+		let channel_id = packet.destination_channel.to_string().into_bytes();
+		let ack = match HostAccounts::<T>::get(&channel_id) {
+			Some(account) if packet.destination_port.as_str() == HOST_PORT_ID => {
+				let success = Pallet::<T>::do_execute(&account, &packet.data)
+					.map_err(|e| log::debug!(target: "pallet_ibc_ica", "execution failed: {:?}", e))
+					.is_ok();
+				Pallet::<T>::do_deposit_event(Event::<T>::Executed {
+					account,
+					channel_id,
+					sequence: packet.sequence.into(),
+					success,
+				});
+				if success {
+					ACK_SUCCESS
+				} else {
+					ACK_ERR
+				}
+			},
+			_ => ACK_ERR,
+		}
+		.to_vec();
+		let packet = packet.clone();
+		OnRecvPacketAck::Successful(
+			Box::new(IcaAcknowledgement(ack.clone())),
+			Box::new(move |_ctx| {
+				<T::IbcHandler as ibc_trait::IbcTrait>::write_acknowledgement(&packet, ack)
+					.map_err(|e| format!("{:?}", e))
+			}),
+		)
+	}
+
+	fn on_acknowledgement_packet(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		packet: &Packet,
+		acknowledgement: &Acknowledgement,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		let owner = Pallet::<T>::controller_port_owner(packet.source_port.as_str())
+			.ok_or_else(|| app_error("not a controller port"))?;
+		Pallet::<T>::do_deposit_event(Event::<T>::TxAcknowledged {
+			owner,
+			channel_id: packet.source_channel.to_string().into_bytes(),
+			sequence: packet.sequence.into(),
+			acknowledgement: acknowledgement.as_ref().to_vec(),
+		});
+		Ok(())
+	}
+
+	fn on_timeout_packet(
+		&mut self,
+		_output: &mut ModuleOutputBuilder,
+		packet: &Packet,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		let owner = Pallet::<T>::controller_port_owner(packet.source_port.as_str())
+			.ok_or_else(|| app_error("not a controller port"))?;
+		Pallet::<T>::do_deposit_event(Event::<T>::TxTimedOut {
+			owner,
+			channel_id: packet.source_channel.to_string().into_bytes(),
+			sequence: packet.sequence.into(),
+		});
+		Ok(())
+	}
+}
+
+pub struct WeightHandler<T: Config>(PhantomData<T>);
+impl<T: Config> Default for WeightHandler<T> {
+	fn default() -> Self {
+		Self(PhantomData::default())
+	}
+}
+
+impl<T: Config> CallbackWeight for WeightHandler<T> {
+	fn on_chan_open_init(&self) -> Weight {
+		0
+	}
+
+	fn on_chan_open_try(&self) -> Weight {
+		<T as Config>::WeightInfo::on_chan_open_try()
+	}
+
+	fn on_chan_open_ack(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Weight {
+		<T as Config>::WeightInfo::on_chan_open_ack()
+	}
+
+	fn on_chan_open_confirm(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Weight {
+		0
+	}
+
+	fn on_chan_close_init(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Weight {
+		0
+	}
+
+	fn on_chan_close_confirm(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Weight {
+		<T as Config>::WeightInfo::on_chan_close_confirm()
+	}
+
+	/// The packet pays for the calls it dispatches.
+	fn on_recv_packet(&self, packet: &Packet) -> Weight {
+		let calls = Pallet::<T>::decode_host_packet(&packet.data)
+			.map(|data| {
+				data.calls.iter().fold(0 as Weight, |weight, call| {
+					weight.saturating_add(call.get_dispatch_info().weight)
+				})
+			})
+			.unwrap_or_default();
+		<T as Config>::WeightInfo::on_recv_packet().saturating_add(calls)
+	}
+
+	fn on_acknowledgement_packet(
+		&self,
+		_packet: &Packet,
+		_acknowledgement: &Acknowledgement,
+	) -> Weight {
+		<T as Config>::WeightInfo::on_acknowledgement_packet()
+	}
+
+	fn on_timeout_packet(&self, _packet: &Packet) -> Weight {
+		<T as Config>::WeightInfo::on_timeout_packet()
+	}
+}
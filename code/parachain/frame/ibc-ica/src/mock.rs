@@ -0,0 +1,176 @@
+use super::*;
+use codec::MaxEncodedLen;
+use frame_support::{
+	construct_runtime, ord_parameter_types, parameter_types,
+	traits::{ConstU32, Everything},
+	PalletId,
+};
+use frame_system::EnsureSignedBy;
+use ibc::{
+	applications::transfer::{msgs::transfer::MsgTransfer, PrefixedCoin},
+	core::ics04_channel::channel::ChannelEnd,
+};
+use ibc_primitives::SendPacketData;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+
+pub type AccountId = u128;
+pub const ALICE: AccountId = 1;
+pub const ADMIN: AccountId = 2;
+pub const BOB: AccountId = 3;
+
+mod ibc_ica {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const IcaPalletId: PalletId = PalletId(*b"test/ica");
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+ord_parameter_types! {
+	pub const Admin: AccountId = ADMIN;
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, MaxEncodedLen, TypeInfo, core::fmt::Debug)]
+pub struct MaxStringSize;
+impl frame_support::traits::Get<u32> for MaxStringSize {
+	fn get() -> u32 {
+		100
+	}
+}
+
+/// Opens every channel as `channel-0` and accepts every packet.
+pub struct MockIbc;
+impl ibc_trait::IbcTrait for MockIbc {
+	fn client_revision_number(_: Vec<u8>, _: Vec<u8>) -> Result<u64, ibc_trait::Error> {
+		Ok(0)
+	}
+
+	fn send_packet(_: SendPacketData) -> Result<(), ibc_trait::Error> {
+		Ok(())
+	}
+
+	fn open_channel(_: PortId, _: ChannelEnd) -> Result<ChannelId, ibc_trait::Error> {
+		Ok(ChannelId::new(0))
+	}
+
+	fn close_channel(_: PortId, _: ChannelId) -> Result<(), ibc_trait::Error> {
+		Ok(())
+	}
+
+	fn channel_end(_: &PortId, _: &ChannelId) -> Result<ChannelEnd, ibc_trait::Error> {
+		Err(ibc_trait::Error::ChannelOrPortError)
+	}
+
+	fn next_sequence_send(_: &PortId, _: &ChannelId) -> Result<u64, ibc_trait::Error> {
+		Ok(1)
+	}
+
+	fn write_acknowledgement(_: &Packet, _: Vec<u8>) -> Result<(), ibc_trait::Error> {
+		Ok(())
+	}
+
+	fn send_transfer(
+		_: MsgTransfer<PrefixedCoin>,
+		_: Option<String>,
+	) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::SendTransferError)
+	}
+
+	fn on_receive_packet(_: &mut ModuleOutputBuilder, _: &Packet) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::ReceivePacketError)
+	}
+
+	fn on_ack_packet(
+		_: &mut ModuleOutputBuilder,
+		_: &Packet,
+		_: &Acknowledgement,
+	) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::AcknowledgementError)
+	}
+
+	fn on_timeout_packet(_: &mut ModuleOutputBuilder, _: &Packet) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::TimeoutError)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_client() -> Result<ibc::core::ics24_host::identifier::ClientId, ibc_trait::Error> {
+		Ok(Default::default())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_connection(
+		_: ibc::core::ics24_host::identifier::ClientId,
+		_: ConnectionId,
+	) -> Result<(), ibc_trait::Error> {
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_channel(
+		_: ibc::core::ics24_host::identifier::PortId,
+		_: ibc::core::ics24_host::identifier::ChannelId,
+		_: ibc::core::ics24_host::identifier::ConnectionId,
+	) -> Result<(), ibc_trait::Error> {
+		Ok(())
+	}
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type IbcHandler = MockIbc;
+	type Call = Call;
+	type PalletId = IcaPalletId;
+	type AdminOrigin = EnsureSignedBy<Admin, AccountId>;
+	type MaxStringSize = MaxStringSize;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Ica: ibc_ica::{Pallet, Storage, Call, Event<T>},
+	}
+);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,343 @@
+use super::*;
+use core::str::FromStr;
+use frame_support::{assert_noop, assert_ok};
+use ibc::{
+	core::ics04_channel::packet::Sequence, handler::HandlerOutputBuilder, timestamp::Timestamp,
+	Height,
+};
+use mock::{Event, *};
+use sp_runtime::traits::{BadOrigin, BlakeTwo256, Hash};
+
+const CONNECTION_ID: &[u8] = b"connection-0";
+const COUNTERPARTY_CONNECTION_ID: &[u8] = b"connection-1";
+
+fn remark_entry() -> CallFilterEntryOf<Runtime> {
+	CallFilterEntry {
+		pallet_name: b"System".to_vec().try_into().unwrap(),
+		function_name: b"remark_with_event".to_vec().try_into().unwrap(),
+	}
+}
+
+fn remark_packet(calls: Vec<Call>) -> Vec<u8> {
+	HostPacketData { calls, memo: vec![] }.encode()
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark_with_event { remark: b"ica".to_vec() })
+}
+
+fn metadata(address: &str) -> Metadata {
+	Metadata {
+		version: VERSION.to_string(),
+		controller_connection_id: "connection-0".to_string(),
+		host_connection_id: "connection-1".to_string(),
+		address: address.to_string(),
+		encoding: HOST_ENCODING.to_string(),
+		tx_type: TX_TYPE.to_string(),
+	}
+}
+
+fn controller_port(owner: AccountId) -> PortId {
+	PortId::from_str(&Ica::controller_port(&owner)).unwrap()
+}
+
+fn host_port() -> PortId {
+	PortId::from_str(HOST_PORT_ID).unwrap()
+}
+
+/// Open a host channel from the controller of `ALICE`, returning the interchain account.
+fn open_host_channel(channel_id: ChannelId) -> AccountId {
+	let version = IbcHandler::<Runtime>::default()
+		.on_chan_open_try(
+			&mut HandlerOutputBuilder::new(),
+			Order::Ordered,
+			&[ConnectionId::from_str("connection-1").unwrap()],
+			&host_port(),
+			&channel_id,
+			&Counterparty::new(controller_port(ALICE), Some(ChannelId::new(0))),
+			&Version::new(VERSION.to_string()),
+			&metadata("").encode(),
+		)
+		.unwrap();
+	let account = Ica::host_account(channel_id.to_string().into_bytes()).unwrap();
+	assert_eq!(
+		Metadata::decode(&version).unwrap().address,
+		format!("0x{}", hex::encode(account.encode()))
+	);
+	account
+}
+
+/// Receive a host packet, returning its acknowledgement.
+fn recv_packet(channel_id: ChannelId, data: Vec<u8>) -> Vec<u8> {
+	let packet = Packet {
+		sequence: Sequence::from(1),
+		source_port: controller_port(ALICE),
+		source_channel: ChannelId::new(0),
+		destination_port: host_port(),
+		destination_channel: channel_id,
+		data,
+		timeout_height: Height::new(0, 1000),
+		timeout_timestamp: Timestamp::none(),
+	};
+	match IbcHandler::<Runtime>::default().on_recv_packet(
+		&mut HandlerOutputBuilder::new(),
+		&packet,
+		&Signer::from_str("relayer").unwrap(),
+	) {
+		OnRecvPacketAck::Successful(ack, _) => ack.as_ref().to_vec(),
+		_ => panic!("host packets are always acknowledged"),
+	}
+}
+
+fn register(owner: AccountId) -> ChannelId {
+	assert_ok!(Ica::register_account(
+		Origin::signed(owner),
+		CONNECTION_ID.to_vec(),
+		COUNTERPARTY_CONNECTION_ID.to_vec()
+	));
+	let channel_id = Ica::interchain_account(owner, CONNECTION_ID).unwrap().channel_id;
+	ChannelId::from_str(core::str::from_utf8(&channel_id).unwrap()).unwrap()
+}
+
+fn open_ack(owner: AccountId, channel_id: &ChannelId, address: &str) -> Result<(), Ics04Error> {
+	IbcHandler::<Runtime>::default().on_chan_open_ack(
+		&mut HandlerOutputBuilder::new(),
+		&controller_port(owner),
+		channel_id,
+		&metadata(address).encode(),
+	)
+}
+
+#[test]
+fn allow_and_disallow_calls() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Ica::allow_call(Origin::signed(ALICE), remark_entry()), BadOrigin);
+		assert!(!Ica::allowed(&remark()));
+
+		assert_ok!(Ica::allow_call(Origin::signed(ADMIN), remark_entry()));
+		System::assert_last_event(Event::Ica(crate::Event::CallAllowed { entry: remark_entry() }));
+		assert!(Ica::allowed(&remark()));
+
+		assert_ok!(Ica::disallow_call(Origin::signed(ADMIN), remark_entry()));
+		System::assert_last_event(Event::Ica(crate::Event::CallDisallowed {
+			entry: remark_entry(),
+		}));
+		assert!(!Ica::allowed(&remark()));
+	});
+}
+
+#[test]
+fn controller_port_round_trip() {
+	let port = Ica::controller_port(&ALICE);
+	assert!(port.starts_with(CONTROLLER_PORT_PREFIX));
+	assert_eq!(Ica::controller_port_owner(&port), Some(ALICE));
+	assert_eq!(Ica::controller_port_owner(HOST_PORT_ID), None);
+}
+
+#[test]
+fn host_executes_only_allowed_calls() {
+	new_test_ext().execute_with(|| {
+		let channel_id = ChannelId::new(1);
+		let account = open_host_channel(channel_id);
+		System::assert_last_event(Event::Ica(crate::Event::HostChannelOpened {
+			channel_id: channel_id.to_string().into_bytes(),
+			account,
+		}));
+
+		assert_eq!(recv_packet(channel_id, remark_packet(vec![remark()])), ACK_ERR);
+		System::assert_last_event(Event::Ica(crate::Event::Executed {
+			account,
+			channel_id: channel_id.to_string().into_bytes(),
+			sequence: 1,
+			success: false,
+		}));
+
+		assert_ok!(Ica::allow_call(Origin::signed(ADMIN), remark_entry()));
+		assert_eq!(recv_packet(channel_id, remark_packet(vec![remark()])), ACK_SUCCESS);
+		System::assert_has_event(Event::System(frame_system::Event::Remarked {
+			sender: account,
+			hash: BlakeTwo256::hash(b"ica"),
+		}));
+	});
+}
+
+#[test]
+fn host_reverts_all_calls_if_one_is_not_allowed() {
+	new_test_ext().execute_with(|| {
+		let channel_id = ChannelId::new(1);
+		open_host_channel(channel_id);
+		assert_ok!(Ica::allow_call(Origin::signed(ADMIN), remark_entry()));
+		let calls = vec![remark(), Call::System(frame_system::Call::remark { remark: vec![] })];
+
+		assert_eq!(recv_packet(channel_id, remark_packet(calls)), ACK_ERR);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			Event::System(frame_system::Event::Remarked { .. })
+		)));
+		assert_eq!(recv_packet(channel_id, b"garbage".to_vec()), ACK_ERR);
+	});
+}
+
+#[test]
+fn host_rejects_packets_of_unknown_channels() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Ica::allow_call(Origin::signed(ADMIN), remark_entry()));
+		assert_eq!(recv_packet(ChannelId::new(1), remark_packet(vec![remark()])), ACK_ERR);
+	});
+}
+
+#[test]
+fn host_rejects_unordered_channels() {
+	new_test_ext().execute_with(|| {
+		assert!(IbcHandler::<Runtime>::default()
+			.on_chan_open_try(
+				&mut HandlerOutputBuilder::new(),
+				Order::Unordered,
+				&[ConnectionId::from_str("connection-1").unwrap()],
+				&host_port(),
+				&ChannelId::new(1),
+				&Counterparty::new(controller_port(ALICE), Some(ChannelId::new(0))),
+				&Version::new(VERSION.to_string()),
+				&metadata("").encode(),
+			)
+			.is_err());
+		assert_eq!(Ica::host_account(ChannelId::new(1).to_string().into_bytes()), None);
+	});
+}
+
+#[test]
+fn send_tx_requires_an_open_account() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Ica::send_tx(Origin::signed(ALICE), CONNECTION_ID.to_vec(), vec![], 10, 0),
+			Error::<Runtime>::AccountNotRegistered
+		);
+
+		let channel_id = register(ALICE);
+		assert_noop!(
+			Ica::send_tx(Origin::signed(ALICE), CONNECTION_ID.to_vec(), vec![], 10, 0),
+			Error::<Runtime>::AccountNotOpen
+		);
+
+		assert_ok!(open_ack(ALICE, &channel_id, "cosmos1ica"));
+		System::assert_last_event(Event::Ica(crate::Event::AccountRegistered {
+			owner: ALICE,
+			connection_id: CONNECTION_ID.to_vec(),
+			address: b"cosmos1ica".to_vec(),
+		}));
+		assert_noop!(
+			Ica::register_account(
+				Origin::signed(ALICE),
+				CONNECTION_ID.to_vec(),
+				COUNTERPARTY_CONNECTION_ID.to_vec()
+			),
+			Error::<Runtime>::AccountAlreadyRegistered
+		);
+		assert_ok!(Ica::send_tx(Origin::signed(ALICE), CONNECTION_ID.to_vec(), vec![1], 10, 0));
+		System::assert_last_event(Event::Ica(crate::Event::TxSent {
+			owner: ALICE,
+			connection_id: CONNECTION_ID.to_vec(),
+		}));
+	});
+}
+
+#[test]
+fn pending_registration_can_be_retried() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		// The host never acknowledged, e.g. the handshake failed on its side.
+		assert_ok!(Ica::register_account(
+			Origin::signed(ALICE),
+			CONNECTION_ID.to_vec(),
+			COUNTERPARTY_CONNECTION_ID.to_vec()
+		));
+		let channel_id = ChannelId::new(0);
+
+		assert!(open_ack(ALICE, &channel_id, "").is_err());
+		assert!(open_ack(BOB, &channel_id, "cosmos1ica").is_err());
+		assert_eq!(Ica::interchain_account(ALICE, CONNECTION_ID).unwrap().address, None);
+
+		assert_ok!(open_ack(ALICE, &channel_id, "cosmos1ica"));
+		assert_eq!(
+			Ica::interchain_account(ALICE, CONNECTION_ID).unwrap().address,
+			Some(b"cosmos1ica".to_vec())
+		);
+	});
+}
+
+#[test]
+fn closing_a_channel_clears_its_accounts() {
+	new_test_ext().execute_with(|| {
+		let channel_id = register(ALICE);
+		assert_ok!(open_ack(ALICE, &channel_id, "cosmos1ica"));
+		let host_channel = ChannelId::new(1);
+		open_host_channel(host_channel);
+		let mut handler = IbcHandler::<Runtime>::default();
+
+		assert!(handler
+			.on_chan_close_init(
+				&mut HandlerOutputBuilder::new(),
+				&controller_port(ALICE),
+				&channel_id
+			)
+			.is_err());
+		assert_ok!(handler.on_chan_close_confirm(
+			&mut HandlerOutputBuilder::new(),
+			&controller_port(ALICE),
+			&channel_id
+		));
+		assert_eq!(Ica::interchain_account(ALICE, CONNECTION_ID), None);
+		assert_ok!(handler.on_chan_close_confirm(
+			&mut HandlerOutputBuilder::new(),
+			&host_port(),
+			&host_channel
+		));
+		assert_eq!(Ica::host_account(host_channel.to_string().into_bytes()), None);
+
+		// A new account can be registered once the channel is closed.
+		register(ALICE);
+	});
+}
+
+#[test]
+fn controller_callbacks_report_the_owner() {
+	new_test_ext().execute_with(|| {
+		let channel_id = register(ALICE);
+		let mut packet = Packet {
+			sequence: Sequence::from(3),
+			source_port: controller_port(ALICE),
+			source_channel: channel_id,
+			destination_port: host_port(),
+			destination_channel: ChannelId::new(1),
+			data: vec![],
+			timeout_height: Height::new(0, 1000),
+			timeout_timestamp: Timestamp::none(),
+		};
+		let mut handler = IbcHandler::<Runtime>::default();
+		let relayer = Signer::from_str("relayer").unwrap();
+
+		assert_ok!(handler.on_acknowledgement_packet(
+			&mut HandlerOutputBuilder::new(),
+			&packet,
+			&Acknowledgement::from(ACK_SUCCESS.to_vec()),
+			&relayer
+		));
+		System::assert_last_event(Event::Ica(crate::Event::TxAcknowledged {
+			owner: ALICE,
+			channel_id: channel_id.to_string().into_bytes(),
+			sequence: 3,
+			acknowledgement: ACK_SUCCESS.to_vec(),
+		}));
+		assert_ok!(handler.on_timeout_packet(&mut HandlerOutputBuilder::new(), &packet, &relayer));
+		System::assert_last_event(Event::Ica(crate::Event::TxTimedOut {
+			owner: ALICE,
+			channel_id: channel_id.to_string().into_bytes(),
+			sequence: 3,
+		}));
+
+		packet.source_port = host_port();
+		assert!(handler
+			.on_timeout_packet(&mut HandlerOutputBuilder::new(), &packet, &relayer)
+			.is_err());
+	});
+}
@@ -0,0 +1,139 @@
+use frame_support::{dispatch::Weight, traits::Get};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn register_account() -> Weight;
+	fn send_tx() -> Weight;
+	fn allow_call() -> Weight;
+	fn disallow_call() -> Weight;
+	fn on_chan_open_try() -> Weight;
+	fn on_chan_open_ack() -> Weight;
+	fn on_chan_close_confirm() -> Weight;
+	fn on_recv_packet() -> Weight;
+	fn on_acknowledgement_packet() -> Weight;
+	fn on_timeout_packet() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn register_account() -> Weight {
+		0
+	}
+
+	fn send_tx() -> Weight {
+		0
+	}
+
+	fn allow_call() -> Weight {
+		0
+	}
+
+	fn disallow_call() -> Weight {
+		0
+	}
+
+	fn on_chan_open_try() -> Weight {
+		0
+	}
+
+	fn on_chan_open_ack() -> Weight {
+		0
+	}
+
+	fn on_chan_close_confirm() -> Weight {
+		0
+	}
+
+	fn on_recv_packet() -> Weight {
+		0
+	}
+
+	fn on_acknowledgement_packet() -> Weight {
+		0
+	}
+
+	fn on_timeout_packet() -> Weight {
+		0
+	}
+}
+
+/// Weights estimated from the storage accessed by each call.
+// TODO: not benchmarked yet, run the benchmark CLI for `pallet_ibc_ica` and replace these
+pub struct SubstrateWeight<T>(PhantomData<T>);
+#[allow(trivial_numeric_casts)]
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Ica InterchainAccounts (r:1 w:1)
+	// Storage: Ibc ChannelCounter (r:1 w:1)
+	// Storage: ParachainInfo ParachainId (r:1 w:0)
+	// Storage: Ibc ChannelsConnection (r:1 w:1)
+	// Storage: ibc connections, channelEnds, nextSequence{Send,Recv,Ack} (r:1 w:4)
+	fn register_account() -> Weight {
+		(156_400_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+
+	// Storage: Ica InterchainAccounts (r:1 w:0)
+	// Storage: Ibc ChannelsConnection (r:2 w:0)
+	// Storage: Ibc ConnectionClient (r:2 w:0)
+	// Storage: ParachainInfo ParachainId (r:1 w:0)
+	// Storage: Ibc PacketCounter (r:1 w:1)
+	// Storage: ibc clients, channelEnds, nextSequenceSend, connections, commitments (r:5 w:2)
+	fn send_tx() -> Weight {
+		(300_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+
+	// Storage: Ica AllowedCalls (r:1 w:1)
+	fn allow_call() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+
+	// Storage: Ica AllowedCalls (r:1 w:1)
+	fn disallow_call() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+
+	// Storage: Ica HostAccounts (r:0 w:1)
+	fn on_chan_open_try() -> Weight {
+		(9_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+
+	// Storage: Ica InterchainAccounts (r:1 w:1)
+	fn on_chan_open_ack() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+
+	// Storage: Ica InterchainAccounts (r:1 w:1)
+	// Storage: Ica HostAccounts (r:0 w:1)
+	fn on_chan_close_confirm() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+
+	// Storage: Ica HostAccounts (r:1 w:0)
+	// Storage: Ica AllowedCalls (r:1 w:0)
+	// Storage: Ibc AcknowledgementCounter (r:1 w:1)
+	// Storage: ParachainInfo ParachainId (r:1 w:0)
+	// Storage: ibc acks (r:0 w:1)
+	fn on_recv_packet() -> Weight {
+		(146_764_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+
+	fn on_acknowledgement_packet() -> Weight {
+		10_000_000 as Weight
+	}
+
+	fn on_timeout_packet() -> Weight {
+		10_000_000 as Weight
+	}
+}
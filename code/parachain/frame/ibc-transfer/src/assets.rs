@@ -0,0 +1,72 @@
+//! Registration of the local assets of incoming IBC denoms.
+//!
+//! The first time a denom is received, a local asset is created with the configured existential
+//! deposit, decimals and fee ratio, and a symbol and name generated from its base denom.
+
+use super::*;
+use composable_traits::{
+	assets::{BasicAssetMetadata, MutateMetadata},
+	currency::{CurrencyFactory, RangeId},
+	defi::DeFiComposableConfig,
+	xcm::assets::{RemoteAssetRegistryMutate, XcmAssetLocation},
+};
+use frame_support::{dispatch::DispatchError, traits::Get, transactional};
+use ibc_trait::ibc_denom_to_foreign_asset_id;
+
+/// Symbol used when none can be generated from the base denom.
+pub const DEFAULT_SYMBOL: &[u8] = b"IBC";
+
+/// Metadata generated for a denom, e.g. `ATOM` for `transfer/channel-0/uatom`.
+pub fn denom_metadata(denom: &str) -> Option<BasicAssetMetadata> {
+	let base = PrefixedDenom::from_str(denom)
+		.map(|denom| denom.base_denom().as_str().to_string())
+		.unwrap_or_else(|_| denom.to_string());
+	// Cosmos denoms are usually in micro units, e.g. `uatom`
+	let unit = match base.strip_prefix('u') {
+		Some(unit) if unit.len() > 1 => unit,
+		_ => base.as_str(),
+	};
+	let mut symbol: Vec<u8> = unit
+		.bytes()
+		.filter(u8::is_ascii_alphanumeric)
+		.map(|byte| byte.to_ascii_uppercase())
+		.take(8)
+		.collect();
+	if symbol.is_empty() {
+		symbol = DEFAULT_SYMBOL.to_vec();
+	}
+	let name: Vec<u8> = base.bytes().take(32).collect();
+	BasicAssetMetadata::try_from(&symbol, &name)
+}
+
+impl<T: Config> Pallet<T>
+where
+	<<T as Config>::AssetRegistry as RemoteAssetRegistryMutate>::AssetId:
+		From<<T as DeFiComposableConfig>::MayBeAssetId>,
+	<<T as Config>::AssetRegistry as RemoteAssetRegistryMutate>::AssetNativeLocation:
+		From<XcmAssetLocation>,
+{
+	/// Creates and registers the local asset of a denom received for the first time.
+	#[transactional]
+	pub fn register_ibc_asset(
+		denom: &str,
+	) -> Result<<T as DeFiComposableConfig>::MayBeAssetId, DispatchError> {
+		let asset_id =
+			T::CurrencyFactory::create(RangeId::IBC_ASSETS, T::IbcAssetExistentialDeposit::get())?;
+		T::AssetRegistry::set_reserve_location(
+			asset_id.into(),
+			ibc_denom_to_foreign_asset_id(denom).into(),
+			T::IbcAssetFeeRatio::get(),
+			Some(T::IbcAssetDecimals::get()),
+		)?;
+		if let Some(metadata) = denom_metadata(denom) {
+			<T::CurrencyFactory as MutateMetadata>::set_metadata(asset_id, metadata)?;
+		}
+		Self::register_asset_id(asset_id, denom.as_bytes().to_vec());
+		Self::deposit_event(Event::<T>::IbcAssetRegistered {
+			asset_id,
+			denom: denom.as_bytes().to_vec(),
+		});
+		Ok(asset_id)
+	}
+}
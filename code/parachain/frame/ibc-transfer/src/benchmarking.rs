@@ -3,8 +3,9 @@
 #[allow(unused)]
 use super::*;
 use composable_traits::{
+	assets::BasicAssetMetadata,
 	currency::{CurrencyFactory, RangeId},
-	defi::DeFiComposableConfig,
+	defi::{DeFiComposableConfig, Ratio},
 	xcm::assets::{RemoteAssetRegistryMutate, XcmAssetLocation},
 };
 use core::str::FromStr;
@@ -36,7 +37,7 @@ use ibc_trait::{get_channel_escrow_address, ibc_denom_to_foreign_asset_id, OpenC
 use primitives::currency::CurrencyId;
 use sp_runtime::{
 	traits::{IdentifyAccount, Zero},
-	AccountId32,
+	AccountId32, Percent,
 };
 
 pub fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
@@ -98,7 +99,8 @@ benchmarks! {
 			source_channel: channel_id.to_string().as_bytes().to_vec(),
 			timeout_timestamp: 1690894363u64.saturating_mul(1000000000),
 			timeout_height: 2000,
-			revision_number: None
+			revision_number: None,
+			memo: None
 		};
 
 		Pallet::<T>::register_asset_id(asset_id.into(), denom.as_bytes().to_vec());
@@ -150,6 +152,46 @@ benchmarks! {
 		}.into())
 	}
 
+	set_rate_limit {
+		let params = RateLimitParams {
+			asset_id: CurrencyId::PICA.into(),
+			max_inflow: Percent::from_percent(10),
+			max_outflow: Percent::from_percent(10),
+			period: 100u32.into(),
+		};
+		let channel_id = b"channel-0".to_vec();
+		let denom = b"PICA".to_vec();
+	}:_(RawOrigin::Root, channel_id.clone(), denom.clone(), params)
+	verify {
+		assert_last_event::<T>(Event::<T>::RateLimitSet { channel_id, denom, params }.into())
+	}
+
+	remove_rate_limit {
+		let params = RateLimitParams {
+			asset_id: CurrencyId::PICA.into(),
+			max_inflow: Percent::from_percent(10),
+			max_outflow: Percent::from_percent(10),
+			period: 100u32.into(),
+		};
+		let channel_id = b"channel-0".to_vec();
+		let denom = b"PICA".to_vec();
+		Pallet::<T>::set_rate_limit(RawOrigin::Root.into(), channel_id.clone(), denom.clone(), params).unwrap();
+	}:_(RawOrigin::Root, channel_id.clone(), denom.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::RateLimitRemoved { channel_id, denom }.into())
+	}
+
+	set_asset_metadata {
+		// cspell:disable-next
+		let asset_id = Pallet::<T>::register_ibc_asset("transfer/channel-15/uatom").unwrap();
+		let metadata = BasicAssetMetadata::try_from(b"ATOM", b"Cosmos Hub Atom").unwrap();
+		let decimals = Some(6);
+		let ratio = Some(Ratio::from_inner(1_000_000));
+	}:_(RawOrigin::Root, asset_id, metadata, decimals, ratio)
+	verify {
+		assert_last_event::<T>(Event::<T>::IbcAssetMetadataUpdated { asset_id, decimals, ratio }.into())
+	}
+
 	on_chan_open_init {
 		let mut output = HandlerOutputBuilder::new();
 		let port_id = PortId::transfer();
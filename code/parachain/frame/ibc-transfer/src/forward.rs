@@ -0,0 +1,191 @@
+//! Packet-forward middleware.
+//!
+//! Received tokens whose memo is `{"forward": {"receiver", "port", "channel", "timeout"}}` are
+//! credited to an intermediate account and sent over `port`/`channel` to `receiver`. If the
+//! forwarded packet fails, the refunded tokens are sent back to the original sender.
+
+use super::*;
+use frame_support::{
+	dispatch::DispatchError,
+	traits::{Get, UnixTime},
+	transactional,
+};
+use ibc::{
+	applications::transfer::{
+		acknowledgement::ACK_SUCCESS_B64, msgs::transfer::MsgTransfer, packet::PacketData,
+		PrefixedCoin,
+	},
+	timestamp::Timestamp,
+	Height,
+};
+use ibc_trait::{channel_id_from_bytes, MEMO_KEY};
+use serde::Deserialize;
+use sp_runtime::traits::{AccountIdConversion, IdentifyAccount, SaturatedConversion};
+
+/// Memo of a packet to forward.
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+pub struct ForwardMemo {
+	pub forward: Forward,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+pub struct Forward {
+	/// Receiver on the next chain
+	pub receiver: String,
+	/// Port to forward over
+	pub port: String,
+	/// Channel to forward over
+	pub channel: String,
+	/// Timeout timestamp of the forwarded packet in nanoseconds, the received packet timeout
+	/// timestamp is used if not set
+	#[serde(default)]
+	pub timeout: Option<u64>,
+	/// Memo of the forwarded packet, e.g. the forward instructions of the next hop
+	#[serde(default)]
+	pub next: Option<serde_json::Value>,
+}
+
+/// Returns the memo of ICS-20 packet data, if any.
+pub fn packet_memo(data: &[u8]) -> Option<String> {
+	serde_json::from_slice::<serde_json::Value>(data)
+		.ok()?
+		.get(MEMO_KEY)?
+		.as_str()
+		.map(String::from)
+		.filter(|memo| !memo.is_empty())
+}
+
+/// Returns the forward instructions of a memo, if any.
+pub fn forward_memo(memo: &str) -> Option<Forward> {
+	serde_json::from_str::<ForwardMemo>(memo).ok().map(|memo| memo.forward)
+}
+
+fn to_signer<T: Config>(account: &T::AccountId) -> Result<Signer, Error<T>> {
+	Signer::from_str(&format!("0x{}", hex::encode(account.encode())))
+		.map_err(|_| Error::<T>::Utf8Error)
+}
+
+impl<T: Config> Pallet<T> {
+	/// Intermediate account holding the tokens of a forwarded packet.
+	pub fn forward_account(channel_id: &ChannelId, sequence: u64) -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating((
+			b"forward",
+			channel_id.to_string().as_bytes(),
+			sequence,
+		))
+	}
+
+	/// Receives the packet on an intermediate account and forwards the tokens, all or nothing.
+	#[transactional]
+	pub(crate) fn do_forward(
+		output: &mut ModuleOutputBuilder,
+		packet: &Packet,
+		forward: Forward,
+	) -> Result<(), DispatchError> {
+		let data: PacketData =
+			serde_json::from_slice(&packet.data).map_err(|_| Error::<T>::InvalidMemo)?;
+		let sequence: u64 = packet.sequence.into();
+		let account = Self::forward_account(&packet.destination_channel, sequence);
+		let intermediate = to_signer::<T>(&account)?;
+
+		let received = PacketData { receiver: intermediate.clone(), ..data.clone() };
+		let received = Packet {
+			data: serde_json::to_vec(&received).map_err(|_| Error::<T>::InvalidMemo)?,
+			..packet.clone()
+		};
+		T::IbcHandler::on_receive_packet(output, &received)
+			.map_err(|_| Error::<T>::ForwardFailed)?;
+
+		let denom = received_denom(packet, &data.token.denom);
+		let forward_channel =
+			ChannelId::from_str(&forward.channel).map_err(|_| Error::<T>::InvalidMemo)?;
+		Self::record_outflow(
+			&forward_channel,
+			denom.to_string().as_bytes(),
+			data.token.amount.as_u256().low_u128().into(),
+		)?;
+
+		let timeout_timestamp = match forward.timeout {
+			Some(timeout) =>
+				Timestamp::from_nanoseconds(timeout).map_err(|_| Error::<T>::InvalidTimestamp)?,
+			None => packet.timeout_timestamp,
+		};
+		let msg = MsgTransfer {
+			source_port: PortId::from_str(&forward.port).map_err(|_| Error::<T>::InvalidMemo)?,
+			source_channel: forward_channel,
+			token: PrefixedCoin { denom, amount: data.token.amount },
+			sender: intermediate,
+			receiver: Signer::from_str(&forward.receiver).map_err(|_| Error::<T>::InvalidMemo)?,
+			timeout_height: Height::zero(),
+			timeout_timestamp,
+		};
+		T::IbcHandler::send_transfer(msg, forward.next.map(|next| next.to_string()))
+			.map_err(|_| Error::<T>::ForwardFailed)?;
+
+		let channel_id = packet.destination_channel.to_string().as_bytes().to_vec();
+		PendingForwards::<T>::insert(
+			&account,
+			PendingForward { channel_id: channel_id.clone(), sender: data.sender.as_ref().into() },
+		);
+		Self::deposit_event(Event::<T>::PacketForwarded {
+			channel_id,
+			sequence,
+			forward_channel: forward.channel.into_bytes(),
+			receiver: forward.receiver.into_bytes(),
+		});
+		Ok(())
+	}
+
+	/// Settles a forwarded packet once acknowledged or timed out, refunding the original sender
+	/// on failure.
+	pub(crate) fn on_forward_result(packet: &Packet, success: bool) {
+		let data = match serde_json::from_slice::<PacketData>(&packet.data) {
+			Ok(data) => data,
+			Err(_) => return,
+		};
+		let account = match T::AccountIdConversion::try_from(data.sender.clone()) {
+			Ok(account) => account.into_account(),
+			Err(_) => return,
+		};
+		let pending = match PendingForwards::<T>::take(&account) {
+			Some(pending) => pending,
+			None => return,
+		};
+		if success {
+			return
+		}
+
+		// The refunded tokens are back on the intermediate account, return them to the sender
+		let refund = || -> Result<(), Error<T>> {
+			let now: u64 = T::UnixTime::now().as_nanos().saturated_into();
+			let timeout = now.saturating_add(T::RefundTimeout::get());
+			let msg = MsgTransfer {
+				source_port: PortId::transfer(),
+				source_channel: channel_id_from_bytes(pending.channel_id.clone())
+					.map_err(|_| Error::<T>::InvalidParams)?,
+				token: data.token.clone(),
+				sender: data.sender.clone(),
+				receiver: Signer::from_str(
+					&String::from_utf8(pending.sender.clone())
+						.map_err(|_| Error::<T>::Utf8Error)?,
+				)
+				.map_err(|_| Error::<T>::Utf8Error)?,
+				timeout_height: Height::zero(),
+				timeout_timestamp: Timestamp::from_nanoseconds(timeout)
+					.map_err(|_| Error::<T>::InvalidTimestamp)?,
+			};
+			T::IbcHandler::send_transfer(msg, None).map_err(|_| Error::<T>::TransferFailed)
+		};
+		let refunded = refund().is_ok();
+		Self::deposit_event(Event::<T>::ForwardRefunded {
+			channel_id: pending.channel_id,
+			receiver: pending.sender,
+			success: refunded,
+		});
+	}
+}
+
+/// Returns true for a successful ICS-20 acknowledgement.
+pub fn is_success_ack(acknowledgement: &Acknowledgement) -> bool {
+	acknowledgement.as_ref() == ACK_SUCCESS_B64
+}
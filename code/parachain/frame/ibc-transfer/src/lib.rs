@@ -27,8 +27,11 @@
 
 //! IBC Transfer module for the runtime.
 //! Implements Ibc transfer application
+pub mod assets;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod forward;
+pub mod rate_limit;
 mod weight;
 use frame_support::dispatch::Weight;
 pub use weight::WeightInfo;
@@ -39,7 +42,10 @@ use frame_system::ensure_signed;
 use ibc::{
 	applications::transfer::{
 		acknowledgement::{Acknowledgement as Ics20Acknowledgement, ACK_ERR_STR},
-		VERSION,
+		denom::{is_receiver_chain_source, PrefixedDenom, TracePrefix},
+		msgs::transfer::MsgTransfer,
+		packet::PacketData,
+		PrefixedCoin, VERSION,
 	},
 	core::{
 		ics04_channel::{
@@ -54,8 +60,9 @@ use ibc::{
 	},
 	signer::Signer,
 };
-use ibc_trait::{CallbackWeight, IbcTrait};
+use ibc_trait::{CallbackWeight, IbcTrait, IbcTransfer};
 pub use pallet::*;
+use rate_limit::FlowDirection;
 use scale_info::prelude::{
 	format,
 	string::{String, ToString},
@@ -66,24 +73,23 @@ use sp_std::{prelude::*, str::FromStr};
 pub mod pallet {
 	use super::*;
 	use composable_traits::{
-		currency::CurrencyFactory,
-		defi::DeFiComposableConfig,
-		xcm::assets::{RemoteAssetRegistryInspect, RemoteAssetRegistryMutate},
+		assets::{BasicAssetMetadata, MutateMetadata},
+		currency::{CurrencyFactory, Exponent},
+		defi::{DeFiComposableConfig, Ratio},
+		xcm::assets::{RemoteAssetRegistryInspect, RemoteAssetRegistryMutate, XcmAssetLocation},
 	};
 	use frame_support::{
 		dispatch::DispatchResult,
 		pallet_prelude::*,
 		traits::{
 			fungibles::{Inspect, Mutate, Transfer},
-			EnsureOrigin, Get,
+			EnsureOrigin, Get, UnixTime,
 		},
 		PalletId, Twox64Concat,
 	};
 	use frame_system::pallet_prelude::*;
 	use ibc::{
-		applications::transfer::{
-			msgs::transfer::MsgTransfer, Amount, PrefixedCoin, PrefixedDenom,
-		},
+		applications::transfer::{msgs::transfer::MsgTransfer, Amount, PrefixedCoin},
 		core::ics04_channel::channel::{ChannelEnd, State},
 		signer::Signer,
 	};
@@ -93,7 +99,10 @@ pub mod pallet {
 		OpenChannelParams,
 	};
 	use primitives::currency::CurrencyId;
-	use sp_runtime::{traits::IdentifyAccount, AccountId32};
+	use sp_runtime::{
+		traits::{IdentifyAccount, Saturating, Zero},
+		AccountId32, Percent,
+	};
 
 	#[derive(
 		frame_support::RuntimeDebug,
@@ -134,6 +143,75 @@ pub mod pallet {
 		/// Revision number, only needed when making a transfer to a parachain
 		/// in which case this should be the para id
 		pub revision_number: Option<u64>,
+		/// Memo of the ICS-20 packet, valid utf8 string bytes
+		pub memo: Option<Vec<u8>>,
+	}
+
+	#[derive(
+		frame_support::RuntimeDebug,
+		PartialEq,
+		Eq,
+		scale_info::TypeInfo,
+		Encode,
+		Decode,
+		Copy,
+		Clone,
+		codec::MaxEncodedLen,
+	)]
+	pub struct RateLimitParams<AssetId, BlockNumber> {
+		/// Local asset whose total issuance is the supply of the denom
+		pub asset_id: AssetId,
+		/// Share of the supply allowed to be received during a window
+		pub max_inflow: Percent,
+		/// Share of the supply allowed to be sent during a window
+		pub max_outflow: Percent,
+		/// Length of a window in blocks
+		pub period: BlockNumber,
+	}
+
+	#[derive(
+		frame_support::RuntimeDebug,
+		PartialEq,
+		Eq,
+		scale_info::TypeInfo,
+		Encode,
+		Decode,
+		Copy,
+		Clone,
+		codec::MaxEncodedLen,
+	)]
+	pub struct RateLimit<AssetId, Balance, BlockNumber> {
+		pub params: RateLimitParams<AssetId, BlockNumber>,
+		/// Tokens received during the current window
+		pub inflow: Balance,
+		/// Tokens sent during the current window
+		pub outflow: Balance,
+		/// Supply at the start of the current window
+		pub supply: Balance,
+		/// Block at which the current window ends
+		pub period_end: BlockNumber,
+	}
+
+	pub type RateLimitParamsOf<T> = RateLimitParams<
+		<T as DeFiComposableConfig>::MayBeAssetId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	pub type RateLimitOf<T> = RateLimit<
+		<T as DeFiComposableConfig>::MayBeAssetId,
+		<T as DeFiComposableConfig>::Balance,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// Packet forwarded by the packet-forward middleware, pending acknowledgement
+	#[derive(
+		frame_support::RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo, Encode, Decode, Clone,
+	)]
+	pub struct PendingForward {
+		/// Channel the tokens were received on
+		pub channel_id: Vec<u8>,
+		/// Sender of the tokens on the source chain, valid utf8 string bytes
+		pub sender: Vec<u8>,
 	}
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -145,9 +223,9 @@ pub mod pallet {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		/// A type for creating local asset Ids
 		type CurrencyFactory: CurrencyFactory<
-			AssetId = <Self as DeFiComposableConfig>::MayBeAssetId,
-			Balance = <Self as DeFiComposableConfig>::Balance,
-		>;
+				AssetId = <Self as DeFiComposableConfig>::MayBeAssetId,
+				Balance = <Self as DeFiComposableConfig>::Balance,
+			> + MutateMetadata<AssetId = <Self as DeFiComposableConfig>::MayBeAssetId>;
 
 		/// Account Id Conversion from SS58 string or hex string
 		type AccountIdConversion: TryFrom<Signer>
@@ -173,6 +251,21 @@ pub mod pallet {
 		type AdminOrigin: EnsureOrigin<Self::Origin>;
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+		/// Time in nanoseconds added to the current time to get the timeout of the refund of a
+		/// failed forwarded packet
+		#[pallet::constant]
+		type RefundTimeout: Get<u64>;
+		/// Decimals of the local assets created for incoming IBC denoms
+		#[pallet::constant]
+		type IbcAssetDecimals: Get<Exponent>;
+		/// Existential deposit of the local assets created for incoming IBC denoms
+		#[pallet::constant]
+		type IbcAssetExistentialDeposit: Get<<Self as DeFiComposableConfig>::Balance>;
+		/// Amount of a new IBC asset paying for a unit of native fees, the asset cannot pay fees
+		/// if not set
+		type IbcAssetFeeRatio: Get<Option<Ratio>>;
+		/// Time source of the refund timeouts
+		type UnixTime: UnixTime;
 		type WeightInfo: WeightInfo;
 	}
 
@@ -193,6 +286,29 @@ pub mod pallet {
 		ChannelOpened { channel_id: Vec<u8>, port_id: Vec<u8> },
 		/// Pallet params updated
 		PalletParamsUpdated { send_enabled: bool, receive_enabled: bool },
+		/// Tokens have been received with a memo
+		MemoReceived { channel_id: Vec<u8>, sequence: u64, memo: Vec<u8> },
+		/// Received tokens have been forwarded to another chain
+		PacketForwarded {
+			channel_id: Vec<u8>,
+			sequence: u64,
+			forward_channel: Vec<u8>,
+			receiver: Vec<u8>,
+		},
+		/// A forwarded packet failed and the tokens have been sent back to the original sender
+		ForwardRefunded { channel_id: Vec<u8>, receiver: Vec<u8>, success: bool },
+		/// Rate limit of a denom over a channel set
+		RateLimitSet { channel_id: Vec<u8>, denom: Vec<u8>, params: RateLimitParamsOf<T> },
+		/// Rate limit of a denom over a channel removed
+		RateLimitRemoved { channel_id: Vec<u8>, denom: Vec<u8> },
+		/// Local asset created for a denom received for the first time
+		IbcAssetRegistered { asset_id: <T as DeFiComposableConfig>::MayBeAssetId, denom: Vec<u8> },
+		/// Metadata, decimals and fee ratio of an IBC asset overridden
+		IbcAssetMetadataUpdated {
+			asset_id: <T as DeFiComposableConfig>::MayBeAssetId,
+			decimals: Option<Exponent>,
+			ratio: Option<Ratio>,
+		},
 	}
 
 	/// Errors inform users that something went wrong.
@@ -216,6 +332,16 @@ pub mod pallet {
 		InvalidParams,
 		/// Error opening channel
 		ChannelInitError,
+		/// Invalid forward memo
+		InvalidMemo,
+		/// Received tokens could not be forwarded
+		ForwardFailed,
+		/// The transfer exceeds the rate limit of the channel
+		RateLimitExceeded,
+		/// No rate limit for this channel and denom
+		RateLimitNotFound,
+		/// Asset is not an IBC asset
+		UnknownIbcAsset,
 	}
 
 	#[pallet::storage]
@@ -239,6 +365,37 @@ pub mod pallet {
 	/// ChannelIds open from this module
 	pub type ChannelIds<T: Config> = StorageValue<_, Vec<Vec<u8>>, ValueQuery>;
 
+	#[pallet::storage]
+	/// Rate limits by channel and denom, denoms represented as utf8 string bytes of their
+	/// representation on this chain
+	pub type RateLimits<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		Vec<u8>,
+		Blake2_128Concat,
+		Vec<u8>,
+		RateLimitOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// End of the rate limit window the outflow of a sent packet was recorded in, by channel and
+	/// sequence, until the packet is acknowledged or times out
+	pub type OutflowWindows<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		Vec<u8>,
+		Blake2_128Concat,
+		u64,
+		<T as frame_system::Config>::BlockNumber,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// Packets forwarded by the packet-forward middleware, by intermediate account
+	pub type PendingForwards<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, PendingForward, OptionQuery>;
+
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
 	// These functions materialize as "extrinsic", which are often compared to transactions.
 	// Dispatch able functions must be annotated with a weight and must return a DispatchResult.
@@ -247,6 +404,10 @@ pub mod pallet {
 	where
 		CurrencyId: From<<T as DeFiComposableConfig>::MayBeAssetId>,
 		AccountId32: From<T::AccountId>,
+		<<T as Config>::AssetRegistry as RemoteAssetRegistryMutate>::AssetId:
+			From<<T as DeFiComposableConfig>::MayBeAssetId>,
+		<<T as Config>::AssetRegistry as RemoteAssetRegistryMutate>::AssetNativeLocation:
+			From<XcmAssetLocation>,
 	{
 		#[frame_support::transactional]
 		#[pallet::weight(<T as Config>::WeightInfo::transfer())]
@@ -279,12 +440,17 @@ pub mod pallet {
 				})
 				.map_err(|_| Error::<T>::Utf8Error)?;
 			let to = String::from_utf8(params.to).map_err(|_| Error::<T>::Utf8Error)?;
+			let memo = params
+				.memo
+				.map(|memo| String::from_utf8(memo).map_err(|_| Error::<T>::Utf8Error))
+				.transpose()?;
 			let denom = PrefixedDenom::from_str(&denom).map_err(|_| Error::<T>::InvalidIbcDenom)?;
 			let ibc_amount = Amount::from_str(&format!("{:?}", amount))
 				.map_err(|_| Error::<T>::InvalidAmount)?;
 			let coin = PrefixedCoin { denom, amount: ibc_amount };
 			let source_channel = channel_id_from_bytes(params.source_channel.clone())
 				.map_err(|_| Error::<T>::Utf8Error)?;
+			Self::record_outflow(&source_channel, coin.denom.to_string().as_bytes(), amount)?;
 			let source_port = PortId::transfer();
 			let revision_number = if let Some(rev_number) = params.revision_number {
 				rev_number
@@ -308,7 +474,7 @@ pub mod pallet {
 				)
 				.map_err(|_| Error::<T>::InvalidTimestamp)?,
 			};
-			T::IbcHandler::send_transfer(data).map_err(|_| Error::<T>::TransferFailed)?;
+			T::IbcHandler::send_transfer(data, memo).map_err(|_| Error::<T>::TransferFailed)?;
 
 			Self::deposit_event(Event::<T>::TokenTransferInitiated {
 				from: origin,
@@ -357,6 +523,78 @@ pub mod pallet {
 			});
 			Ok(())
 		}
+
+		/// Set the rate limit of a denom over a channel, starting a new window.
+		/// Denom should be the representation of the tokens on this chain.
+		#[pallet::weight(<T as Config>::WeightInfo::set_rate_limit())]
+		pub fn set_rate_limit(
+			origin: OriginFor<T>,
+			channel_id: Vec<u8>,
+			denom: Vec<u8>,
+			params: RateLimitParamsOf<T>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			channel_id_from_bytes(channel_id.clone()).map_err(|_| Error::<T>::InvalidParams)?;
+			let prefixed = String::from_utf8(denom.clone()).map_err(|_| Error::<T>::Utf8Error)?;
+			PrefixedDenom::from_str(&prefixed).map_err(|_| Error::<T>::InvalidIbcDenom)?;
+			ensure!(!params.period.is_zero(), Error::<T>::InvalidParams);
+			RateLimits::<T>::insert(
+				&channel_id,
+				&denom,
+				RateLimit {
+					params,
+					inflow: Zero::zero(),
+					outflow: Zero::zero(),
+					supply: <T as Config>::MultiCurrency::total_issuance(params.asset_id),
+					period_end: frame_system::Pallet::<T>::block_number()
+						.saturating_add(params.period),
+				},
+			);
+			Self::deposit_event(Event::<T>::RateLimitSet { channel_id, denom, params });
+			Ok(())
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::remove_rate_limit())]
+		pub fn remove_rate_limit(
+			origin: OriginFor<T>,
+			channel_id: Vec<u8>,
+			denom: Vec<u8>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				RateLimits::<T>::contains_key(&channel_id, &denom),
+				Error::<T>::RateLimitNotFound
+			);
+			RateLimits::<T>::remove(&channel_id, &denom);
+			Self::deposit_event(Event::<T>::RateLimitRemoved { channel_id, denom });
+			Ok(())
+		}
+
+		/// Override the generated metadata, the decimals and the fee ratio of an IBC asset.
+		/// As in the assets registry, `None` decimals stand for 12 and a `None` ratio for an asset
+		/// that cannot pay fees.
+		#[frame_support::transactional]
+		#[pallet::weight(<T as Config>::WeightInfo::set_asset_metadata())]
+		pub fn set_asset_metadata(
+			origin: OriginFor<T>,
+			asset_id: <T as DeFiComposableConfig>::MayBeAssetId,
+			metadata: BasicAssetMetadata,
+			decimals: Option<Exponent>,
+			ratio: Option<Ratio>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let denom = IbcAssetIds::<T>::get(asset_id).ok_or(Error::<T>::UnknownIbcAsset)?;
+			let denom = String::from_utf8(denom).map_err(|_| Error::<T>::Utf8Error)?;
+			<T::CurrencyFactory as MutateMetadata>::set_metadata(asset_id, metadata)?;
+			T::AssetRegistry::set_reserve_location(
+				asset_id.into(),
+				ibc_trait::ibc_denom_to_foreign_asset_id(&denom).into(),
+				ratio,
+				decimals,
+			)?;
+			Self::deposit_event(Event::<T>::IbcAssetMetadataUpdated { asset_id, decimals, ratio });
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -368,12 +606,51 @@ pub mod pallet {
 			Params::<T>::get().receive_enabled
 		}
 
+		/// Sends an ICS-20 transfer initiated by another module, counting it towards the outflow
+		/// quota of its channel.
+		#[frame_support::transactional]
+		pub fn do_send_transfer(
+			data: MsgTransfer<PrefixedCoin>,
+			memo: Option<String>,
+		) -> DispatchResult {
+			ensure!(Self::is_send_enabled(), Error::<T>::TransferFailed);
+			let amount = data.token.amount.as_u256().low_u128().into();
+			Self::record_outflow(
+				&data.source_channel,
+				data.token.denom.to_string().as_bytes(),
+				amount,
+			)?;
+			T::IbcHandler::send_transfer(data, memo).map_err(|_| Error::<T>::TransferFailed.into())
+		}
+
 		pub fn register_asset_id(
 			asset_id: <T as DeFiComposableConfig>::MayBeAssetId,
 			denom: Vec<u8>,
 		) {
 			IbcAssetIds::<T>::insert(asset_id, denom)
 		}
+
+		/// Receives the tokens of a packet, forwarding them if asked by the memo, all or nothing.
+		#[frame_support::transactional]
+		pub(crate) fn do_receive(
+			output: &mut ModuleOutputBuilder,
+			packet: &Packet,
+			memo: Option<&str>,
+		) -> DispatchResult {
+			let data: PacketData =
+				serde_json::from_slice(&packet.data).map_err(|_| Error::<T>::InvalidParams)?;
+			Self::record_flow(
+				packet.destination_channel.to_string().as_bytes(),
+				received_denom(packet, &data.token.denom).to_string().as_bytes(),
+				data.token.amount.as_u256().low_u128().into(),
+				FlowDirection::Inflow,
+			)?;
+			match memo.and_then(forward::forward_memo) {
+				Some(forward) => Self::do_forward(output, packet, forward),
+				None => T::IbcHandler::on_receive_packet(output, packet)
+					.map_err(|_| Error::<T>::TransferFailed.into()),
+			}
+		}
 	}
 
 	impl<T: Config> Pallet<T>
@@ -424,6 +701,35 @@ pub mod pallet {
 	}
 }
 
+/// Denom of the tokens of a received packet on this chain.
+pub fn received_denom(packet: &Packet, denom: &PrefixedDenom) -> PrefixedDenom {
+	let mut denom = denom.clone();
+	if is_receiver_chain_source(packet.source_port.clone(), packet.source_channel, &denom) {
+		denom.remove_trace_prefix(&TracePrefix::new(
+			packet.source_port.clone(),
+			packet.source_channel,
+		));
+	} else {
+		denom.add_trace_prefix(TracePrefix::new(
+			packet.destination_port.clone(),
+			packet.destination_channel,
+		));
+	}
+	denom
+}
+
+impl<T: Config> IbcTransfer for Pallet<T> {
+	fn send_transfer(
+		data: MsgTransfer<PrefixedCoin>,
+		memo: Option<String>,
+	) -> Result<(), ibc_trait::Error> {
+		Self::do_send_transfer(data, memo).map_err(|e| {
+			log::debug!(target: "pallet_ibc_transfer", "send_transfer: {:?}", e);
+			ibc_trait::Error::SendTransferError
+		})
+	}
+}
+
 #[derive(Clone)]
 pub struct IbcCallbackHandler<T: Config>(PhantomData<T>);
 
@@ -536,9 +842,18 @@ impl<T: Config + Send + Sync> Module for IbcCallbackHandler<T> {
 		packet: &Packet,
 		_relayer: &Signer,
 	) -> OnRecvPacketAck {
-		let ack = if T::IbcHandler::on_receive_packet(output, packet).is_err() {
+		let memo = forward::packet_memo(&packet.data);
+		let received = Pallet::<T>::do_receive(output, packet, memo.as_deref()).is_ok();
+		let ack = if !received {
 			ACK_ERR_STR.to_string().as_bytes().to_vec()
 		} else {
+			if let Some(memo) = memo {
+				Pallet::<T>::deposit_event(Event::<T>::MemoReceived {
+					channel_id: packet.destination_channel.to_string().as_bytes().to_vec(),
+					sequence: packet.sequence.into(),
+					memo: memo.into_bytes(),
+				});
+			}
 			Ics20Acknowledgement::success().as_ref().to_vec()
 		};
 		let packet = packet.clone();
@@ -559,7 +874,11 @@ impl<T: Config + Send + Sync> Module for IbcCallbackHandler<T> {
 	) -> Result<(), Ics04Error> {
 		T::IbcHandler::on_ack_packet(output, packet, acknowledgement).map_err(|_| {
 			Ics04Error::app_module("[ibc-transfer]: Error processing acknowledgement".to_string())
-		})
+		})?;
+		let success = forward::is_success_ack(acknowledgement);
+		Pallet::<T>::settle_outflow(packet, !success);
+		Pallet::<T>::on_forward_result(packet, success);
+		Ok(())
 	}
 
 	fn on_timeout_packet(
@@ -570,7 +889,10 @@ impl<T: Config + Send + Sync> Module for IbcCallbackHandler<T> {
 	) -> Result<(), Ics04Error> {
 		T::IbcHandler::on_timeout_packet(output, packet).map_err(|_| {
 			Ics04Error::app_module("[ibc-transfer]: Error processing timeout packet".to_string())
-		})
+		})?;
+		Pallet::<T>::settle_outflow(packet, true);
+		Pallet::<T>::on_forward_result(packet, false);
+		Ok(())
 	}
 }
 
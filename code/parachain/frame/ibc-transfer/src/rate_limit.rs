@@ -0,0 +1,87 @@
+//! Rate limits of the tokens flowing through a channel.
+//!
+//! Governance sets, per channel and denom, the share of the supply that may flow in and out
+//! during a window of blocks. The supply is the total issuance of the local asset when the
+//! window starts. Transfers and received packets exceeding a quota are rejected.
+
+use super::*;
+use frame_support::{ensure, traits::fungibles::Inspect};
+use ibc::applications::transfer::packet::PacketData;
+use sp_runtime::traits::{Saturating, Zero};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FlowDirection {
+	Inflow,
+	Outflow,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Records a flow of `amount` tokens of `denom` through `channel_id`, failing if it
+	/// exceeds the quota of the current window.
+	pub fn record_flow(
+		channel_id: &[u8],
+		denom: &[u8],
+		amount: <T as DeFiComposableConfig>::Balance,
+		direction: FlowDirection,
+	) -> Result<(), Error<T>> {
+		RateLimits::<T>::try_mutate(channel_id, denom, |limit| {
+			let limit = match limit {
+				Some(limit) => limit,
+				None => return Ok(()),
+			};
+			let now = frame_system::Pallet::<T>::block_number();
+			if now >= limit.period_end {
+				limit.inflow = Zero::zero();
+				limit.outflow = Zero::zero();
+				limit.supply = <T as Config>::MultiCurrency::total_issuance(limit.params.asset_id);
+				limit.period_end = now.saturating_add(limit.params.period);
+			}
+			let (flow, quota) = match direction {
+				FlowDirection::Inflow => (&mut limit.inflow, limit.params.max_inflow),
+				FlowDirection::Outflow => (&mut limit.outflow, limit.params.max_outflow),
+			};
+			let total = flow.saturating_add(amount);
+			ensure!(total <= quota.mul_floor(limit.supply), Error::<T>::RateLimitExceeded);
+			*flow = total;
+			Ok(())
+		})
+	}
+
+	/// Records the outflow of the next packet sent over `channel_id`, along with the window it
+	/// is counted in.
+	pub(crate) fn record_outflow(
+		channel_id: &ChannelId,
+		denom: &[u8],
+		amount: <T as DeFiComposableConfig>::Balance,
+	) -> Result<(), Error<T>> {
+		let channel = channel_id.to_string().into_bytes();
+		Self::record_flow(&channel, denom, amount, FlowDirection::Outflow)?;
+		if let Some(limit) = RateLimits::<T>::get(&channel, denom) {
+			let sequence = T::IbcHandler::next_sequence_send(&PortId::transfer(), channel_id)
+				.map_err(|_| Error::<T>::TransferFailed)?;
+			OutflowWindows::<T>::insert(&channel, sequence, limit.period_end);
+		}
+		Ok(())
+	}
+
+	/// Settles the outflow of a packet sent from this chain, reverting it if the packet failed
+	/// during the window it was counted in.
+	pub(crate) fn settle_outflow(packet: &Packet, failed: bool) {
+		let channel = packet.source_channel.to_string().into_bytes();
+		let window = match OutflowWindows::<T>::take(&channel, u64::from(packet.sequence)) {
+			Some(window) => window,
+			None => return,
+		};
+		let data = match serde_json::from_slice::<PacketData>(&packet.data) {
+			Ok(data) if failed => data,
+			_ => return,
+		};
+		let amount: <T as DeFiComposableConfig>::Balance =
+			data.token.amount.as_u256().low_u128().into();
+		RateLimits::<T>::mutate(&channel, data.token.denom.to_string().as_bytes(), |limit| {
+			if let Some(limit) = limit.as_mut().filter(|limit| limit.period_end == window) {
+				limit.outflow = limit.outflow.saturating_sub(amount);
+			}
+		});
+	}
+}
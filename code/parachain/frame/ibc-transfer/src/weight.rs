@@ -4,6 +4,9 @@ pub trait WeightInfo {
 	fn transfer() -> Weight;
 	fn open_channel() -> Weight;
 	fn set_pallet_params() -> Weight;
+	fn set_rate_limit() -> Weight;
+	fn remove_rate_limit() -> Weight;
+	fn set_asset_metadata() -> Weight;
 	fn on_chan_open_init() -> Weight;
 	fn on_chan_open_try() -> Weight;
 	fn on_chan_open_ack() -> Weight;
@@ -28,6 +31,18 @@ impl WeightInfo for () {
 		0
 	}
 
+	fn set_rate_limit() -> Weight {
+		0
+	}
+
+	fn remove_rate_limit() -> Weight {
+		0
+	}
+
+	fn set_asset_metadata() -> Weight {
+		0
+	}
+
 	fn on_chan_open_init() -> Weight {
 		0
 	}
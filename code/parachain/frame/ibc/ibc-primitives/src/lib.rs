@@ -188,6 +188,14 @@ pub struct QueryPacketCommitmentsResponse {
 	pub height: u64,
 }
 
+/// Packet commitments or acknowledgements for a set of sequences, with their trie keys
+#[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, Ord, PartialOrd)]
+pub struct QueryPacketStatesResponse {
+	pub states: Vec<PacketState>,
+	pub trie_keys: Vec<Vec<u8>>,
+	pub height: u64,
+}
+
 #[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, Ord, PartialOrd)]
 pub struct QueryPacketAcknowledgementResponse {
 	pub ack: Vec<u8>,
@@ -3,7 +3,9 @@ use ibc::{core::ics04_channel::packet::Packet, events::IbcEvent as RawIbcEvent};
 use ibc_runtime_api::IbcRuntimeApi;
 use pallet_ibc::events::IbcEvent;
 use sc_client_api::HeaderBackend;
+use serde::{Deserialize, Serialize};
 use sp_api::{ApiRef, BlockId, BlockT, ProvideRuntimeApi};
+use sp_runtime::traits::NumberFor;
 
 /// Filter out none relayer events and modify
 /// Fetch actual packet and acknowledgements from off chain storage and modify packets
@@ -145,3 +147,151 @@ where
 		_ => Some(event),
 	}
 }
+
+/// Relayer events of a finalized block
+#[derive(Serialize, Deserialize)]
+pub struct BlockEvents<Hash, BlockNumber> {
+	/// Block hash
+	pub block_hash: Hash,
+	/// Block number
+	pub block_number: BlockNumber,
+	/// Events deposited in the block
+	pub events: Vec<RawIbcEvent>,
+}
+
+/// Relayer events of a block matching the filter, none if there is no such event
+pub fn finalized_block_events<C, Block>(
+	client: &C,
+	hash: Block::Hash,
+	filter: &EventFilter,
+) -> Option<BlockEvents<Block::Hash, NumberFor<Block>>>
+where
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: IbcRuntimeApi<Block>,
+	Block: BlockT,
+{
+	let at = BlockId::Hash(hash);
+	let api = client.runtime_api();
+	let block_number = client.number(hash).ok().flatten()?;
+	let events = api
+		.block_events(&at, None)
+		.ok()?
+		.into_iter()
+		.filter(|event| filter.matches(event))
+		.filter_map(|event| filter_map_pallet_event::<C, Block>(&at, &api, event))
+		.collect::<Vec<_>>();
+	(!events.is_empty()).then(|| BlockEvents { block_hash: hash, block_number, events })
+}
+
+/// Filter of the events streamed by `ibc_subscribeEvents`.
+/// Events not related to a channel, e.g. client updates, are filtered out if any field is set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventFilter {
+	/// Only stream events of this channel
+	pub channel_id: Option<String>,
+	/// Only stream events of this port
+	pub port_id: Option<String>,
+}
+
+impl EventFilter {
+	/// Returns true if the event is related to the channel and port of the filter.
+	/// Packet events match on either end of the packet.
+	pub fn matches(&self, event: &IbcEvent) -> bool {
+		if self.channel_id.is_none() && self.port_id.is_none() {
+			return true
+		}
+		channel_ends(event).into_iter().any(|(port_id, channel_id)| {
+			self.port_id.as_ref().map_or(true, |filter| filter.as_bytes() == port_id) &&
+				self.channel_id
+					.as_ref()
+					.map_or(true, |filter| Some(filter.as_bytes()) == channel_id)
+		})
+	}
+}
+
+/// Port and channel of the channel ends an event is related to
+fn channel_ends(event: &IbcEvent) -> Vec<(&[u8], Option<&[u8]>)> {
+	match event {
+		IbcEvent::OpenInitChannel { port_id, channel_id, .. } |
+		IbcEvent::OpenTryChannel { port_id, channel_id, .. } |
+		IbcEvent::OpenAckChannel { port_id, channel_id, .. } |
+		IbcEvent::OpenConfirmChannel { port_id, channel_id, .. } |
+		IbcEvent::CloseConfirmChannel { port_id, channel_id, .. } =>
+			vec![(port_id.as_slice(), channel_id.as_deref())],
+		IbcEvent::CloseInitChannel { port_id, channel_id, .. } |
+		IbcEvent::AcknowledgePacket { port_id, channel_id, .. } |
+		IbcEvent::TimeoutPacket { port_id, channel_id, .. } |
+		IbcEvent::TimeoutOnClosePacket { port_id, channel_id, .. } =>
+			vec![(port_id.as_slice(), Some(channel_id.as_slice()))],
+		IbcEvent::SendPacket { port_id, channel_id, dest_port, dest_channel, .. } |
+		IbcEvent::ReceivePacket { port_id, channel_id, dest_port, dest_channel, .. } |
+		IbcEvent::WriteAcknowledgement { port_id, channel_id, dest_port, dest_channel, .. } => vec![
+			(port_id.as_slice(), Some(channel_id.as_slice())),
+			(dest_port.as_slice(), Some(dest_channel.as_slice())),
+		],
+		_ => vec![],
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn send_packet() -> IbcEvent {
+		IbcEvent::SendPacket {
+			revision_height: 1,
+			revision_number: 0,
+			port_id: b"transfer".to_vec(),
+			channel_id: b"channel-0".to_vec(),
+			dest_port: b"transfer".to_vec(),
+			dest_channel: b"channel-7".to_vec(),
+			sequence: 1,
+		}
+	}
+
+	fn update_client() -> IbcEvent {
+		IbcEvent::UpdateClient {
+			client_id: b"11-beefy-0".to_vec(),
+			client_type: b"11-beefy".to_vec(),
+			revision_height: 1,
+			revision_number: 0,
+			consensus_height: 1,
+			consensus_revision_number: 0,
+		}
+	}
+
+	fn filter(port_id: Option<&str>, channel_id: Option<&str>) -> EventFilter {
+		EventFilter { port_id: port_id.map(Into::into), channel_id: channel_id.map(Into::into) }
+	}
+
+	#[test]
+	fn empty_filter_matches_everything() {
+		assert!(EventFilter::default().matches(&send_packet()));
+		assert!(EventFilter::default().matches(&update_client()));
+	}
+
+	#[test]
+	fn filter_matches_either_end_of_packets() {
+		assert!(filter(None, Some("channel-0")).matches(&send_packet()));
+		assert!(filter(None, Some("channel-7")).matches(&send_packet()));
+		assert!(filter(Some("transfer"), Some("channel-7")).matches(&send_packet()));
+		assert!(!filter(None, Some("channel-1")).matches(&send_packet()));
+		assert!(!filter(Some("wasm.addr"), Some("channel-0")).matches(&send_packet()));
+	}
+
+	#[test]
+	fn filter_skips_events_without_channel() {
+		assert!(!filter(Some("transfer"), None).matches(&update_client()));
+		let open_init = IbcEvent::OpenInitChannel {
+			revision_height: 1,
+			revision_number: 0,
+			port_id: b"transfer".to_vec(),
+			channel_id: None,
+			connection_id: b"connection-0".to_vec(),
+			counterparty_port_id: b"transfer".to_vec(),
+			counterparty_channel_id: None,
+		};
+		assert!(filter(Some("transfer"), None).matches(&open_init));
+		assert!(!filter(None, Some("channel-0")).matches(&open_init));
+	}
+}
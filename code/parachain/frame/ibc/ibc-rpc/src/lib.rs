@@ -29,6 +29,7 @@ use ibc::{
 
 use std::{collections::HashMap, fmt::Display, str::FromStr, sync::Arc};
 
+use futures::{FutureExt, StreamExt};
 use ibc_proto::{
 	cosmos::base::{query::v1beta1::PageResponse, v1beta1::Coin},
 	ibc::{
@@ -54,14 +55,15 @@ use jsonrpsee::{
 	core::{Error as RpcError, RpcResult as Result},
 	proc_macros::rpc,
 	types::{error::CallError, ErrorObject},
+	PendingSubscription,
 };
 use pallet_ibc::events::IbcEvent;
 use sc_chain_spec::Properties;
-use sc_client_api::{BlockBackend, ProofProvider};
+use sc_client_api::{BlockBackend, BlockchainEvents, ProofProvider};
 use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_core::{blake2_256, storage::ChildInfo};
+use sp_core::{blake2_256, storage::ChildInfo, traits::SpawnNamed};
 use sp_runtime::{
 	generic::BlockId,
 	traits::{BlakeTwo256, Block as BlockT, Header as HeaderT},
@@ -69,7 +71,7 @@ use sp_runtime::{
 use sp_trie::TrieMut;
 use tendermint_proto::Protobuf;
 pub mod events;
-use events::filter_map_pallet_event;
+use events::{filter_map_pallet_event, finalized_block_events, BlockEvents, EventFilter};
 
 /// Connection handshake proof
 #[derive(Serialize, Deserialize)]
@@ -109,6 +111,17 @@ pub struct Proof {
 	pub height: ibc_proto::ibc::core::client::v1::Height,
 }
 
+/// Packet commitments or acknowledgements with a single proof for all of them
+#[derive(Serialize, Deserialize)]
+pub struct PacketStatesWithProof {
+	/// Packet states, sequences without a commitment or acknowledgement are skipped
+	pub states: Vec<PacketState>,
+	/// Trie proof for all the packet states
+	pub proof: Vec<u8>,
+	/// Proof height
+	pub height: ibc_proto::ibc::core::client::v1::Height,
+}
+
 /// Generate trie proof given inputs to trie and keys
 pub fn generate_raw_proof(inputs: Vec<(Vec<u8>, Vec<u8>)>, keys: Vec<Vec<u8>>) -> Result<Vec<u8>> {
 	let keys = keys.iter().collect::<Vec<_>>();
@@ -281,6 +294,26 @@ where
 		port_id: String,
 	) -> Result<QueryPacketAcknowledgementsResponse>;
 
+	/// Query packet commitments for many sequences with a single proof for all of them
+	#[method(name = "ibc_queryPacketCommitmentsWithProof")]
+	fn query_packet_commitments_with_proof(
+		&self,
+		height: u32,
+		channel_id: String,
+		port_id: String,
+		seqs: Vec<u64>,
+	) -> Result<PacketStatesWithProof>;
+
+	/// Query packet acknowledgements for many sequences with a single proof for all of them
+	#[method(name = "ibc_queryPacketAcknowledgementsWithProof")]
+	fn query_packet_acknowledgements_with_proof(
+		&self,
+		height: u32,
+		channel_id: String,
+		port_id: String,
+		seqs: Vec<u64>,
+	) -> Result<PacketStatesWithProof>;
+
 	/// Query unreceived packet commitments
 	#[method(name = "ibc_queryUnreceivedPackets")]
 	fn query_unreceived_packets(
@@ -377,6 +410,15 @@ where
 		&self,
 		block_numbers: Vec<BlockNumberOrHash<Hash>>,
 	) -> Result<HashMap<String, Vec<RawIbcEvent>>>;
+
+	/// Subscribe to the Ibc Events of finalized blocks, optionally only those of a channel or
+	/// port. Blocks without matching events are skipped.
+	#[subscription(
+		name = "ibc_subscribeEvents" => "ibc_events",
+		unsubscribe = "ibc_unsubscribeEvents",
+		item = BlockEvents<Hash, BlockNumber>,
+	)]
+	fn subscribe_events(&self, filter: Option<EventFilter>);
 }
 
 /// Converts a runtime trap into an RPC error.
@@ -393,13 +435,75 @@ pub struct IbcRpcHandler<C, B> {
 	client: Arc<C>,
 	/// A copy of the chain properties.
 	pub chain_props: Properties,
+	/// Executor of the subscription tasks.
+	executor: Arc<dyn SpawnNamed>,
 	_marker: std::marker::PhantomData<B>,
 }
 
 impl<C, B> IbcRpcHandler<C, B> {
 	/// Create new `IbcRpcHandler` with the given reference to the client.
-	pub fn new(client: Arc<C>, chain_props: Properties) -> Self {
-		Self { client, chain_props, _marker: Default::default() }
+	pub fn new(client: Arc<C>, chain_props: Properties, executor: Arc<dyn SpawnNamed>) -> Self {
+		Self { client, chain_props, executor, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> IbcRpcHandler<C, Block>
+where
+	Block: BlockT,
+	C: Send
+		+ Sync
+		+ 'static
+		+ ProvideRuntimeApi<Block>
+		+ HeaderBackend<Block>
+		+ ProofProvider<Block>,
+	C::Api: IbcRuntimeApi<Block>,
+{
+	/// Generates a single proof for packet states read at `at`.
+	fn packet_states_with_proof(
+		&self,
+		at: &BlockId<Block>,
+		result: ibc_primitives::QueryPacketStatesResponse,
+	) -> Result<PacketStatesWithProof> {
+		let api = self.client.runtime_api();
+		let para_id = api
+			.para_id(at)
+			.map_err(|_| runtime_error_into_rpc_error("Error getting para id"))?;
+		let child_trie_key = api
+			.child_trie_key(at)
+			.map_err(|_| runtime_error_into_rpc_error("Failed to get child trie key"))?;
+		let child_info = ChildInfo::new_default(&child_trie_key);
+		let mut keys = result.trie_keys;
+		let proof = self
+			.client
+			.read_child_proof(at, &child_info, &mut keys.iter_mut().map(|nodes| &nodes[..]))
+			.map_err(runtime_error_into_rpc_error)?
+			.iter_nodes()
+			.collect::<Vec<_>>()
+			.encode();
+		let states = result
+			.states
+			.into_iter()
+			.map(|packet_state| {
+				let port_id = String::from_utf8(packet_state.port_id)
+					.map_err(|_| runtime_error_into_rpc_error("Failed to decode port id"))?;
+				let channel_id = String::from_utf8(packet_state.channel_id)
+					.map_err(|_| runtime_error_into_rpc_error("Failed to decode channel id"))?;
+				Ok(PacketState {
+					port_id,
+					channel_id,
+					sequence: packet_state.sequence,
+					data: packet_state.data,
+				})
+			})
+			.collect::<Result<Vec<_>>>()?;
+		Ok(PacketStatesWithProof {
+			states,
+			proof,
+			height: ibc_proto::ibc::core::client::v1::Height {
+				revision_number: para_id.into(),
+				revision_height: result.height,
+			},
+		})
 	}
 }
 
@@ -413,7 +517,8 @@ where
 		+ ProvideRuntimeApi<Block>
 		+ HeaderBackend<Block>
 		+ ProofProvider<Block>
-		+ BlockBackend<Block>,
+		+ BlockBackend<Block>
+		+ BlockchainEvents<Block>,
 	C::Api: IbcRuntimeApi<Block>,
 {
 	fn query_packets(
@@ -1080,6 +1185,50 @@ where
 		})
 	}
 
+	fn query_packet_commitments_with_proof(
+		&self,
+		height: u32,
+		channel_id: String,
+		port_id: String,
+		seqs: Vec<u64>,
+	) -> Result<PacketStatesWithProof> {
+		let api = self.client.runtime_api();
+		let at = BlockId::Number(height.into());
+		let result = api
+			.packet_commitments_batch(
+				&at,
+				channel_id.as_bytes().to_vec(),
+				port_id.as_bytes().to_vec(),
+				seqs,
+			)
+			.ok()
+			.flatten()
+			.ok_or_else(|| runtime_error_into_rpc_error("Failed to fetch commitments"))?;
+		self.packet_states_with_proof(&at, result)
+	}
+
+	fn query_packet_acknowledgements_with_proof(
+		&self,
+		height: u32,
+		channel_id: String,
+		port_id: String,
+		seqs: Vec<u64>,
+	) -> Result<PacketStatesWithProof> {
+		let api = self.client.runtime_api();
+		let at = BlockId::Number(height.into());
+		let result = api
+			.packet_acknowledgements_batch(
+				&at,
+				channel_id.as_bytes().to_vec(),
+				port_id.as_bytes().to_vec(),
+				seqs,
+			)
+			.ok()
+			.flatten()
+			.ok_or_else(|| runtime_error_into_rpc_error("Failed to fetch acknowledgements"))?;
+		self.packet_states_with_proof(&at, result)
+	}
+
 	fn query_unreceived_packets(
 		&self,
 		height: u32,
@@ -1454,4 +1603,31 @@ where
 		}
 		Ok(events)
 	}
+
+	fn subscribe_events(&self, pending: PendingSubscription, filter: Option<EventFilter>) {
+		let client = self.client.clone();
+		let filter = filter.unwrap_or_default();
+		let stream = self
+			.client
+			.finality_notification_stream()
+			.flat_map(move |notification| {
+				// Blocks finalized at once are notified together
+				let events = notification
+					.tree_route
+					.iter()
+					.copied()
+					.chain(std::iter::once(notification.hash))
+					.filter_map(|hash| finalized_block_events(&*client, hash, &filter))
+					.collect::<Vec<_>>();
+				futures::stream::iter(events)
+			})
+			.boxed();
+
+		let fut = async move {
+			if let Some(mut sink) = pending.accept() {
+				sink.pipe_from_stream(stream).await;
+			}
+		};
+		self.executor.spawn("ibc-rpc-subscription", Some("rpc"), fut.boxed());
+	}
 }
@@ -60,6 +60,12 @@ sp_api::decl_runtime_apis! {
 
 		fn packet_acknowledgements(channel_id: Vec<u8>, port_id: Vec<u8>) -> Option<QueryPacketAcknowledgementsResponse>;
 
+		/// Packet commitments for many sequences, with the trie keys needed to prove them at once
+		fn packet_commitments_batch(channel_id: Vec<u8>, port_id: Vec<u8>, seqs: Vec<u64>) -> Option<QueryPacketStatesResponse>;
+
+		/// Acknowledgements for many sequences, with the trie keys needed to prove them at once
+		fn packet_acknowledgements_batch(channel_id: Vec<u8>, port_id: Vec<u8>, seqs: Vec<u64>) -> Option<QueryPacketStatesResponse>;
+
 		fn unreceived_packets(channel_id: Vec<u8>, port_id: Vec<u8>, seqs: Vec<u64>) -> Option<Vec<u64>>;
 
 		fn unreceived_acknowledgements(channel_id: Vec<u8>, port_id: Vec<u8>, seqs: Vec<u64>) -> Option<Vec<u64>>;
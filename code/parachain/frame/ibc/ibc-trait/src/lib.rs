@@ -14,7 +14,7 @@ use ibc::{
 			packet::Packet,
 		},
 		ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
-		ics26_routing::context::ModuleOutputBuilder,
+		ics26_routing::context::{Module, ModuleId, ModuleOutputBuilder},
 	},
 	signer::Signer,
 };
@@ -52,6 +52,8 @@ pub enum Error {
 	BindPortError,
 	/// Failed to initialize a new channel
 	ChannelInitError,
+	/// Failed to close a channel
+	ChannelCloseError,
 	/// Failed to decode a value
 	DecodingError,
 	/// Failed to decode commitment prefix
@@ -60,6 +62,9 @@ pub enum Error {
 	Other,
 }
 
+/// Key of the memo in the ICS-20 packet data.
+pub const MEMO_KEY: &str = "memo";
+
 #[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
 /// Captures all parameters needed to initialize a channel
 pub struct OpenChannelParams {
@@ -86,6 +91,12 @@ impl TryFrom<&OpenChannelParams> for Order {
 	}
 }
 
+/// Sends ICS-20 transfers on behalf of other modules, through the checks of the transfer
+/// application such as its rate limits.
+pub trait IbcTransfer {
+	fn send_transfer(data: MsgTransfer<PrefixedCoin>, memo: Option<String>) -> Result<(), Error>;
+}
+
 /// Captures the functions modules can use to interact with the ibc pallet
 /// Currently allows modules to register packets and create channels
 pub trait IbcTrait {
@@ -94,6 +105,12 @@ pub trait IbcTrait {
 	fn send_packet(data: SendPacketData) -> Result<(), Error>;
 	/// Allows a module to open a channel
 	fn open_channel(port_id: PortId, channel_end: ChannelEnd) -> Result<ChannelId, Error>;
+	/// Allows a module to close a channel bound to its port
+	fn close_channel(port_id: PortId, channel_id: ChannelId) -> Result<(), Error>;
+	/// Returns the channel end of a port and channel pair
+	fn channel_end(port_id: &PortId, channel_id: &ChannelId) -> Result<ChannelEnd, Error>;
+	/// Returns the sequence of the next packet sent over a channel
+	fn next_sequence_send(port_id: &PortId, channel_id: &ChannelId) -> Result<u64, Error>;
 	/// Modules use this to write acknowledgements into the ibc store
 	/// To be used in a successful execution of OnRecvPacket callback
 	fn write_acknowledgement(packet: &Packet, ack: Vec<u8>) -> Result<(), Error>;
@@ -101,8 +118,8 @@ pub trait IbcTrait {
 	// We need these here because the implementation of the ics20 requires access to the context
 	// which is defined in pallet-ibc, we cannot import the context in ibc-transfer pallet because
 	// it would cause a cyclic dependency
-	/// Perform an ibc token transfer
-	fn send_transfer(data: MsgTransfer<PrefixedCoin>) -> Result<(), Error>;
+	/// Perform an ibc token transfer, the memo is added to the packet data when set
+	fn send_transfer(data: MsgTransfer<PrefixedCoin>, memo: Option<String>) -> Result<(), Error>;
 	/// on receive packet callback for ibc token transfer
 	fn on_receive_packet(output: &mut ModuleOutputBuilder, packet: &Packet) -> Result<(), Error>;
 	/// on acknowledgement packet callback for ibc token transfer
@@ -117,6 +134,13 @@ pub trait IbcTrait {
 	fn create_client() -> Result<ClientId, Error>;
 	#[cfg(feature = "runtime-benchmarks")]
 	fn create_connection(client_id: ClientId, connection_id: ConnectionId) -> Result<(), Error>;
+	/// Store an open unordered channel over the connection, ready to send packets.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_channel(
+		port_id: PortId,
+		channel_id: ChannelId,
+		connection_id: ConnectionId,
+	) -> Result<(), Error>;
 }
 
 /// Callback Weight
@@ -197,6 +221,41 @@ impl CallbackWeight for () {
 	}
 }
 
+/// Router for modules whose ports are not known statically by pallet-ibc, e.g. ports bound by
+/// smart contracts.
+/// The ibc router consults it for every port and module id it does not handle itself.
+pub trait ModuleRouter: Default + Clone {
+	/// Returns the callback handler of the module, if routed by this router.
+	fn get_route_mut(&mut self, module_id: &ModuleId) -> Option<&mut dyn Module>;
+
+	/// Whether this router handles the module.
+	fn has_route(module_id: &ModuleId) -> bool;
+
+	/// Returns the module bound to the port, if any.
+	fn lookup_module_by_port(port_id: &PortId) -> Option<ModuleId>;
+
+	/// Returns the callback weight handler of the module bound to the port, if any.
+	fn get_weight(port_id: &PortId) -> Option<Box<dyn CallbackWeight>>;
+}
+
+impl ModuleRouter for () {
+	fn get_route_mut(&mut self, _module_id: &ModuleId) -> Option<&mut dyn Module> {
+		None
+	}
+
+	fn has_route(_module_id: &ModuleId) -> bool {
+		false
+	}
+
+	fn lookup_module_by_port(_port_id: &PortId) -> Option<ModuleId> {
+		None
+	}
+
+	fn get_weight(_port_id: &PortId) -> Option<Box<dyn CallbackWeight>> {
+		None
+	}
+}
+
 /// Get port_id from raw bytes
 pub fn port_id_from_bytes(port: Vec<u8>) -> Result<PortId, Error> {
 	PortId::from_str(&String::from_utf8(port).map_err(|_| Error::DecodingError)?)
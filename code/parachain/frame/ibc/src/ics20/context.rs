@@ -1,7 +1,6 @@
 use super::super::*;
 use crate::routing::Context;
 use composable_traits::{
-	currency::{CurrencyFactory, RangeId},
 	defi::DeFiComposableConfig,
 	xcm::assets::{RemoteAssetRegistryInspect, RemoteAssetRegistryMutate, XcmAssetLocation},
 };
@@ -16,7 +15,7 @@ use ibc::{
 };
 use ibc_trait::{get_channel_escrow_address, ibc_denom_to_foreign_asset_id};
 use primitives::currency::CurrencyId;
-use sp_runtime::traits::{IdentifyAccount, Zero};
+use sp_runtime::traits::IdentifyAccount;
 
 impl<T: Config + Send + Sync> Ics20Reader for Context<T>
 where
@@ -126,24 +125,11 @@ where
 			) {
 			asset_id
 		} else {
-			let local_asset_id = <T as transfer::Config>::CurrencyFactory::create(
-				RangeId::IBC_ASSETS,
-				<T as DeFiComposableConfig>::Balance::zero(),
-			)
-			.map_err(|_| {
-				Ics20Error::unknown_msg_type("Error creating a local asset id".to_string())
-			})?;
-			<T as transfer::Config>::AssetRegistry::set_reserve_location(
-				local_asset_id.into(),
-				foreign_asset_id.into(),
-				None,
-				None,
-			)
-			.map_err(|_| {
-				Ics20Error::unknown_msg_type("Error registering local asset id".to_string())
-			})?;
-			transfer::Pallet::<T>::register_asset_id(local_asset_id, denom.as_bytes().to_vec());
-			local_asset_id.into()
+			transfer::Pallet::<T>::register_ibc_asset(&denom)
+				.map_err(|_| {
+					Ics20Error::unknown_msg_type("Error registering local asset id".to_string())
+				})?
+				.into()
 		};
 
 		<<T as transfer::Config>::MultiCurrency as Mutate<T::AccountId>>::mint_into(
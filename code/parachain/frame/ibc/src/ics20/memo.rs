@@ -0,0 +1,91 @@
+use ibc::{
+	applications::transfer::{
+		context::Ics20Context, denom::is_sender_chain_source, error::Error as Ics20Error,
+		msgs::transfer::MsgTransfer, packet::PacketData, PrefixedCoin,
+	},
+	core::ics04_channel::{
+		context::{ChannelKeeper, ChannelReader},
+		handler::send_packet::send_packet,
+		packet::Packet,
+	},
+	handler::{HandlerOutput, HandlerOutputBuilder},
+};
+use ibc_trait::MEMO_KEY;
+use scale_info::prelude::{format, string::String};
+use sp_std::prelude::*;
+
+/// Encodes ICS-20 packet data with a memo.
+pub fn packet_data_with_memo(data: &PacketData, memo: String) -> Result<Vec<u8>, Ics20Error> {
+	let mut value = serde_json::to_value(data)
+		.map_err(|_| Ics20Error::unknown_msg_type("Error encoding packet data".into()))?;
+	value
+		.as_object_mut()
+		.ok_or_else(|| Ics20Error::unknown_msg_type("Invalid packet data".into()))?
+		.insert(MEMO_KEY.into(), serde_json::Value::String(memo));
+	serde_json::to_vec(&value)
+		.map_err(|_| Ics20Error::unknown_msg_type("Error encoding packet data".into()))
+}
+
+/// Same as [`ibc::applications::transfer::relay::send_transfer::send_transfer`], with a memo
+/// added to the packet data.
+pub fn send_transfer_with_memo<Ctx>(
+	ctx: &mut Ctx,
+	output: &mut HandlerOutputBuilder<()>,
+	msg: MsgTransfer<PrefixedCoin>,
+	memo: String,
+) -> Result<(), Ics20Error>
+where
+	Ctx: Ics20Context,
+{
+	if !ctx.is_send_enabled() {
+		return Err(Ics20Error::send_disabled())
+	}
+
+	let source_channel_end = ctx
+		.channel_end(&(msg.source_port.clone(), msg.source_channel))
+		.map_err(Ics20Error::ics04_channel)?;
+	let destination_port = source_channel_end.counterparty().port_id().clone();
+	let destination_channel = *source_channel_end.counterparty().channel_id().ok_or_else(|| {
+		Ics20Error::destination_channel_not_found(msg.source_port.clone(), msg.source_channel)
+	})?;
+	let sequence = ctx
+		.get_next_sequence_send(&(msg.source_port.clone(), msg.source_channel))
+		.map_err(Ics20Error::ics04_channel)?;
+
+	let token = msg.token;
+	let sender: <Ctx as Ics20Context>::AccountId =
+		msg.sender.clone().try_into().map_err(|_| Ics20Error::parse_account_failure())?;
+	if is_sender_chain_source(msg.source_port.clone(), msg.source_channel, &token.denom) {
+		let escrow_address =
+			ctx.get_channel_escrow_address(&msg.source_port, msg.source_channel)?;
+		ctx.send_coins(&sender, &escrow_address, &token)?;
+	} else {
+		ctx.burn_coins(&sender, &token)?;
+	}
+
+	let data = PacketData {
+		token: token.clone(),
+		sender: msg.sender.clone(),
+		receiver: msg.receiver.clone(),
+	};
+	let packet = Packet {
+		sequence,
+		source_port: msg.source_port,
+		source_channel: msg.source_channel,
+		destination_port,
+		destination_channel,
+		data: packet_data_with_memo(&data, memo)?,
+		timeout_height: msg.timeout_height,
+		timeout_timestamp: msg.timeout_timestamp,
+	};
+
+	let HandlerOutput { result, log, events } =
+		send_packet(ctx, packet).map_err(Ics20Error::ics04_channel)?;
+	ctx.store_packet_result(result).map_err(Ics20Error::ics04_channel)?;
+	output.merge_output(HandlerOutput::builder().with_log(log).with_events(events).with_result(()));
+	output.log(format!(
+		"IBC fungible token transfer: {} --({})--> {}",
+		msg.sender, token, msg.receiver
+	));
+	Ok(())
+}
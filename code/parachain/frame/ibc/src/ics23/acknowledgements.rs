@@ -17,6 +17,13 @@ use sp_std::{marker::PhantomData, prelude::*, str::FromStr};
 pub struct Acknowledgements<T>(PhantomData<T>);
 
 impl<T: Config> Acknowledgements<T> {
+	/// Trie key of an acknowledgement commitment, used to generate its proof
+	pub fn key((port_id, channel_id, sequence): (PortId, ChannelId, Sequence)) -> Vec<u8> {
+		let ack_path = AcksPath { port_id, channel_id, sequence };
+		let ack_path = format!("{}", ack_path);
+		apply_prefix(T::CONNECTION_PREFIX, vec![ack_path])
+	}
+
 	pub fn insert(
 		(port_id, channel_id, sequence): (PortId, ChannelId, Sequence),
 		ack: AcknowledgementCommitment,
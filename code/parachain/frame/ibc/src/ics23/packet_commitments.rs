@@ -17,6 +17,13 @@ use sp_std::{marker::PhantomData, prelude::*, str::FromStr};
 pub struct PacketCommitment<T>(PhantomData<T>);
 
 impl<T: Config> PacketCommitment<T> {
+	/// Trie key of a packet commitment, used to generate its proof
+	pub fn key((port_id, channel_id, sequence): (PortId, ChannelId, Sequence)) -> Vec<u8> {
+		let commitment_path = CommitmentsPath { port_id, channel_id, sequence };
+		let commitment_path = format!("{}", commitment_path);
+		apply_prefix(T::CONNECTION_PREFIX, vec![commitment_path])
+	}
+
 	pub fn insert(
 		(port_id, channel_id, sequence): (PortId, ChannelId, Sequence),
 		commitment: PacketCommitmentType,
@@ -38,6 +38,7 @@ use ibc::{
 			context::{ChannelKeeper, ChannelReader},
 			msgs::{
 				acknowledgement::Acknowledgement,
+				chan_close_init::{MsgChannelCloseInit, TYPE_URL as CHANNEL_CLOSE_INIT_TYPE_URL},
 				chan_open_init::{MsgChannelOpenInit, TYPE_URL as CHANNEL_OPEN_INIT_TYPE_URL},
 			},
 			packet::{Packet, Sequence},
@@ -62,7 +63,7 @@ use ibc_primitives::{
 	QueryConsensusStateResponse, QueryNextSequenceReceiveResponse,
 	QueryPacketAcknowledgementResponse, QueryPacketAcknowledgementsResponse,
 	QueryPacketCommitmentResponse, QueryPacketCommitmentsResponse, QueryPacketReceiptResponse,
-	SendPacketData,
+	QueryPacketStatesResponse, SendPacketData,
 };
 use ibc_trait::{
 	apply_prefix, channel_id_from_bytes, client_id_from_bytes, connection_id_from_bytes,
@@ -308,6 +309,62 @@ where
 		Ok(QueryPacketAcknowledgementsResponse { acks, height: host_height::<T>() })
 	}
 
+	/// Packet commitments for the given sequences with their trie keys, sequences without a
+	/// commitment are skipped
+	pub fn packet_commitments_batch(
+		channel_id_bytes: Vec<u8>,
+		port_id_bytes: Vec<u8>,
+		seqs: Vec<u64>,
+	) -> Result<QueryPacketStatesResponse, Error<T>> {
+		let channel_id = channel_id_from_bytes(channel_id_bytes.clone())
+			.map_err(|_| Error::<T>::DecodingError)?;
+		let port_id =
+			port_id_from_bytes(port_id_bytes.clone()).map_err(|_| Error::<T>::DecodingError)?;
+		let (states, trie_keys) = seqs
+			.into_iter()
+			.filter_map(|seq| {
+				let key = (port_id.clone(), channel_id, seq.into());
+				let commitment = PacketCommitment::<T>::get(key.clone())?;
+				let packet_state = PacketState {
+					port_id: port_id_bytes.clone(),
+					channel_id: channel_id_bytes.clone(),
+					sequence: seq,
+					data: commitment,
+				};
+				Some((packet_state, PacketCommitment::<T>::key(key)))
+			})
+			.unzip();
+		Ok(QueryPacketStatesResponse { states, trie_keys, height: host_height::<T>() })
+	}
+
+	/// Acknowledgements for the given sequences with their trie keys, sequences without an
+	/// acknowledgement are skipped
+	pub fn packet_acknowledgements_batch(
+		channel_id_bytes: Vec<u8>,
+		port_id_bytes: Vec<u8>,
+		seqs: Vec<u64>,
+	) -> Result<QueryPacketStatesResponse, Error<T>> {
+		let channel_id = channel_id_from_bytes(channel_id_bytes.clone())
+			.map_err(|_| Error::<T>::DecodingError)?;
+		let port_id =
+			port_id_from_bytes(port_id_bytes.clone()).map_err(|_| Error::<T>::DecodingError)?;
+		let (states, trie_keys) = seqs
+			.into_iter()
+			.filter_map(|seq| {
+				let key = (port_id.clone(), channel_id, seq.into());
+				let ack = Acknowledgements::<T>::get(key.clone())?;
+				let packet_state = PacketState {
+					port_id: port_id_bytes.clone(),
+					channel_id: channel_id_bytes.clone(),
+					sequence: seq,
+					data: ack,
+				};
+				Some((packet_state, Acknowledgements::<T>::key(key)))
+			})
+			.unzip();
+		Ok(QueryPacketStatesResponse { states, trie_keys, height: host_height::<T>() })
+	}
+
 	pub fn unreceived_packets(
 		channel_id: Vec<u8>,
 		port_id: Vec<u8>,
@@ -710,15 +767,65 @@ where
 		Ok(channel_id)
 	}
 
+	fn close_channel(port_id: PortId, channel_id: ChannelId) -> Result<(), IbcHandlerError> {
+		let mut ctx = crate::routing::Context::<T>::new();
+		// Signer does not matter in this case
+		let value = MsgChannelCloseInit {
+			port_id,
+			channel_id,
+			signer: Signer::from_str(MODULE_ID).map_err(|_| IbcHandlerError::ChannelCloseError)?,
+		}
+		.encode_vec();
+		let msg = ibc_proto::google::protobuf::Any {
+			type_url: CHANNEL_CLOSE_INIT_TYPE_URL.to_string(),
+			value,
+		};
+		let res = ibc::core::ics26_routing::handler::deliver::<
+			_,
+			crate::host_functions::HostFunctions,
+		>(&mut ctx, msg)
+		.map_err(|_| IbcHandlerError::ChannelCloseError)?;
+		Self::deposit_event(res.events.into());
+		Ok(())
+	}
+
+	fn channel_end(
+		port_id: &PortId,
+		channel_id: &ChannelId,
+	) -> Result<ChannelEnd, IbcHandlerError> {
+		let ctx = Context::<T>::default();
+		ctx.channel_end(&(port_id.clone(), *channel_id))
+			.map_err(|_| IbcHandlerError::ChannelOrPortError)
+	}
+
+	fn next_sequence_send(
+		port_id: &PortId,
+		channel_id: &ChannelId,
+	) -> Result<u64, IbcHandlerError> {
+		let ctx = Context::<T>::default();
+		ctx.get_next_sequence_send(&(port_id.clone(), *channel_id))
+			.map(u64::from)
+			.map_err(|_| IbcHandlerError::ChannelOrPortError)
+	}
+
 	fn send_transfer(
 		msg: ibc::applications::transfer::msgs::transfer::MsgTransfer<
 			ibc::applications::transfer::PrefixedCoin,
 		>,
+		memo: Option<String>,
 	) -> Result<(), IbcHandlerError> {
 		let mut handler_output = HandlerOutputBuilder::default();
 		let mut ctx = Context::<T>::default();
-		send_transfer::<_, _>(&mut ctx, &mut handler_output, msg)
-			.map_err(|_| IbcHandlerError::SendTransferError)?;
+		match memo {
+			Some(memo) => crate::ics20::memo::send_transfer_with_memo(
+				&mut ctx,
+				&mut handler_output,
+				msg,
+				memo,
+			),
+			None => send_transfer::<_, _>(&mut ctx, &mut handler_output, msg),
+		}
+		.map_err(|_| IbcHandlerError::SendTransferError)?;
 		let result = handler_output.with_result(());
 		Self::deposit_event(result.events.into());
 		Ok(())
@@ -849,6 +956,31 @@ where
 		ctx.store_connection_to_client(connection_id, &client_id).unwrap();
 		Ok(())
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_channel(
+		port_id: PortId,
+		channel_id: ChannelId,
+		connection_id: ConnectionId,
+	) -> Result<(), IbcHandlerError> {
+		use ibc::core::ics04_channel::{
+			channel::{Counterparty, Order, State},
+			Version,
+		};
+		let channel_end = ChannelEnd::new(
+			State::Open,
+			Order::Unordered,
+			Counterparty::new(port_id.clone(), Some(ChannelId::new(0))),
+			vec![connection_id.clone()],
+			Version::default(),
+		);
+		let mut ctx = crate::routing::Context::<T>::new();
+		ctx.store_channel((port_id.clone(), channel_id), &channel_end).unwrap();
+		ctx.store_connection_channels(connection_id, &(port_id.clone(), channel_id))
+			.unwrap();
+		ctx.store_next_sequence_send((port_id, channel_id), 1u64.into()).unwrap();
+		Ok(())
+	}
 }
 
 pub fn host_height<T: Config>() -> u64
@@ -146,6 +146,7 @@ pub mod pallet {
 		frame_system::Config
 		+ balances::Config
 		+ pallet_ibc_ping::Config
+		+ pallet_ibc_ica::Config
 		+ parachain_info::Config
 		+ transfer::Config
 		+ DeFiComposableConfig
@@ -170,6 +171,8 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 		/// Origin allowed to create light clients and initiate connections
 		type AdminOrigin: EnsureOrigin<Self::Origin>;
+		/// Router for modules binding ports dynamically, e.g. CosmWasm contracts
+		type Router: ibc_trait::ModuleRouter;
 	}
 
 	#[pallet::pallet]
@@ -15,7 +15,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
 	DispatchError, MultiSignature,
 };
-use std::time::{Duration, Instant};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use system::EnsureRoot;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -43,6 +43,7 @@ frame_support::construct_runtime!(
 		Balances: balances,
 		ParachainInfo: parachain_info,
 		Ping: pallet_ibc_ping,
+		Ica: pallet_ibc_ica,
 		IbcTransfer: transfer,
 		GovernanceRegistry: governance_registry,
 		AssetsRegistry: assets_registry,
@@ -94,12 +95,43 @@ impl pallet_ibc_ping::Config for Test {
 	type IbcHandler = Ibc;
 }
 
+parameter_types! {
+	pub const IcaPalletId: frame_support::PalletId = frame_support::PalletId(*b"test/ica");
+}
+
+#[derive(
+	PartialEq,
+	Eq,
+	Copy,
+	Clone,
+	codec::Encode,
+	codec::Decode,
+	codec::MaxEncodedLen,
+	scale_info::TypeInfo,
+	Debug,
+)]
+pub struct MaxStringSize;
+impl frame_support::traits::Get<u32> for MaxStringSize {
+	fn get() -> u32 {
+		100
+	}
+}
+
+impl pallet_ibc_ica::Config for Test {
+	type Event = Event;
+	type IbcHandler = Ibc;
+	type Call = Call;
+	type PalletId = IcaPalletId;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxStringSize = MaxStringSize;
+	type WeightInfo = ();
+}
+
 pub struct MockUnixTime;
 
 impl frame_support::traits::UnixTime for MockUnixTime {
 	fn now() -> Duration {
-		let now_time = Instant::now().elapsed();
-		now_time
+		SystemTime::now().duration_since(UNIX_EPOCH).unwrap()
 	}
 }
 
@@ -145,11 +177,16 @@ impl assets::Config for Test {
 	type CurrencyValidator = ValidateCurrencyId;
 	type WeightInfo = ();
 	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type Router = ();
 }
 
 parameter_types! {
 	pub const MaxLocks: u32 = 256;
 	pub const TransferPalletId: frame_support::PalletId = frame_support::PalletId(*b"transfer");
+	pub const RefundTimeout: u64 = 60 * 60 * 1_000_000_000;
+	pub const IbcAssetDecimals: composable_traits::currency::Exponent = 6;
+	pub const IbcAssetExistentialDeposit: Balance = 0;
+	pub const IbcAssetFeeRatio: Option<composable_traits::defi::Ratio> = None;
 }
 
 parameter_type_with_key! {
@@ -190,6 +227,11 @@ impl transfer::Config for Test {
 	type AccountIdConversion = IbcAccount<Test>;
 	type AssetRegistry = AssetsRegistry;
 	type AdminOrigin = EnsureRoot<AccountId>;
+	type RefundTimeout = RefundTimeout;
+	type IbcAssetDecimals = IbcAssetDecimals;
+	type IbcAssetExistentialDeposit = IbcAssetExistentialDeposit;
+	type IbcAssetFeeRatio = IbcAssetFeeRatio;
+	type UnixTime = MockUnixTime;
 	type WeightInfo = ();
 }
 
@@ -224,8 +266,16 @@ impl<T: pallet_ibc::Config> IdentifyAccount for IbcAccount<T> {
 
 impl TryFrom<Signer> for IbcAccount<Test> {
 	type Error = &'static str;
-	fn try_from(_: Signer) -> Result<Self, Self::Error> {
-		Ok(IbcAccount(AccountId::new([0; 32])))
+	/// Hex encoded accounts, such as forward accounts, are decoded, other signers map to a
+	/// default account.
+	fn try_from(signer: Signer) -> Result<Self, Self::Error> {
+		let account = signer
+			.as_ref()
+			.strip_prefix("0x")
+			.and_then(|account| hex::decode(account).ok())
+			.and_then(|account| <[u8; 32]>::try_from(account).ok())
+			.unwrap_or([0; 32]);
+		Ok(IbcAccount(AccountId::new(account)))
 	}
 }
 
@@ -11,6 +11,7 @@ use ibc::{
 		ics26_routing::context::ModuleId,
 	},
 };
+use ibc_trait::ModuleRouter;
 
 impl<T: Config + Sync + Send> PortReader for Context<T> {
 	fn lookup_module_by_port(&self, port_id: &PortId) -> Result<ModuleId, ICS05Error> {
@@ -19,7 +20,13 @@ impl<T: Config + Sync + Send> PortReader for Context<T> {
 				.map_err(|_| ICS05Error::module_not_found(port_id.clone()))?),
 			TRANSFER_PORT_ID => Ok(ModuleId::from_str(TRANSFER_MODULE_ID)
 				.map_err(|_| ICS05Error::module_not_found(port_id.clone()))?),
-			_ => Err(ICS05Error::module_not_found(port_id.clone())),
+			pallet_ibc_ica::HOST_PORT_ID => Ok(ModuleId::from_str(pallet_ibc_ica::MODULE_ID)
+				.map_err(|_| ICS05Error::module_not_found(port_id.clone()))?),
+			port if port.starts_with(pallet_ibc_ica::CONTROLLER_PORT_PREFIX) =>
+				Ok(ModuleId::from_str(pallet_ibc_ica::MODULE_ID)
+					.map_err(|_| ICS05Error::module_not_found(port_id.clone()))?),
+			_ => T::Router::lookup_module_by_port(port_id)
+				.ok_or_else(|| ICS05Error::module_not_found(port_id.clone())),
 		}
 	}
 }
@@ -4,6 +4,7 @@ use ibc::{
 	applications::transfer::MODULE_ID_STR as IBC_TRANSFER_MODULE_ID,
 	core::ics26_routing::context::{Ics26Context, Module, ModuleId, ReaderContext, Router},
 };
+use ibc_trait::ModuleRouter;
 use scale_info::prelude::string::ToString;
 
 #[derive(Clone)]
@@ -27,14 +28,18 @@ impl<T: Config + Send + Sync> Context<T> {
 #[derive(Clone)]
 pub struct IbcRouter<T: Config> {
 	pallet_ibc_ping: pallet_ibc_ping::IbcHandler<T>,
+	pallet_ibc_ica: pallet_ibc_ica::IbcHandler<T>,
 	ibc_transfer: transfer::IbcCallbackHandler<T>,
+	sub_router: T::Router,
 }
 
 impl<T: Config> Default for IbcRouter<T> {
 	fn default() -> Self {
 		Self {
 			pallet_ibc_ping: pallet_ibc_ping::IbcHandler::<T>::default(),
+			pallet_ibc_ica: pallet_ibc_ica::IbcHandler::<T>::default(),
 			ibc_transfer: transfer::IbcCallbackHandler::<T>::default(),
+			sub_router: T::Router::default(),
 		}
 	}
 }
@@ -43,16 +48,17 @@ impl<T: Config + Send + Sync> Router for IbcRouter<T> {
 	fn get_route_mut(&mut self, module_id: &impl Borrow<ModuleId>) -> Option<&mut dyn Module> {
 		match module_id.borrow().to_string().as_str() {
 			pallet_ibc_ping::MODULE_ID => Some(&mut self.pallet_ibc_ping),
+			pallet_ibc_ica::MODULE_ID => Some(&mut self.pallet_ibc_ica),
 			IBC_TRANSFER_MODULE_ID => Some(&mut self.ibc_transfer),
-			&_ => None,
+			&_ => self.sub_router.get_route_mut(module_id.borrow()),
 		}
 	}
 
 	fn has_route(&self, module_id: &impl Borrow<ModuleId>) -> bool {
 		matches!(
 			module_id.borrow().to_string().as_str(),
-			pallet_ibc_ping::MODULE_ID | IBC_TRANSFER_MODULE_ID
-		)
+			pallet_ibc_ping::MODULE_ID | pallet_ibc_ica::MODULE_ID | IBC_TRANSFER_MODULE_ID
+		) || T::Router::has_route(module_id.borrow())
 	}
 }
 
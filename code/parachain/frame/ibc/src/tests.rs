@@ -236,3 +236,445 @@ fn should_send_ping_packet() {
 		assert_eq!(offchain_packet.len(), 1);
 	})
 }
+
+#[test]
+fn packet_data_memo_round_trip() {
+	use ibc::applications::transfer::{packet::PacketData, PrefixedCoin};
+
+	let data = PacketData {
+		token: PrefixedCoin::from_str("100transfer/channel-0/uatom").unwrap(),
+		sender: Signer::from_str("alice").unwrap(),
+		receiver: Signer::from_str("bob").unwrap(),
+	};
+	let memo = r#"{"forward":{"receiver":"carol","port":"transfer","channel":"channel-1"}}"#;
+	let bytes = crate::ics20::memo::packet_data_with_memo(&data, memo.to_string()).unwrap();
+
+	assert_eq!(serde_json::from_slice::<PacketData>(&bytes).unwrap(), data);
+	assert_eq!(transfer::forward::packet_memo(&bytes).as_deref(), Some(memo));
+	let forward = transfer::forward::forward_memo(memo).unwrap();
+	assert_eq!(forward.receiver, "carol");
+	assert_eq!(forward.channel, "channel-1");
+	assert_eq!(forward.timeout, None);
+	assert!(transfer::forward::forward_memo("hello").is_none());
+}
+
+#[test]
+fn rate_limit_rejects_flows_over_quota() {
+	use frame_support::{assert_noop, traits::fungibles::Mutate};
+	use sp_runtime::Percent;
+	use transfer::{rate_limit::FlowDirection, RateLimitParams};
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let asset_id = 2;
+		let alice = AccountId32::new([1; 32]);
+		<Assets as Mutate<AccountId32>>::mint_into(asset_id, &alice, 1_000).unwrap();
+		let channel_id = b"channel-0".to_vec();
+		let denom = b"transfer/channel-0/uatom".to_vec();
+		let params = RateLimitParams {
+			asset_id,
+			max_inflow: Percent::from_percent(10),
+			max_outflow: Percent::from_percent(20),
+			period: 10,
+		};
+		assert_ok!(IbcTransfer::set_rate_limit(
+			Origin::root(),
+			channel_id.clone(),
+			denom.clone(),
+			params
+		));
+
+		assert_ok!(IbcTransfer::record_flow(&channel_id, &denom, 100, FlowDirection::Inflow));
+		assert_noop!(
+			IbcTransfer::record_flow(&channel_id, &denom, 1, FlowDirection::Inflow),
+			transfer::Error::<Test>::RateLimitExceeded
+		);
+		assert_ok!(IbcTransfer::record_flow(&channel_id, &denom, 200, FlowDirection::Outflow));
+		assert_noop!(
+			IbcTransfer::record_flow(&channel_id, &denom, 1, FlowDirection::Outflow),
+			transfer::Error::<Test>::RateLimitExceeded
+		);
+
+		// Quotas are restored once the window is over
+		System::set_block_number(11);
+		assert_ok!(IbcTransfer::record_flow(&channel_id, &denom, 100, FlowDirection::Inflow));
+
+		// Other denoms are not limited
+		assert_ok!(IbcTransfer::record_flow(
+			&channel_id,
+			b"PICA",
+			1_000_000,
+			FlowDirection::Outflow
+		));
+	});
+}
+
+#[test]
+fn ibc_assets_are_registered_with_metadata() {
+	use composable_traits::{assets::BasicAssetMetadata, defi::Ratio};
+	use transfer::assets::denom_metadata;
+
+	// cspell:disable-next
+	let metadata = denom_metadata("transfer/channel-0/uatom").unwrap();
+	assert_eq!(metadata, BasicAssetMetadata::try_from(b"ATOM", b"uatom").unwrap());
+	let metadata = denom_metadata("transfer/channel-0/gravity0xdac17f958d2ee523").unwrap();
+	assert_eq!(metadata.symbol.as_vec(), b"GRAVITY0");
+	assert_eq!(denom_metadata("transfer/channel-0/u-").unwrap().symbol.as_vec(), b"U");
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// cspell:disable-next
+		let denom = "transfer/channel-0/uatom";
+		let asset_id = IbcTransfer::register_ibc_asset(denom).unwrap();
+		assert_eq!(transfer::IbcAssetIds::<Test>::get(asset_id), Some(denom.as_bytes().to_vec()));
+		assert_eq!(
+			CurrencyFactory::get_assets_metadata(asset_id),
+			BasicAssetMetadata::try_from(b"ATOM", b"uatom")
+		);
+		assert_eq!(
+			AssetsRegistry::from_local_asset(asset_id).map(|foreign| foreign.decimals),
+			Some(Some(6))
+		);
+
+		let metadata = BasicAssetMetadata::try_from(b"ATOM", b"Cosmos Hub Atom");
+		let ratio = Some(Ratio::from_inner(1_000_000));
+		assert_ok!(IbcTransfer::set_asset_metadata(
+			Origin::root(),
+			asset_id,
+			metadata.clone().unwrap(),
+			Some(8),
+			ratio
+		));
+		assert_eq!(CurrencyFactory::get_assets_metadata(asset_id), metadata);
+		assert_eq!(AssetsRegistry::asset_ratio(asset_id), ratio);
+		assert_eq!(
+			AssetsRegistry::from_local_asset(asset_id).map(|foreign| foreign.decimals),
+			Some(Some(8))
+		);
+
+		// Decimals and ratio are unset by `None`
+		assert_ok!(IbcTransfer::set_asset_metadata(
+			Origin::root(),
+			asset_id,
+			metadata.clone().unwrap(),
+			None,
+			None
+		));
+		assert_eq!(CurrencyFactory::get_assets_metadata(asset_id), metadata);
+		assert_eq!(AssetsRegistry::asset_ratio(asset_id), None);
+		assert_eq!(
+			AssetsRegistry::from_local_asset(asset_id).map(|foreign| foreign.decimals),
+			Some(None)
+		);
+	});
+}
+
+// Opens the transfer channel-0 to channel-1 of the counterparty and enables transfers
+fn open_transfer_channel() {
+	use ibc::applications::transfer::VERSION;
+	use transfer::PalletParams;
+
+	frame_system::Pallet::<Test>::set_block_number(1_u32);
+
+	let mock_client_state = MockClientState::new(MockHeader::new(Height::new(0, 1)));
+	let mock_cs_state = MockConsensusState::new(MockHeader::new(Height::new(0, 1)));
+	let client_id = ClientId::new(mock_client_state.client_type(), 0).unwrap();
+	let counterparty_client_id = ClientId::new(mock_client_state.client_type(), 1).unwrap();
+	let msg = MsgCreateAnyClient::new(
+		AnyClientState::Mock(mock_client_state),
+		AnyConsensusState::Mock(mock_cs_state),
+		Signer::from_str(MODULE_ID).unwrap(),
+	)
+	.unwrap()
+	.encode_vec();
+
+	let msg = Any { type_url: TYPE_URL.to_string().as_bytes().to_vec(), value: msg };
+	assert_ok!(Ibc::create_client(Origin::root(), msg));
+
+	let params = ConnectionParams {
+		version: (
+			"1".as_bytes().to_vec(),
+			vec![
+				Order::Ordered.as_str().as_bytes().to_vec(),
+				Order::Unordered.as_str().as_bytes().to_vec(),
+			],
+		),
+		client_id: client_id.as_bytes().to_vec(),
+		counterparty_client_id: counterparty_client_id.as_bytes().to_vec(),
+		commitment_prefix: "ibc".as_bytes().to_vec(),
+		delay_period: 1000,
+	};
+	assert_ok!(Ibc::initiate_connection(Origin::root(), params));
+
+	crate::Pallet::<Test>::insert_default_consensus_state(1);
+	let proofs = || {
+		Proofs::new(
+			vec![0u8; 32].try_into().unwrap(),
+			Some(vec![0u8; 32].try_into().unwrap()),
+			Some(
+				ConsensusProof::new(
+					vec![0u8; 32].try_into().unwrap(),
+					Height::new(u32::from(ParachainInfo::get()).into(), 1),
+				)
+				.unwrap(),
+			),
+			None,
+			Height::new(0, 1),
+		)
+		.unwrap()
+	};
+	let value = conn_open_ack::MsgConnectionOpenAck {
+		connection_id: ConnectionId::new(0),
+		counterparty_connection_id: ConnectionId::new(1),
+		client_state: None,
+		proofs: proofs(),
+		version: ConnVersion::default(),
+		signer: Signer::from_str(MODULE_ID).unwrap(),
+	}
+	.encode_vec();
+	let msg = Any { type_url: conn_open_ack::TYPE_URL.as_bytes().to_vec(), value };
+	assert_ok!(Ibc::deliver(Origin::signed(AccountId32::new([0; 32])), vec![msg]));
+
+	let params = OpenChannelParams {
+		order: 1,
+		connection_id: "connection-0".as_bytes().to_vec(),
+		counterparty_port_id: "transfer".as_bytes().to_vec(),
+		version: VERSION.as_bytes().to_vec(),
+	};
+	assert_ok!(IbcTransfer::open_channel(Origin::root(), params));
+
+	let value = chan_open_ack::MsgChannelOpenAck {
+		port_id: PortId::transfer(),
+		channel_id: ChannelId::new(0),
+		counterparty_channel_id: ChannelId::new(1),
+		counterparty_version: ChanVersion::new(VERSION.to_string()),
+		proofs: proofs(),
+		signer: Signer::from_str(MODULE_ID).unwrap(),
+	}
+	.encode_vec();
+	let msg = Any { type_url: chan_open_ack::TYPE_URL.as_bytes().to_vec(), value };
+	assert_ok!(Ibc::deliver(Origin::signed(AccountId32::new([0; 32])), vec![msg]));
+
+	assert_ok!(IbcTransfer::set_pallet_params(
+		Origin::root(),
+		PalletParams { send_enabled: true, receive_enabled: true }
+	));
+}
+
+// Packet received on channel-0 carrying a memo
+fn packet_with_memo(memo: &str) -> ibc::core::ics04_channel::packet::Packet {
+	use ibc::applications::transfer::{packet::PacketData, PrefixedCoin};
+
+	let data = PacketData {
+		token: PrefixedCoin::from_str("100uatom").unwrap(),
+		sender: Signer::from_str("alice").unwrap(),
+		receiver: Signer::from_str("bob").unwrap(),
+	};
+	ibc::core::ics04_channel::packet::Packet {
+		sequence: 1u64.into(),
+		source_port: PortId::transfer(),
+		source_channel: ChannelId::new(1),
+		destination_port: PortId::transfer(),
+		destination_channel: ChannelId::new(0),
+		data: crate::ics20::memo::packet_data_with_memo(&data, memo.to_string()).unwrap(),
+		timeout_height: Height::zero(),
+		timeout_timestamp: ibc::timestamp::Timestamp::from_nanoseconds(
+			ibc::timestamp::Timestamp::now().nanoseconds() + RefundTimeout::get(),
+		)
+		.unwrap(),
+	}
+}
+
+#[test]
+fn received_tokens_are_forwarded() {
+	use ibc::core::ics26_routing::context::{Module, ModuleOutputBuilder, OnRecvPacketAck};
+	use transfer::{IbcCallbackHandler, PendingForward, PendingForwards};
+
+	new_test_ext().execute_with(|| {
+		open_transfer_channel();
+		let memo = r#"{"forward":{"receiver":"carol","port":"transfer","channel":"channel-0"}}"#;
+		let mut output = ModuleOutputBuilder::new();
+
+		let ack = IbcCallbackHandler::<Test>::default().on_recv_packet(
+			&mut output,
+			&packet_with_memo(memo),
+			&Signer::from_str("relayer").unwrap(),
+		);
+
+		assert!(matches!(ack, OnRecvPacketAck::Successful(..)));
+		let account = IbcTransfer::forward_account(&ChannelId::new(0), 1);
+		assert_eq!(
+			PendingForwards::<Test>::get(&account),
+			Some(PendingForward { channel_id: b"channel-0".to_vec(), sender: b"alice".to_vec() })
+		);
+		System::assert_has_event(
+			transfer::Event::<Test>::PacketForwarded {
+				channel_id: b"channel-0".to_vec(),
+				sequence: 1,
+				forward_channel: b"channel-0".to_vec(),
+				receiver: b"carol".to_vec(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn failed_forward_is_reverted() {
+	use composable_traits::xcm::assets::RemoteAssetRegistryInspect;
+	use ibc::core::ics26_routing::context::{Module, ModuleOutputBuilder};
+	use ibc_trait::ibc_denom_to_foreign_asset_id;
+	use transfer::{IbcCallbackHandler, PendingForwards};
+
+	new_test_ext().execute_with(|| {
+		open_transfer_channel();
+		// No channel-5 to forward over
+		let memo = r#"{"forward":{"receiver":"carol","port":"transfer","channel":"channel-5"}}"#;
+		let mut output = ModuleOutputBuilder::new();
+
+		IbcCallbackHandler::<Test>::default().on_recv_packet(
+			&mut output,
+			&packet_with_memo(memo),
+			&Signer::from_str("relayer").unwrap(),
+		);
+
+		let account = IbcTransfer::forward_account(&ChannelId::new(0), 1);
+		assert!(!PendingForwards::<Test>::contains_key(&account));
+		assert_eq!(
+			AssetsRegistry::location_to_asset(ibc_denom_to_foreign_asset_id(
+				"transfer/channel-0/uatom"
+			)),
+			None
+		);
+	});
+}
+
+// Packet forwarding the tokens of a packet received with `packet_with_memo` over channel-0
+fn forwarded_packet(
+	received: &ibc::core::ics04_channel::packet::Packet,
+) -> ibc::core::ics04_channel::packet::Packet {
+	use ibc::applications::transfer::{packet::PacketData, PrefixedCoin};
+
+	let account = IbcTransfer::forward_account(&ChannelId::new(0), 1);
+	let data = PacketData {
+		token: PrefixedCoin::from_str("100transfer/channel-0/uatom").unwrap(),
+		sender: Signer::from_str(&format!("0x{}", hex::encode(&account))).unwrap(),
+		receiver: Signer::from_str("carol").unwrap(),
+	};
+	ibc::core::ics04_channel::packet::Packet {
+		sequence: 1u64.into(),
+		source_port: PortId::transfer(),
+		source_channel: ChannelId::new(0),
+		destination_port: PortId::transfer(),
+		destination_channel: ChannelId::new(1),
+		data: serde_json::to_vec(&data).unwrap(),
+		timeout_height: Height::zero(),
+		timeout_timestamp: received.timeout_timestamp,
+	}
+}
+
+#[test]
+fn failed_forwarded_packet_is_refunded() {
+	use ibc::{
+		applications::transfer::acknowledgement::ACK_ERR_STR,
+		core::ics26_routing::context::{Module, ModuleOutputBuilder},
+	};
+	use transfer::{IbcCallbackHandler, PendingForwards};
+
+	new_test_ext().execute_with(|| {
+		open_transfer_channel();
+		let memo = r#"{"forward":{"receiver":"carol","port":"transfer","channel":"channel-0"}}"#;
+		let received = packet_with_memo(memo);
+		let relayer = Signer::from_str("relayer").unwrap();
+		let mut handler = IbcCallbackHandler::<Test>::default();
+		let mut output = ModuleOutputBuilder::new();
+		handler.on_recv_packet(&mut output, &received, &relayer);
+
+		assert_ok!(handler.on_acknowledgement_packet(
+			&mut output,
+			&forwarded_packet(&received),
+			&ACK_ERR_STR.as_bytes().to_vec().into(),
+			&relayer,
+		));
+
+		let account = IbcTransfer::forward_account(&ChannelId::new(0), 1);
+		assert!(!PendingForwards::<Test>::contains_key(&account));
+		System::assert_has_event(
+			transfer::Event::<Test>::ForwardRefunded {
+				channel_id: b"channel-0".to_vec(),
+				receiver: b"alice".to_vec(),
+				success: true,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn failed_packet_outflow_is_reverted_in_its_window_only() {
+	use frame_support::traits::fungibles::Mutate;
+	use ibc::core::ics26_routing::context::{Module, ModuleOutputBuilder};
+	use sp_runtime::Percent;
+	use transfer::{
+		rate_limit::FlowDirection, IbcCallbackHandler, OutflowWindows, RateLimitParams, RateLimits,
+	};
+
+	let outflow = || {
+		RateLimits::<Test>::get(b"channel-0".to_vec(), b"transfer/channel-0/uatom".to_vec())
+			.unwrap()
+			.outflow
+	};
+
+	for expire_window in [false, true] {
+		new_test_ext().execute_with(|| {
+			open_transfer_channel();
+			let denom = "transfer/channel-0/uatom";
+			let asset_id = IbcTransfer::register_ibc_asset(denom).unwrap();
+			<Assets as Mutate<AccountId32>>::mint_into(asset_id, &AccountId32::new([1; 32]), 1_000)
+				.unwrap();
+			let params = RateLimitParams {
+				asset_id,
+				max_inflow: Percent::from_percent(50),
+				max_outflow: Percent::from_percent(50),
+				period: 10,
+			};
+			assert_ok!(IbcTransfer::set_rate_limit(
+				Origin::root(),
+				b"channel-0".to_vec(),
+				denom.as_bytes().to_vec(),
+				params
+			));
+
+			let memo =
+				r#"{"forward":{"receiver":"carol","port":"transfer","channel":"channel-0"}}"#;
+			let received = packet_with_memo(memo);
+			let relayer = Signer::from_str("relayer").unwrap();
+			let mut handler = IbcCallbackHandler::<Test>::default();
+			let mut output = ModuleOutputBuilder::new();
+			handler.on_recv_packet(&mut output, &received, &relayer);
+			assert_eq!(outflow(), 100);
+			assert_eq!(OutflowWindows::<Test>::get(b"channel-0".to_vec(), 1), Some(11));
+
+			let expected = if expire_window {
+				System::set_block_number(11);
+				assert_ok!(IbcTransfer::record_flow(
+					b"channel-0",
+					denom.as_bytes(),
+					1,
+					FlowDirection::Outflow
+				));
+				1
+			} else {
+				0
+			};
+			assert_ok!(handler.on_timeout_packet(
+				&mut output,
+				&forwarded_packet(&received),
+				&relayer
+			));
+
+			assert_eq!(outflow(), expected);
+			assert_eq!(OutflowWindows::<Test>::get(b"channel-0".to_vec(), 1), None);
+		});
+	}
+}
@@ -5,10 +5,10 @@ use ibc::core::{
 	ics02_client::{client_type::ClientType, msgs::ClientMsg},
 	ics03_connection::{context::ConnectionReader, msgs::ConnectionMsg},
 	ics04_channel::msgs::{ChannelMsg, PacketMsg},
-	ics24_host::identifier::ClientId,
+	ics24_host::identifier::{ClientId, PortId},
 	ics26_routing::msgs::Ics26Envelope,
 };
-use ibc_trait::{client_id_from_bytes, CallbackWeight};
+use ibc_trait::{client_id_from_bytes, CallbackWeight, ModuleRouter};
 use scale_info::prelude::string::ToString;
 
 pub trait WeightInfo {
@@ -117,7 +117,13 @@ impl<T: Config> WeightRouter<T> {
 				Some(Box::new(pallet_ibc_ping::WeightHandler::<T>::default())),
 			ibc::applications::transfer::PORT_ID_STR =>
 				Some(Box::new(transfer::WeightHandler::<T>::default())),
-			_ => None,
+			pallet_ibc_ica::HOST_PORT_ID =>
+				Some(Box::new(pallet_ibc_ica::WeightHandler::<T>::default())),
+			port if port.starts_with(pallet_ibc_ica::CONTROLLER_PORT_PREFIX) =>
+				Some(Box::new(pallet_ibc_ica::WeightHandler::<T>::default())),
+			_ => PortId::from_str(port_id)
+				.ok()
+				.and_then(|port_id| T::Router::get_weight(&port_id)),
 		}
 	}
 }
@@ -4,13 +4,14 @@ use crate::{self as pallet_lending, *};
 use composable_traits::{
 	currency::{Exponent, LocalAssets},
 	defi::DeFiComposableConfig,
+	dex::fake::DexFake,
 	governance::{GovernanceRegistry, SignedRawOrigin},
 	oracle::Price,
 };
 
 use frame_support::{
 	ord_parameter_types, parameter_types,
-	traits::{ConstU32, Everything, GenesisBuild},
+	traits::{ConstU128, ConstU32, Everything, GenesisBuild},
 	weights::{WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial},
 	PalletId,
 };
@@ -361,7 +362,10 @@ impl pallet_liquidations::Config for Runtime {
 	type WeightInfo = pallet_liquidations::weights::SubstrateWeight<Self>;
 	type CanModifyStrategies = EnsureRoot<Self::AccountId>;
 	type XcmSender = XcmFake;
+	// liquidations are not swapped on DEX in lending tests
+	type Dex = DexFake<Tokens, ConstU128<0>, AccountId>;
 	type MaxLiquidationStrategiesAmount = ConstU32<3>;
+	type MaxTimeoutStepsPerBlock = ConstU32<100>;
 }
 
 pub type Extrinsic = TestXt<Call, ()>;
@@ -5,12 +5,13 @@ use composable_support::math::safe::SafeAdd;
 use composable_traits::{
 	currency::{Exponent, LocalAssets},
 	defi::DeFiComposableConfig,
+	dex::fake::DexFake,
 	governance::{GovernanceRegistry, SignedRawOrigin},
 	oracle::Price,
 };
 use frame_support::{
 	ord_parameter_types, parameter_types,
-	traits::{ConstU32, Everything, GenesisBuild, OnRuntimeUpgrade},
+	traits::{ConstU128, ConstU32, Everything, GenesisBuild, OnRuntimeUpgrade},
 	weights::{WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial},
 	PalletId,
 };
@@ -373,7 +374,10 @@ impl pallet_liquidations::Config for Runtime {
 	type WeightInfo = pallet_liquidations::weights::SubstrateWeight<Self>;
 	type CanModifyStrategies = EnsureRoot<Self::AccountId>;
 	type XcmSender = XcmFake;
+	// liquidations are not swapped on DEX in lending tests
+	type Dex = DexFake<Tokens, ConstU128<0>, AccountId>;
 	type MaxLiquidationStrategiesAmount = ConstU32<3>;
+	type MaxTimeoutStepsPerBlock = ConstU32<100>;
 }
 
 pub type Extrinsic = TestExtrinsic;
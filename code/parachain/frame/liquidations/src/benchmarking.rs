@@ -6,7 +6,7 @@ use composable_traits::{
 	time::{LinearDecrease, TimeReleaseFunction},
 };
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-use frame_support::traits::{fungibles::Mutate, Currency, Get};
+use frame_support::traits::{fungibles::Mutate, Currency, Get, Hooks};
 use frame_system::RawOrigin;
 use sp_runtime::{traits::Saturating, FixedPointNumber};
 use sp_std::prelude::*;
@@ -34,10 +34,11 @@ benchmarks! {
 	add_liquidation_strategy {
 		// Only root allowed to add new strategies.
 		let origin = RawOrigin::Root;
-		let config = LiquidationStrategyConfiguration::DutchAuction(
-				TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 10 * 60 }),
-			);
-		}: _(origin, config)
+		let config = LiquidationStrategyConfiguration::DutchAuction {
+				function: TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 10 * 60 }),
+				reserve: None,
+		};
+		}: _(origin, config, 10_u32.into())
 
    sell {
 		let x in 1..<T as Config>::MaxLiquidationStrategiesAmount::get() - 1;
@@ -47,10 +48,11 @@ benchmarks! {
 		let caller: T::AccountId = whitelisted_caller();
 		let origin = RawOrigin::Signed(caller.clone());
 		let root_origin = RawOrigin::<T::AccountId>::Root;
-		let config = LiquidationStrategyConfiguration::DutchAuction(
-				TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 10 * 60 }),
-		);
-		Liquidations::<T>::add_liquidation_strategy(root_origin.clone().into(), config.clone()).unwrap();
+		let config = LiquidationStrategyConfiguration::DutchAuction {
+				function: TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 10 * 60 }),
+				reserve: None,
+		};
+		Liquidations::<T>::add_liquidation_strategy(root_origin.clone().into(), config.clone(), 10_u32.into()).unwrap();
 		let native_token_amount = <<T as pallet_dutch_auction::Config>::NativeCurrency as Currency<T::AccountId>>::minimum_balance().saturating_mul(1_000_000_000_u32.into());
 		<<T as pallet_dutch_auction::Config>::NativeCurrency as Currency<T::AccountId>>::make_free_balance_be(&caller, native_token_amount);
 		orml_tokens::Pallet::<T>::mint_into(order.pair.base.into(), &caller, 1_000_000_u32.into()).unwrap();
@@ -59,6 +61,31 @@ benchmarks! {
 		let mut configurations:Vec<T::LiquidationStrategyId> = (begin..end).map(|x| x.into()).collect();
 		configurations.push(1.into());
 		   }: _(origin, order, configurations)
+
+	on_initialize {
+		// one step scans the block of timeouts
+		let x in 1..<T as Config>::MaxTimeoutStepsPerBlock::get() - 1;
+		let pair = assets::<T>();
+		let one: <T as DeFiComposableConfig>::Balance = 1_u32.into();
+		let order = Sell::new(pair.base, pair.quote, one, Ratio::saturating_from_integer(one));
+		let caller: T::AccountId = whitelisted_caller();
+		let origin = RawOrigin::Signed(caller.clone());
+		let config = LiquidationStrategyConfiguration::DutchAuction {
+				function: TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 10 * 60 }),
+				reserve: None,
+		};
+		Liquidations::<T>::add_liquidation_strategy(RawOrigin::Root.into(), config, 1_u32.into()).unwrap();
+		let strategy_id = StrategyIndex::<T>::get();
+		let native_token_amount = <<T as pallet_dutch_auction::Config>::NativeCurrency as Currency<T::AccountId>>::minimum_balance().saturating_mul(1_000_000_000_u32.into());
+		<<T as pallet_dutch_auction::Config>::NativeCurrency as Currency<T::AccountId>>::make_free_balance_be(&caller, native_token_amount);
+		orml_tokens::Pallet::<T>::mint_into(order.pair.base.into(), &caller, 1_000_000_u32.into()).unwrap();
+		for _ in 0..x {
+			Liquidations::<T>::sell(origin.clone().into(), order.clone(), vec![strategy_id, strategy_id]).unwrap();
+		}
+		let timeout_at = frame_system::Pallet::<T>::block_number().saturating_add(1_u32.into());
+		}: {
+			Liquidations::<T>::on_initialize(timeout_at);
+		}
 }
 
 impl_benchmark_test_suite!(
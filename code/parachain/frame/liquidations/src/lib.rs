@@ -34,6 +34,7 @@
 
 #[cfg(any(feature = "runtime-benchmarks", test))]
 mod benchmarking;
+pub mod migrations;
 mod mock;
 
 #[cfg(test)]
@@ -60,20 +61,31 @@ pub mod pallet {
 		math::wrapping_next::WrappingNext,
 	};
 	use composable_traits::{
-		defi::{DeFiComposableConfig, DeFiEngine, Sell, SellEngine},
+		defi::{CurrencyPair, DeFiComposableConfig, DeFiEngine, OrderIdLike, Sell, SellEngine},
+		dex::{Amm, AssetAmount},
 		liquidation::Liquidation,
 		time::{LinearDecrease, StairstepExponentialDecrease, TimeReleaseFunction},
+		xcm::XcmSellRequestTransactConfiguration,
 	};
 	use frame_support::{
-		dispatch::DispatchResultWithPostInfo,
-		pallet_prelude::{OptionQuery, StorageMap, StorageValue},
-		traits::{EnsureOrigin, Get, IsType, UnixTime},
-		BoundedVec, PalletId, Parameter, Twox64Concat,
+		dispatch::{DispatchResult, DispatchResultWithPostInfo},
+		pallet_prelude::{OptionQuery, StorageDoubleMap, StorageMap, StorageValue},
+		traits::{EnsureOrigin, Get, Hooks, IsType, StorageVersion, UnixTime},
+		transactional,
+		weights::Weight,
+		BoundedVec, CloneNoBound, EqNoBound, PalletId, Parameter, PartialEqNoBound,
+		RuntimeDebugNoBound, Twox64Concat,
+	};
+	use frame_system::{
+		ensure_signed,
+		pallet_prelude::{BlockNumberFor, OriginFor},
 	};
-	use frame_system::{ensure_signed, pallet_prelude::OriginFor};
 	use scale_info::TypeInfo;
-	use sp_runtime::{DispatchError, Permill, Perquintill};
-	use sp_std::vec::Vec;
+	use sp_runtime::{
+		traits::{One, Saturating},
+		DispatchError, Permill, Perquintill,
+	};
+	use sp_std::{fmt::Debug, vec::Vec};
 
 	#[cfg(feature = "std")]
 	use frame_support::traits::GenesisBuild;
@@ -95,6 +107,14 @@ pub mod pallet {
 			AccountId = Self::AccountId,
 		>;
 
+		/// is used for immediate sells, expected to be `pallet-dex-router`
+		type Dex: Amm<
+			AssetId = <Self as DeFiComposableConfig>::MayBeAssetId,
+			Balance = Self::Balance,
+			AccountId = Self::AccountId,
+			PoolId = CurrencyPair<<Self as DeFiComposableConfig>::MayBeAssetId>,
+		>;
+
 		type LiquidationStrategyId: Default
 			+ FullCodec
 			+ MaxEncodedLen
@@ -103,7 +123,8 @@ pub mod pallet {
 			+ Copy
 			+ From<u32>;
 
-		type OrderId: Default + FullCodec + MaxEncodedLen + sp_std::fmt::Debug;
+		/// identifies both liquidations and orders of sell engines
+		type OrderId: OrderIdLike + WrappingNext;
 
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
@@ -117,27 +138,93 @@ pub mod pallet {
 
 		type CanModifyStrategies: EnsureOrigin<Self::Origin>;
 		type MaxLiquidationStrategiesAmount: Get<u32>;
+
+		/// maximum number of steps `on_initialize` takes per block, each block of timeouts
+		/// scanned and each timed out liquidation is a step, the rest is carried over to next
+		/// blocks
+		#[pallet::constant]
+		type MaxTimeoutStepsPerBlock: Get<u32>;
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub (crate) fn deposit_event)]
 	pub enum Event<T: Config> {
 		PositionWasSentToLiquidation {},
+		/// strategy accepted the order, it either sold it or waits for fill
+		LiquidationStrategyStarted {
+			liquidation_id: T::OrderId,
+			strategy_id: T::LiquidationStrategyId,
+		},
+		/// strategy did not fill the order in time, not sold remainder falls through to next
+		/// strategies
+		LiquidationStrategyTimedOut {
+			liquidation_id: T::OrderId,
+			strategy_id: T::LiquidationStrategyId,
+			remaining: T::Balance,
+		},
+		/// none of next strategies accepted the remainder, so timed out strategy continues
+		LiquidationFallThroughFailed {
+			liquidation_id: T::OrderId,
+		},
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		NoLiquidationEngineFound,
 		InvalidLiquidationStrategiesVector,
-		OnlyDutchAuctionStrategyIsImplemented,
+		/// selling on engine of other parachain requires moving collateral to it, which is not
+		/// supported yet
+		XcmStrategyIsNotImplemented,
 	}
 
+	/// strategies got timeout in version 1
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut block = NextTimeoutBlock::<T>::get().unwrap_or(now);
+			let mut steps = T::MaxTimeoutStepsPerBlock::get();
+			let mut scanned: u32 = 0;
+			let mut timed_out: u32 = 0;
+			while block <= now && steps > 0 {
+				steps -= 1;
+				scanned += 1;
+				let mut due = LiquidationTimeouts::<T>::iter_key_prefix(block);
+				let batch: Vec<_> = due.by_ref().take(steps as usize).collect();
+				let exhausted = due.next().is_none();
+				for liquidation_id in batch.iter() {
+					LiquidationTimeouts::<T>::remove(block, liquidation_id);
+					Self::on_strategy_timeout(*liquidation_id);
+				}
+				steps -= batch.len() as u32;
+				timed_out += batch.len() as u32;
+				if !exhausted {
+					break
+				}
+				block = block.saturating_add(One::one());
+			}
+			NextTimeoutBlock::<T>::put(block);
+			T::WeightInfo::on_initialize(timed_out).saturating_add(
+				T::DbWeight::get().reads_writes(Weight::from(scanned).saturating_add(1), 1),
+			)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::migrate_to_v1::<T>()
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
+		/// Adds strategy used to liquidate.
+		/// If order is not filled within `timeout` blocks, it falls through to next strategies
+		/// requested.
 		#[pallet::weight(T::WeightInfo::add_liquidation_strategy())]
 		pub fn add_liquidation_strategy(
 			origin: OriginFor<T>,
@@ -145,10 +232,11 @@ pub mod pallet {
 			// TODO: User parachains pallet to validate parachain is connected
 			// TODO: use hardcoded swap interface to validate native token is supported
 			configuration: LiquidationStrategyConfiguration,
+			timeout: T::BlockNumber,
 		) -> DispatchResultWithPostInfo {
 			T::CanModifyStrategies::ensure_origin(origin)?;
 			let index = StrategyIndex::<T>::increment();
-			Strategies::<T>::insert(index, configuration);
+			Strategies::<T>::insert(index, LiquidationStrategy { configuration, timeout });
 			Ok(().into())
 		}
 
@@ -164,7 +252,6 @@ pub mod pallet {
 		}
 
 		// TODO: Add API to manage callback from liquidation engine and managing it state
-		// TODO: each step from request to have its slots so can tackle
 		// TODO: add incentivised API to allow "progress" finalization if it stalled (or OCW)
 	}
 
@@ -174,7 +261,7 @@ pub mod pallet {
 		_,
 		Twox64Concat,
 		T::LiquidationStrategyId,
-		LiquidationStrategyConfiguration,
+		LiquidationStrategyOf<T>,
 		OptionQuery,
 	>;
 
@@ -194,6 +281,35 @@ pub mod pallet {
 	pub type DefaultStrategyIndex<T: Config> =
 		StorageValue<_, T::LiquidationStrategyId, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn liquidation_index)]
+	#[allow(clippy::disallowed_types)]
+	pub type LiquidationIndex<T: Config> =
+		StorageValue<_, T::OrderId, ValueQuery, Nonce<DefaultInit, WrappingIncrement>>;
+
+	/// liquidations waiting for strategy to fill order, with strategies to fall through to
+	#[pallet::storage]
+	#[pallet::getter(fn pending_liquidations)]
+	pub type PendingLiquidations<T: Config> =
+		StorageMap<_, Twox64Concat, T::OrderId, PendingLiquidationOf<T>, OptionQuery>;
+
+	/// pending liquidations by block their strategy times out at
+	#[pallet::storage]
+	pub type LiquidationTimeouts<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Twox64Concat,
+		T::OrderId,
+		(),
+		OptionQuery,
+	>;
+
+	/// first block which timeouts are not all processed yet, timeouts are processed up to
+	/// `MaxTimeoutStepsPerBlock` per block in order of blocks
+	#[pallet::storage]
+	pub type NextTimeoutBlock<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
 	impl<T: Config> DeFiEngine for Pallet<T> {
 		type MayBeAssetId = T::MayBeAssetId;
 
@@ -215,11 +331,67 @@ pub mod pallet {
 
 	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
 	pub enum LiquidationStrategyConfiguration {
-		DutchAuction(TimeReleaseFunction),
+		/// sell on `pallet-dutch-auction`, price does not decrease below `reserve` part of order
+		/// price if provided
+		DutchAuction { function: TimeReleaseFunction, reserve: Option<Perquintill> },
+		/// immediate swap via `pallet-dex-router`, for not less than order price reduced by
+		/// `slippage`
 		Pablo { slippage: Perquintill },
-		Xcm(composable_traits::xcm::XcmSellRequestTransactConfiguration),
+		/// request to sell on engine of other parachain
+		Xcm(XcmSellRequestTransactConfiguration),
 	}
 
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+	pub struct LiquidationStrategy<BlockNumber> {
+		pub configuration: LiquidationStrategyConfiguration,
+		/// blocks to wait for order to be filled before falling through to next strategy
+		pub timeout: BlockNumber,
+	}
+
+	pub type LiquidationStrategyOf<T> =
+		LiquidationStrategy<<T as frame_system::Config>::BlockNumber>;
+
+	#[derive(
+		CloneNoBound,
+		RuntimeDebugNoBound,
+		PartialEqNoBound,
+		EqNoBound,
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		TypeInfo,
+	)]
+	#[scale_info(skip_type_params(MaxStrategies))]
+	pub struct PendingLiquidation<
+		AccountId: Clone + Debug + PartialEq + Eq,
+		AssetId: Clone + Debug + PartialEq + Eq,
+		Balance: Clone + Debug + PartialEq + Eq,
+		StrategyId: Clone + Debug + PartialEq + Eq,
+		OrderId: Clone + Debug + PartialEq + Eq,
+		MaxStrategies: Get<u32>,
+	> {
+		pub from_to: AccountId,
+		pub order: Sell<AssetId, Balance>,
+		/// strategy waiting for fill
+		pub strategy_id: StrategyId,
+		/// order of strategy sell engine
+		pub order_id: OrderId,
+		/// strategies to fall through to, in order
+		pub next_strategies: BoundedVec<StrategyId, MaxStrategies>,
+	}
+
+	pub type PendingLiquidationOf<T> = PendingLiquidation<
+		<T as frame_system::Config>::AccountId,
+		<T as DeFiComposableConfig>::MayBeAssetId,
+		<T as DeFiComposableConfig>::Balance,
+		<T as Config>::LiquidationStrategyId,
+		<T as Config>::OrderId,
+		<T as Config>::MaxLiquidationStrategiesAmount,
+	>;
+
+	/// default for strategies created in genesis
+	pub const DEFAULT_STRATEGY_TIMEOUT: u32 = 100;
+
 	#[cfg(feature = "std")]
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig {
@@ -227,18 +399,32 @@ pub mod pallet {
 			// DutchAction
 			let index = StrategyIndex::<T>::increment();
 			DefaultStrategyIndex::<T>::set(index);
-			let linear_ten_minutes = LiquidationStrategyConfiguration::DutchAuction(
-				TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 10 * 60 }),
+			let linear_ten_minutes = LiquidationStrategyConfiguration::DutchAuction {
+				function: TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 10 * 60 }),
+				reserve: None,
+			};
+			Strategies::<T>::insert(
+				index,
+				LiquidationStrategy {
+					configuration: linear_ten_minutes,
+					timeout: DEFAULT_STRATEGY_TIMEOUT.into(),
+				},
 			);
-			Strategies::<T>::insert(index, linear_ten_minutes);
 
 			let index = StrategyIndex::<T>::increment();
 			let exponential =
 				StairstepExponentialDecrease { step: 10, cut: Permill::from_rational(95_u32, 100) };
-			let exponential = LiquidationStrategyConfiguration::DutchAuction(
-				TimeReleaseFunction::StairstepExponentialDecrease(exponential),
+			let exponential = LiquidationStrategyConfiguration::DutchAuction {
+				function: TimeReleaseFunction::StairstepExponentialDecrease(exponential),
+				reserve: None,
+			};
+			Strategies::<T>::insert(
+				index,
+				LiquidationStrategy {
+					configuration: exponential,
+					timeout: DEFAULT_STRATEGY_TIMEOUT.into(),
+				},
 			);
-			Strategies::<T>::insert(index, exponential);
 		}
 	}
 
@@ -269,22 +455,123 @@ pub mod pallet {
 					.try_push(DefaultStrategyIndex::<T>::get())
 					.map_err(|()| Error::<T>::InvalidLiquidationStrategiesVector)?;
 			};
-			for id in configuration {
-				let configuration = Strategies::<T>::get(id);
-				if let Some(configuration) = configuration {
-					let result = match configuration {
-						LiquidationStrategyConfiguration::DutchAuction(configuration) =>
-							T::DutchAuction::ask(from_to, order.clone(), configuration),
-						_ => return Err(Error::<T>::OnlyDutchAuctionStrategyIsImplemented.into()),
-					};
-					if let Ok(order_id) = result {
-						Self::deposit_event(Event::<T>::PositionWasSentToLiquidation {});
-						return Ok(order_id)
+			let liquidation_id = LiquidationIndex::<T>::increment();
+			Self::start_strategies(liquidation_id, from_to, order, configuration)?;
+			Self::deposit_event(Event::<T>::PositionWasSentToLiquidation {});
+			Ok(liquidation_id)
+		}
+
+		/// Tries `strategies` in order until one accepts `order`.
+		/// If accepted strategy waits for the order to be filled, remaining strategies are
+		/// scheduled to take over after its timeout.
+		fn start_strategies(
+			liquidation_id: T::OrderId,
+			from_to: &T::AccountId,
+			order: Sell<T::MayBeAssetId, T::Balance>,
+			strategies: BoundedVec<T::LiquidationStrategyId, T::MaxLiquidationStrategiesAmount>,
+		) -> DispatchResult {
+			let mut strategies = strategies.into_iter();
+			while let Some(strategy_id) = strategies.next() {
+				let strategy = match Strategies::<T>::get(strategy_id) {
+					Some(strategy) => strategy,
+					None => continue,
+				};
+				let result = Self::execute_strategy(from_to, &order, strategy.configuration);
+				let order_id = match result {
+					Ok(order_id) => order_id,
+					Err(_) => continue,
+				};
+				Self::deposit_event(Event::<T>::LiquidationStrategyStarted {
+					liquidation_id,
+					strategy_id,
+				});
+
+				let next_strategies: Vec<_> = strategies.collect();
+				if let Some(order_id) = order_id {
+					if !next_strategies.is_empty() {
+						let next_strategies = BoundedVec::try_from(next_strategies)
+							.map_err(|_| Error::<T>::InvalidLiquidationStrategiesVector)?;
+						let timeout_at = frame_system::Pallet::<T>::block_number()
+							.saturating_add(strategy.timeout.max(One::one()));
+						PendingLiquidations::<T>::insert(
+							liquidation_id,
+							PendingLiquidation {
+								from_to: from_to.clone(),
+								order,
+								strategy_id,
+								order_id,
+								next_strategies,
+							},
+						);
+						LiquidationTimeouts::<T>::insert(timeout_at, liquidation_id, ());
 					}
 				}
+				return Ok(())
 			}
 
 			Err(Error::<T>::NoLiquidationEngineFound.into())
 		}
+
+		/// Returns order id of sell engine if strategy waits for the order to be filled,
+		/// or `None` if strategy is done with the order.
+		#[transactional]
+		fn execute_strategy(
+			from_to: &T::AccountId,
+			order: &Sell<T::MayBeAssetId, T::Balance>,
+			configuration: LiquidationStrategyConfiguration,
+		) -> Result<Option<T::OrderId>, DispatchError> {
+			match configuration {
+				LiquidationStrategyConfiguration::DutchAuction { function, reserve } => {
+					let reserve_price =
+						reserve.map(|reserve| order.take.limit.saturating_mul(reserve.into()));
+					T::DutchAuction::ask(from_to, order.clone(), function, reserve_price).map(Some)
+				},
+				LiquidationStrategyConfiguration::Pablo { slippage } => {
+					let min_receive =
+						slippage.left_from_one().mul_floor(order.take.quote_limit_amount()?);
+					T::Dex::do_swap(
+						from_to,
+						order.pair.clone(),
+						AssetAmount::new(order.pair.base, order.take.amount),
+						AssetAmount::new(order.pair.quote, min_receive),
+						false,
+					)?;
+					Ok(None)
+				},
+				LiquidationStrategyConfiguration::Xcm(_) =>
+					Err(Error::<T>::XcmStrategyIsNotImplemented.into()),
+			}
+		}
+
+		/// Not sold remainder of timed out strategy order falls through to next strategies.
+		/// If none of them accepts it, timed out strategy continues.
+		fn on_strategy_timeout(liquidation_id: T::OrderId) {
+			if let Some(pending) = PendingLiquidations::<T>::take(liquidation_id) {
+				if let Some(remaining) = T::DutchAuction::remaining(pending.order_id) {
+					Self::deposit_event(Event::<T>::LiquidationStrategyTimedOut {
+						liquidation_id,
+						strategy_id: pending.strategy_id,
+						remaining,
+					});
+					if Self::fall_through(liquidation_id, pending, remaining).is_err() {
+						Self::deposit_event(Event::<T>::LiquidationFallThroughFailed {
+							liquidation_id,
+						});
+					}
+				}
+			}
+		}
+
+		#[transactional]
+		fn fall_through(
+			liquidation_id: T::OrderId,
+			pending: PendingLiquidationOf<T>,
+			remaining: T::Balance,
+		) -> DispatchResult {
+			T::DutchAuction::cancel(pending.order_id)?;
+			let mut order = pending.order;
+			order.take.amount = remaining;
+			Self::start_strategies(liquidation_id, &pending.from_to, order, pending.next_strategies)
+		}
 	}
 }
@@ -0,0 +1,42 @@
+use crate::pallet::*;
+use codec::{Decode, Encode};
+use composable_traits::{time::TimeReleaseFunction, xcm::XcmSellRequestTransactConfiguration};
+use frame_support::{
+	traits::{Get, StorageVersion},
+	weights::Weight,
+};
+use sp_runtime::Perquintill;
+
+/// [`LiquidationStrategyConfiguration`] stored before strategies had timeout
+#[derive(Encode, Decode)]
+pub enum LiquidationStrategyConfigurationV0 {
+	DutchAuction(TimeReleaseFunction),
+	Pablo { slippage: Perquintill },
+	Xcm(XcmSellRequestTransactConfiguration),
+}
+
+/// Wraps stored strategies into [`LiquidationStrategy`] with [`DEFAULT_STRATEGY_TIMEOUT`],
+/// existing dutch auctions have no reserve price.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if StorageVersion::get::<Pallet<T>>() >= 1 {
+		return T::DbWeight::get().reads(1)
+	}
+
+	let mut translated = 0_u64;
+	Strategies::<T>::translate::<LiquidationStrategyConfigurationV0, _>(|_id, configuration| {
+		translated += 1;
+		let configuration = match configuration {
+			LiquidationStrategyConfigurationV0::DutchAuction(function) =>
+				LiquidationStrategyConfiguration::DutchAuction { function, reserve: None },
+			LiquidationStrategyConfigurationV0::Pablo { slippage } =>
+				LiquidationStrategyConfiguration::Pablo { slippage },
+			LiquidationStrategyConfigurationV0::Xcm(configuration) =>
+				LiquidationStrategyConfiguration::Xcm(configuration),
+		};
+		Some(LiquidationStrategy { configuration, timeout: DEFAULT_STRATEGY_TIMEOUT.into() })
+	});
+	StorageVersion::new(1).put::<Pallet<T>>();
+	log::info!("migrated {} liquidation strategies to v1", translated);
+
+	T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+}
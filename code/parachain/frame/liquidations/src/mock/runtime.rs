@@ -4,7 +4,9 @@ use crate::{
 	weights::SubstrateWeight,
 };
 
-use composable_traits::{defi::DeFiComposableConfig, xcm::assets::XcmAssetLocation};
+use composable_traits::{
+	defi::DeFiComposableConfig, dex::fake::DexFake, xcm::assets::XcmAssetLocation,
+};
 use frame_support::{
 	ord_parameter_types, parameter_types,
 	traits::{ConstU32, Everything, GenesisBuild},
@@ -235,9 +237,11 @@ impl pallet_dutch_auction::Config for Runtime {
 
 parameter_types! {
 	pub const LiquidationPalletId : PalletId = PalletId(*b"liquidat");
+	/// how much of `quote` is received for one `base`
+	pub static DexPrice: Balance = 1;
 }
 
-type LiquidationStrategyId = u32;
+pub type LiquidationStrategyId = u32;
 impl pallet_liquidations::Config for Runtime {
 	type Event = Event;
 	type UnixTime = Timestamp;
@@ -248,7 +252,9 @@ impl pallet_liquidations::Config for Runtime {
 	type PalletId = LiquidationPalletId;
 	type CanModifyStrategies = EnsureRoot<Self::AccountId>;
 	type XcmSender = XcmFake;
+	type Dex = DexFake<Tokens, DexPrice, AccountId>;
 	type MaxLiquidationStrategiesAmount = ConstU32<3>;
+	type MaxTimeoutStepsPerBlock = ConstU32<3>;
 }
 
 #[allow(dead_code)] // not really dead
@@ -1,17 +1,26 @@
 use crate::{
 	self as pallet_liquidations,
+	migrations::LiquidationStrategyConfigurationV0,
 	mock::{currency::*, runtime::*},
+	LiquidationStrategy, LiquidationStrategyConfiguration, NextTimeoutBlock, PendingLiquidations,
+	Strategies, DEFAULT_STRATEGY_TIMEOUT,
 };
 use codec::Encode;
 use composable_traits::{
 	defi::{Ratio, Sell},
 	liquidation::Liquidation,
+	time::{LinearDecrease, TimeReleaseFunction},
+	xcm::{XcmSellRequestTransactConfiguration, XcmTransactConfiguration},
 };
 use frame_support::{
-	assert_noop,
-	traits::{fungible::Mutate as NativeMutate, fungibles::Mutate},
+	assert_err, assert_noop, assert_ok,
+	traits::{
+		fungible::Mutate as NativeMutate,
+		fungibles::{Inspect, Mutate},
+		Hooks, StorageVersion,
+	},
 };
-use sp_runtime::{FixedPointNumber, FixedU128};
+use sp_runtime::{FixedPointNumber, FixedU128, Perquintill};
 
 // ensure that we take extra for sell, at least amount to remove
 #[test]
@@ -24,14 +33,15 @@ fn successful_liquidate() {
 		Tokens::mint_into(KUSD, &ALICE, 100000000000).unwrap();
 		let who = AccountId::from_raw(ALICE.0);
 		let amount = 100;
-		let order = <Liquidations as Liquidation>::liquidate(
+		<Liquidations as Liquidation>::liquidate(
 			&who,
 			Sell::new(KUSD, PICA, 100, Ratio::saturating_from_integer(1)),
 			vec![],
 		)
 		.expect("can creator order for existing currencies if enough of amounts");
+		let order_id = pallet_dutch_auction::OrdersIndex::<Runtime>::get();
 		let order =
-			pallet_dutch_auction::SellOrders::<Runtime>::get(order).expect("order was placed");
+			pallet_dutch_auction::SellOrders::<Runtime>::get(order_id).expect("order was placed");
 		assert_eq!(order.from_to, who);
 		assert_eq!(order.order.take.amount, amount);
 	});
@@ -51,6 +61,154 @@ fn do_not_have_amount_to_liquidate() {
 	});
 }
 
+fn add_swap_strategy(slippage: Perquintill) -> LiquidationStrategyId {
+	assert_ok!(Liquidations::add_liquidation_strategy(
+		Origin::root(),
+		LiquidationStrategyConfiguration::Pablo { slippage },
+		10,
+	));
+	Liquidations::strategy_index()
+}
+
+fn with_collateral(execute: impl FnOnce(AccountId)) {
+	new_test_externalities().execute_with(|| {
+		Tokens::mint_into(KUSD, &ALICE, 1_000).unwrap();
+		execute(AccountId::from_raw(ALICE.0))
+	})
+}
+
+#[test]
+fn swap_within_slippage() {
+	with_collateral(|who| {
+		let swap = add_swap_strategy(Perquintill::from_percent(10));
+		DexPrice::set(1);
+		assert_ok!(<Liquidations as Liquidation>::liquidate(
+			&who,
+			Sell::new(KUSD, PICA, 100, Ratio::saturating_from_integer(1)),
+			vec![swap],
+		));
+		assert_eq!(Tokens::balance(KUSD, &ALICE), 900);
+		assert_eq!(Tokens::balance(PICA, &ALICE), 100);
+	});
+}
+
+#[test]
+fn swap_out_of_slippage_falls_through_immediately() {
+	with_collateral(|who| {
+		let swap = add_swap_strategy(Perquintill::from_percent(10));
+		let dutch_auction = Liquidations::default_strategy_index();
+		DexPrice::set(0);
+		assert_ok!(<Liquidations as Liquidation>::liquidate(
+			&who,
+			Sell::new(KUSD, PICA, 100, Ratio::saturating_from_integer(1)),
+			vec![swap, dutch_auction],
+		));
+		let order_id = pallet_dutch_auction::OrdersIndex::<Runtime>::get();
+		let order =
+			pallet_dutch_auction::SellOrders::<Runtime>::get(order_id).expect("order was placed");
+		assert_eq!(order.order.take.amount, 100);
+		assert_eq!(Tokens::balance(PICA, &ALICE), 0);
+	});
+}
+
+#[test]
+fn not_filled_auction_falls_through_after_timeout() {
+	with_collateral(|who| {
+		let swap = add_swap_strategy(Perquintill::from_percent(10));
+		let dutch_auction = Liquidations::default_strategy_index();
+		let timeout = Liquidations::strategies(dutch_auction).expect("genesis strategy").timeout;
+		DexPrice::set(1);
+		let liquidation_id = <Liquidations as Liquidation>::liquidate(
+			&who,
+			Sell::new(KUSD, PICA, 100, Ratio::saturating_from_integer(1)),
+			vec![dutch_auction, swap],
+		)
+		.unwrap();
+		let order_id = pallet_dutch_auction::OrdersIndex::<Runtime>::get();
+		assert!(PendingLiquidations::<Runtime>::get(liquidation_id).is_some());
+
+		Liquidations::on_initialize(System::block_number() + timeout - 1);
+		assert!(pallet_dutch_auction::SellOrders::<Runtime>::get(order_id).is_some());
+
+		Liquidations::on_initialize(System::block_number() + timeout);
+		System::assert_has_event(Event::Liquidations(crate::Event::LiquidationStrategyTimedOut {
+			liquidation_id,
+			strategy_id: dutch_auction,
+			remaining: 100,
+		}));
+		System::assert_last_event(Event::Liquidations(crate::Event::LiquidationStrategyStarted {
+			liquidation_id,
+			strategy_id: swap,
+		}));
+		assert!(pallet_dutch_auction::SellOrders::<Runtime>::get(order_id).is_none());
+		assert!(PendingLiquidations::<Runtime>::get(liquidation_id).is_none());
+		assert_eq!(Tokens::balance(KUSD, &ALICE), 900);
+		assert_eq!(Tokens::balance(PICA, &ALICE), 100);
+	});
+}
+
+#[test]
+fn timeouts_are_carried_over_to_next_blocks() {
+	with_collateral(|who| {
+		let swap = add_swap_strategy(Perquintill::from_percent(10));
+		let dutch_auction = Liquidations::default_strategy_index();
+		let timeout = Liquidations::strategies(dutch_auction).expect("genesis strategy").timeout;
+		DexPrice::set(1);
+		let liquidation_ids: Vec<_> = (0..3)
+			.map(|_| {
+				<Liquidations as Liquidation>::liquidate(
+					&who,
+					Sell::new(KUSD, PICA, 100, Ratio::saturating_from_integer(1)),
+					vec![dutch_auction, swap],
+				)
+				.unwrap()
+			})
+			.collect();
+		let timeout_at = System::block_number() + timeout;
+		let pending = || {
+			liquidation_ids
+				.iter()
+				.filter(|id| PendingLiquidations::<Runtime>::get(id).is_some())
+				.count()
+		};
+
+		// scanning the block of timeouts is a step too
+		Liquidations::on_initialize(timeout_at);
+		assert_eq!(pending(), 1);
+		assert_eq!(NextTimeoutBlock::<Runtime>::get(), Some(timeout_at));
+
+		Liquidations::on_initialize(timeout_at + 1);
+		assert_eq!(pending(), 0);
+		assert_eq!(NextTimeoutBlock::<Runtime>::get(), Some(timeout_at + 2));
+		assert_eq!(Tokens::balance(PICA, &ALICE), 300);
+	});
+}
+
+#[test]
+fn xcm_strategy_is_not_accepted() {
+	with_collateral(|who| {
+		assert_ok!(Liquidations::add_liquidation_strategy(
+			Origin::root(),
+			LiquidationStrategyConfiguration::Xcm(XcmSellRequestTransactConfiguration {
+				location: XcmTransactConfiguration::new(3000.into(), 42, 1),
+				configuration_id: 1,
+				fee: 1_000,
+			}),
+			10,
+		));
+		let xcm = Liquidations::strategy_index();
+		assert_err!(
+			<Liquidations as Liquidation>::liquidate(
+				&who,
+				Sell::new(KUSD, PICA, 100, Ratio::saturating_from_integer(1)),
+				vec![xcm],
+			),
+			crate::Error::<Runtime>::NoLiquidationEngineFound
+		);
+		assert_eq!(Tokens::balance(KUSD, &ALICE), 1_000);
+	});
+}
+
 /// This is used if we will hard code TX for each network.
 #[derive(Encode)]
 pub enum LiquidationsCall {
@@ -94,3 +252,42 @@ fn pallet_do_not_treat_infinitely_large_strategies_vector() {
 }
 
 // TODO: add XCM end to end tests with callbacks
+
+#[test]
+fn migrate_strategies_without_timeout() {
+	new_test_externalities().execute_with(|| {
+		let function = TimeReleaseFunction::LinearDecrease(LinearDecrease { total: 42 });
+		let slippage = Perquintill::from_percent(10);
+		for (id, legacy) in [
+			(42, LiquidationStrategyConfigurationV0::DutchAuction(function.clone())),
+			(43, LiquidationStrategyConfigurationV0::Pablo { slippage }),
+		] {
+			frame_support::storage::unhashed::put(
+				&Strategies::<Runtime>::hashed_key_for(id),
+				&legacy,
+			);
+		}
+		StorageVersion::new(0).put::<Liquidations>();
+
+		Liquidations::on_runtime_upgrade();
+
+		assert_eq!(
+			Liquidations::strategies(42),
+			Some(LiquidationStrategy {
+				configuration: LiquidationStrategyConfiguration::DutchAuction {
+					function,
+					reserve: None
+				},
+				timeout: DEFAULT_STRATEGY_TIMEOUT.into(),
+			})
+		);
+		assert_eq!(
+			Liquidations::strategies(43),
+			Some(LiquidationStrategy {
+				configuration: LiquidationStrategyConfiguration::Pablo { slippage },
+				timeout: DEFAULT_STRATEGY_TIMEOUT.into(),
+			})
+		);
+		assert_eq!(StorageVersion::get::<Liquidations>(), crate::pallet::STORAGE_VERSION);
+	});
+}
@@ -9,6 +9,7 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn add_liquidation_strategy() -> Weight;
 	fn sell(vector_length: u32) -> Weight;
+	fn on_initialize(timed_out: u32) -> Weight;
 }
 
 /// Weight functions for `liquidations`.
@@ -35,4 +36,20 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(x as Weight)))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// Storage: Liquidations LiquidationTimeouts (r:1 w:1)
+	// Storage: Liquidations PendingLiquidations (r:1 w:2)
+	// Storage: DutchAuction SellOrders (r:1 w:2)
+	// Storage: Liquidations Strategies (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: DutchAuction OrdersIndex (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// TODO: not benchmarked yet, estimated from the storage accessed
+	fn on_initialize(x: u32) -> Weight {
+		(2_135_000 as Weight)
+			.saturating_add((61_452_000 as Weight).saturating_mul(x as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(x as Weight)))
+			.saturating_add(T::DbWeight::get().writes((8 as Weight).saturating_mul(x as Weight)))
+	}
 }
@@ -0,0 +1,176 @@
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use composable_traits::mosaic::{BudgetStatus, PendingTransfers, RelayerStatus};
+use core::{
+	fmt::{Debug, Display},
+	str::FromStr,
+};
+use jsonrpsee::{
+	core::{Error as RpcError, RpcResult},
+	proc_macros::rpc,
+	types::{error::CallError, ErrorObject},
+};
+use mosaic_runtime_api::MosaicRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_std::sync::Arc;
+
+#[rpc(client, server)]
+pub trait MosaicApi<BlockHash, AccountId, AssetId, NetworkId, RemoteAmmId, Balance, BlockNumber>
+where
+	AccountId: FromStr + Display,
+	AssetId: FromStr + Display,
+	NetworkId: FromStr + Display,
+	RemoteAmmId: FromStr + Display,
+	Balance: FromStr + Display,
+	BlockNumber: FromStr + Display,
+{
+	#[method(name = "mosaic_pendingTransfers")]
+	fn pending_transfers(
+		&self,
+		account: SafeRpcWrapper<AccountId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<
+		PendingTransfers<
+			SafeRpcWrapper<AssetId>,
+			SafeRpcWrapper<Balance>,
+			SafeRpcWrapper<BlockNumber>,
+		>,
+	>;
+
+	#[method(name = "mosaic_budgets")]
+	fn budgets(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<
+		Vec<
+			BudgetStatus<
+				SafeRpcWrapper<AssetId>,
+				SafeRpcWrapper<NetworkId>,
+				SafeRpcWrapper<Balance>,
+			>,
+		>,
+	>;
+
+	#[method(name = "mosaic_relayer")]
+	fn relayer(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<RelayerStatus<SafeRpcWrapper<AccountId>, SafeRpcWrapper<BlockNumber>>>>;
+
+	#[method(name = "mosaic_remoteAmmIds")]
+	fn remote_amm_ids(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(SafeRpcWrapper<NetworkId>, Vec<SafeRpcWrapper<RemoteAmmId>>)>>;
+}
+
+pub struct Mosaic<C, Block> {
+	client: Arc<C>,
+	_marker: sp_std::marker::PhantomData<Block>,
+}
+
+impl<C, M> Mosaic<C, M> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn into_rpc_result<T, E: Debug>(result: Result<T, E>) -> RpcResult<T> {
+	result.map_err(|e| {
+		RpcError::Call(CallError::Custom(ErrorObject::owned(
+			9876,
+			"Something wrong",
+			Some(format!("{:?}", e)),
+		)))
+	})
+}
+
+impl<C, Block, AccountId, AssetId, NetworkId, RemoteAmmId, Balance, BlockNumber>
+	MosaicApiServer<
+		<Block as BlockT>::Hash,
+		AccountId,
+		AssetId,
+		NetworkId,
+		RemoteAmmId,
+		Balance,
+		BlockNumber,
+	> for Mosaic<C, (Block, AccountId, AssetId, NetworkId, RemoteAmmId, Balance, BlockNumber)>
+where
+	Block: BlockT,
+	AccountId: Send + Sync + 'static + Codec + FromStr + Display,
+	AssetId: Send + Sync + 'static + Codec + FromStr + Display,
+	NetworkId: Send + Sync + 'static + Codec + FromStr + Display,
+	RemoteAmmId: Send + Sync + 'static + Codec + FromStr + Display,
+	Balance: Send + Sync + 'static + Codec + FromStr + Display,
+	BlockNumber: Send + Sync + 'static + Codec + FromStr + Display,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api:
+		MosaicRuntimeApi<Block, AccountId, AssetId, NetworkId, RemoteAmmId, Balance, BlockNumber>,
+{
+	fn pending_transfers(
+		&self,
+		account: SafeRpcWrapper<AccountId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<
+		PendingTransfers<
+			SafeRpcWrapper<AssetId>,
+			SafeRpcWrapper<Balance>,
+			SafeRpcWrapper<BlockNumber>,
+		>,
+	> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		// calling ../../runtime-api
+		into_rpc_result(api.pending_transfers(&at, account))
+	}
+
+	fn budgets(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<
+		Vec<
+			BudgetStatus<
+				SafeRpcWrapper<AssetId>,
+				SafeRpcWrapper<NetworkId>,
+				SafeRpcWrapper<Balance>,
+			>,
+		>,
+	> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		// calling ../../runtime-api
+		into_rpc_result(api.budgets(&at))
+	}
+
+	fn relayer(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<RelayerStatus<SafeRpcWrapper<AccountId>, SafeRpcWrapper<BlockNumber>>>> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		// calling ../../runtime-api
+		into_rpc_result(api.relayer(&at))
+	}
+
+	fn remote_amm_ids(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(SafeRpcWrapper<NetworkId>, Vec<SafeRpcWrapper<RemoteAmmId>>)>> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		// calling ../../runtime-api
+		into_rpc_result(api.remote_amm_ids(&at))
+	}
+}
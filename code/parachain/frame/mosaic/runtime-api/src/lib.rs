@@ -0,0 +1,38 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use composable_traits::mosaic::{BudgetStatus, PendingTransfers, RelayerStatus};
+use sp_std::vec::Vec;
+
+// Mosaic Runtime API declaration. Implemented for each runtime at
+// `runtime/<runtime-name>/src/lib.rs`.
+sp_api::decl_runtime_apis! {
+	pub trait MosaicRuntimeApi<AccountId, AssetId, NetworkId, RemoteAmmId, Balance, BlockNumber>
+	where
+		AccountId: Codec,
+		AssetId: Codec,
+		NetworkId: Codec,
+		RemoteAmmId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Retrieve the incoming and outgoing transfers of `account` which are still locked, with
+		/// the block at which they can be claimed.
+		fn pending_transfers(
+			account: SafeRpcWrapper<AccountId>,
+		) -> PendingTransfers<SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>, SafeRpcWrapper<BlockNumber>>;
+
+		/// Retrieve the decayed penalty and remaining budget of every asset wide and per network
+		/// budget.
+		fn budgets() -> Vec<BudgetStatus<SafeRpcWrapper<AssetId>, SafeRpcWrapper<NetworkId>, SafeRpcWrapper<Balance>>>;
+
+		/// Retrieve the current relayer and its scheduled rotation, if a relayer is set.
+		fn relayer() -> Option<RelayerStatus<SafeRpcWrapper<AccountId>, SafeRpcWrapper<BlockNumber>>>;
+
+		/// Retrieve the whitelisted remote AMM IDs, grouped by network.
+		fn remote_amm_ids() -> Vec<(SafeRpcWrapper<NetworkId>, Vec<SafeRpcWrapper<RemoteAmmId>>)>;
+	}
+}
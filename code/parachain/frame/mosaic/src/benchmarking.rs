@@ -6,8 +6,10 @@ use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whiteli
 use frame_support::{
 	assert_ok,
 	traits::{fungibles::Mutate, Get},
+	BoundedVec,
 };
 use frame_system::RawOrigin;
+use sp_std::{vec, vec::Vec};
 const MIN_TRANSFER_SIZE: u128 = 1_000_000_000_000;
 const MAX_TRANSFER_SIZE: u128 = 100_000_000_000_000_000;
 const BUDGET_AMOUNT: u128 = 100_000_000_000_000_000_000;
@@ -50,6 +52,40 @@ benchmarks! {
 	  BudgetPenaltyDecayer::linear(5.into());
 	}: _(RawOrigin::Root, asset_id, amount, decayer.into())
 
+	set_network_budget {
+		let relayer: T::AccountId = whitelisted_caller();
+		assert_ok!(Mosaic::<T>::set_relayer(RawOrigin::Root.into(), relayer.clone()));
+
+		let network_id: T::NetworkId = 1.into();
+		let network_info = NetworkInfo {
+			enabled: true,
+			min_transfer_size: MIN_TRANSFER_SIZE.into(),
+			max_transfer_size: MAX_TRANSFER_SIZE.into(),
+		};
+		assert_ok!(Mosaic::<T>::set_network(RawOrigin::Signed(relayer).into(), network_id.clone(), network_info));
+
+		let asset_id: AssetIdOf<T> = 1.into();
+		let amount: BalanceOf<T> = BUDGET_AMOUNT.into();
+		let decayer: BudgetPenaltyDecayer<BalanceOf<T>, T::BlockNumber> =
+			BudgetPenaltyDecayer::exponential(5.into());
+		assert_ok!(Mosaic::<T>::set_budget(RawOrigin::Root.into(), asset_id, amount, decayer.clone().into()));
+	}: _(RawOrigin::Root, asset_id, network_id, amount, decayer.into())
+
+	set_asset_network {
+		let relayer: T::AccountId = whitelisted_caller();
+		assert_ok!(Mosaic::<T>::set_relayer(RawOrigin::Root.into(), relayer.clone()));
+
+		let network_id: T::NetworkId = 1.into();
+		let network_info = NetworkInfo {
+			enabled: true,
+			min_transfer_size: MIN_TRANSFER_SIZE.into(),
+			max_transfer_size: MAX_TRANSFER_SIZE.into(),
+		};
+		assert_ok!(Mosaic::<T>::set_network(RawOrigin::Signed(relayer.clone()).into(), network_id.clone(), network_info.clone()));
+
+		let asset_id: AssetIdOf<T> = 1.into();
+	}: _(RawOrigin::Signed(relayer), asset_id, network_id, Some(network_info))
+
   transfer_to {
 		let relayer: T::AccountId = whitelisted_caller();
 	  assert_ok!(Mosaic::<T>::set_relayer(RawOrigin::Root.into(), relayer.clone()));
@@ -273,6 +309,28 @@ benchmarks! {
 		assert_ok!(Mosaic::<T>::add_remote_amm_id(RawOrigin::Root.into(), network_id.clone(), amm_id.clone()));
 
 	}: _(RawOrigin::Root, network_id.clone(), amm_id.clone())
+
+	set_relayer_committee {
+		let m in 1 .. T::MaxRelayers::get();
+		let members: Vec<T::AccountId> = (0..m).map(|i| account("relayer", i, 0)).collect();
+		for member in &members {
+			assert_ok!(T::Assets::mint_into(T::RelayerBondAssetId::get(), member, T::RelayerBond::get()));
+		}
+		let members: BoundedVec<T::AccountId, T::MaxRelayers> = members.try_into().unwrap();
+	}: _(RawOrigin::Root, members, m)
+
+	slash_relayer {
+		let relayer: T::AccountId = account("relayer", 0, 0);
+		let other: T::AccountId = account("relayer", 1, 0);
+		for member in [&relayer, &other] {
+			assert_ok!(T::Assets::mint_into(T::RelayerBondAssetId::get(), member, T::RelayerBond::get()));
+		}
+		let members: BoundedVec<T::AccountId, T::MaxRelayers> =
+			vec![relayer.clone(), other].try_into().unwrap();
+		assert_ok!(Mosaic::<T>::set_relayer_committee(RawOrigin::Root.into(), members, 1));
+		let subject = Id::repeat_byte(1);
+		ConflictingAttestations::<T>::insert(&relayer, subject, ());
+	}: _(RawOrigin::Root, relayer, subject)
 }
 
 impl_benchmark_test_suite!(Mosaic, crate::mock::new_test_ext(), crate::mock::Test,);
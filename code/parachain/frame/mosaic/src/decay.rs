@@ -14,6 +14,9 @@ pub trait Decayer<Balance, BlockNumber> {
 	/// Determine how many blocks are required to pass until the `amount` fully recover from this
 	/// decayer. Returns `None` if the recovery period cannot be computed.
 	fn full_recovery_period(&self, amount: Balance) -> Option<BlockNumber>;
+
+	/// Whether the parameters of the decayer allow it to decay, e.g. its period is not zero.
+	fn is_valid(&self) -> bool;
 }
 
 /// Recommend type for storing the decay function of a penalty.
@@ -21,6 +24,11 @@ pub trait Decayer<Balance, BlockNumber> {
 pub enum BudgetPenaltyDecayer<Balance, BlockNumber> {
 	/// Linear variant of the decay function, which decreases every block.
 	Linear(LinearDecay<Balance, BlockNumber>),
+	/// Exponential variant of the decay function, which halves every `half_life` blocks.
+	Exponential(ExponentialDecay<Balance, BlockNumber>),
+	/// Step variant of the decay function, which decreases by a fixed amount every `period`
+	/// blocks.
+	Step(StepDecay<Balance, BlockNumber>),
 }
 
 impl<Balance, BlockNumber> BudgetPenaltyDecayer<Balance, BlockNumber> {
@@ -28,13 +36,25 @@ impl<Balance, BlockNumber> BudgetPenaltyDecayer<Balance, BlockNumber> {
 	pub fn linear(n: Balance) -> BudgetPenaltyDecayer<Balance, BlockNumber> {
 		BudgetPenaltyDecayer::Linear(LinearDecay { factor: n, _marker: PhantomData })
 	}
+
+	pub fn exponential(half_life: BlockNumber) -> BudgetPenaltyDecayer<Balance, BlockNumber> {
+		BudgetPenaltyDecayer::Exponential(ExponentialDecay { half_life, _marker: PhantomData })
+	}
+
+	pub fn step(
+		amount: Balance,
+		period: BlockNumber,
+	) -> BudgetPenaltyDecayer<Balance, BlockNumber> {
+		BudgetPenaltyDecayer::Step(StepDecay { amount, period })
+	}
 }
 
 impl<Balance, BlockNumber> Decayer<Balance, BlockNumber>
 	for BudgetPenaltyDecayer<Balance, BlockNumber>
 where
-	BlockNumber: CheckedSub + Saturating + Into<Balance> + TryFrom<Balance> + One + CheckedAdd,
-	Balance: CheckedMul + CheckedDiv + Saturating + Zero,
+	BlockNumber:
+		CheckedSub + Saturating + Into<Balance> + TryFrom<Balance> + One + Zero + CheckedAdd + Copy,
+	Balance: CheckedMul + CheckedDiv + CheckedAdd + Saturating + Zero + One + From<u8>,
 {
 	fn checked_decay(
 		&self,
@@ -44,12 +64,24 @@ where
 	) -> Option<Balance> {
 		match self {
 			BudgetPenaltyDecayer::Linear(lin) => lin.checked_decay(amount, current, last),
+			BudgetPenaltyDecayer::Exponential(exp) => exp.checked_decay(amount, current, last),
+			BudgetPenaltyDecayer::Step(step) => step.checked_decay(amount, current, last),
 		}
 	}
 
 	fn full_recovery_period(&self, amount: Balance) -> Option<BlockNumber> {
 		match self {
 			BudgetPenaltyDecayer::Linear(lin) => lin.full_recovery_period(amount),
+			BudgetPenaltyDecayer::Exponential(exp) => exp.full_recovery_period(amount),
+			BudgetPenaltyDecayer::Step(step) => step.full_recovery_period(amount),
+		}
+	}
+
+	fn is_valid(&self) -> bool {
+		match self {
+			BudgetPenaltyDecayer::Linear(lin) => lin.is_valid(),
+			BudgetPenaltyDecayer::Exponential(exp) => exp.is_valid(),
+			BudgetPenaltyDecayer::Step(step) => step.is_valid(),
 		}
 	}
 }
@@ -83,6 +115,102 @@ where
 		let block_full_period_plus_one: BlockNumber = block_full_period.checked_add(&One::one())?;
 		Some(block_full_period_plus_one)
 	}
+
+	fn is_valid(&self) -> bool {
+		true
+	}
+}
+
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Default, Debug, PartialEq, Eq, Clone)]
+pub struct ExponentialDecay<Balance, BlockNumber> {
+	/// Number of blocks after which the amount is halved. In between, the amount decreases
+	/// linearly towards the next halving.
+	half_life: BlockNumber,
+	_marker: core::marker::PhantomData<Balance>,
+}
+
+impl<Balance, BlockNumber> Decayer<Balance, BlockNumber> for ExponentialDecay<Balance, BlockNumber>
+where
+	BlockNumber: Saturating + Into<Balance> + TryFrom<Balance> + Zero + Copy,
+	Balance: CheckedMul + CheckedDiv + Saturating + Zero + One + From<u8>,
+{
+	fn checked_decay(
+		&self,
+		amount: Balance,
+		current: BlockNumber,
+		last: BlockNumber,
+	) -> Option<Balance> {
+		let two = Balance::from(2);
+		let half_life: Balance = self.half_life.into();
+		let diff: Balance = current.saturating_sub(last).into();
+		let mut halvings = diff.checked_div(&half_life)?;
+		let remainder = diff.saturating_sub(halvings.checked_mul(&half_life)?);
+
+		let mut decayed = amount;
+		// Terminates after at most the bit width of `Balance` iterations.
+		while !halvings.is_zero() && !decayed.is_zero() {
+			decayed = decayed.checked_div(&two)?;
+			halvings = halvings.saturating_sub(One::one());
+		}
+
+		let reduction =
+			decayed.checked_div(&two)?.checked_mul(&remainder)?.checked_div(&half_life)?;
+		Some(decayed.saturating_sub(reduction))
+	}
+
+	fn full_recovery_period(&self, amount: Balance) -> Option<BlockNumber> {
+		let two = Balance::from(2);
+		let mut halvings: u8 = 0;
+		let mut remaining = amount;
+		while !remaining.is_zero() {
+			remaining = remaining.checked_div(&two)?;
+			halvings = halvings.checked_add(1)?;
+		}
+		let full_period = Balance::from(halvings).checked_mul(&self.half_life.into())?;
+		TryFrom::<Balance>::try_from(full_period).ok()
+	}
+	fn is_valid(&self) -> bool {
+		!self.half_life.is_zero()
+	}
+}
+
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Default, Debug, PartialEq, Eq, Clone)]
+pub struct StepDecay<Balance, BlockNumber> {
+	/// Amount by which we decay every `period`.
+	amount: Balance,
+	/// Number of blocks between two decays. Decays happen on multiples of `period`, so that the
+	/// schedule does not depend on when the amount was last updated.
+	period: BlockNumber,
+}
+
+impl<Balance, BlockNumber> Decayer<Balance, BlockNumber> for StepDecay<Balance, BlockNumber>
+where
+	BlockNumber: Into<Balance> + TryFrom<Balance> + Zero + Copy,
+	Balance: CheckedMul + CheckedDiv + CheckedAdd + Saturating + One,
+{
+	fn checked_decay(
+		&self,
+		amount: Balance,
+		current: BlockNumber,
+		last: BlockNumber,
+	) -> Option<Balance> {
+		let period: Balance = self.period.into();
+		let steps = current
+			.into()
+			.checked_div(&period)?
+			.saturating_sub(last.into().checked_div(&period)?);
+		let reduction = steps.checked_mul(&self.amount)?;
+		Some(amount.saturating_sub(reduction))
+	}
+
+	fn full_recovery_period(&self, amount: Balance) -> Option<BlockNumber> {
+		let steps = amount.checked_div(&self.amount)?.checked_add(&One::one())?;
+		let full_period = steps.checked_mul(&self.period.into())?;
+		TryFrom::<Balance>::try_from(full_period).ok()
+	}
+	fn is_valid(&self) -> bool {
+		!self.period.is_zero()
+	}
 }
 
 #[cfg(test)]
@@ -100,4 +228,30 @@ mod tests {
 			assert!(prev > penalty);
 		});
 	}
+
+	#[test]
+	fn test_exponential_halves_every_half_life() {
+		let penalty_decayer = BudgetPenaltyDecayer::exponential(10);
+
+		assert_eq!(penalty_decayer.checked_decay(1000, 10, 0), Some(500));
+		assert_eq!(penalty_decayer.checked_decay(1000, 15, 0), Some(375));
+		assert_eq!(penalty_decayer.checked_decay(1000, 20, 0), Some(250));
+		assert_eq!(penalty_decayer.checked_decay(1000, 0, 10), Some(1000));
+
+		let period = penalty_decayer.full_recovery_period(1000).unwrap();
+		assert_eq!(penalty_decayer.checked_decay(1000, period, 0), Some(0));
+		assert!(penalty_decayer.checked_decay(1000, period - 10, 0).unwrap() > 0);
+	}
+
+	#[test]
+	fn test_step_decreases_on_period_boundaries() {
+		let penalty_decayer = BudgetPenaltyDecayer::step(100, 10);
+
+		assert_eq!(penalty_decayer.checked_decay(1000, 9, 0), Some(1000));
+		assert_eq!(penalty_decayer.checked_decay(1000, 10, 9), Some(900));
+		assert_eq!(penalty_decayer.checked_decay(1000, 35, 5), Some(700));
+
+		let period = penalty_decayer.full_recovery_period(1000).unwrap();
+		assert_eq!(penalty_decayer.checked_decay(1000, period, 0), Some(0));
+	}
 }
@@ -36,22 +36,25 @@ pub mod pallet {
 	};
 	use codec::FullCodec;
 	use composable_support::{math::safe::SafeAdd, types::EthereumAddress, validation::Validated};
-	use composable_traits::mosaic::{Claim, RelayerInterface, TransferTo};
+	use composable_traits::mosaic::{
+		BudgetStatus, Claim, PendingTransfer, PendingTransfers, RelayerInterface, RelayerStatus,
+		TransferTo,
+	};
 	use frame_support::{
 		dispatch::DispatchResultWithPostInfo,
 		pallet_prelude::*,
-		traits::fungibles::{Inspect, Mutate, Transfer},
-		transactional, PalletId,
+		traits::fungibles::{Inspect, Mutate, MutateHold, Transfer},
+		transactional, BoundedVec, PalletId,
 	};
 	use frame_system::pallet_prelude::*;
-	use num_traits::{CheckedSub, Zero};
+	use num_traits::{CheckedSub, One, Zero};
 	use scale_info::TypeInfo;
 	use sp_core::H256;
 	use sp_runtime::{
-		traits::{AccountIdConversion, Keccak256, Saturating},
+		traits::{AccountIdConversion, Hash, Keccak256, Saturating},
 		DispatchError,
 	};
-	use sp_std::{fmt::Debug, str};
+	use sp_std::{fmt::Debug, str, vec, vec::Vec};
 
 	pub(crate) type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 	pub(crate) type BalanceOf<T> = <<T as Config>::Assets as Inspect<AccountIdOf<T>>>::Balance;
@@ -68,7 +71,9 @@ pub mod pallet {
 
 		type PalletId: Get<PalletId>;
 
-		type Assets: Mutate<AccountIdOf<Self>> + Transfer<AccountIdOf<Self>>;
+		type Assets: Mutate<AccountIdOf<Self>>
+			+ Transfer<AccountIdOf<Self>>
+			+ MutateHold<AccountIdOf<Self>>;
 
 		/// The minimum time to live before a relayer account rotation.
 		#[pallet::constant]
@@ -110,6 +115,23 @@ pub mod pallet {
 		/// it is also used as the origin capable of stopping attackers.
 		type ControlOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The maximum number of relayers in the attestation committee.
+		#[pallet::constant]
+		type MaxRelayers: Get<u32>;
+
+		/// The asset in which committee relayers post their bond.
+		#[pallet::constant]
+		type RelayerBondAssetId: Get<AssetIdOf<Self>>;
+
+		/// The amount held from each committee relayer, burned if the relayer is slashed.
+		#[pallet::constant]
+		type RelayerBond: Get<BalanceOf<Self>>;
+
+		/// The number of blocks after the first attestation of a subject at which its pending
+		/// attestations expire, if they did not reach the threshold.
+		#[pallet::constant]
+		type AttestationTTL: Get<BlockNumberOf<Self>>;
+
 		/// Weight implementation used for extrinsics.
 		type WeightInfo: WeightInfo;
 	}
@@ -185,6 +207,48 @@ pub mod pallet {
 	pub type Relayer<T: Config> =
 		StorageValue<_, StaleRelayer<T::AccountId, T::BlockNumber>, OptionQuery>;
 
+	/// The relayers allowed to attest incoming mints and outgoing transfer acceptances.
+	///
+	/// If empty, the single [`Relayer`] acts alone.
+	#[pallet::storage]
+	#[pallet::getter(fn relayer_committee)]
+	#[allow(clippy::disallowed_types)]
+	pub type RelayerCommittee<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxRelayers>, ValueQuery>;
+
+	/// The number of matching attestations required before an attested action executes.
+	#[pallet::storage]
+	#[pallet::getter(fn attestation_threshold)]
+	#[allow(clippy::disallowed_types)]
+	pub type AttestationThreshold<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Pending attestations, keyed by subject and relayer. The value is the hash of the attested
+	/// data.
+	#[pallet::storage]
+	#[pallet::getter(fn attestations)]
+	pub type Attestations<T: Config> =
+		StorageDoubleMap<_, Identity, Id, Blake2_128Concat, T::AccountId, Id, OptionQuery>;
+
+	/// Subjects with pending attestations, keyed by the block at which the attestations expire.
+	#[pallet::storage]
+	pub type AttestationExpiries<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BlockNumberOf<T>, Identity, Id, (), OptionQuery>;
+
+	/// Subjects which reached the threshold, with the hash of the data that was executed.
+	#[pallet::storage]
+	#[pallet::getter(fn executed_attestations)]
+	pub type ExecutedAttestations<T: Config> = StorageMap<_, Identity, Id, Id, OptionQuery>;
+
+	/// Relayers which attested data conflicting with what was already executed for a subject.
+	/// These can be slashed by the [`ControlOrigin`](Config::ControlOrigin).
+	///
+	/// Attestations made before the subject was executed are never recorded here, as the
+	/// relayer could not know which data would reach the threshold.
+	#[pallet::storage]
+	#[pallet::getter(fn conflicting_attestations)]
+	pub type ConflictingAttestations<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Identity, Id, (), OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn asset_infos)]
 	pub type AssetsInfo<T: Config> = StorageMap<
@@ -195,11 +259,40 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// Budgets for incoming transactions of an asset from a specific network. If set, these are
+	/// used instead of the asset wide budget in [`AssetsInfo`], so that minting from one network
+	/// does not consume the capacity of the others.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_network_budgets)]
+	pub type AssetNetworkBudgets<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		Blake2_128Concat,
+		NetworkIdOf<T>,
+		AssetInfo<BlockNumberFor<T>, BalanceOf<T>, T::BudgetPenaltyDecayer>,
+		OptionQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn network_infos)]
 	pub type NetworkInfos<T: Config> =
 		StorageMap<_, Blake2_128Concat, NetworkIdOf<T>, NetworkInfo<BalanceOf<T>>, OptionQuery>;
 
+	/// Transfer limits of an asset on a specific network, overriding the limits in
+	/// [`NetworkInfos`].
+	#[pallet::storage]
+	#[pallet::getter(fn asset_network_infos)]
+	pub type AssetNetworkInfos<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		Blake2_128Concat,
+		NetworkIdOf<T>,
+		NetworkInfo<BalanceOf<T>>,
+		OptionQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn time_lock_period)]
 	#[allow(clippy::disallowed_types)]
@@ -293,8 +386,21 @@ pub mod pallet {
 			amount: BalanceOf<T>,
 			decay: T::BudgetPenaltyDecayer,
 		},
+		/// The budget of `asset_id` for incoming transactions from `network_id` was updated.
+		NetworkBudgetUpdated {
+			asset_id: AssetIdOf<T>,
+			network_id: NetworkIdOf<T>,
+			amount: BalanceOf<T>,
+			decay: T::BudgetPenaltyDecayer,
+		},
 		/// The `NetworkInfos` `network_info` was updated for `network_id`.
 		NetworksUpdated { network_id: NetworkIdOf<T>, network_info: NetworkInfo<BalanceOf<T>> },
+		/// The `AssetNetworkInfos` `network_info` was updated for `asset_id` on `network_id`.
+		AssetNetworkUpdated {
+			asset_id: AssetIdOf<T>,
+			network_id: NetworkIdOf<T>,
+			network_info: Option<NetworkInfo<BalanceOf<T>>>,
+		},
 		/// An outgoing tx is created, and locked in the outgoing tx pool.
 		TransferOut {
 			id: Id,
@@ -373,6 +479,15 @@ pub mod pallet {
 			network_id: NetworkIdOf<T>,
 			remote_asset_id: RemoteAssetIdOf<T>,
 		},
+		/// The relayer committee and attestation threshold have been set.
+		RelayerCommitteeSet { members: Vec<AccountIdOf<T>>, threshold: u32 },
+		/// A committee relayer attested `payload` for `subject`.
+		TransferAttested { relayer: AccountIdOf<T>, subject: Id, payload: Id },
+		/// A committee relayer attested data conflicting with the data already executed for
+		/// `subject`.
+		ConflictingAttestation { relayer: AccountIdOf<T>, subject: Id },
+		/// A committee relayer was removed from the committee and its bond burned.
+		RelayerSlashed { relayer: AccountIdOf<T>, amount: BalanceOf<T> },
 	}
 
 	#[pallet::error]
@@ -396,6 +511,27 @@ pub mod pallet {
 		RemoteAmmIdNotFound,
 		RemoteAmmIdAlreadyExists,
 		DestinationAmmIdNotWhitelisted,
+		InvalidAttestationThreshold,
+		DuplicateRelayer,
+		AlreadyAttested,
+		NoConflictingAttestation,
+		RelayerNotInCommittee,
+		InvalidDecay,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let mut expired: u64 = 0;
+			for (subject, ()) in AttestationExpiries::<T>::drain_prefix(now) {
+				let _ = Attestations::<T>::clear_prefix(subject, T::MaxRelayers::get(), None);
+				expired = expired.saturating_add(1);
+			}
+			T::DbWeight::get().reads_writes(
+				expired.saturating_add(1),
+				expired.saturating_mul(u64::from(T::MaxRelayers::get()).saturating_add(1)),
+			)
+		}
 	}
 
 	#[pallet::call]
@@ -471,6 +607,7 @@ pub mod pallet {
 		///
 		/// # Restrictions
 		/// - This can only be called by the [`ControlOrigin`](Config::ControlOrigin)
+		/// - The `decay` must be able to decay, its half life or period is not zero.
 		#[pallet::weight(T::WeightInfo::set_budget())]
 		#[transactional]
 		pub fn set_budget(
@@ -482,6 +619,7 @@ pub mod pallet {
 			// Can also be token governance associated I reckon, as Angular holders should be able
 			// to grant mosaic permission to mint. We'll save that for phase 3.
 			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(decay.is_valid(), Error::<T>::InvalidDecay);
 
 			<Pallet<T> as RelayerInterface>::set_budget(asset_id, amount, decay.clone());
 
@@ -489,6 +627,76 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Sets the relayer budget for _incoming_ transactions for specific assets from a specific
+		/// network. This budget is used instead of the asset wide budget for that network. Does
+		/// not reset the current `penalty`.
+		///
+		/// # Restrictions
+		/// - This can only be called by the [`ControlOrigin`](Config::ControlOrigin)
+		/// - The `decay` must be able to decay, its half life or period is not zero.
+		/// - The asset must have an asset wide budget.
+		/// - The network must be supported.
+		#[pallet::weight(T::WeightInfo::set_network_budget())]
+		#[transactional]
+		pub fn set_network_budget(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			network_id: NetworkIdOf<T>,
+			amount: BalanceOf<T>,
+			decay: T::BudgetPenaltyDecayer,
+		) -> DispatchResultWithPostInfo {
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(decay.is_valid(), Error::<T>::InvalidDecay);
+			ensure!(AssetsInfo::<T>::contains_key(asset_id), Error::<T>::UnsupportedAsset);
+			ensure!(
+				NetworkInfos::<T>::contains_key(network_id.clone()),
+				Error::<T>::UnsupportedNetwork
+			);
+
+			<Pallet<T> as RelayerInterface>::set_network_budget(
+				asset_id,
+				network_id.clone(),
+				amount,
+				decay.clone(),
+			);
+
+			Self::deposit_event(Event::NetworkBudgetUpdated {
+				asset_id,
+				network_id,
+				amount,
+				decay,
+			});
+			Ok(().into())
+		}
+
+		/// Sets or removes the transfer limits of an asset on a network, overriding the network
+		/// wide limits set by [`set_network`](Pallet::set_network).
+		///
+		/// Only callable by the current Relayer
+		#[pallet::weight(T::WeightInfo::set_asset_network())]
+		#[transactional]
+		pub fn set_asset_network(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			network_id: NetworkIdOf<T>,
+			network_info: Option<NetworkInfo<BalanceOf<T>>>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_relayer(origin)?;
+			ensure!(
+				NetworkInfos::<T>::contains_key(network_id.clone()),
+				Error::<T>::UnsupportedNetwork
+			);
+
+			<Pallet<T> as RelayerInterface>::set_asset_network(
+				asset_id,
+				network_id.clone(),
+				network_info.clone(),
+			);
+
+			Self::deposit_event(Event::AssetNetworkUpdated { asset_id, network_id, network_info });
+			Ok(().into())
+		}
+
 		/// Creates an outgoing transaction request, locking the funds locally until picked up by
 		/// the Relayer.
 		///
@@ -520,9 +728,7 @@ pub mod pallet {
 			let caller = ensure_signed(origin)?;
 			ensure!(AssetsInfo::<T>::contains_key(asset_id), Error::<T>::UnsupportedAsset);
 			let remote_asset_id = Self::get_remote_mapping(asset_id, network_id.clone())?;
-			let network_info =
-				NetworkInfos::<T>::get(network_id.clone()).ok_or(Error::<T>::UnsupportedNetwork)?;
-			ensure!(network_info.enabled, Error::<T>::NetworkDisabled);
+			let network_info = Self::network_info(asset_id, network_id.clone())?;
 			ensure!(network_info.max_transfer_size >= amount, Error::<T>::ExceedsMaxTransferSize);
 			ensure!(network_info.min_transfer_size <= amount, Error::<T>::BelowMinTransferSize);
 
@@ -573,13 +779,16 @@ pub mod pallet {
 		/// remainder of the transaction.
 		///
 		/// # Restrictions
-		/// - Only callable by the current Relayer
+		/// - Only callable by the current Relayer, or a member of the relayer committee
 		/// - Outgoing transaction must exist for the user
 		/// - Amount must be equal or lower than what the user has locked
 		///
 		/// # Note
 		/// - Reclaim period is not reset if not all the funds are moved; meaning that the clock
 		///   remains ticking for the relayer to pick up the rest of the transaction.
+		/// - If a relayer committee is set, the transfer is only accepted once
+		///   [`AttestationThreshold`] members attested the same `network_id`, `remote_asset_id` and
+		///   `amount` for the current outgoing transaction.
 		#[pallet::weight(T::WeightInfo::accept_transfer())]
 		#[transactional]
 		pub fn accept_transfer(
@@ -589,9 +798,20 @@ pub mod pallet {
 			remote_asset_id: RemoteAssetIdOf<T>,
 			amount: BalanceOf<T>,
 		) -> DispatchResultWithPostInfo {
-			Self::ensure_relayer(origin)?;
+			let attestor = Self::ensure_attestor(origin)?;
 			let asset_id = Self::get_local_mapping(remote_asset_id.clone(), network_id.clone())?;
 
+			if let Some(relayer) = attestor {
+				let (balance, lock_until) = OutgoingTransactions::<T>::get(&from, asset_id)
+					.ok_or(Error::<T>::NoOutgoingTx)?;
+				let subject =
+					Keccak256::hash_of(&(b"outgoing", &from, asset_id, balance, lock_until));
+				let payload = Keccak256::hash_of(&(&network_id, &remote_asset_id, amount));
+				if !Self::attest(relayer, subject, payload)? {
+					return Ok(().into())
+				}
+			}
+
 			<Pallet<T> as RelayerInterface>::accept_transfer(
 				asset_id,
 				from,
@@ -624,7 +844,9 @@ pub mod pallet {
 		/// Mints new tokens into the pallet's wallet, ready for the user to be picked up after
 		/// `lock_time` blocks have expired.
 		///
-		/// Only callable by the current Relayer
+		/// Only callable by the current Relayer, or a member of the relayer committee. If a
+		/// committee is set, the mint is only executed once [`AttestationThreshold`] members
+		/// attested the same data for `(network_id, id)`.
 		#[pallet::weight(T::WeightInfo::timelocked_mint())]
 		#[transactional]
 		pub fn timelocked_mint(
@@ -636,11 +858,21 @@ pub mod pallet {
 			lock_time: BlockNumberOf<T>,
 			id: Id,
 		) -> DispatchResultWithPostInfo {
-			let (_caller, current_block) = Self::ensure_relayer(origin)?;
+			let attestor = Self::ensure_attestor(origin)?;
 			let asset_id = Self::get_local_mapping(remote_asset_id.clone(), network_id.clone())?;
+			let current_block = <frame_system::Pallet<T>>::block_number();
+
+			if let Some(relayer) = attestor {
+				let subject = Keccak256::hash_of(&(b"incoming", &network_id, id));
+				let payload = Keccak256::hash_of(&(&remote_asset_id, &to, amount, lock_time));
+				if !Self::attest(relayer, subject, payload)? {
+					return Ok(().into())
+				}
+			}
 
 			<Pallet<T> as RelayerInterface>::timelocked_mint(
 				asset_id,
+				network_id.clone(),
 				current_block,
 				to.clone(),
 				amount,
@@ -835,6 +1067,93 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Sets the relayer committee and the number of matching attestations required to mint
+		/// or accept a transfer.
+		///
+		/// New members have [`RelayerBond`](Config::RelayerBond) held, removed members have it
+		/// released. Pending attestations of removed members no longer count towards the
+		/// threshold. Setting an empty committee with a threshold of 0 falls back to the single
+		/// [`Relayer`].
+		///
+		/// This can only be called by the [`ControlOrigin`](Config::ControlOrigin)
+		#[pallet::weight(T::WeightInfo::set_relayer_committee(members.len() as u32))]
+		#[transactional]
+		pub fn set_relayer_committee(
+			origin: OriginFor<T>,
+			members: BoundedVec<T::AccountId, T::MaxRelayers>,
+			threshold: u32,
+		) -> DispatchResultWithPostInfo {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				(members.is_empty() && threshold == 0) ||
+					(threshold > 0 && threshold as usize <= members.len()),
+				Error::<T>::InvalidAttestationThreshold
+			);
+			for (index, member) in members.iter().enumerate() {
+				ensure!(!members[..index].contains(member), Error::<T>::DuplicateRelayer);
+			}
+
+			let asset_id = T::RelayerBondAssetId::get();
+			let bond = T::RelayerBond::get();
+			let previous = RelayerCommittee::<T>::get();
+			for removed in previous.iter().filter(|member| !members.contains(member)) {
+				T::Assets::release(asset_id, removed, bond, true)?;
+			}
+			for added in members.iter().filter(|member| !previous.contains(member)) {
+				T::Assets::hold(asset_id, added, bond)?;
+			}
+
+			RelayerCommittee::<T>::set(members.clone());
+			AttestationThreshold::<T>::set(threshold);
+
+			Self::deposit_event(Event::<T>::RelayerCommitteeSet {
+				members: members.into_inner(),
+				threshold,
+			});
+
+			Ok(().into())
+		}
+
+		/// Slashes a committee relayer which attested data conflicting with the data already
+		/// executed for `subject`. The relayer is removed from the committee and its bond is
+		/// burned.
+		///
+		/// The threshold is left untouched, so the [`ControlOrigin`](Config::ControlOrigin) is
+		/// expected to reconfigure the committee if it can no longer be reached.
+		///
+		/// This can only be called by the [`ControlOrigin`](Config::ControlOrigin)
+		#[pallet::weight(T::WeightInfo::slash_relayer())]
+		#[transactional]
+		pub fn slash_relayer(
+			origin: OriginFor<T>,
+			relayer: T::AccountId,
+			subject: Id,
+		) -> DispatchResultWithPostInfo {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				ConflictingAttestations::<T>::take(&relayer, subject).is_some(),
+				Error::<T>::NoConflictingAttestation
+			);
+			RelayerCommittee::<T>::try_mutate(|members| -> DispatchResult {
+				let index = members
+					.iter()
+					.position(|member| member == &relayer)
+					.ok_or(Error::<T>::RelayerNotInCommittee)?;
+				members.remove(index);
+				Ok(())
+			})?;
+
+			let asset_id = T::RelayerBondAssetId::get();
+			let amount = T::Assets::release(asset_id, &relayer, T::RelayerBond::get(), true)?;
+			let amount = T::Assets::burn_from(asset_id, &relayer, amount)?;
+
+			Self::deposit_event(Event::<T>::RelayerSlashed { relayer, amount });
+
+			Ok(().into())
+		}
 	}
 
 	#[pallet::extra_constants]
@@ -875,6 +1194,194 @@ pub mod pallet {
 			Ok((relayer, current_block))
 		}
 
+		/// Ensures that `origin` may attest relayed data.
+		///
+		/// Returns `None` if no committee is set and `origin` is the single relayer, in which case
+		/// the data is executed directly. Returns the committee member otherwise.
+		pub(crate) fn ensure_attestor(
+			origin: OriginFor<T>,
+		) -> Result<Option<AccountIdOf<T>>, DispatchError> {
+			let committee = RelayerCommittee::<T>::get();
+			if committee.is_empty() {
+				Self::ensure_relayer(origin)?;
+				return Ok(None)
+			}
+			let acc = ensure_signed(origin).map_err(|_| DispatchError::BadOrigin)?;
+			ensure!(committee.contains(&acc), DispatchError::BadOrigin);
+			Ok(Some(acc))
+		}
+
+		/// Records the attestation of `payload` for `subject` by `relayer`.
+		///
+		/// Returns `true` if the attestation made `payload` reach the threshold, in which case the
+		/// caller must execute it. Attestations for already executed subjects are only checked
+		/// for conflicts. Pending attestations expire [`AttestationTTL`](Config::AttestationTTL)
+		/// blocks after the first attestation of the subject.
+		pub(crate) fn attest(
+			relayer: AccountIdOf<T>,
+			subject: Id,
+			payload: Id,
+		) -> Result<bool, DispatchError> {
+			if let Some(executed) = ExecutedAttestations::<T>::get(subject) {
+				if executed != payload {
+					ConflictingAttestations::<T>::insert(&relayer, subject, ());
+					Self::deposit_event(Event::<T>::ConflictingAttestation { relayer, subject });
+				}
+				return Ok(false)
+			}
+
+			ensure!(
+				!Attestations::<T>::contains_key(subject, &relayer),
+				Error::<T>::AlreadyAttested
+			);
+			if Attestations::<T>::iter_prefix(subject).next().is_none() {
+				let expires_at = <frame_system::Pallet<T>>::block_number()
+					.saturating_add(T::AttestationTTL::get());
+				AttestationExpiries::<T>::insert(expires_at, subject, ());
+			}
+			Attestations::<T>::insert(subject, &relayer, payload);
+			Self::deposit_event(Event::<T>::TransferAttested { relayer, subject, payload });
+
+			let committee = RelayerCommittee::<T>::get();
+			let matching = Attestations::<T>::iter_prefix(subject)
+				.filter(|(member, attested)| attested == &payload && committee.contains(member))
+				.count();
+			if (matching as u32) < AttestationThreshold::<T>::get() {
+				return Ok(false)
+			}
+
+			let _ = Attestations::<T>::clear_prefix(subject, T::MaxRelayers::get(), None);
+			ExecutedAttestations::<T>::insert(subject, payload);
+			Ok(true)
+		}
+
+		/// Returns the transfer limits of `asset_id` on `network_id`, ensuring that the network is
+		/// enabled for the asset.
+		pub(crate) fn network_info(
+			asset_id: AssetIdOf<T>,
+			network_id: NetworkIdOf<T>,
+		) -> Result<NetworkInfo<BalanceOf<T>>, DispatchError> {
+			let network_info =
+				NetworkInfos::<T>::get(network_id.clone()).ok_or(Error::<T>::UnsupportedNetwork)?;
+			ensure!(network_info.enabled, Error::<T>::NetworkDisabled);
+			let network_info =
+				AssetNetworkInfos::<T>::get(asset_id, network_id).unwrap_or(network_info);
+			ensure!(network_info.enabled, Error::<T>::NetworkDisabled);
+			Ok(network_info)
+		}
+
+		/// Returns the penalty of `info` decayed up to `current_block`.
+		fn decayed_penalty(
+			info: &AssetInfo<BlockNumberOf<T>, BalanceOf<T>, T::BudgetPenaltyDecayer>,
+			current_block: BlockNumberOf<T>,
+		) -> BalanceOf<T> {
+			info.penalty_decayer
+				.checked_decay(info.penalty, current_block, info.last_mint_block)
+				.unwrap_or_else(Zero::zero)
+		}
+
+		/// Returns the incoming and outgoing transfers of `account` which are still locked in the
+		/// pallet.
+		pub fn pending_transfers(
+			account: AccountIdOf<T>,
+		) -> PendingTransfers<AssetIdOf<T>, BalanceOf<T>, BlockNumberOf<T>> {
+			// Transactions can be claimed once the block number exceeds the stored one.
+			let to_pending =
+				|(asset_id, (amount, locked_until)): (_, (_, BlockNumberOf<T>))| PendingTransfer {
+					asset_id,
+					amount,
+					unlock_block: locked_until.saturating_add(One::one()),
+				};
+			PendingTransfers {
+				incoming: IncomingTransactions::<T>::iter_prefix(&account)
+					.map(to_pending)
+					.collect(),
+				outgoing: OutgoingTransactions::<T>::iter_prefix(&account)
+					.map(to_pending)
+					.collect(),
+			}
+		}
+
+		/// Returns the decayed penalty and remaining budget of every asset wide and per network
+		/// budget.
+		pub fn budgets() -> Vec<BudgetStatus<AssetIdOf<T>, NetworkIdOf<T>, BalanceOf<T>>> {
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			AssetsInfo::<T>::iter()
+				.map(|(asset_id, info)| Self::budget_status(asset_id, None, info, current_block))
+				.chain(AssetNetworkBudgets::<T>::iter().map(|(asset_id, network_id, info)| {
+					Self::budget_status(asset_id, Some(network_id), info, current_block)
+				}))
+				.collect()
+		}
+
+		fn budget_status(
+			asset_id: AssetIdOf<T>,
+			network_id: Option<NetworkIdOf<T>>,
+			info: AssetInfo<BlockNumberOf<T>, BalanceOf<T>, T::BudgetPenaltyDecayer>,
+			current_block: BlockNumberOf<T>,
+		) -> BudgetStatus<AssetIdOf<T>, NetworkIdOf<T>, BalanceOf<T>> {
+			let penalty = Self::decayed_penalty(&info, current_block);
+			BudgetStatus {
+				asset_id,
+				network_id,
+				budget: info.budget,
+				penalty,
+				remaining: info.budget.saturating_sub(penalty),
+			}
+		}
+
+		/// Returns the current relayer and its scheduled rotation, if a relayer is set.
+		pub fn relayer_status() -> Option<RelayerStatus<AccountIdOf<T>, BlockNumberOf<T>>> {
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			Relayer::<T>::get().map(|relayer| {
+				let relayer = relayer.update(current_block);
+				let next = relayer.next();
+				RelayerStatus {
+					current: relayer.account_id().clone(),
+					next: next.map(|(account, _)| account.clone()),
+					ttl: next.map(|(_, ttl)| *ttl),
+				}
+			})
+		}
+
+		/// Returns the whitelisted remote AMM IDs, grouped by network.
+		pub fn remote_amm_ids() -> Vec<(NetworkIdOf<T>, Vec<RemoteAmmIdOf<T>>)> {
+			let mut networks: Vec<(NetworkIdOf<T>, Vec<RemoteAmmIdOf<T>>)> = Vec::new();
+			for (network_id, amm_id, ()) in RemoteAmmWhitelist::<T>::iter() {
+				match networks.iter_mut().find(|(id, _)| id == &network_id) {
+					Some((_, amm_ids)) => amm_ids.push(amm_id),
+					None => networks.push((network_id, vec![amm_id])),
+				}
+			}
+			networks
+		}
+
+		/// Decays the penalty of `info` and adds `amount` to it, ensuring that the penalised budget
+		/// covers `amount`.
+		fn charge_budget(
+			info: &mut Option<AssetInfo<BlockNumberOf<T>, BalanceOf<T>, T::BudgetPenaltyDecayer>>,
+			current_block: BlockNumberOf<T>,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let asset_info = info.take().ok_or(Error::<T>::UnsupportedAsset)?;
+			let new_penalty = Self::decayed_penalty(&asset_info, current_block);
+			let AssetInfo { budget, penalty_decayer, .. } = asset_info;
+
+			let penalised_budget = budget.saturating_sub(new_penalty);
+
+			// Check if the relayer has a sufficient budget to mint the requested amount.
+			ensure!(amount <= penalised_budget, Error::<T>::InsufficientBudget);
+
+			*info = Some(AssetInfo {
+				last_mint_block: current_block,
+				budget,
+				penalty: new_penalty.saturating_add(amount),
+				penalty_decayer,
+			});
+
+			Ok(())
+		}
+
 		pub(crate) fn get_local_mapping(
 			remote_asset_id: RemoteAssetIdOf<T>,
 			network_id: NetworkIdOf<T>,
@@ -1018,10 +1525,44 @@ pub mod pallet {
 			});
 		}
 
+		fn set_network_budget(
+			asset_id: Self::AssetId,
+			network_id: Self::NetworkId,
+			amount: Self::Balance,
+			decay: Self::BudgetPenaltyDecayer,
+		) {
+			let current_block = <frame_system::Pallet<T>>::block_number();
+
+			AssetNetworkBudgets::<T>::mutate(asset_id, network_id, |item| {
+				let new = item
+					.take()
+					.map(|mut asset_info| {
+						asset_info.budget = amount;
+						asset_info.penalty_decayer = decay.clone();
+						asset_info
+					})
+					.unwrap_or_else(|| AssetInfo {
+						last_mint_block: current_block,
+						budget: amount,
+						penalty: Zero::zero(),
+						penalty_decayer: decay.clone(),
+					});
+				*item = Some(new);
+			});
+		}
+
 		fn set_network(network_id: Self::NetworkId, network_info: Self::NetworkInfo) {
 			NetworkInfos::<T>::insert(network_id, network_info);
 		}
 
+		fn set_asset_network(
+			asset_id: Self::AssetId,
+			network_id: Self::NetworkId,
+			network_info: Option<Self::NetworkInfo>,
+		) {
+			AssetNetworkInfos::<T>::set(asset_id, network_id, network_info);
+		}
+
 		fn set_relayer(relayer: Self::AccountId) {
 			Relayer::<T>::set(Some(StaleRelayer::new(relayer)));
 		}
@@ -1032,47 +1573,34 @@ pub mod pallet {
 
 		fn timelocked_mint(
 			asset_id: Self::AssetId,
+			network_id: Self::NetworkId,
 			current_block: Self::BlockNumber,
 			to: Self::AccountId,
 			amount: Self::Balance,
 			lock_time: Self::BlockNumber,
 		) -> DispatchResultWithPostInfo {
-			AssetsInfo::<T>::try_mutate_exists::<_, _, DispatchError, _>(asset_id, |info| {
-				let AssetInfo { last_mint_block, penalty, budget, penalty_decayer } =
-					info.take().ok_or(Error::<T>::UnsupportedAsset)?;
-
-				let new_penalty = penalty_decayer
-					.checked_decay(penalty, current_block, last_mint_block)
-					.unwrap_or_else(Zero::zero);
-
-				let penalised_budget = budget.saturating_sub(new_penalty);
-
-				// Check if the relayer has a sufficient budget to mint the requested amount.
-				ensure!(amount <= penalised_budget, Error::<T>::InsufficientBudget);
-
-				T::Assets::mint_into(
-					asset_id,
-					&Self::sub_account_id(SubAccount::new_incoming(to.clone())),
-					amount,
-				)?;
-
-				let lock_at = current_block.saturating_add(lock_time);
+			if AssetNetworkBudgets::<T>::contains_key(asset_id, network_id.clone()) {
+				AssetNetworkBudgets::<T>::try_mutate_exists(asset_id, network_id, |info| {
+					Self::charge_budget(info, current_block, amount)
+				})?;
+			} else {
+				AssetsInfo::<T>::try_mutate_exists(asset_id, |info| {
+					Self::charge_budget(info, current_block, amount)
+				})?;
+			}
 
-				IncomingTransactions::<T>::mutate(to.clone(), asset_id, |prev| match prev {
-					Some((balance, _)) =>
-						*prev = Some(((*balance).saturating_add(amount), lock_at)),
-					_ => *prev = Some((amount, lock_at)),
-				});
+			T::Assets::mint_into(
+				asset_id,
+				&Self::sub_account_id(SubAccount::new_incoming(to.clone())),
+				amount,
+			)?;
 
-				*info = Some(AssetInfo {
-					last_mint_block: current_block,
-					budget,
-					penalty: new_penalty.saturating_add(amount),
-					penalty_decayer,
-				});
+			let lock_at = current_block.saturating_add(lock_time);
 
-				Ok(())
-			})?;
+			IncomingTransactions::<T>::mutate(to, asset_id, |prev| match prev {
+				Some((balance, _)) => *prev = Some(((*balance).saturating_add(amount), lock_at)),
+				_ => *prev = Some((amount, lock_at)),
+			});
 
 			Ok(().into())
 		}
@@ -32,6 +32,8 @@ pub const ALICE: AccountId = 1_u128;
 pub const BOB: AccountId = 2_u128;
 pub const CHARLIE: AccountId = 3_u128;
 pub const RELAYER: AccountId = 4_u128;
+pub const RELAYER_BOND_ASSET_ID: AssetId = 42;
+pub const RELAYER_BOND: Balance = 1000;
 
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -104,6 +106,10 @@ parameter_types! {
 	pub const MosaicPalletId: PalletId = PalletId(*b"plt_msac");
 	pub const MinimumTTL: BlockNumber = 10;
 	pub const MinimumTimeLockPeriod: BlockNumber = 20;
+	pub const MaxRelayers: u32 = 8;
+	pub const RelayerBondAssetId: AssetId = RELAYER_BOND_ASSET_ID;
+	pub const RelayerBond: Balance = RELAYER_BOND;
+	pub const AttestationTTL: BlockNumber = 10;
 }
 
 impl pallet_mosaic::Config for Test {
@@ -119,6 +125,10 @@ impl pallet_mosaic::Config for Test {
 	type WeightInfo = ();
 	type RemoteAmmId = RemoteAmmId;
 	type AmmMinimumAmountOut = AmmMinimumAmountOut;
+	type MaxRelayers = MaxRelayers;
+	type RelayerBondAssetId = RelayerBondAssetId;
+	type RelayerBond = RelayerBond;
+	type AttestationTTL = AttestationTTL;
 }
 
 // Build genesis storage according to the mock runtime.
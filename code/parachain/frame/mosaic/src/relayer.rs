@@ -34,6 +34,11 @@ impl<AccountId, BlockNumber> RelayerConfig<AccountId, BlockNumber> {
 	pub fn account_id(&self) -> &AccountId {
 		&self.current
 	}
+
+	/// Returns the scheduled account and the block at which it becomes the relayer, if any.
+	pub fn next(&self) -> Option<(&AccountId, &BlockNumber)> {
+		self.next.as_ref().map(|next| (&next.account, &next.ttl))
+	}
 }
 
 impl<AccountId, BlockNumber> From<RelayerConfig<AccountId, BlockNumber>>
@@ -34,10 +34,14 @@ use composable_support::{types::EthereumAddress, validation::Validated};
 use composable_tests_helpers::{prop_assert_noop, prop_assert_ok};
 use frame_support::{
 	assert_err, assert_noop, assert_ok,
+	dispatch::DispatchResultWithPostInfo,
 	traits::fungibles::{Inspect, Mutate},
 };
 use proptest::prelude::*;
-use sp_runtime::{DispatchError, TokenError};
+use sp_runtime::{
+	traits::{Hash, Keccak256},
+	DispatchError, TokenError,
+};
 
 pub trait OriginExt {
 	fn relayer() -> Origin {
@@ -305,6 +309,20 @@ mod budget {
 				);
 			})
 		}
+
+		#[test]
+		fn cannot_set_budget_with_zero_period() {
+			new_test_ext().execute_with(|| {
+				assert_noop!(
+					Mosaic::set_budget(Origin::root(), 1, 1, BudgetPenaltyDecayer::exponential(0)),
+					Error::<Test>::InvalidDecay
+				);
+				assert_noop!(
+					Mosaic::set_budget(Origin::root(), 1, 1, BudgetPenaltyDecayer::step(10, 0)),
+					Error::<Test>::InvalidDecay
+				);
+			})
+		}
 	}
 
 	#[test]
@@ -355,6 +373,153 @@ mod budget {
 			);
 		})
 	}
+
+	#[test]
+	fn network_budgets_are_isolated() {
+		new_test_ext().execute_with(|| {
+			initialize();
+			let other_network_id = NETWORK_ID + 1;
+			let other_remote_asset_id = [2u8; 20];
+			assert_ok!(Mosaic::set_network(
+				Origin::relayer(),
+				other_network_id,
+				NetworkInfo { enabled: true, min_transfer_size: 1, max_transfer_size: 100000 },
+			));
+			assert_ok!(Mosaic::update_asset_mapping(
+				Origin::root(),
+				ASSET_ID,
+				other_network_id,
+				Some(other_remote_asset_id)
+			));
+			assert_ok!(Mosaic::set_network_budget(
+				Origin::root(),
+				ASSET_ID,
+				NETWORK_ID,
+				100,
+				BudgetPenaltyDecayer::step(10, 5)
+			));
+
+			do_timelocked_mint(ALICE, 100, 10);
+			assert_noop!(
+				Mosaic::timelocked_mint(
+					Origin::relayer(),
+					NETWORK_ID,
+					REMOTE_ASSET_ID,
+					ALICE,
+					1,
+					10,
+					Default::default()
+				),
+				Error::<Test>::InsufficientBudget
+			);
+
+			// the asset wide budget is untouched and used by the other network.
+			assert_eq!(Mosaic::asset_infos(ASSET_ID).expect("budget must exists").penalty, 0);
+			assert_ok!(Mosaic::timelocked_mint(
+				Origin::relayer(),
+				other_network_id,
+				other_remote_asset_id,
+				BOB,
+				1000,
+				10,
+				Default::default()
+			));
+		})
+	}
+
+	#[test]
+	fn cannot_set_network_budget_for_unsupported_asset_or_network() {
+		new_test_ext().execute_with(|| {
+			initialize();
+			assert_noop!(
+				Mosaic::set_network_budget(
+					Origin::root(),
+					ASSET_ID + 1,
+					NETWORK_ID,
+					100,
+					BudgetPenaltyDecayer::linear(5)
+				),
+				Error::<Test>::UnsupportedAsset
+			);
+			assert_noop!(
+				Mosaic::set_network_budget(
+					Origin::root(),
+					ASSET_ID,
+					NETWORK_ID + 1,
+					100,
+					BudgetPenaltyDecayer::linear(5)
+				),
+				Error::<Test>::UnsupportedNetwork
+			);
+		})
+	}
+
+	#[test]
+	fn cannot_set_network_budget_with_zero_period() {
+		new_test_ext().execute_with(|| {
+			initialize();
+			assert_noop!(
+				Mosaic::set_network_budget(
+					Origin::root(),
+					ASSET_ID,
+					NETWORK_ID,
+					100,
+					BudgetPenaltyDecayer::exponential(0)
+				),
+				Error::<Test>::InvalidDecay
+			);
+			assert_noop!(
+				Mosaic::set_network_budget(
+					Origin::root(),
+					ASSET_ID,
+					NETWORK_ID,
+					100,
+					BudgetPenaltyDecayer::step(10, 0)
+				),
+				Error::<Test>::InvalidDecay
+			);
+		})
+	}
+
+	#[test]
+	fn asset_network_limits_override_network_limits() {
+		new_test_ext().execute_with(|| {
+			initialize();
+			assert_ok!(Mosaic::set_asset_network(
+				Origin::relayer(),
+				ASSET_ID,
+				NETWORK_ID,
+				Some(NetworkInfo { enabled: true, min_transfer_size: 1, max_transfer_size: 50 }),
+			));
+			let transfer = |amount| {
+				Mosaic::transfer_to(
+					Origin::alice(),
+					NETWORK_ID,
+					ASSET_ID,
+					EthereumAddress([0; 20]),
+					amount,
+					amount,
+					false,
+					ALICE,
+					None,
+					true,
+				)
+			};
+			assert_noop!(transfer(100), Error::<Test>::ExceedsMaxTransferSize);
+			assert_ok!(transfer(50));
+
+			assert_ok!(Mosaic::set_asset_network(
+				Origin::relayer(),
+				ASSET_ID,
+				NETWORK_ID,
+				Some(NetworkInfo { enabled: false, min_transfer_size: 1, max_transfer_size: 50 }),
+			));
+			assert_noop!(transfer(50), Error::<Test>::NetworkDisabled);
+
+			assert_ok!(Mosaic::set_asset_network(Origin::relayer(), ASSET_ID, NETWORK_ID, None));
+			assert_ok!(transfer(100));
+		})
+	}
 }
 
 #[test]
@@ -1350,6 +1515,326 @@ mod claim_to {
 	}
 }
 
+mod queries {
+	use super::*;
+	use composable_traits::mosaic::{BudgetStatus, PendingTransfer, RelayerStatus};
+
+	#[test]
+	fn pending_transfers_report_unlock_blocks() {
+		new_test_ext().execute_with(|| {
+			initialize();
+			do_timelocked_mint(ALICE, 50, 10);
+			assert_ok!(Mosaic::transfer_to(
+				Origin::alice(),
+				NETWORK_ID,
+				ASSET_ID,
+				EthereumAddress([0; 20]),
+				100,
+				100,
+				false,
+				ALICE,
+				None,
+				true,
+			));
+
+			let pending = Mosaic::pending_transfers(ALICE);
+			assert_eq!(
+				pending.incoming,
+				vec![PendingTransfer { asset_id: ASSET_ID, amount: 50, unlock_block: 12 }]
+			);
+			let unlock_block = System::block_number() + MinimumTimeLockPeriod::get() + 1;
+			assert_eq!(
+				pending.outgoing,
+				vec![PendingTransfer { asset_id: ASSET_ID, amount: 100, unlock_block }]
+			);
+
+			// the incoming transfer cannot be claimed before, but can be claimed at, its unlock
+			// block.
+			System::set_block_number(11);
+			assert_noop!(
+				Mosaic::claim_to(Origin::alice(), ASSET_ID, ALICE),
+				Error::<Test>::TxStillLocked
+			);
+			System::set_block_number(12);
+			assert_ok!(Mosaic::claim_to(Origin::alice(), ASSET_ID, ALICE));
+			assert!(Mosaic::pending_transfers(ALICE).incoming.is_empty());
+		})
+	}
+
+	#[test]
+	fn budgets_report_decayed_penalty() {
+		new_test_ext().execute_with(|| {
+			initialize();
+			do_timelocked_mint(ALICE, 500, 10);
+			System::set_block_number(11);
+
+			assert_eq!(
+				Mosaic::budgets(),
+				vec![BudgetStatus {
+					asset_id: ASSET_ID,
+					network_id: None,
+					budget: BUDGET,
+					penalty: 400,
+					remaining: BUDGET - 400,
+				}]
+			);
+		})
+	}
+
+	#[test]
+	fn relayer_status_reports_rotation() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(Mosaic::relayer_status(), None);
+			initialize();
+			assert_ok!(Mosaic::rotate_relayer(Origin::relayer(), BOB, Validated::new(15).unwrap()));
+			assert_eq!(
+				Mosaic::relayer_status(),
+				Some(RelayerStatus { current: RELAYER, next: Some(BOB), ttl: Some(16) })
+			);
+
+			System::set_block_number(16);
+			assert_eq!(
+				Mosaic::relayer_status(),
+				Some(RelayerStatus { current: BOB, next: None, ttl: None })
+			);
+		})
+	}
+
+	#[test]
+	fn remote_amm_ids_are_grouped_by_network() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Mosaic::add_remote_amm_id(Origin::root(), 1, 10));
+			assert_ok!(Mosaic::add_remote_amm_id(Origin::root(), 1, 11));
+			assert_ok!(Mosaic::add_remote_amm_id(Origin::root(), 2, 20));
+
+			let mut amm_ids = Mosaic::remote_amm_ids();
+			amm_ids.sort();
+			amm_ids.iter_mut().for_each(|(_, ids)| ids.sort());
+			assert_eq!(amm_ids, vec![(1, vec![10, 11]), (2, vec![20])]);
+		})
+	}
+}
+
+mod relayer_committee {
+	use super::*;
+	use frame_support::traits::Hooks;
+
+	const COMMITTEE: [AccountId; 3] = [ALICE, BOB, CHARLIE];
+
+	fn initialize_committee(threshold: u32) {
+		initialize();
+		for member in COMMITTEE {
+			assert_ok!(Tokens::mint_into(RELAYER_BOND_ASSET_ID, &member, RELAYER_BOND));
+		}
+		assert_ok!(Mosaic::set_relayer_committee(
+			Origin::root(),
+			COMMITTEE.to_vec().try_into().unwrap(),
+			threshold
+		));
+	}
+
+	fn attest_mint(relayer: AccountId, amount: Balance) -> DispatchResultWithPostInfo {
+		Mosaic::timelocked_mint(
+			Origin::signed(relayer),
+			NETWORK_ID,
+			REMOTE_ASSET_ID,
+			CHARLIE,
+			amount,
+			10,
+			Id::repeat_byte(1),
+		)
+	}
+
+	#[test]
+	fn set_relayer_committee_holds_bonds() {
+		new_test_ext().execute_with(|| {
+			initialize_committee(2);
+			assert_eq!(Mosaic::relayer_committee().into_inner(), COMMITTEE.to_vec());
+			assert_eq!(Mosaic::attestation_threshold(), 2);
+			assert_eq!(Tokens::reducible_balance(RELAYER_BOND_ASSET_ID, &BOB, false), 0);
+
+			assert_ok!(Mosaic::set_relayer_committee(
+				Origin::root(),
+				vec![ALICE, CHARLIE].try_into().unwrap(),
+				1
+			));
+			assert_eq!(Tokens::reducible_balance(RELAYER_BOND_ASSET_ID, &BOB, false), RELAYER_BOND);
+		})
+	}
+
+	#[test]
+	fn set_relayer_committee_rejects_invalid_threshold() {
+		new_test_ext().execute_with(|| {
+			initialize();
+			assert_noop!(
+				Mosaic::set_relayer_committee(Origin::root(), vec![ALICE].try_into().unwrap(), 2),
+				Error::<Test>::InvalidAttestationThreshold
+			);
+			assert_noop!(
+				Mosaic::set_relayer_committee(Origin::root(), vec![ALICE].try_into().unwrap(), 0),
+				Error::<Test>::InvalidAttestationThreshold
+			);
+			assert_noop!(
+				Mosaic::set_relayer_committee(
+					Origin::root(),
+					vec![ALICE, ALICE].try_into().unwrap(),
+					1
+				),
+				Error::<Test>::DuplicateRelayer
+			);
+		})
+	}
+
+	#[test]
+	fn mint_requires_threshold_attestations() {
+		new_test_ext().execute_with(|| {
+			initialize_committee(2);
+
+			assert_ok!(attest_mint(ALICE, 50));
+			assert_eq!(Mosaic::incoming_transactions(CHARLIE, ASSET_ID), None);
+			assert_noop!(attest_mint(ALICE, 50), Error::<Test>::AlreadyAttested);
+
+			assert_ok!(attest_mint(BOB, 50));
+			assert_eq!(Mosaic::incoming_transactions(CHARLIE, ASSET_ID), Some((50, 11)));
+
+			// attesting an executed subject with the same data is a no-op.
+			assert_ok!(attest_mint(CHARLIE, 50));
+			assert_eq!(Mosaic::incoming_transactions(CHARLIE, ASSET_ID), Some((50, 11)));
+		})
+	}
+
+	#[test]
+	fn single_relayer_cannot_attest_when_committee_is_set() {
+		new_test_ext().execute_with(|| {
+			initialize_committee(2);
+			assert_noop!(
+				Mosaic::timelocked_mint(
+					Origin::relayer(),
+					NETWORK_ID,
+					REMOTE_ASSET_ID,
+					CHARLIE,
+					50,
+					10,
+					Id::repeat_byte(1),
+				),
+				DispatchError::BadOrigin
+			);
+		})
+	}
+
+	fn mint_subject() -> Id {
+		Keccak256::hash_of(&(b"incoming", NETWORK_ID, Id::repeat_byte(1)))
+	}
+
+	#[test]
+	fn conflicting_attestor_can_be_slashed() {
+		new_test_ext().execute_with(|| {
+			initialize_committee(2);
+
+			assert_ok!(attest_mint(BOB, 50));
+			assert_ok!(attest_mint(CHARLIE, 50));
+			assert_eq!(Mosaic::incoming_transactions(CHARLIE, ASSET_ID), Some((50, 11)));
+
+			// contradicting the executed data is a conflict.
+			assert_ok!(attest_mint(ALICE, 1000));
+			let subject = mint_subject();
+			assert!(Mosaic::conflicting_attestations(ALICE, subject).is_some());
+			assert_noop!(
+				Mosaic::slash_relayer(Origin::root(), BOB, subject),
+				Error::<Test>::NoConflictingAttestation
+			);
+
+			let issuance = Tokens::total_issuance(RELAYER_BOND_ASSET_ID);
+			assert_ok!(Mosaic::slash_relayer(Origin::root(), ALICE, subject));
+			assert_eq!(Mosaic::relayer_committee().into_inner(), vec![BOB, CHARLIE]);
+			assert_eq!(Tokens::balance(RELAYER_BOND_ASSET_ID, &ALICE), 0);
+			assert_eq!(Tokens::total_issuance(RELAYER_BOND_ASSET_ID), issuance - RELAYER_BOND);
+		})
+	}
+
+	#[test]
+	fn minority_attesting_before_execution_is_not_slashable() {
+		new_test_ext().execute_with(|| {
+			initialize_committee(2);
+
+			assert_ok!(attest_mint(ALICE, 1000));
+			assert_ok!(attest_mint(BOB, 50));
+			assert_ok!(attest_mint(CHARLIE, 50));
+			assert_eq!(Mosaic::incoming_transactions(CHARLIE, ASSET_ID), Some((50, 11)));
+
+			let subject = mint_subject();
+			assert_eq!(Mosaic::conflicting_attestations(ALICE, subject), None);
+			assert_eq!(Mosaic::attestations(subject, ALICE), None);
+			assert_noop!(
+				Mosaic::slash_relayer(Origin::root(), ALICE, subject),
+				Error::<Test>::NoConflictingAttestation
+			);
+		})
+	}
+
+	#[test]
+	fn pending_attestations_expire() {
+		new_test_ext().execute_with(|| {
+			initialize_committee(2);
+
+			assert_ok!(attest_mint(ALICE, 50));
+			let subject = mint_subject();
+			let expires_at = System::block_number() + AttestationTTL::get();
+
+			assert!(Mosaic::attestations(subject, ALICE).is_some());
+
+			Mosaic::on_initialize(expires_at - 1);
+			assert!(Mosaic::attestations(subject, ALICE).is_some());
+
+			Mosaic::on_initialize(expires_at);
+			assert_eq!(Mosaic::attestations(subject, ALICE), None);
+
+			// the expired attestation no longer counts towards the threshold.
+			assert_ok!(attest_mint(BOB, 50));
+			assert_eq!(Mosaic::incoming_transactions(CHARLIE, ASSET_ID), None);
+			assert_ok!(attest_mint(ALICE, 50));
+			assert_eq!(Mosaic::incoming_transactions(CHARLIE, ASSET_ID), Some((50, 11)));
+		})
+	}
+
+	#[test]
+	fn accept_transfer_requires_threshold_attestations() {
+		new_test_ext().execute_with(|| {
+			initialize_committee(2);
+			assert_ok!(Mosaic::transfer_to(
+				Origin::alice(),
+				NETWORK_ID,
+				ASSET_ID,
+				EthereumAddress([0; 20]),
+				100,
+				100,
+				false,
+				ALICE,
+				None,
+				true,
+			));
+
+			assert_ok!(Mosaic::accept_transfer(
+				Origin::signed(BOB),
+				ALICE,
+				NETWORK_ID,
+				REMOTE_ASSET_ID,
+				100
+			));
+			assert!(Mosaic::outgoing_transactions(ALICE, ASSET_ID).is_some());
+
+			assert_ok!(Mosaic::accept_transfer(
+				Origin::signed(CHARLIE),
+				ALICE,
+				NETWORK_ID,
+				REMOTE_ASSET_ID,
+				100
+			));
+			assert_eq!(Mosaic::outgoing_transactions(ALICE, ASSET_ID), None);
+		})
+	}
+}
+
 #[cfg(test)]
 mod test_validation {
 	use super::*;
@@ -21,6 +21,10 @@ pub trait WeightInfo {
   fn update_asset_mapping() -> Weight;
   fn add_remote_amm_id() -> Weight;
   fn remove_remote_amm_id() -> Weight;
+  fn set_relayer_committee(m: u32) -> Weight;
+  fn slash_relayer() -> Weight;
+  fn set_network_budget() -> Weight;
+  fn set_asset_network() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -79,5 +83,20 @@ impl WeightInfo for () {
   fn remove_remote_amm_id() -> Weight {
     10_000 as Weight
   }
-}
 
+  fn set_relayer_committee(_m: u32) -> Weight {
+    10_000 as Weight
+  }
+
+  fn slash_relayer() -> Weight {
+    10_000 as Weight
+  }
+
+  fn set_network_budget() -> Weight {
+    10_000 as Weight
+  }
+
+  fn set_asset_network() -> Weight {
+    10_000 as Weight
+  }
+}
@@ -160,6 +160,8 @@ parameter_types! {
 	pub const PbloStakeFinancialNftCollectionId: CurrencyId = 1001;
 	// REVIEW(benluelo): Use a better value for this?
 	pub const TreasuryAccountId: AccountId = 123_456_789_u128;
+	pub const StakingRewardsGaugeEpochLength: BlockNumber = 100;
+	pub const MaxStakingRewardsGauges: u32 = 10;
 }
 
 impl pallet_staking_rewards::Config for Test {
@@ -187,6 +189,8 @@ impl pallet_staking_rewards::Config for Test {
 	type LockId = StakingRewardsLockId;
 	type TreasuryAccount = TreasuryAccountId;
 	type ExistentialDeposits = ExistentialDeposits;
+	type GaugeEpochLength = StakingRewardsGaugeEpochLength;
+	type MaxGauges = MaxStakingRewardsGauges;
 }
 
 ord_parameter_types! {
@@ -21,7 +21,7 @@ use frame_support::{
 use frame_system::{EventRecord, RawOrigin};
 use sp_arithmetic::{fixed_point::FixedU64, traits::SaturatedConversion, Perbill, Permill};
 use sp_runtime::traits::{BlockNumberProvider, One};
-use sp_std::collections::btree_map::BTreeMap;
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
 use crate::test_helpers::stake_and_assert;
 
@@ -73,6 +73,38 @@ fn reward_config<T: Config>(
 		.unwrap()
 }
 
+/// Creates `gauge_count` reward pools and registers them as gauges.
+fn create_gauges<T: Config>(gauge_count: u32) -> Vec<T::AssetId> {
+	let owner: T::AccountId = account("owner", 0, 0);
+	(0..gauge_count)
+		.map(|gauge| {
+			let gauge = gauge as u128;
+			let pool_id =
+				<Pallet<T> as ManageStaking>::create_staking_pool(RewardRateBasedIncentive {
+					owner: owner.clone(),
+					asset_id: (BASE_ASSET_ID + 1_000 + gauge).into(),
+					start_block: 2_u128.saturated_into(),
+					end_block: 5_u128.saturated_into(),
+					reward_configs: reward_config::<T>(1),
+					lock: lock_config::<T>(),
+					share_asset_id: (X_ASSET_ID + 2_000 + gauge).into(),
+					financial_nft_asset_id: (STAKING_FNFT_COLLECTION_ID + 3_000 + gauge).into(),
+					minimum_staking_amount: 10_000_u128.into(),
+				})
+				.unwrap();
+			Pallet::<T>::register_gauge(RawOrigin::Root.into(), pool_id).unwrap();
+			pool_id
+		})
+		.collect()
+}
+
+fn gauge_emission<T: Config>() -> GaugeEmissionOf<T> {
+	GaugeEmission {
+		reward_asset_id: BASE_ASSET_ID.into(),
+		reward_rate: RewardRate::per_second(1_000_u128),
+	}
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::Event = generic_event.into();
@@ -307,5 +339,68 @@ benchmarks! {
 
 	}: _(RawOrigin::Signed(user), pool_id,  asset_id, amount, true)
 
+	register_gauge {
+		frame_system::Pallet::<T>::set_block_number(1.into());
+		let user: T::AccountId = account("user", 0, 0);
+		let pool_id = <Pallet<T> as ManageStaking>::create_staking_pool(get_reward_pool::<T>(user, 1)).unwrap();
+	}: _(RawOrigin::Root, pool_id)
+	verify {
+		assert_last_event::<T>(Event::GaugeRegistered { pool_id }.into());
+	}
+
+	remove_gauge {
+		frame_system::Pallet::<T>::set_block_number(1.into());
+		let pool_id = create_gauges::<T>(1)[0];
+		Pallet::<T>::set_gauge_emission(RawOrigin::Root.into(), Some(gauge_emission::<T>()))?;
+	}: _(RawOrigin::Root, pool_id)
+	verify {
+		assert_last_event::<T>(Event::GaugeRemoved { pool_id }.into());
+	}
+
+	set_gauge_emission {
+		let g in 1 .. T::MaxGauges::get();
+		frame_system::Pallet::<T>::set_block_number(1.into());
+		create_gauges::<T>(g);
+		Pallet::<T>::set_gauge_emission(RawOrigin::Root.into(), Some(gauge_emission::<T>()))?;
+		// changing the reward asset stops the emission of the previous one in every gauge
+		let emission = Some(GaugeEmission { reward_asset_id: (BASE_ASSET_ID + 1).into(), ..gauge_emission::<T>() });
+	}: _(RawOrigin::Root, emission.clone())
+	verify {
+		assert_last_event::<T>(Event::GaugeEmissionUpdated { emission }.into());
+	}
+
+	vote_gauges {
+		let g in 1 .. T::MaxGauges::get();
+		let asset_id = BASE_ASSET_ID.into();
+		let amount = 100_500_u128.into();
+		let staker = whitelisted_caller();
+		let pool_owner: T::AccountId = account("owner", 0, 0);
+
+		frame_system::Pallet::<T>::set_block_number(1.into());
+		<Pallet<T>>::create_reward_pool(RawOrigin::Root.into(), get_reward_pool::<T>(pool_owner, 1))?;
+		<T::Assets as Mutate<T::AccountId>>::mint_into(asset_id, &staker, amount * 2.into())?;
+		let weight = Permill::from_rational(1, g);
+		let weights: BoundedBTreeMap<_, _, _> = create_gauges::<T>(g)
+			.into_iter()
+			.map(|pool_id| (pool_id, weight))
+			.try_collect()
+			.unwrap();
+
+		frame_system::Pallet::<T>::set_block_number(2.into());
+		<Pallet<T>>::stake(RawOrigin::Signed(staker.clone()).into(), asset_id, amount, ONE_HOUR)?;
+	}: _(RawOrigin::Signed(staker), STAKING_FNFT_COLLECTION_ID.into(), FNFT_INSTANCE_ID_BASE.into(), weights)
+
+	apply_gauge_votes {
+		let g in 1 .. T::MaxGauges::get();
+		frame_system::Pallet::<T>::set_block_number(1.into());
+		let weight: T::Balance = 1_000_u128.into();
+		for pool_id in create_gauges::<T>(g) {
+			GaugeWeights::<T>::insert(0, pool_id, weight);
+		}
+		Pallet::<T>::set_gauge_emission(RawOrigin::Root.into(), Some(gauge_emission::<T>()))?;
+	}: {
+		apply_gauge_votes::<T>();
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::test::new_test_ext(), crate::runtime::Test);
 }
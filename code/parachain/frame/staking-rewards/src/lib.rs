@@ -62,10 +62,11 @@ use frame_support::{
 	transactional, BoundedBTreeMap,
 };
 pub use pallet::*;
-use sp_runtime::SaturatedConversion;
+use sp_runtime::{PerThing, Perbill, Permill, SaturatedConversion};
 use sp_std::{
 	cmp,
 	ops::{Div, Mul},
+	vec::Vec,
 };
 
 #[frame_support::pallet]
@@ -101,7 +102,7 @@ pub mod pallet {
 			},
 			Defensive, DefensiveSaturating, TryCollect, UnixTime,
 		},
-		transactional, BoundedBTreeMap, PalletId,
+		transactional, BoundedBTreeMap, BoundedBTreeSet, PalletId,
 	};
 	use frame_system::pallet_prelude::*;
 	use orml_traits::{GetByKey, LockIdentifier, MultiLockableCurrency};
@@ -116,8 +117,10 @@ pub mod pallet {
 	use sp_std::{fmt::Debug, ops::Mul, vec, vec::Vec};
 
 	use crate::{
-		add_to_rewards_pot, claim_of_stake, do_reward_accumulation, prelude::*,
-		update_rewards_pool, validation::ValidSplitRatio, RewardAccumulationCalculationError,
+		add_to_rewards_pot, claim_of_stake, do_reward_accumulation, gauge_epoch_hook, prelude::*,
+		register_gauge, remove_gauge, set_gauge_emission, update_rewards_pool,
+		validation::ValidSplitRatio, vote_gauges, withdraw_gauge_vote,
+		RewardAccumulationCalculationError,
 	};
 
 	#[pallet::event]
@@ -208,6 +211,43 @@ pub mod pallet {
 			reward_asset_id: T::AssetId,
 			amount_slashed: T::Balance,
 		},
+		/// A reward pool was registered as a gauge.
+		GaugeRegistered {
+			pool_id: T::AssetId,
+		},
+		/// A reward pool was removed from the gauges.
+		GaugeRemoved {
+			pool_id: T::AssetId,
+		},
+		/// The global gauge emission budget was updated.
+		GaugeEmissionUpdated {
+			emission: Option<GaugeEmissionOf<T>>,
+		},
+		/// A staking position voted on how the gauge emission is split.
+		GaugeVoted {
+			/// FNFT Collection Id
+			fnft_collection_id: T::AssetId,
+			/// FNFT Instance Id
+			fnft_instance_id: T::FinancialNftInstanceId,
+			/// The epoch the vote was cast in.
+			epoch: GaugeEpoch,
+			/// The voting power of the position, i.e. its share.
+			power: T::Balance,
+		},
+		/// The votes of an epoch were applied to the reward rate of a gauge.
+		GaugeRewardRateUpdated {
+			/// The epoch the votes were cast in.
+			epoch: GaugeEpoch,
+			pool_id: T::AssetId,
+			reward_asset_id: T::AssetId,
+			reward_rate: RewardRate<T::Balance>,
+		},
+		/// The votes of an epoch could not be applied to the reward rate of a gauge.
+		GaugeRewardRateUpdateError {
+			/// The epoch the votes were cast in.
+			epoch: GaugeEpoch,
+			pool_id: T::AssetId,
+		},
 	}
 
 	#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
@@ -217,6 +257,37 @@ pub mod pallet {
 		ArithmeticError,
 	}
 
+	/// Index of a gauge voting epoch.
+	pub type GaugeEpoch = u32;
+
+	/// The global emission budget that is split across the gauges according to the votes of the
+	/// stakers.
+	#[derive(RuntimeDebug, PartialEq, Eq, Clone, MaxEncodedLen, Encode, Decode, TypeInfo)]
+	pub struct GaugeEmission<AssetId, Balance> {
+		/// The asset emitted as a reward into the gauges.
+		pub reward_asset_id: AssetId,
+		/// The total reward rate, split across all of the gauges.
+		pub reward_rate: RewardRate<Balance>,
+	}
+
+	/// The vote of a staking position on how the gauge emission should be split.
+	#[derive(
+		RuntimeDebugNoBound, PartialEqNoBound, EqNoBound, CloneNoBound, Encode, Decode, TypeInfo,
+	)]
+	#[scale_info(skip_type_params(MaxGauges))]
+	pub struct GaugeVote<
+		AssetId: Debug + PartialEq + Eq + Clone + Ord,
+		Balance: Debug + PartialEq + Eq + Clone,
+		MaxGauges: Get<u32>,
+	> {
+		/// The epoch the vote was cast in. Votes are only counted in the epoch they were cast in.
+		pub epoch: GaugeEpoch,
+		/// The share of the position at the time of voting.
+		pub power: Balance,
+		/// The part of the voting power given to each gauge.
+		pub weights: BoundedBTreeMap<AssetId, Permill, MaxGauges>,
+	}
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Error when creating reward configs.
@@ -229,6 +300,16 @@ pub mod pallet {
 		DurationPresetNotFound,
 		/// Too many rewarded asset types per pool violating the storage allowed.
 		TooManyRewardAssetTypes,
+		/// The reward pool is not registered as a gauge.
+		GaugeNotFound,
+		/// The reward pool is already registered as a gauge.
+		GaugeAlreadyRegistered,
+		/// The maximum number of gauges has been reached.
+		TooManyGauges,
+		/// The weights of a gauge vote add up to more than 100%.
+		GaugeVoteWeightsExceedOne,
+		/// Only positions that are still locked can vote on gauges.
+		StakeLockExpired,
 		/// Invalid start block number provided for creating a pool.
 		StartBlockMustBeAfterCurrentBlock,
 		/// Invalid end block number provided for creating a pool.
@@ -395,6 +476,15 @@ pub mod pallet {
 		type TreasuryAccount: Get<Self::AccountId>;
 
 		type ExistentialDeposits: GetByKey<Self::AssetId, Self::Balance>;
+
+		/// Length of a gauge voting epoch in blocks. The votes cast during an epoch are applied to
+		/// the reward rates of the gauges when the next epoch starts.
+		#[pallet::constant]
+		type GaugeEpochLength: Get<Self::BlockNumber>;
+
+		/// Maximum number of reward pools that can be registered as gauges.
+		#[pallet::constant]
+		type MaxGauges: Get<u32>;
 	}
 
 	/// Abstraction over RewardPoolConfiguration type
@@ -425,6 +515,13 @@ pub mod pallet {
 		<T as Config>::MaxRewardConfigsPerPool,
 	>;
 
+	/// Abstraction over GaugeEmission type
+	pub(crate) type GaugeEmissionOf<T> = GaugeEmission<AssetIdOf<T>, BalanceOf<T>>;
+
+	/// Abstraction over GaugeVote type
+	pub(crate) type GaugeVoteOf<T> =
+		GaugeVote<AssetIdOf<T>, BalanceOf<T>, <T as Config>::MaxGauges>;
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub (super) trait Store)]
 	#[pallet::without_storage_info]
@@ -451,6 +548,48 @@ pub mod pallet {
 	pub(super) type RewardsPotIsEmpty<T: Config> =
 		StorageDoubleMap<_, Blake2_128Concat, T::AssetId, Blake2_128Concat, T::AssetId, ()>;
 
+	/// Reward pools whose reward rate is directed by the gauge votes.
+	#[pallet::storage]
+	#[pallet::getter(fn gauges)]
+	pub type Gauges<T: Config> =
+		StorageValue<_, BoundedBTreeSet<T::AssetId, T::MaxGauges>, ValueQuery>;
+
+	/// The global emission budget split across the gauges. No reward rates are set by the gauges
+	/// while this is not set.
+	#[pallet::storage]
+	#[pallet::getter(fn gauge_emission)]
+	pub type GaugeEmissionBudget<T: Config> = StorageValue<_, GaugeEmissionOf<T>, OptionQuery>;
+
+	/// The epoch the gauge votes are currently being cast in.
+	#[pallet::storage]
+	#[pallet::getter(fn current_gauge_epoch)]
+	pub type CurrentGaugeEpoch<T: Config> = StorageValue<_, GaugeEpoch, ValueQuery>;
+
+	/// The total voting power given to each gauge in an epoch.
+	#[pallet::storage]
+	#[pallet::getter(fn gauge_weights)]
+	pub type GaugeWeights<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		GaugeEpoch,
+		Blake2_128Concat,
+		T::AssetId,
+		T::Balance,
+		ValueQuery,
+	>;
+
+	/// The last gauge vote of a staking position.
+	#[pallet::storage]
+	#[pallet::getter(fn gauge_votes)]
+	pub type GaugeVotes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId, // collection id
+		Blake2_128Concat,
+		FinancialNftInstanceIdOf<T>,
+		GaugeVoteOf<T>,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		_phantom: sp_std::marker::PhantomData<T>,
@@ -523,8 +662,8 @@ pub mod pallet {
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		/// Weight: see `begin_block`
-		fn on_initialize(_: T::BlockNumber) -> Weight {
-			Self::accumulate_rewards_hook()
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			Self::accumulate_rewards_hook().saturating_add(gauge_epoch_hook::<T>(n))
 		}
 	}
 
@@ -677,6 +816,60 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			add_to_rewards_pot::<T>(who, pool_id, asset_id, amount, keep_alive)
 		}
+
+		/// Register a reward pool as a gauge, so that its reward rate is directed by the votes of
+		/// the stakers.
+		///
+		/// Emits `GaugeRegistered` when successful.
+		#[pallet::weight(T::WeightInfo::register_gauge())]
+		pub fn register_gauge(origin: OriginFor<T>, pool_id: T::AssetId) -> DispatchResult {
+			T::RewardPoolUpdateOrigin::ensure_origin(origin)?;
+			register_gauge::<T>(pool_id)
+		}
+
+		/// Remove a reward pool from the gauges. The gauge emission to the pool is stopped.
+		///
+		/// Emits `GaugeRemoved` when successful.
+		#[pallet::weight(T::WeightInfo::remove_gauge())]
+		pub fn remove_gauge(origin: OriginFor<T>, pool_id: T::AssetId) -> DispatchResult {
+			T::RewardPoolUpdateOrigin::ensure_origin(origin)?;
+			remove_gauge::<T>(pool_id)
+		}
+
+		/// Set the global emission budget split across the gauges, or stop the gauge emission if
+		/// `None`.
+		///
+		/// Emits `GaugeEmissionUpdated` when successful.
+		#[pallet::weight(T::WeightInfo::set_gauge_emission(T::MaxGauges::get()))]
+		pub fn set_gauge_emission(
+			origin: OriginFor<T>,
+			emission: Option<GaugeEmissionOf<T>>,
+		) -> DispatchResult {
+			T::RewardPoolUpdateOrigin::ensure_origin(origin)?;
+			set_gauge_emission::<T>(emission)
+		}
+
+		/// Vote with a locked position on how the gauge emission is split across the gauges.
+		///
+		/// The voting power of the position is its share, and `weights` gives a part of it to
+		/// each gauge. The votes are applied to the reward rates of the gauges when the epoch
+		/// ends. Voting again in the same epoch replaces the previous vote.
+		///
+		/// Emits `GaugeVoted` when successful.
+		#[pallet::weight(T::WeightInfo::vote_gauges(weights.len() as u32))]
+		pub fn vote_gauges(
+			origin: OriginFor<T>,
+			fnft_collection_id: T::AssetId,
+			fnft_instance_id: T::FinancialNftInstanceId,
+			weights: BoundedBTreeMap<T::AssetId, Permill, T::MaxGauges>,
+		) -> DispatchResult {
+			Self::ensure_stake_owner(
+				ensure_signed(origin)?,
+				&fnft_collection_id,
+				&fnft_instance_id,
+			)?;
+			vote_gauges::<T>(fnft_collection_id, fnft_instance_id, weights)
+		}
 	}
 
 	impl<T: Config> ManageStaking for Pallet<T> {
@@ -997,6 +1190,9 @@ pub mod pallet {
 					stake.share = stake.share.safe_add(&new_shares)?;
 					stake.lock.started_at = T::UnixTime::now().as_secs();
 
+					// the voting power of the position changed, its vote is cast again
+					refresh_gauge_vote::<T>(&fnft_collection_id, &fnft_instance_id, stake.share)?;
+
 					Ok(())
 				})
 			})
@@ -1054,6 +1250,7 @@ pub mod pallet {
 			)?;
 
 			Stakes::<T>::remove(fnft_collection_id, fnft_instance_id);
+			withdraw_gauge_vote::<T>(fnft_collection_id, fnft_instance_id);
 
 			// transfer slashed stake to the treasury
 			if is_early_unlock {
@@ -1195,6 +1392,8 @@ pub mod pallet {
 			)?;
 
 			Stakes::<T>::insert(&fnft_collection_id, &new_fnft_instance_id, &new_position);
+			// the voting power of the position changed, it has to vote again
+			withdraw_gauge_vote::<T>(fnft_collection_id, existing_fnft_instance_id);
 
 			Ok((*fnft_collection_id, new_fnft_instance_id))
 		}
@@ -1664,6 +1863,266 @@ fn update_rewards_pool<T: Config>(
 	})
 }
 
+fn register_gauge<T: Config>(pool_id: T::AssetId) -> DispatchResult {
+	ensure!(RewardPools::<T>::contains_key(pool_id), Error::<T>::RewardsPoolNotFound);
+
+	Gauges::<T>::try_mutate(|gauges| {
+		ensure!(!gauges.contains(&pool_id), Error::<T>::GaugeAlreadyRegistered);
+		gauges.try_insert(pool_id).map_err(|_| Error::<T>::TooManyGauges)?;
+		Ok::<_, DispatchError>(())
+	})?;
+
+	Pallet::<T>::deposit_event(Event::<T>::GaugeRegistered { pool_id });
+
+	Ok(())
+}
+
+#[transactional]
+fn remove_gauge<T: Config>(pool_id: T::AssetId) -> DispatchResult {
+	Gauges::<T>::try_mutate(|gauges| {
+		ensure!(gauges.remove(&pool_id), Error::<T>::GaugeNotFound);
+		Ok::<_, DispatchError>(())
+	})?;
+
+	let epoch = CurrentGaugeEpoch::<T>::get();
+	GaugeWeights::<T>::remove(epoch, pool_id);
+
+	if let Some(emission) = GaugeEmissionBudget::<T>::get() {
+		let reward_rate = RewardRate { period: emission.reward_rate.period, amount: Zero::zero() };
+		set_gauge_reward_rate::<T>(pool_id, emission.reward_asset_id, reward_rate)?;
+	}
+
+	Pallet::<T>::deposit_event(Event::<T>::GaugeRemoved { pool_id });
+
+	Ok(())
+}
+
+#[transactional]
+fn set_gauge_emission<T: Config>(emission: Option<GaugeEmissionOf<T>>) -> DispatchResult {
+	let previous = GaugeEmissionBudget::<T>::get();
+
+	// Stop emitting the previous reward asset, the new budget is only applied once the current
+	// epoch ends.
+	if let Some(previous) = previous {
+		if emission.as_ref().map(|emission| emission.reward_asset_id) !=
+			Some(previous.reward_asset_id)
+		{
+			for pool_id in Gauges::<T>::get() {
+				let reward_rate = RewardRate {
+					period: previous.reward_rate.period.clone(),
+					amount: Zero::zero(),
+				};
+				set_gauge_reward_rate::<T>(pool_id, previous.reward_asset_id, reward_rate)?;
+			}
+		}
+	}
+
+	GaugeEmissionBudget::<T>::set(emission.clone());
+
+	Pallet::<T>::deposit_event(Event::<T>::GaugeEmissionUpdated { emission });
+
+	Ok(())
+}
+
+#[transactional]
+fn vote_gauges<T: Config>(
+	fnft_collection_id: T::AssetId,
+	fnft_instance_id: T::FinancialNftInstanceId,
+	weights: BoundedBTreeMap<T::AssetId, Permill, T::MaxGauges>,
+) -> DispatchResult {
+	let stake =
+		Stakes::<T>::get(fnft_collection_id, fnft_instance_id).ok_or(Error::<T>::StakeNotFound)?;
+
+	ensure!(
+		stake.lock.started_at.safe_add(&stake.lock.duration)? > T::UnixTime::now().as_secs(),
+		Error::<T>::StakeLockExpired
+	);
+
+	let gauges = Gauges::<T>::get();
+	ensure!(weights.keys().all(|pool_id| gauges.contains(pool_id)), Error::<T>::GaugeNotFound);
+
+	let total_weight = weights
+		.values()
+		.fold(0_u64, |total, weight| total.saturating_add(weight.deconstruct().into()));
+	ensure!(total_weight <= Permill::ACCURACY.into(), Error::<T>::GaugeVoteWeightsExceedOne);
+
+	withdraw_gauge_vote::<T>(&fnft_collection_id, &fnft_instance_id);
+
+	let epoch = CurrentGaugeEpoch::<T>::get();
+	let power = stake.share;
+
+	for (pool_id, weight) in &weights {
+		GaugeWeights::<T>::try_mutate(epoch, pool_id, |total| {
+			*total = total.safe_add(&weight.mul_floor(power))?;
+			Ok::<_, DispatchError>(())
+		})?;
+	}
+
+	GaugeVotes::<T>::insert(
+		fnft_collection_id,
+		fnft_instance_id,
+		GaugeVote { epoch, power, weights },
+	);
+
+	Pallet::<T>::deposit_event(Event::<T>::GaugeVoted {
+		fnft_collection_id,
+		fnft_instance_id,
+		epoch,
+		power,
+	});
+
+	Ok(())
+}
+
+/// Removes the vote of a position, taking its voting power out of the tally if the vote was cast
+/// in the current epoch.
+pub(crate) fn withdraw_gauge_vote<T: Config>(
+	fnft_collection_id: &T::AssetId,
+	fnft_instance_id: &T::FinancialNftInstanceId,
+) {
+	if let Some(vote) = GaugeVotes::<T>::take(fnft_collection_id, fnft_instance_id) {
+		if vote.epoch == CurrentGaugeEpoch::<T>::get() {
+			for (pool_id, weight) in vote.weights {
+				GaugeWeights::<T>::mutate(vote.epoch, pool_id, |total| {
+					*total = total.saturating_sub(weight.mul_floor(vote.power))
+				});
+			}
+		}
+	}
+}
+
+/// Casts the vote of a position again with its new voting power `power`, if the vote was cast in
+/// the current epoch.
+pub(crate) fn refresh_gauge_vote<T: Config>(
+	fnft_collection_id: &T::AssetId,
+	fnft_instance_id: &T::FinancialNftInstanceId,
+	power: T::Balance,
+) -> DispatchResult {
+	GaugeVotes::<T>::try_mutate_exists(fnft_collection_id, fnft_instance_id, |maybe_vote| {
+		let vote = match maybe_vote {
+			Some(vote) if vote.epoch == CurrentGaugeEpoch::<T>::get() => vote,
+			_ => return Ok(()),
+		};
+
+		for (pool_id, weight) in &vote.weights {
+			GaugeWeights::<T>::try_mutate(vote.epoch, pool_id, |total| {
+				*total = total
+					.saturating_sub(weight.mul_floor(vote.power))
+					.safe_add(&weight.mul_floor(power))?;
+				Ok::<_, DispatchError>(())
+			})?;
+		}
+		vote.power = power;
+
+		Ok(())
+	})
+}
+
+/// Ends the current gauge epoch every `GaugeEpochLength` blocks, applying its votes.
+pub(crate) fn gauge_epoch_hook<T: Config>(now: T::BlockNumber) -> Weight {
+	let epoch_length = T::GaugeEpochLength::get();
+
+	if epoch_length.is_zero() || !(now % epoch_length).is_zero() {
+		return 0
+	}
+
+	let number_of_gauges = apply_gauge_votes::<T>();
+
+	T::WeightInfo::apply_gauge_votes(number_of_gauges)
+}
+
+/// Ends the current gauge epoch and splits the gauge emission across the gauges proportionally to
+/// the voting power they received during the epoch. Returns the number of gauges.
+pub(crate) fn apply_gauge_votes<T: Config>() -> u32 {
+	let epoch = CurrentGaugeEpoch::<T>::mutate(|epoch| {
+		let ended = *epoch;
+		*epoch = epoch.wrapping_add(1);
+		ended
+	});
+
+	let weights = Gauges::<T>::get()
+		.into_iter()
+		.map(|pool_id| (pool_id, GaugeWeights::<T>::get(epoch, pool_id).into()))
+		.collect::<Vec<(T::AssetId, u128)>>();
+	// the number of gauges is bounded by T::MaxGauges
+	let number_of_gauges = weights.len() as u32;
+
+	let _ = GaugeWeights::<T>::clear_prefix(epoch, T::MaxGauges::get(), None);
+
+	if let Some(emission) = GaugeEmissionBudget::<T>::get() {
+		let total_weight =
+			weights.iter().fold(0_u128, |total, (_, weight)| total.saturating_add(*weight));
+		let budget: u128 = emission.reward_rate.amount.into();
+
+		for (pool_id, weight) in weights {
+			let amount = if total_weight.is_zero() {
+				0
+			} else {
+				Perbill::from_rational(weight, total_weight).mul_floor(budget)
+			};
+			let reward_rate =
+				RewardRate { period: emission.reward_rate.period.clone(), amount: amount.into() };
+
+			match set_gauge_reward_rate::<T>(pool_id, emission.reward_asset_id, reward_rate.clone())
+			{
+				Ok(()) => Pallet::<T>::deposit_event(Event::<T>::GaugeRewardRateUpdated {
+					epoch,
+					pool_id,
+					reward_asset_id: emission.reward_asset_id,
+					reward_rate,
+				}),
+				Err(_) => Pallet::<T>::deposit_event(Event::<T>::GaugeRewardRateUpdateError {
+					epoch,
+					pool_id,
+				}),
+			}
+		}
+	}
+
+	number_of_gauges
+}
+
+/// Sets the reward rate of `reward_asset_id` in a gauge, accumulating the rewards released at the
+/// previous rate first. The reward is added to the pool if it is not rewarded yet.
+fn set_gauge_reward_rate<T: Config>(
+	pool_id: T::AssetId,
+	reward_asset_id: T::AssetId,
+	reward_rate: RewardRate<T::Balance>,
+) -> DispatchResult {
+	RewardPools::<T>::try_mutate(pool_id, |pool| {
+		let pool = pool.as_mut().ok_or(Error::<T>::RewardsPoolNotFound)?;
+
+		let now_seconds = T::UnixTime::now().as_secs();
+
+		match pool.rewards.get_mut(&reward_asset_id) {
+			Some(reward) => {
+				if reward.reward_rate.amount.is_zero() {
+					// nothing was released at the previous rate, start releasing from now on
+					reward.last_updated_timestamp = now_seconds;
+				} else {
+					Pallet::<T>::reward_accumulation_hook_reward_update_calculation(
+						pool_id,
+						reward_asset_id,
+						reward,
+						now_seconds,
+					);
+				}
+				reward.reward_rate = reward_rate;
+			},
+			None => {
+				pool.rewards
+					.try_insert(
+						reward_asset_id,
+						Reward::from_config(RewardConfig { reward_rate }, now_seconds),
+					)
+					.map_err(|_| Error::<T>::TooManyRewardAssetTypes)?;
+			},
+		}
+
+		Ok(())
+	})
+}
+
 /// Calculates the update to the reward and unlocks the accumulated rewards from the pool account.
 pub(crate) fn do_reward_accumulation<T: Config>(
 	pool_id: T::AssetId,
@@ -248,6 +248,8 @@ parameter_types! {
 	pub const StakingRewardsLockId: LockIdentifier = *b"stk_lock";
 	// REVIEW(benluelo): Use a better value for this?
 	pub const TreasuryAccountId: AccountId = sr25519::Public([10_u8; 32]);
+	pub const GaugeEpochLength: u64 = 10;
+	pub const MaxGauges: u32 = 8;
 }
 
 impl crate::Config for Test {
@@ -276,6 +278,9 @@ impl crate::Config for Test {
 
 	type LockId = StakingRewardsLockId;
 	type TreasuryAccount = TreasuryAccountId;
+
+	type GaugeEpochLength = GaugeEpochLength;
+	type MaxGauges = MaxGauges;
 }
 
 impl InstanceFilter<Call> for ProxyType {
@@ -49,6 +49,7 @@ use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
 
 pub(crate) mod prelude;
 
+mod test_gauges;
 mod test_reward_accumulation_hook;
 mod test_update_reward_pools;
 
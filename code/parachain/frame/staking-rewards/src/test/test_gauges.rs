@@ -0,0 +1,242 @@
+use crate::{
+	test_helpers::{create_rewards_pool_and_assert, stake_and_assert},
+	CurrentGaugeEpoch, Error, GaugeEmission, GaugeWeights, Gauges,
+};
+use composable_tests_helpers::test::{block::process_and_progress_blocks, helper::RuntimeTrait};
+use frame_support::traits::TryCollect;
+
+use crate::test::prelude::*;
+
+use super::*;
+
+type XBTC = Currency<2001, 12>;
+
+const BTC_STAKING_FNFT_COLLECTION_ID: CurrencyId = 2;
+const EMISSION_PER_SECOND: u128 = 100;
+
+fn create_gauged_pools() {
+	create_rewards_pool_and_assert::<Test>(get_default_reward_pool());
+	create_rewards_pool_and_assert::<Test>(RewardRateBasedIncentive {
+		owner: ALICE,
+		asset_id: BTC::ID,
+		start_block: 2,
+		end_block: ONE_YEAR_OF_BLOCKS + 1,
+		reward_configs: default_reward_config(),
+		lock: default_lock_config(),
+		share_asset_id: XBTC::ID,
+		financial_nft_asset_id: BTC_STAKING_FNFT_COLLECTION_ID,
+		minimum_staking_amount: MINIMUM_STAKING_AMOUNT,
+	});
+
+	for pool_id in [PICA::ID, BTC::ID] {
+		Test::assert_extrinsic_event(
+			StakingRewards::register_gauge(Origin::root(), pool_id),
+			crate::Event::GaugeRegistered { pool_id },
+		);
+	}
+
+	let emission = GaugeEmission {
+		reward_asset_id: USDT::ID,
+		reward_rate: RewardRate::per_second(EMISSION_PER_SECOND),
+	};
+	Test::assert_extrinsic_event(
+		StakingRewards::set_gauge_emission(Origin::root(), Some(emission.clone())),
+		crate::Event::GaugeEmissionUpdated { emission: Some(emission) },
+	);
+}
+
+fn weights(
+	weights: impl IntoIterator<Item = (u128, Permill)>,
+) -> BoundedBTreeMap<u128, Permill, MaxGauges> {
+	weights.into_iter().try_collect().unwrap()
+}
+
+fn reward_rate_of(pool_id: u128, reward_asset_id: u128) -> u128 {
+	StakingRewards::pools(pool_id)
+		.expect("pool expected")
+		.rewards
+		.get(&reward_asset_id)
+		.expect("reward expected")
+		.reward_rate
+		.amount
+}
+
+fn with_gauge_stake(execute: impl FnOnce(u64)) {
+	new_test_ext().execute_with(|| {
+		process_and_progress_blocks::<StakingRewards, Test>(1);
+		create_gauged_pools();
+		process_and_progress_blocks::<StakingRewards, Test>(1);
+
+		mint_assets([ALICE, BOB], [PICA::ID], PICA::units(1_000));
+		let fnft_instance_id =
+			stake_and_assert::<Test>(ALICE, PICA::ID, PICA::units(100), ONE_HOUR);
+
+		execute(fnft_instance_id)
+	})
+}
+
+#[test]
+fn gauge_votes_split_the_emission_in_the_next_epoch() {
+	with_gauge_stake(|fnft_instance_id| {
+		assert_ok!(StakingRewards::vote_gauges(
+			Origin::signed(ALICE),
+			STAKING_FNFT_COLLECTION_ID,
+			fnft_instance_id,
+			weights([(PICA::ID, Permill::from_percent(75)), (BTC::ID, Permill::from_percent(25))]),
+		));
+
+		// votes are not applied before the epoch ends
+		assert_eq!(reward_rate_of(PICA::ID, USDT::ID), 10);
+		assert_eq!(reward_rate_of(BTC::ID, USDT::ID), 10);
+
+		process_and_progress_blocks::<StakingRewards, Test>(
+			(GaugeEpochLength::get() - System::block_number()) as usize,
+		);
+
+		assert_eq!(CurrentGaugeEpoch::<Test>::get(), 1);
+		assert_eq!(reward_rate_of(PICA::ID, USDT::ID), EMISSION_PER_SECOND * 3 / 4);
+		assert_eq!(reward_rate_of(BTC::ID, USDT::ID), EMISSION_PER_SECOND / 4);
+
+		// no votes were cast in the next epoch, so the emission stops
+		process_and_progress_blocks::<StakingRewards, Test>(GaugeEpochLength::get() as usize);
+
+		assert_eq!(CurrentGaugeEpoch::<Test>::get(), 2);
+		assert_eq!(reward_rate_of(PICA::ID, USDT::ID), 0);
+		assert_eq!(reward_rate_of(BTC::ID, USDT::ID), 0);
+	});
+}
+
+#[test]
+fn voting_again_replaces_the_previous_vote() {
+	with_gauge_stake(|fnft_instance_id| {
+		assert_ok!(StakingRewards::vote_gauges(
+			Origin::signed(ALICE),
+			STAKING_FNFT_COLLECTION_ID,
+			fnft_instance_id,
+			weights([(PICA::ID, Permill::one())]),
+		));
+		assert_ok!(StakingRewards::vote_gauges(
+			Origin::signed(ALICE),
+			STAKING_FNFT_COLLECTION_ID,
+			fnft_instance_id,
+			weights([(BTC::ID, Permill::one())]),
+		));
+
+		let share = StakingRewards::stakes(STAKING_FNFT_COLLECTION_ID, fnft_instance_id)
+			.expect("stake expected")
+			.share;
+		assert_eq!(GaugeWeights::<Test>::get(0, PICA::ID), 0);
+		assert_eq!(GaugeWeights::<Test>::get(0, BTC::ID), share);
+	});
+}
+
+#[test]
+fn unstake_withdraws_the_gauge_vote() {
+	with_gauge_stake(|fnft_instance_id| {
+		assert_ok!(StakingRewards::vote_gauges(
+			Origin::signed(ALICE),
+			STAKING_FNFT_COLLECTION_ID,
+			fnft_instance_id,
+			weights([(PICA::ID, Permill::one())]),
+		));
+
+		unstake_and_assert::<Test>(ALICE, STAKING_FNFT_COLLECTION_ID, fnft_instance_id, true);
+
+		assert_eq!(GaugeWeights::<Test>::get(0, PICA::ID), 0);
+	});
+}
+
+#[test]
+fn extend_refreshes_the_gauge_vote() {
+	with_gauge_stake(|fnft_instance_id| {
+		assert_ok!(StakingRewards::vote_gauges(
+			Origin::signed(ALICE),
+			STAKING_FNFT_COLLECTION_ID,
+			fnft_instance_id,
+			weights([(PICA::ID, Permill::from_percent(50)), (BTC::ID, Permill::from_percent(50))]),
+		));
+
+		assert_ok!(StakingRewards::extend(
+			Origin::signed(ALICE),
+			STAKING_FNFT_COLLECTION_ID,
+			fnft_instance_id,
+			PICA::units(50)
+		));
+
+		let share = StakingRewards::stakes(STAKING_FNFT_COLLECTION_ID, fnft_instance_id)
+			.expect("stake expected")
+			.share;
+		assert_eq!(GaugeWeights::<Test>::get(0, PICA::ID), share / 2);
+		assert_eq!(GaugeWeights::<Test>::get(0, BTC::ID), share / 2);
+		assert_eq!(
+			StakingRewards::gauge_votes(STAKING_FNFT_COLLECTION_ID, fnft_instance_id)
+				.expect("vote expected")
+				.power,
+			share
+		);
+	});
+}
+
+#[test]
+fn vote_gauges_should_fail_for_invalid_votes() {
+	with_gauge_stake(|fnft_instance_id| {
+		assert_noop!(
+			StakingRewards::vote_gauges(
+				Origin::signed(BOB),
+				STAKING_FNFT_COLLECTION_ID,
+				fnft_instance_id,
+				weights([(PICA::ID, Permill::one())]),
+			),
+			Error::<Test>::OnlyStakeOwnerCanInteractWithStake
+		);
+
+		assert_noop!(
+			StakingRewards::vote_gauges(
+				Origin::signed(ALICE),
+				STAKING_FNFT_COLLECTION_ID,
+				fnft_instance_id,
+				weights([(USDT::ID, Permill::one())]),
+			),
+			Error::<Test>::GaugeNotFound
+		);
+
+		assert_noop!(
+			StakingRewards::vote_gauges(
+				Origin::signed(ALICE),
+				STAKING_FNFT_COLLECTION_ID,
+				fnft_instance_id,
+				weights([(PICA::ID, Permill::one()), (BTC::ID, Permill::from_percent(1))]),
+			),
+			Error::<Test>::GaugeVoteWeightsExceedOne
+		);
+	});
+}
+
+#[test]
+fn removing_a_gauge_stops_its_emission() {
+	with_gauge_stake(|fnft_instance_id| {
+		assert_ok!(StakingRewards::vote_gauges(
+			Origin::signed(ALICE),
+			STAKING_FNFT_COLLECTION_ID,
+			fnft_instance_id,
+			weights([(PICA::ID, Permill::from_percent(50)), (BTC::ID, Permill::from_percent(50))]),
+		));
+
+		process_and_progress_blocks::<StakingRewards, Test>(
+			(GaugeEpochLength::get() - System::block_number()) as usize,
+		);
+		assert_eq!(reward_rate_of(BTC::ID, USDT::ID), EMISSION_PER_SECOND / 2);
+
+		Test::assert_extrinsic_event(
+			StakingRewards::remove_gauge(Origin::root(), BTC::ID),
+			crate::Event::GaugeRemoved { pool_id: BTC::ID },
+		);
+
+		assert!(!Gauges::<Test>::get().contains(&BTC::ID));
+		assert_eq!(reward_rate_of(BTC::ID, USDT::ID), 0);
+		assert_noop!(
+			StakingRewards::remove_gauge(Origin::root(), BTC::ID),
+			Error::<Test>::GaugeNotFound
+		);
+	});
+}
@@ -11,6 +11,11 @@ pub trait WeightInfo {
 	fn update_rewards_pool(r: u32) -> Weight;
 	fn claim(r: u32) -> Weight;
 	fn add_to_rewards_pot() -> Weight;
+	fn register_gauge() -> Weight;
+	fn remove_gauge() -> Weight;
+	fn set_gauge_emission(g: u32) -> Weight;
+	fn vote_gauges(g: u32) -> Weight;
+	fn apply_gauge_votes(g: u32) -> Weight;
 }
 
 impl WeightInfo for () {
@@ -53,4 +58,24 @@ impl WeightInfo for () {
 	fn add_to_rewards_pot() -> Weight {
 		10_000
 	}
+
+	fn register_gauge() -> Weight {
+		10_000
+	}
+
+	fn remove_gauge() -> Weight {
+		10_000
+	}
+
+	fn set_gauge_emission(_g: u32) -> Weight {
+		10_000
+	}
+
+	fn vote_gauges(_g: u32) -> Weight {
+		10_000
+	}
+
+	fn apply_gauge_votes(_g: u32) -> Weight {
+		10_000
+	}
 }
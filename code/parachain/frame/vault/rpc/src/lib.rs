@@ -0,0 +1,176 @@
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use composable_traits::vault::{RentStatus, StrategyOverview};
+use core::{
+	fmt::{Debug, Display},
+	str::FromStr,
+};
+use jsonrpsee::{
+	core::{Error as RpcError, RpcResult},
+	proc_macros::rpc,
+	types::{error::CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT, DispatchError};
+use sp_std::sync::Arc;
+use vault_runtime_api::VaultRuntimeApi;
+
+#[rpc(client, server)]
+pub trait VaultApi<BlockHash, AccountId, VaultId, Balance, BlockNumber>
+where
+	AccountId: FromStr + Display,
+	VaultId: FromStr + Display,
+	Balance: FromStr + Display,
+	BlockNumber: FromStr + Display,
+{
+	#[method(name = "vault_previewDeposit")]
+	fn preview_deposit(
+		&self,
+		vault_id: SafeRpcWrapper<VaultId>,
+		asset_amount: SafeRpcWrapper<Balance>,
+		at: Option<BlockHash>,
+	) -> RpcResult<SafeRpcWrapper<Balance>>;
+
+	#[method(name = "vault_previewWithdraw")]
+	fn preview_withdraw(
+		&self,
+		vault_id: SafeRpcWrapper<VaultId>,
+		lp_amount: SafeRpcWrapper<Balance>,
+		at: Option<BlockHash>,
+	) -> RpcResult<SafeRpcWrapper<Balance>>;
+
+	#[method(name = "vault_totalAssets")]
+	fn total_assets(
+		&self,
+		vault_id: SafeRpcWrapper<VaultId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<SafeRpcWrapper<Balance>>;
+
+	#[method(name = "vault_strategies")]
+	fn strategies(
+		&self,
+		vault_id: SafeRpcWrapper<VaultId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(SafeRpcWrapper<AccountId>, StrategyOverview<SafeRpcWrapper<Balance>>)>>;
+
+	#[method(name = "vault_rentStatus")]
+	fn rent_status(
+		&self,
+		vault_id: SafeRpcWrapper<VaultId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<RentStatus<SafeRpcWrapper<Balance>, SafeRpcWrapper<BlockNumber>>>;
+}
+
+pub struct Vault<C, Block> {
+	client: Arc<C>,
+	_marker: sp_std::marker::PhantomData<Block>,
+}
+
+impl<C, M> Vault<C, M> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Flattens the result of a runtime API call, mapping both the API error and the `DispatchError`
+/// returned by the runtime into an RPC error.
+fn into_rpc_result<T, E: Debug>(result: Result<Result<T, DispatchError>, E>) -> RpcResult<T> {
+	result
+		.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})?
+		.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9877,
+				"Vault query failed",
+				Some(format!("{:?}", e)),
+			)))
+		})
+}
+
+impl<C, Block, AccountId, VaultId, Balance, BlockNumber>
+	VaultApiServer<<Block as BlockT>::Hash, AccountId, VaultId, Balance, BlockNumber>
+	for Vault<C, (Block, AccountId, VaultId, Balance, BlockNumber)>
+where
+	Block: BlockT,
+	AccountId: Send + Sync + 'static + Codec + FromStr + Display,
+	VaultId: Send + Sync + 'static + Codec + FromStr + Display,
+	Balance: Send + Sync + 'static + Codec + FromStr + Display,
+	BlockNumber: Send + Sync + 'static + Codec + FromStr + Display,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api: VaultRuntimeApi<Block, AccountId, VaultId, Balance, BlockNumber>,
+{
+	fn preview_deposit(
+		&self,
+		vault_id: SafeRpcWrapper<VaultId>,
+		asset_amount: SafeRpcWrapper<Balance>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<SafeRpcWrapper<Balance>> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		// calling ../../runtime-api
+		into_rpc_result(api.preview_deposit(&at, vault_id, asset_amount))
+	}
+
+	fn preview_withdraw(
+		&self,
+		vault_id: SafeRpcWrapper<VaultId>,
+		lp_amount: SafeRpcWrapper<Balance>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<SafeRpcWrapper<Balance>> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		// calling ../../runtime-api
+		into_rpc_result(api.preview_withdraw(&at, vault_id, lp_amount))
+	}
+
+	fn total_assets(
+		&self,
+		vault_id: SafeRpcWrapper<VaultId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<SafeRpcWrapper<Balance>> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		// calling ../../runtime-api
+		into_rpc_result(api.total_assets(&at, vault_id))
+	}
+
+	fn strategies(
+		&self,
+		vault_id: SafeRpcWrapper<VaultId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(SafeRpcWrapper<AccountId>, StrategyOverview<SafeRpcWrapper<Balance>>)>> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		// calling ../../runtime-api
+		into_rpc_result(api.strategies(&at, vault_id))
+	}
+
+	fn rent_status(
+		&self,
+		vault_id: SafeRpcWrapper<VaultId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<RentStatus<SafeRpcWrapper<Balance>, SafeRpcWrapper<BlockNumber>>> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		// calling ../../runtime-api
+		into_rpc_result(api.rent_status(&at, vault_id))
+	}
+}
@@ -0,0 +1,50 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use composable_traits::vault::{RentStatus, StrategyOverview};
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+// Vault Runtime API declaration. Implemented for each runtime at
+// `runtime/<runtime-name>/src/lib.rs`.
+sp_api::decl_runtime_apis! {
+	pub trait VaultRuntimeApi<AccountId, VaultId, Balance, BlockNumber>
+	where
+		AccountId: Codec,
+		VaultId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Retrieve the amount of LP tokens minted when depositing `asset_amount` into the vault.
+		fn preview_deposit(
+			vault_id: SafeRpcWrapper<VaultId>,
+			asset_amount: SafeRpcWrapper<Balance>,
+		) -> Result<SafeRpcWrapper<Balance>, DispatchError>;
+
+		/// Retrieve the amount of assets received when withdrawing `lp_amount` from the vault.
+		fn preview_withdraw(
+			vault_id: SafeRpcWrapper<VaultId>,
+			lp_amount: SafeRpcWrapper<Balance>,
+		) -> Result<SafeRpcWrapper<Balance>, DispatchError>;
+
+		/// Retrieve the total amount of assets controlled by the vault, including the assets
+		/// allocated to its strategies.
+		fn total_assets(
+			vault_id: SafeRpcWrapper<VaultId>,
+		) -> Result<SafeRpcWrapper<Balance>, DispatchError>;
+
+		/// Retrieve the allocation and reported balances of every strategy of the vault.
+		fn strategies(
+			vault_id: SafeRpcWrapper<VaultId>,
+		) -> Result<Vec<(SafeRpcWrapper<AccountId>, StrategyOverview<SafeRpcWrapper<Balance>>)>, DispatchError>;
+
+		/// Retrieve the rent deposit of the vault, together with the number of blocks until it
+		/// may be tombstoned or deleted.
+		fn rent_status(
+			vault_id: SafeRpcWrapper<VaultId>,
+		) -> Result<RentStatus<SafeRpcWrapper<Balance>, SafeRpcWrapper<BlockNumber>>, DispatchError>;
+	}
+}
@@ -73,8 +73,8 @@ pub mod pallet {
 		currency::RangeId,
 		defi::Rate,
 		vault::{
-			CapabilityVault, Deposit, FundsAvailability, ReportableStrategicVault, Vault,
-			VaultConfig,
+			CapabilityVault, Deposit, FundsAvailability, RentStatus, ReportableStrategicVault,
+			Vault, VaultConfig,
 		},
 	};
 	use frame_support::{
@@ -104,7 +104,7 @@ pub mod pallet {
 		},
 		ArithmeticError, DispatchError, FixedPointNumber, Perquintill,
 	};
-	use sp_std::{cmp::Ordering, fmt::Debug};
+	use sp_std::{cmp::Ordering, fmt::Debug, vec::Vec};
 
 	#[allow(missing_docs)]
 	pub type AssetIdOf<T> =
@@ -885,6 +885,68 @@ pub mod pallet {
 		fn vault_info(vault_idx: &T::VaultId) -> Result<VaultInfo<T>, DispatchError> {
 			Ok(Vaults::<T>::try_get(vault_idx).map_err(|_err| Error::<T>::VaultDoesNotExist)?)
 		}
+
+		/// Simulates a deposit of `asset_amount` into the vault, returning the amount of LP tokens
+		/// which would be minted. Fails with the same errors as [`Vault::deposit`], except for
+		/// the ones related to the balance of the depositor.
+		pub fn preview_deposit(
+			vault_id: &T::VaultId,
+			asset_amount: T::Balance,
+		) -> Result<T::Balance, DispatchError> {
+			ensure!(
+				asset_amount > T::MinimumDeposit::get(),
+				Error::<T>::AmountMustGteMinimumDeposit
+			);
+			let vault = Self::vault_info(vault_id)?;
+			ensure!(vault.capabilities.deposits_allowed(), Error::<T>::DepositsHalted);
+			Self::do_calculate_lp_tokens_to_mint(vault_id, &vault, asset_amount)
+		}
+
+		/// Simulates a withdrawal of `lp_amount` from the vault, returning the amount of assets
+		/// which would be received. Fails with the same errors as [`Vault::withdraw`], except for
+		/// the ones related to the LP token balance of the withdrawer.
+		pub fn preview_withdraw(
+			vault_id: &T::VaultId,
+			lp_amount: T::Balance,
+		) -> Result<T::Balance, DispatchError> {
+			ensure!(
+				lp_amount > T::MinimumWithdrawal::get(),
+				Error::<T>::AmountMustGteMinimumWithdrawal
+			);
+			let vault = Self::vault_info(vault_id)?;
+			ensure!(vault.capabilities.withdrawals_allowed(), Error::<T>::WithdrawalsHalted);
+			let lp_shares_value_amount = Self::do_lp_share_value(vault_id, &vault, lp_amount)?;
+			let vault_owned_amount =
+				T::Currency::balance(vault.asset_id, &Self::account_id(vault_id));
+			ensure!(lp_shares_value_amount <= vault_owned_amount, Error::<T>::NotEnoughLiquidity);
+			Ok(lp_shares_value_amount)
+		}
+
+		/// The total amount of assets controlled by the vault, including the balances reported by
+		/// its strategies.
+		pub fn total_assets(vault_id: &T::VaultId) -> Result<T::Balance, DispatchError> {
+			Ok(Self::assets_under_management(vault_id)?)
+		}
+
+		/// The allocation and reported balances of every strategy of the vault.
+		pub fn strategies(
+			vault_id: &T::VaultId,
+		) -> Result<Vec<(T::AccountId, StrategyOverview<T::Balance>)>, DispatchError> {
+			ensure!(Vaults::<T>::contains_key(vault_id), Error::<T>::VaultDoesNotExist);
+			Ok(CapitalStructure::<T>::iter_prefix(vault_id).collect())
+		}
+
+		/// The rent status of the vault at the current block.
+		pub fn rent_status(
+			vault_id: &T::VaultId,
+		) -> Result<RentStatus<BalanceOf<T>, BlockNumberOf<T>>, DispatchError> {
+			let vault = Self::vault_info(vault_id)?;
+			Ok(rent::rent_status::<T>(
+				<frame_system::Pallet<T>>::block_number(),
+				vault.deposit,
+				vault.capabilities.is_tombstoned(),
+			))
+		}
 	}
 
 	impl<T: Config> Vault for Pallet<T> {
@@ -1,7 +1,10 @@
 use crate::{BalanceOf, BlockNumberOf, Config};
-use composable_traits::vault::Deposit;
+use composable_traits::vault::{Deposit, RentStatus};
 use frame_support::pallet_prelude::*;
-use sp_runtime::{traits::Saturating, SaturatedConversion};
+use sp_runtime::{
+	traits::{One, Saturating, Zero},
+	SaturatedConversion,
+};
 
 #[derive(Copy, Clone, Encode, Decode, Debug, PartialEq, Eq)]
 pub enum Verdict<T: Config> {
@@ -51,6 +54,50 @@ pub fn evaluate_eviction<T: Config>(
 	}
 }
 
+/// Computes the block at which the rent of the deposit is exhausted, after which the vault can be
+/// evicted. Returns `None` if no rent is being charged.
+pub fn eviction_block<T: Config>(
+	amount: BalanceOf<T>,
+	at: BlockNumberOf<T>,
+) -> Option<BlockNumberOf<T>> {
+	let rent = T::RentPerBlock::get();
+	if rent.is_zero() {
+		return None
+	}
+	let mut num_blocks = amount / rent;
+	if !(amount % rent).is_zero() {
+		num_blocks = num_blocks.saturating_add(One::one());
+	}
+	// Rent is never charged for the block at which it was last paid.
+	let num_blocks = num_blocks.max(One::one()).saturated_into::<u128>();
+	Some(at.saturating_add(num_blocks.saturated_into()))
+}
+
+pub fn rent_status<T: Config>(
+	current_block: BlockNumberOf<T>,
+	deposit: Deposit<BalanceOf<T>, BlockNumberOf<T>>,
+	tombstoned: bool,
+) -> RentStatus<BalanceOf<T>, BlockNumberOf<T>> {
+	let (blocks_until_eviction, blocks_until_deletion) = match deposit {
+		Deposit::Existential => (None, None),
+		Deposit::Rent { at, .. } if tombstoned => {
+			let deletion_block = at.saturating_add(T::TombstoneDuration::get());
+			(None, Some(deletion_block.saturating_sub(current_block)))
+		},
+		Deposit::Rent { amount, at } => match eviction_block::<T>(amount, at) {
+			Some(eviction_block) => {
+				let deletion_block = eviction_block.saturating_add(T::TombstoneDuration::get());
+				(
+					Some(eviction_block.saturating_sub(current_block)),
+					Some(deletion_block.saturating_sub(current_block)),
+				)
+			},
+			None => (None, None),
+		},
+	};
+	RentStatus { deposit, tombstoned, blocks_until_eviction, blocks_until_deletion }
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -92,4 +139,47 @@ mod tests {
 			)
 		})
 	}
+
+	#[test]
+	fn test_eviction_block_matches_eviction() {
+		ExtBuilder::default().build().execute_with(|| {
+			let deposit = Deposit::Rent { amount: 10, at: 0 };
+			assert_eq!(eviction_block::<Test>(10, 0), Some(10));
+			assert!(matches!(evaluate_eviction::<Test>(9, deposit), Verdict::Charge { .. }));
+			assert_eq!(evaluate_eviction::<Test>(10, deposit), Verdict::Evict);
+		})
+	}
+
+	#[test]
+	fn test_rent_status() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_eq!(
+				rent_status::<Test>(5, Deposit::Existential, false),
+				RentStatus {
+					deposit: Deposit::Existential,
+					tombstoned: false,
+					blocks_until_eviction: None,
+					blocks_until_deletion: None,
+				}
+			);
+			assert_eq!(
+				rent_status::<Test>(5, Deposit::Rent { amount: 10, at: 0 }, false),
+				RentStatus {
+					deposit: Deposit::Rent { amount: 10, at: 0 },
+					tombstoned: false,
+					blocks_until_eviction: Some(5),
+					blocks_until_deletion: Some(15),
+				}
+			);
+			assert_eq!(
+				rent_status::<Test>(5, Deposit::Rent { amount: 0, at: 2 }, true),
+				RentStatus {
+					deposit: Deposit::Rent { amount: 0, at: 2 },
+					tombstoned: true,
+					blocks_until_eviction: None,
+					blocks_until_deletion: Some(7),
+				}
+			);
+		})
+	}
 }
@@ -943,6 +943,85 @@ fn liquidate_strategy_can_not_be_executed_by_non_manager_accounts() {
 	});
 }
 
+#[test]
+fn preview_deposit_and_withdraw_match_actual_amounts() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (id, _) = create_vault(CHARLIE, MockCurrencyId::A);
+		Tokens::mint_into(MockCurrencyId::A, &ALICE, 1000).unwrap();
+		Tokens::mint_into(MockCurrencyId::A, &BOB, 1000).unwrap();
+		<Vaults as Vault>::deposit(&id, &ALICE, 300).unwrap();
+
+		let previewed_lp = Vaults::preview_deposit(&id, 200).unwrap();
+		assert_eq!(<Vaults as Vault>::deposit(&id, &BOB, 200), Ok(previewed_lp));
+
+		let previewed_assets = Vaults::preview_withdraw(&id, previewed_lp).unwrap();
+		assert_eq!(<Vaults as Vault>::withdraw(&id, &BOB, previewed_lp), Ok(previewed_assets));
+		assert_eq!(Tokens::balance(MockCurrencyId::A, &BOB), 1000);
+	});
+}
+
+#[test]
+fn preview_fails_on_stopped_vault() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (id, _) = create_vault(CHARLIE, MockCurrencyId::A);
+		Tokens::mint_into(MockCurrencyId::A, &ALICE, 1000).unwrap();
+		<Vaults as Vault>::deposit(&id, &ALICE, 300).unwrap();
+		Vaults::emergency_shutdown(Origin::root(), id).unwrap();
+		assert_eq!(Vaults::preview_deposit(&id, 100), Err(Error::<Test>::DepositsHalted.into()));
+		assert_eq!(
+			Vaults::preview_withdraw(&id, 100),
+			Err(Error::<Test>::WithdrawalsHalted.into())
+		);
+		assert_eq!(Vaults::preview_deposit(&42, 100), Err(Error::<Test>::VaultDoesNotExist.into()));
+	});
+}
+
+#[test]
+fn total_assets_include_strategy_allocations() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (id, _) = create_vault(CHARLIE, MockCurrencyId::A);
+		Tokens::mint_into(MockCurrencyId::A, &ALICE, 1000).unwrap();
+		<Vaults as Vault>::deposit(&id, &ALICE, 1000).unwrap();
+		<Vaults as StrategicVault>::withdraw(&id, &CHARLIE, 900).unwrap();
+
+		assert_eq!(Tokens::balance(MockCurrencyId::A, &Vaults::account_id(&id)), 100);
+		assert_eq!(Vaults::total_assets(&id), Ok(1000));
+
+		let strategies = Vaults::strategies(&id).unwrap();
+		assert_eq!(strategies.len(), 1);
+		let (account, overview) = strategies[0];
+		assert_eq!(account, CHARLIE);
+		assert_eq!(overview.allocation, DEFAULT_STRATEGY_SHARE);
+		assert_eq!(overview.balance, 900);
+		assert_eq!(Vaults::strategies(&42), Err(Error::<Test>::VaultDoesNotExist.into()));
+	});
+}
+
+#[test]
+fn rent_status_reports_blocks_until_eviction_and_deletion() {
+	ExtBuilder::default().build().execute_with(|| {
+		Balances::mint_into(&ALICE, ExistentialDeposit::get() * 3).unwrap();
+		System::set_block_number(1);
+		let id = create_vault_with_deposit(MockCurrencyId::A, ExistentialDeposit::get() / 2);
+		let rent = ExistentialDeposit::get() / 2 - CreationDeposit::get();
+
+		let status = Vaults::rent_status(&id).unwrap();
+		assert!(!status.tombstoned);
+		assert_eq!(status.blocks_until_eviction, Some(rent as BlockNumber));
+		assert_eq!(
+			status.blocks_until_deletion,
+			Some(rent as BlockNumber + TombstoneDuration::get())
+		);
+
+		System::set_block_number(1000);
+		Vaults::claim_surcharge(Origin::none(), id, Some(CHARLIE)).unwrap();
+		let status = Vaults::rent_status(&id).unwrap();
+		assert!(status.tombstoned);
+		assert_eq!(status.blocks_until_eviction, None);
+		assert_eq!(status.blocks_until_deletion, Some(TombstoneDuration::get()));
+	});
+}
+
 fn do_liquidate_strategy_successfully_liquidates_a_strategy_account(
 	strategy_account_id: AccountId,
 	total_funds: Balance,
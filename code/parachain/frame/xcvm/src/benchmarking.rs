@@ -0,0 +1,136 @@
+//! Benchmarking setup for pallet-xcvm
+
+use super::*;
+use crate::Pallet as Xcvm;
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{
+	fungibles::{Inspect, Mutate},
+	Get,
+};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Convert;
+use sp_std::{collections::btree_map::BTreeMap, vec, vec::Vec};
+use xcvm_core::{
+	AssetId, BindingValue, Displayed, Funds, Instruction, Juno, Predicate, Program, StaticNetwork,
+};
+
+/// Maximum number of assets of an instruction, the runtime must know as many XCVM assets.
+const MAX_ASSETS: u32 = 3;
+
+/// Balance of each asset of the interpreter.
+const BALANCE: u128 = 1_000_000_000_000;
+
+/// First `n` XCVM assets known to the runtime, along their local asset.
+fn known_assets<T: Config>(n: u32) -> Vec<(AssetId, AssetIdOf<T>)> {
+	let assets: Vec<_> = (1..=u128::from(u8::MAX))
+		.filter_map(|id| T::AssetIdConvert::convert(AssetId(id)).map(|asset| (AssetId(id), asset)))
+		.take(n as usize)
+		.collect();
+	assert_eq!(assets.len(), n as usize, "the runtime must know {} XCVM assets", n);
+	assets
+}
+
+/// Interpreter account of `caller`, holding `BALANCE` of each of the `assets`.
+fn funded_interpreter<T: Config>(
+	caller: &T::AccountId,
+	assets: &[(AssetId, AssetIdOf<T>)],
+) -> T::AccountId
+where
+	T::Assets: Mutate<T::AccountId>,
+{
+	let account = Xcvm::<T>::interpreter_account(T::NetworkId::get(), &caller.encode());
+	for (_, asset) in assets {
+		T::Assets::mint_into(*asset, &account, BALANCE.into()).expect("the asset must be mintable");
+	}
+	account
+}
+
+/// Half of the balance of each of the `assets`.
+fn half_of<T: Config>(assets: &[(AssetId, AssetIdOf<T>)]) -> Funds {
+	Funds(assets.iter().map(|(asset_id, _)| (*asset_id, (BALANCE / 2).into())).collect())
+}
+
+fn program(instructions: Vec<XCVMInstruction>) -> XCVMProgram {
+	Program { tag: vec![], instructions: instructions.into() }
+}
+
+benchmarks! {
+	where_clause {
+		where T::Assets: Mutate<T::AccountId>,
+			<T as Config>::Call: From<frame_system::Call<T>>
+	}
+
+	execute {
+		let n in 1 .. MAX_ASSETS;
+		let caller: T::AccountId = whitelisted_caller();
+		let mut funds = BTreeMap::new();
+		for (_, asset) in known_assets::<T>(n) {
+			T::Assets::mint_into(asset, &caller, BALANCE.into()).expect("the asset must be mintable");
+			funds.insert(asset, BALANCE.into());
+		}
+	}: _(RawOrigin::Signed(caller.clone()), funds.clone(), program(vec![]))
+	verify {
+		let account = Xcvm::<T>::interpreter_account(T::NetworkId::get(), &caller.encode());
+		for (asset, amount) in funds {
+			assert_eq!(T::Assets::balance(asset, &account), amount);
+		}
+	}
+
+	transfer {
+		let n in 1 .. MAX_ASSETS;
+		let caller: T::AccountId = whitelisted_caller();
+		let assets = known_assets::<T>(n);
+		funded_interpreter::<T>(&caller, &assets);
+		let to: T::AccountId = account("recipient", 0, 0);
+		let program = program(vec![Instruction::Transfer { to: to.encode(), assets: half_of::<T>(&assets) }]);
+	}: execute(RawOrigin::Signed(caller), BTreeMap::new(), program)
+	verify {
+		for (_, asset) in assets {
+			assert_eq!(T::Assets::balance(asset, &to), (BALANCE / 2).into());
+		}
+	}
+
+	call {
+		let caller: T::AccountId = whitelisted_caller();
+		let account = funded_interpreter::<T>(&caller, &[]);
+		// The remark is bound to the interpreter account, measuring the bindings as well
+		let remark = account.encode();
+		let call = <T as Config>::Call::from(
+			frame_system::Call::<T>::remark { remark: remark.clone() }
+		).encode();
+		let encoded = call.get(..call.len() - remark.len()).expect("the remark ends the call").to_vec();
+		let bindings = vec![(encoded.len() as u16, BindingValue::Self_)];
+		let program = program(vec![Instruction::Call { encoded, bindings }]);
+	}: execute(RawOrigin::Signed(caller), BTreeMap::new(), program)
+
+	spawn {
+		let n in 1 .. MAX_ASSETS;
+		let caller: T::AccountId = whitelisted_caller();
+		let assets = known_assets::<T>(n);
+		let account = funded_interpreter::<T>(&caller, &assets);
+		T::Transport::setup_benchmark(Juno::ID).expect("the transport must reach Juno");
+		let program = program(vec![Instruction::Spawn {
+			network: Juno::ID,
+			salt: vec![],
+			assets: half_of::<T>(&assets),
+			program: program(vec![]),
+		}]);
+	}: execute(RawOrigin::Signed(caller), BTreeMap::new(), program)
+	verify {
+		for (_, asset) in assets {
+			assert_eq!(T::Assets::balance(asset, &account), (BALANCE / 2).into());
+		}
+	}
+
+	predicate {
+		let caller: T::AccountId = whitelisted_caller();
+		let assets = known_assets::<T>(1);
+		funded_interpreter::<T>(&caller, &assets);
+		let (asset, _) = assets.first().copied().expect("one asset is known");
+		let predicate = Predicate::BalanceAtLeast { asset, amount: Displayed(BALANCE) };
+		let program = program(vec![Instruction::Assert { predicate }]);
+	}: execute(RawOrigin::Signed(caller), BTreeMap::new(), program)
+}
+
+impl_benchmark_test_suite!(Xcvm, crate::mock::new_test_ext(), crate::mock::Test);
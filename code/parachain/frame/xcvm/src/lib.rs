@@ -0,0 +1,472 @@
+//! Native XCVM interpreter.
+//!
+//! Executes XCVM programs on the runtime. Each program is run by the interpreter account of its
+//! origin `(network, user)`, a sub-account of the pallet, which owns the assets of the program:
+//!
+//! * `Transfer` moves assets through [`Config::Assets`].
+//! * `Call` dispatches a SCALE encoded runtime call from the interpreter account, provided that it
+//!   is allowed by [`Config::CallFilter`].
+//! * `Spawn` hands the program and its assets over to the [`Config::Transport`] (XCM, IBC,
+//!   Mosaic...) relaying it to the other network, e.g. the
+//!   [`IbcTransport`](transport::IbcTransport).
+//!
+//! Programs are limited to `MAX_EXPANDED_INSTRUCTIONS` once their `Repeat` and `IfElse`
+//! instructions are expanded. Every instruction is weight metered, the weight of a program is
+//! charged upfront and the unused part is refunded once executed.
+
+#![cfg_attr(
+	not(test),
+	warn(
+		clippy::disallowed_methods,
+		clippy::disallowed_types,
+		clippy::indexing_slicing,
+		clippy::todo,
+		clippy::unwrap_used,
+		clippy::panic
+	)
+)] // allow in tests
+#![warn(clippy::unseparated_literal_suffix)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod transport;
+pub mod weights;
+
+/// Relay the programs spawned by the interpreter to other networks.
+pub trait Transport<AccountId, AssetId, Balance> {
+	/// Relay `program` to `network` along the `assets` of the interpreter account `from` of
+	/// `origin_user_id` from `origin_network`, given by XCVM and local asset. The assets must be
+	/// taken from `from`, either escrowed or burnt depending on the transport.
+	fn spawn(
+		from: &AccountId,
+		origin_network: xcvm_core::NetworkId,
+		origin_user_id: &[u8],
+		network: xcvm_core::NetworkId,
+		salt: sp_std::vec::Vec<u8>,
+		assets: sp_std::vec::Vec<(xcvm_core::AssetId, AssetId, Balance)>,
+		program: XCVMProgram,
+	) -> sp_runtime::DispatchResult;
+
+	/// Set up the transport so that the benchmarks can spawn programs to `network`.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn setup_benchmark(network: xcvm_core::NetworkId) -> sp_runtime::DispatchResult;
+}
+
+impl<AccountId, AssetId, Balance> Transport<AccountId, AssetId, Balance> for () {
+	fn spawn(
+		_from: &AccountId,
+		_origin_network: xcvm_core::NetworkId,
+		_origin_user_id: &[u8],
+		_network: xcvm_core::NetworkId,
+		_salt: sp_std::vec::Vec<u8>,
+		_assets: sp_std::vec::Vec<(xcvm_core::AssetId, AssetId, Balance)>,
+		_program: XCVMProgram,
+	) -> sp_runtime::DispatchResult {
+		Err(sp_runtime::DispatchError::Other("no transport"))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn setup_benchmark(_network: xcvm_core::NetworkId) -> sp_runtime::DispatchResult {
+		Err(sp_runtime::DispatchError::Other("no transport"))
+	}
+}
+
+/// Instruction executed by the pallet, accounts are SCALE encoded `AccountId`.
+pub type XCVMInstruction = xcvm_core::Instruction<
+	xcvm_core::NetworkId,
+	sp_std::vec::Vec<u8>,
+	sp_std::vec::Vec<u8>,
+	xcvm_core::Funds,
+>;
+
+pub type XCVMProgram =
+	xcvm_core::Program<sp_std::collections::vec_deque::VecDeque<XCVMInstruction>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use crate::weights::WeightInfo;
+	use codec::{DecodeLimit, Encode};
+	use frame_support::{
+		dispatch::{DispatchResultWithPostInfo, Dispatchable},
+		pallet_prelude::*,
+		traits::{
+			fungibles::{Inspect, Transfer},
+			Contains,
+		},
+		transactional,
+		weights::{GetDispatchInfo, PostDispatchInfo},
+		PalletId,
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use sp_runtime::traits::{AccountIdConversion, Convert};
+	use sp_std::{
+		collections::{btree_map::BTreeMap, vec_deque::VecDeque},
+		vec::Vec,
+	};
+	use xcvm_core::{
+		BindingValue, Bindings, Funds, Instruction, NetworkId, Predicate,
+		MAX_EXPANDED_INSTRUCTIONS, MAX_REPEAT,
+	};
+
+	/// Maximum nesting depth when decoding a runtime call.
+	pub const MAX_CALL_DEPTH: u32 = 256;
+
+	pub(crate) type AssetIdOf<T> = <T as Config>::AssetId;
+	pub(crate) type BalanceOf<T> = <T as Config>::Balance;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The runtime call, `Call` instructions are decoded as such.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo;
+
+		/// Calls that programs are allowed to dispatch.
+		type CallFilter: Contains<<Self as Config>::Call>;
+
+		type AssetId: Parameter + Member + Copy + Ord + MaxEncodedLen;
+
+		type Balance: frame_support::traits::tokens::Balance + From<u128> + Into<u128>;
+
+		type Assets: Inspect<Self::AccountId, AssetId = AssetIdOf<Self>, Balance = BalanceOf<Self>>
+			+ Transfer<Self::AccountId, AssetId = AssetIdOf<Self>, Balance = BalanceOf<Self>>;
+
+		/// Local asset of an XCVM asset, `None` if the asset is not available on the runtime.
+		type AssetIdConvert: Convert<xcvm_core::AssetId, Option<AssetIdOf<Self>>>;
+
+		/// Transport relaying the spawned programs to other networks.
+		type Transport: Transport<Self::AccountId, AssetIdOf<Self>, BalanceOf<Self>>;
+
+		/// Origin of the programs relayed from other networks, resolving to the relayer.
+		type RemoteOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+		/// Network the programs are executed on.
+		#[pallet::constant]
+		type NetworkId: Get<NetworkId>;
+
+		/// Maximum weight of a call whose parameters are bound at execution time.
+		#[pallet::constant]
+		type MaxCallWeight: Get<Weight>;
+
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Remote programs can't originate from the local network.
+		InvalidNetwork,
+		/// The XCVM asset has no local counterpart.
+		UnknownAsset,
+		/// The account is not a SCALE encoded account of the runtime.
+		InvalidAccount,
+		/// The call is not a SCALE encoded call of the runtime.
+		InvalidCallEncoding,
+		/// The call is not allowed by the call filter.
+		CallNotAllowed,
+		/// The weight of a bound call exceeds `MaxCallWeight`.
+		CallWeightTooHigh,
+		/// The bindings are not ordered or out of the payload.
+		InvalidBindings,
+		/// The binding value is not available on the runtime.
+		UnsupportedBinding,
+		/// An `Assert` instruction failed.
+		AssertionFailed,
+		/// A `Repeat` instruction exceeds `MAX_REPEAT` iterations.
+		RepeatTooLarge,
+		/// The program exceeds `MAX_EXPANDED_INSTRUCTIONS` once expanded.
+		ProgramTooLarge,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A program has been executed by the interpreter `account`.
+		Executed { account: T::AccountId, network: NetworkId, user_id: Vec<u8>, tag: Vec<u8> },
+		/// A program has been handed over to the transport.
+		Spawned { account: T::AccountId, network: NetworkId, salt: Vec<u8>, tag: Vec<u8> },
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Execute `program` on behalf of the signer, `funds` are transferred to its interpreter
+		/// account beforehand.
+		#[pallet::weight(
+			T::WeightInfo::execute(funds.len() as u32)
+				.saturating_add(Pallet::<T>::program_weight(&program.instructions))
+		)]
+		#[transactional]
+		pub fn execute(
+			origin: OriginFor<T>,
+			funds: BTreeMap<AssetIdOf<T>, BalanceOf<T>>,
+			program: XCVMProgram,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let network = T::NetworkId::get();
+			let user_id = who.encode();
+			let account = Self::interpreter_account(network, &user_id);
+			let deposits = funds.len() as u32;
+			for (asset, amount) in funds {
+				T::Assets::transfer(asset, &who, &account, amount, false)?;
+			}
+			let weight = Self::do_execute(network, user_id, &who, program)?;
+			Ok(Some(T::WeightInfo::execute(deposits).saturating_add(weight)).into())
+		}
+
+		/// Execute `program` of a `user_id` from another `network`. The assets of the program
+		/// must have been credited to the interpreter account by the relayer.
+		#[pallet::weight(
+			T::WeightInfo::execute(0).saturating_add(Pallet::<T>::program_weight(&program.instructions))
+		)]
+		#[transactional]
+		pub fn execute_remote(
+			origin: OriginFor<T>,
+			network: NetworkId,
+			user_id: Vec<u8>,
+			program: XCVMProgram,
+		) -> DispatchResultWithPostInfo {
+			let relayer = T::RemoteOrigin::ensure_origin(origin)?;
+			// Local users must execute their programs themselves
+			ensure!(network != T::NetworkId::get(), Error::<T>::InvalidNetwork);
+			let weight = Self::do_execute(network, user_id, &relayer, program)?;
+			Ok(Some(T::WeightInfo::execute(0).saturating_add(weight)).into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Interpreter account of `user_id` from `network`.
+		pub fn interpreter_account(network: NetworkId, user_id: &[u8]) -> T::AccountId {
+			// Hashed as the sub account is truncated to the size of the account
+			let origin = sp_io::hashing::blake2_256(&(network, user_id).encode());
+			T::PalletId::get().into_sub_account_truncating(origin)
+		}
+
+		/// Upper bound of the weight of `instructions`.
+		pub fn program_weight(instructions: &VecDeque<XCVMInstruction>) -> Weight {
+			instructions.iter().fold(0, |weight, instruction| {
+				weight.saturating_add(Self::instruction_weight(instruction))
+			})
+		}
+
+		fn instruction_weight(instruction: &XCVMInstruction) -> Weight {
+			match instruction {
+				Instruction::Transfer { assets, .. } =>
+					T::WeightInfo::transfer(assets.0.len() as u32),
+				Instruction::Call { encoded, bindings } => {
+					// The bound parameters are unknown until execution, the call is hence bounded
+					// by `MaxCallWeight`.
+					let call_weight = if bindings.is_empty() {
+						Self::decode_call(encoded)
+							.map(|call| call.get_dispatch_info().weight)
+							.unwrap_or(0)
+					} else {
+						T::MaxCallWeight::get()
+					};
+					T::WeightInfo::call().saturating_add(call_weight)
+				},
+				Instruction::Spawn { assets, .. } => T::WeightInfo::spawn(assets.0.len() as u32),
+				Instruction::Assert { .. } => T::WeightInfo::predicate(),
+				Instruction::IfElse { then, otherwise, .. } => T::WeightInfo::predicate()
+					.saturating_add(
+						Self::program_weight(then).max(Self::program_weight(otherwise)),
+					),
+				Instruction::Repeat { times, instructions } =>
+					Self::program_weight(instructions).saturating_mul((*times).into()),
+			}
+		}
+
+		pub(crate) fn do_execute(
+			origin_network: NetworkId,
+			user_id: Vec<u8>,
+			relayer: &T::AccountId,
+			program: XCVMProgram,
+		) -> Result<Weight, DispatchError> {
+			ensure!(
+				xcvm_core::expanded_len(&program.instructions) <= MAX_EXPANDED_INSTRUCTIONS,
+				Error::<T>::ProgramTooLarge
+			);
+			let account = Self::interpreter_account(origin_network, &user_id);
+			let mut weight: Weight = 0;
+			let mut instructions = program.instructions;
+			while let Some(instruction) = instructions.pop_front() {
+				match instruction {
+					Instruction::Transfer { to, assets } => {
+						let to = T::AccountId::decode(&mut to.as_slice())
+							.map_err(|_| Error::<T>::InvalidAccount)?;
+						let assets = Self::resolve_assets(&account, assets)?;
+						weight =
+							weight.saturating_add(T::WeightInfo::transfer(assets.len() as u32));
+						for (_, asset, amount) in assets {
+							T::Assets::transfer(asset, &account, &to, amount, false)?;
+						}
+					},
+					Instruction::Call { encoded, bindings } => {
+						let bound = !bindings.is_empty();
+						let encoded = Self::apply_bindings(&account, relayer, encoded, bindings)?;
+						let call = Self::decode_call(&encoded)?;
+						ensure!(T::CallFilter::contains(&call), Error::<T>::CallNotAllowed);
+						let call_weight = call.get_dispatch_info().weight;
+						ensure!(
+							!bound || call_weight <= T::MaxCallWeight::get(),
+							Error::<T>::CallWeightTooHigh
+						);
+						let post_info = call
+							.dispatch(RawOrigin::Signed(account.clone()).into())
+							.map_err(|e| e.error)?;
+						weight = weight
+							.saturating_add(T::WeightInfo::call())
+							.saturating_add(post_info.actual_weight.unwrap_or(call_weight));
+					},
+					Instruction::Spawn { network: target, salt, assets, program } => {
+						let assets = Self::resolve_assets(&account, assets)?;
+						weight = weight.saturating_add(T::WeightInfo::spawn(assets.len() as u32));
+						let tag = program.tag.clone();
+						T::Transport::spawn(
+							&account,
+							origin_network,
+							&user_id,
+							target,
+							salt.clone(),
+							assets,
+							program,
+						)?;
+						Self::deposit_event(Event::<T>::Spawned {
+							account: account.clone(),
+							network: target,
+							salt,
+							tag,
+						});
+					},
+					Instruction::Assert { predicate } => {
+						weight = weight.saturating_add(T::WeightInfo::predicate());
+						ensure!(Self::check(&account, predicate)?, Error::<T>::AssertionFailed);
+					},
+					Instruction::IfElse { predicate, then, otherwise } => {
+						weight = weight.saturating_add(T::WeightInfo::predicate());
+						let branch =
+							if Self::check(&account, predicate)? { then } else { otherwise };
+						for instruction in branch.into_iter().rev() {
+							instructions.push_front(instruction);
+						}
+					},
+					Instruction::Repeat { times, instructions: body } => {
+						ensure!(times <= MAX_REPEAT, Error::<T>::RepeatTooLarge);
+						for _ in 0..times {
+							for instruction in body.iter().rev() {
+								instructions.push_front(instruction.clone());
+							}
+						}
+					},
+				}
+			}
+			Self::deposit_event(Event::<T>::Executed {
+				account,
+				network: origin_network,
+				user_id,
+				tag: program.tag,
+			});
+			Ok(weight)
+		}
+
+		fn decode_call(encoded: &[u8]) -> Result<<T as Config>::Call, DispatchError> {
+			<T as Config>::Call::decode_all_with_depth_limit(MAX_CALL_DEPTH, &mut &*encoded)
+				.map_err(|_| Error::<T>::InvalidCallEncoding.into())
+		}
+
+		/// XCVM and local assets and absolute amounts of `funds` with respect to the balances of
+		/// `account`.
+		fn resolve_assets(
+			account: &T::AccountId,
+			Funds(funds): Funds,
+		) -> Result<Vec<(xcvm_core::AssetId, AssetIdOf<T>, BalanceOf<T>)>, DispatchError> {
+			let mut assets = Vec::with_capacity(funds.len());
+			for (asset_id, amount) in funds {
+				let asset = Self::local_asset(asset_id)?;
+				let amount = amount.apply(T::Assets::balance(asset, account).into());
+				if amount != 0 {
+					assets.push((asset_id, asset, amount.into()));
+				}
+			}
+			Ok(assets)
+		}
+
+		fn local_asset(asset_id: xcvm_core::AssetId) -> Result<AssetIdOf<T>, DispatchError> {
+			T::AssetIdConvert::convert(asset_id).ok_or_else(|| Error::<T>::UnknownAsset.into())
+		}
+
+		fn balance(
+			account: &T::AccountId,
+			asset_id: xcvm_core::AssetId,
+		) -> Result<u128, DispatchError> {
+			Ok(T::Assets::balance(Self::local_asset(asset_id)?, account).into())
+		}
+
+		fn check(
+			account: &T::AccountId,
+			predicate: Predicate<Vec<u8>>,
+		) -> Result<bool, DispatchError> {
+			Ok(match predicate {
+				Predicate::BalanceAtLeast { asset, amount } =>
+					Self::balance(account, asset)? >= amount.0,
+				Predicate::BalanceAtMost { asset, amount } =>
+					Self::balance(account, asset)? <= amount.0,
+				Predicate::QueryEq { query, expected } => {
+					// Only the length of the expected value is read, whatever the stored value.
+					let mut value = sp_std::vec![0; expected.len()];
+					sp_io::storage::read(&query, &mut value, 0)
+						.map_or(false, |len| len as usize == expected.len() && value == expected)
+				},
+			})
+		}
+
+		/// Insert the SCALE encoded binding values in `payload`, the bindings must be ordered by
+		/// offset.
+		fn apply_bindings(
+			account: &T::AccountId,
+			relayer: &T::AccountId,
+			payload: Vec<u8>,
+			bindings: Bindings,
+		) -> Result<Vec<u8>, DispatchError> {
+			if bindings.is_empty() {
+				return Ok(payload)
+			}
+			let mut bound = Vec::with_capacity(payload.len());
+			let mut position = 0;
+			for (offset, binding) in bindings {
+				let offset = usize::from(offset);
+				let chunk = payload.get(position..offset).ok_or(Error::<T>::InvalidBindings)?;
+				bound.extend_from_slice(chunk);
+				position = offset;
+				match binding {
+					BindingValue::Self_ => account.encode_to(&mut bound),
+					BindingValue::Relayer => relayer.encode_to(&mut bound),
+					BindingValue::Result => return Err(Error::<T>::UnsupportedBinding.into()),
+					BindingValue::Balance(asset_id) =>
+						T::Assets::balance(Self::local_asset(asset_id)?, account)
+							.encode_to(&mut bound),
+					BindingValue::AssetId(asset_id) =>
+						Self::local_asset(asset_id)?.encode_to(&mut bound),
+				}
+			}
+			bound.extend_from_slice(payload.get(position..).ok_or(Error::<T>::InvalidBindings)?);
+			Ok(bound)
+		}
+	}
+}
@@ -0,0 +1,277 @@
+use crate::{self as pallet_xcvm, transport::IbcTransport};
+use core::{cell::RefCell, time::Duration};
+use frame_support::{
+	ord_parameter_types, parameter_types,
+	traits::{Contains, Everything, UnixTime},
+	weights::Weight,
+	PalletId,
+};
+use frame_system::EnsureSignedBy;
+use ibc::{
+	applications::transfer::{msgs::transfer::MsgTransfer, PrefixedCoin},
+	core::{
+		ics04_channel::{
+			channel::ChannelEnd, msgs::acknowledgement::Acknowledgement, packet::Packet,
+		},
+		ics24_host::identifier::{ChannelId, PortId},
+		ics26_routing::context::ModuleOutputBuilder,
+	},
+};
+use ibc_primitives::SendPacketData;
+use orml_traits::parameter_type_with_key;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Convert, IdentityLookup},
+	AccountId32,
+};
+use xcvm_core::{Juno, NetworkId, Picasso, StaticNetwork};
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+pub type Amount = i128;
+pub type CurrencyId = u128;
+
+pub const ALICE: AccountId = AccountId32::new([1; 32]);
+pub const BOB: AccountId = AccountId32::new([2; 32]);
+pub const RELAYER: AccountId = AccountId32::new([3; 32]);
+pub const ESCROW: AccountId = AccountId32::new([4; 32]);
+
+pub const PICA: CurrencyId = 1;
+pub const ETH: CurrencyId = 2;
+pub const USDT: CurrencyId = 4;
+
+/// Current time of the runtime, in seconds.
+pub const NOW: u64 = 1_000;
+/// Timeout of the XCVM packets, in nanoseconds.
+pub const PACKET_TIMEOUT: u64 = 1_000_000_000;
+/// Channel of the XCVM packets to Juno.
+pub const JUNO_CHANNEL: &[u8] = b"channel-0";
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Tokens: orml_tokens::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Xcvm: pallet_xcvm::{Pallet, Call, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		0
+	};
+}
+
+type ReserveIdentifier = [u8; 8];
+impl orml_tokens::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ();
+	type ReserveIdentifier = ReserveIdentifier;
+	type MaxReserves = frame_support::traits::ConstU32<2>;
+	type DustRemovalWhitelist = Everything;
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+}
+
+/// Programs are not allowed to dispatch system calls but remarks.
+pub struct NoSystemCalls;
+impl Contains<Call> for NoSystemCalls {
+	fn contains(call: &Call) -> bool {
+		!matches!(call, Call::System(call) if !matches!(call, frame_system::Call::remark { .. }))
+	}
+}
+
+pub struct AssetIdConvert;
+impl Convert<xcvm_core::AssetId, Option<CurrencyId>> for AssetIdConvert {
+	fn convert(asset_id: xcvm_core::AssetId) -> Option<CurrencyId> {
+		match asset_id.0 {
+			PICA | ETH | USDT => Some(asset_id.0),
+			_ => None,
+		}
+	}
+}
+
+thread_local! {
+	/// Data, channel and timeout of the packets sent.
+	pub static SENT_PACKETS: RefCell<Vec<(Vec<u8>, Vec<u8>, u64)>> = RefCell::new(vec![]);
+}
+
+/// Records the packets sent, every other operation fails.
+pub struct MockIbc;
+impl ibc_trait::IbcTrait for MockIbc {
+	fn client_revision_number(_: Vec<u8>, _: Vec<u8>) -> Result<u64, ibc_trait::Error> {
+		Ok(0)
+	}
+
+	fn send_packet(data: SendPacketData) -> Result<(), ibc_trait::Error> {
+		SENT_PACKETS.with(|packets| {
+			packets.borrow_mut().push((data.data, data.channel_id, data.timeout_timestamp))
+		});
+		Ok(())
+	}
+
+	fn open_channel(_: PortId, _: ChannelEnd) -> Result<ChannelId, ibc_trait::Error> {
+		Err(ibc_trait::Error::ChannelInitError)
+	}
+
+	fn close_channel(_: PortId, _: ChannelId) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::ChannelCloseError)
+	}
+
+	fn channel_end(_: &PortId, _: &ChannelId) -> Result<ChannelEnd, ibc_trait::Error> {
+		Err(ibc_trait::Error::ChannelOrPortError)
+	}
+
+	fn next_sequence_send(_: &PortId, _: &ChannelId) -> Result<u64, ibc_trait::Error> {
+		Ok(1)
+	}
+
+	fn write_acknowledgement(_: &Packet, _: Vec<u8>) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::WriteAcknowledgementError)
+	}
+
+	fn send_transfer(
+		_: MsgTransfer<PrefixedCoin>,
+		_: Option<String>,
+	) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::SendTransferError)
+	}
+
+	fn on_receive_packet(_: &mut ModuleOutputBuilder, _: &Packet) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::ReceivePacketError)
+	}
+
+	fn on_ack_packet(
+		_: &mut ModuleOutputBuilder,
+		_: &Packet,
+		_: &Acknowledgement,
+	) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::AcknowledgementError)
+	}
+
+	fn on_timeout_packet(_: &mut ModuleOutputBuilder, _: &Packet) -> Result<(), ibc_trait::Error> {
+		Err(ibc_trait::Error::TimeoutError)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_client() -> Result<ibc::core::ics24_host::identifier::ClientId, ibc_trait::Error> {
+		Ok(Default::default())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_connection(
+		_: ibc::core::ics24_host::identifier::ClientId,
+		_: ibc::core::ics24_host::identifier::ConnectionId,
+	) -> Result<(), ibc_trait::Error> {
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_channel(
+		_: PortId,
+		_: ChannelId,
+		_: ibc::core::ics24_host::identifier::ConnectionId,
+	) -> Result<(), ibc_trait::Error> {
+		Ok(())
+	}
+}
+
+/// Only Juno is reachable, over `JUNO_CHANNEL`.
+pub struct XcvmChannels;
+impl Convert<NetworkId, Option<(Vec<u8>, Vec<u8>)>> for XcvmChannels {
+	fn convert(network: NetworkId) -> Option<(Vec<u8>, Vec<u8>)> {
+		(network == Juno::ID).then(|| (b"xcvm".to_vec(), JUNO_CHANNEL.to_vec()))
+	}
+}
+
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> Duration {
+		Duration::from_secs(NOW)
+	}
+}
+
+parameter_types! {
+	pub const XcvmPalletId: PalletId = PalletId(*b"pal_xcvm");
+	pub const ThisNetwork: NetworkId = Picasso::ID;
+	pub const MaxCallWeight: Weight = 1_000_000_000;
+	pub const Escrow: AccountId = ESCROW;
+	pub const PacketTimeout: u64 = PACKET_TIMEOUT;
+}
+
+ord_parameter_types! {
+	pub const Relayer: AccountId = RELAYER;
+}
+
+impl pallet_xcvm::Config for Test {
+	type Event = Event;
+	type Call = Call;
+	type CallFilter = NoSystemCalls;
+	type AssetId = CurrencyId;
+	type Balance = Balance;
+	type Assets = Tokens;
+	type AssetIdConvert = AssetIdConvert;
+	type Transport =
+		IbcTransport<MockIbc, Tokens, XcvmChannels, Escrow, MockUnixTime, PacketTimeout>;
+	type RemoteOrigin = EnsureSignedBy<Relayer, AccountId>;
+	type NetworkId = ThisNetwork;
+	type MaxCallWeight = MaxCallWeight;
+	type PalletId = XcvmPalletId;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	orml_tokens::GenesisConfig::<Test> {
+		balances: vec![(ALICE, PICA, 1_000_000), (ALICE, USDT, 1_000_000)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,296 @@
+use crate::{
+	mock::*, transport::XCVMPacket, Error, Event as XcvmEvent, Pallet as XcvmPallet, XCVMProgram,
+};
+use codec::{Decode, Encode};
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+use orml_traits::MultiCurrency;
+use sp_std::collections::btree_map::BTreeMap;
+use xcvm_core::{
+	Amount, BindingValue, Displayed, Ethereum, Funds, Juno, Picasso, Predicate, ProgramBuilder,
+	StaticNetwork, MAX_PARTS, MAX_REPEAT,
+};
+
+type Builder = ProgramBuilder<Picasso, Vec<u8>, Funds>;
+
+fn alice_account() -> AccountId {
+	XcvmPallet::<Test>::interpreter_account(Picasso::ID, &ALICE.encode())
+}
+
+fn execute(funds: &[(CurrencyId, Balance)], program: XCVMProgram) -> sp_runtime::DispatchResult {
+	XcvmPallet::<Test>::execute(
+		Origin::signed(ALICE),
+		funds.iter().copied().collect::<BTreeMap<_, _>>(),
+		program,
+	)
+	.map(|_| ())
+	.map_err(|e| e.error)
+}
+
+#[test]
+fn execute_transfer() {
+	new_test_ext().execute_with(|| {
+		let program = Builder::new(b"transfer".to_vec())
+			.transfer(BOB.encode(), Funds::from([(PICA, 400_u128)]))
+			.transfer(BOB.encode(), Funds::from([(USDT, Amount::ratio(MAX_PARTS))]))
+			.build();
+		assert_ok!(execute(&[(PICA, 1_000), (USDT, 1_000)], program));
+
+		assert_eq!(Tokens::balance(PICA, &ALICE), 999_000);
+		assert_eq!(Tokens::balance(PICA, &alice_account()), 600);
+		assert_eq!(Tokens::balance(PICA, &BOB), 400);
+		assert_eq!(Tokens::balance(USDT, &alice_account()), 0);
+		assert_eq!(Tokens::balance(USDT, &BOB), 1_000);
+		System::assert_last_event(
+			XcvmEvent::<Test>::Executed {
+				account: alice_account(),
+				network: Picasso::ID,
+				user_id: ALICE.encode(),
+				tag: b"transfer".to_vec(),
+			}
+			.into(),
+		);
+
+		// Unknown assets and accounts are rejected
+		let program = Builder::new(vec![]).transfer(BOB.encode(), Funds::from([(3_u128, 1_u128)]));
+		assert_noop!(execute(&[], program.build()), Error::<Test>::UnknownAsset);
+		let program = Builder::new(vec![]).transfer(vec![1, 2], Funds::from([(PICA, 1_u128)]));
+		assert_noop!(execute(&[], program.build()), Error::<Test>::InvalidAccount);
+	});
+}
+
+#[test]
+fn execute_call() {
+	new_test_ext().execute_with(|| {
+		let transfer =
+			Call::Tokens(orml_tokens::Call::transfer { dest: BOB, currency_id: USDT, amount: 100 })
+				.encode();
+		let program = Builder::new(vec![]).call_raw(transfer.clone()).build();
+		assert_ok!(execute(&[(USDT, 1_000)], program));
+		assert_eq!(Tokens::balance(USDT, &BOB), 100);
+		assert_eq!(Tokens::balance(USDT, &alice_account()), 900);
+
+		// Calls must pass the call filter
+		let remark =
+			Call::System(frame_system::Call::remark_with_event { remark: vec![] }).encode();
+		let program = Builder::new(vec![]).call_raw(remark).build();
+		assert_noop!(execute(&[], program), Error::<Test>::CallNotAllowed);
+		let program = Builder::new(vec![]).call_raw(vec![0xFF, 0xFF]).build();
+		assert_noop!(execute(&[], program), Error::<Test>::InvalidCallEncoding);
+
+		// Programs from other networks are executed by the relayer, the `dest` of the transfer is
+		// bound to the relayer account.
+		let user_id = b"juno_user".to_vec();
+		let account = XcvmPallet::<Test>::interpreter_account(Juno::ID, &user_id);
+		assert_ok!(<Tokens as MultiCurrency<AccountId>>::transfer(USDT, &ALICE, &account, 1_000));
+		let bound_transfer = [&transfer[..2], &transfer[34..]].concat();
+		let program = Builder::new(vec![])
+			.call_raw_with_bindings(bound_transfer, vec![(2, BindingValue::Relayer)])
+			.build();
+		assert_ok!(XcvmPallet::<Test>::execute_remote(
+			Origin::signed(RELAYER),
+			Juno::ID,
+			user_id,
+			program
+		));
+		assert_eq!(Tokens::balance(USDT, &RELAYER), 100);
+		assert_eq!(Tokens::balance(USDT, &account), 900);
+	});
+}
+
+#[test]
+fn execute_spawn() {
+	new_test_ext().execute_with(|| {
+		let spawned = Builder::new(vec![])
+			.spawn::<Juno, Juno, (), _>(
+				b"spawned".to_vec(),
+				b"salt".to_vec(),
+				Funds::from([(PICA, Amount::ratio(MAX_PARTS / 2))]),
+				Ok,
+			)
+			.unwrap()
+			.build();
+		let relayed = match spawned.instructions[0].clone() {
+			xcvm_core::Instruction::Spawn { program, .. } => program,
+			_ => unreachable!(),
+		};
+		assert_ok!(execute(&[(PICA, 1_000)], spawned));
+
+		assert_eq!(Tokens::balance(PICA, &ESCROW), 500);
+		assert_eq!(Tokens::balance(PICA, &alice_account()), 500);
+		System::assert_has_event(
+			XcvmEvent::<Test>::Spawned {
+				account: alice_account(),
+				network: Juno::ID,
+				salt: b"salt".to_vec(),
+				tag: b"spawned".to_vec(),
+			}
+			.into(),
+		);
+
+		// The program is relayed over the channel to Juno along the escrowed assets
+		let packets = SENT_PACKETS.with(|packets| packets.borrow().clone());
+		assert_eq!(packets.len(), 1);
+		let (data, channel_id, timeout) = packets[0].clone();
+		assert_eq!(channel_id, JUNO_CHANNEL.to_vec());
+		assert_eq!(timeout, NOW * 1_000_000_000 + PACKET_TIMEOUT);
+		assert_eq!(
+			XCVMPacket::decode(&mut data.as_slice()).unwrap(),
+			XCVMPacket {
+				origin_network_id: Picasso::ID,
+				origin_user_id: ALICE.encode(),
+				salt: b"salt".to_vec(),
+				program: relayed.encode_binary(),
+				assets: vec![(PICA.into(), 500)],
+			}
+		);
+
+		// Networks without a channel can't be reached
+		let program = Builder::new(vec![])
+			.spawn::<Ethereum, Ethereum, (), _>(vec![], vec![], Funds::from([(PICA, 1_u128)]), Ok)
+			.unwrap()
+			.build();
+		assert_noop!(
+			execute(&[], program),
+			sp_runtime::DispatchError::Other("no channel to the network")
+		);
+	});
+}
+
+#[test]
+fn execute_remote() {
+	new_test_ext().execute_with(|| {
+		let user_id = b"juno_user".to_vec();
+		let account = XcvmPallet::<Test>::interpreter_account(Juno::ID, &user_id);
+		assert_ok!(<Tokens as MultiCurrency<AccountId>>::transfer(PICA, &ALICE, &account, 1_000));
+		let program = Builder::new(b"remote".to_vec())
+			.transfer(BOB.encode(), Funds::from([(PICA, 400_u128)]))
+			.build();
+
+		// Only the relayer executes remote programs, local users execute their own programs
+		assert_noop!(
+			XcvmPallet::<Test>::execute_remote(
+				Origin::signed(ALICE),
+				Juno::ID,
+				user_id.clone(),
+				program.clone()
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XcvmPallet::<Test>::execute_remote(
+				Origin::root(),
+				Juno::ID,
+				user_id.clone(),
+				program.clone()
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XcvmPallet::<Test>::execute_remote(
+				Origin::signed(RELAYER),
+				Picasso::ID,
+				ALICE.encode(),
+				program.clone()
+			),
+			Error::<Test>::InvalidNetwork
+		);
+
+		// The program is run by the interpreter of the remote user
+		assert_ok!(XcvmPallet::<Test>::execute_remote(
+			Origin::signed(RELAYER),
+			Juno::ID,
+			user_id.clone(),
+			program
+		));
+		assert_eq!(Tokens::balance(PICA, &account), 600);
+		assert_eq!(Tokens::balance(PICA, &BOB), 400);
+		assert_eq!(Tokens::balance(PICA, &alice_account()), 0);
+		System::assert_last_event(
+			XcvmEvent::<Test>::Executed {
+				account,
+				network: Juno::ID,
+				user_id,
+				tag: b"remote".to_vec(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn execute_control_flow() {
+	new_test_ext().execute_with(|| {
+		let transfer = |amount: u128| xcvm_core::Instruction::Transfer {
+			to: BOB.encode(),
+			assets: Funds::from([(PICA, amount)]),
+		};
+		let at_least = |amount: u128| Predicate::BalanceAtLeast {
+			asset: PICA.into(),
+			amount: Displayed(amount),
+		};
+		let mut program = Builder::new(vec![]).build();
+		program.instructions.extend([
+			xcvm_core::Instruction::IfElse {
+				predicate: at_least(1_000),
+				then: [transfer(100)].into(),
+				otherwise: [transfer(1)].into(),
+			},
+			xcvm_core::Instruction::Repeat { times: 3, instructions: [transfer(10)].into() },
+			xcvm_core::Instruction::IfElse {
+				predicate: at_least(1_000),
+				then: [transfer(100)].into(),
+				otherwise: [transfer(1)].into(),
+			},
+		]);
+		assert_ok!(execute(&[(PICA, 1_000)], program));
+		assert_eq!(Tokens::balance(PICA, &BOB), 131);
+
+		// A failing assertion reverts the whole program
+		let mut program = Builder::new(vec![]).build();
+		program
+			.instructions
+			.extend([transfer(100), xcvm_core::Instruction::Assert { predicate: at_least(1_000) }]);
+		assert_noop!(execute(&[], program), Error::<Test>::AssertionFailed);
+
+		let mut program = Builder::new(vec![]).build();
+		program.instructions.push_back(xcvm_core::Instruction::Repeat {
+			times: MAX_REPEAT + 1,
+			instructions: [transfer(1)].into(),
+		});
+		assert_noop!(execute(&[], program), Error::<Test>::RepeatTooLarge);
+
+		// Nested repeats are bounded by the expanded length of the program
+		let mut program = Builder::new(vec![]).build();
+		program.instructions.push_back(xcvm_core::Instruction::Repeat {
+			times: MAX_REPEAT,
+			instructions: [xcvm_core::Instruction::Repeat {
+				times: MAX_REPEAT,
+				instructions: [transfer(1)].into(),
+			}]
+			.into(),
+		});
+		assert_noop!(execute(&[], program), Error::<Test>::ProgramTooLarge);
+	});
+}
+
+#[test]
+fn execute_query_eq() {
+	new_test_ext().execute_with(|| {
+		frame_support::storage::unhashed::put_raw(b"key", b"value");
+		let query_eq = |expected: &[u8]| {
+			let mut program = Builder::new(vec![]).build();
+			program.instructions.push_back(xcvm_core::Instruction::Assert {
+				predicate: Predicate::QueryEq {
+					query: b"key".to_vec(),
+					expected: expected.to_vec(),
+				},
+			});
+			execute(&[], program)
+		};
+		assert_ok!(query_eq(b"value"));
+		// The stored value must match the expected one as a whole
+		assert_noop!(query_eq(b"val"), Error::<Test>::AssertionFailed);
+		assert_noop!(query_eq(b"value2"), Error::<Test>::AssertionFailed);
+		assert_noop!(query_eq(b"other"), Error::<Test>::AssertionFailed);
+	});
+}
@@ -0,0 +1,106 @@
+//! IBC transport of the programs spawned by the interpreter.
+
+use crate::{Transport, XCVMProgram};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{fungibles::Transfer, Get, UnixTime},
+	RuntimeDebug,
+};
+use ibc_primitives::SendPacketData;
+use ibc_trait::IbcTrait;
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Convert, DispatchError, DispatchResult, SaturatedConversion};
+use sp_std::{marker::PhantomData, vec::Vec};
+use xcvm_core::{AssetId, NetworkId};
+
+/// Program spawned to another network, sent SCALE encoded as the data of an IBC packet.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct XCVMPacket {
+	/// Network of the user whose interpreter spawned the program.
+	pub origin_network_id: NetworkId,
+	/// User whose interpreter spawned the program.
+	pub origin_user_id: Vec<u8>,
+	pub salt: Vec<u8>,
+	/// Program in the XCVM binary encoding.
+	pub program: Vec<u8>,
+	/// Assets escrowed by the transport, by XCVM asset.
+	pub assets: Vec<(AssetId, u128)>,
+}
+
+/// Sends the spawned programs as [`XCVMPacket`]s over IBC.
+///
+/// * `Ibc` sends the packets.
+/// * `Assets` escrows the spawned assets on the `Escrow` account.
+/// * `Channels` resolves the `(port, channel)` bound to a network, as utf8 bytes.
+/// * The packets time out `Timeout` nanoseconds after the current `Time`.
+pub struct IbcTransport<Ibc, Assets, Channels, Escrow, Time, Timeout>(
+	PhantomData<(Ibc, Assets, Channels, Escrow, Time, Timeout)>,
+);
+
+impl<AccountId, LocalAssetId, Balance, Ibc, Assets, Channels, Escrow, Time, Timeout>
+	Transport<AccountId, LocalAssetId, Balance>
+	for IbcTransport<Ibc, Assets, Channels, Escrow, Time, Timeout>
+where
+	Balance: Copy + Into<u128>,
+	Ibc: IbcTrait,
+	Assets: Transfer<AccountId, AssetId = LocalAssetId, Balance = Balance>,
+	Channels: Convert<NetworkId, Option<(Vec<u8>, Vec<u8>)>>,
+	Escrow: Get<AccountId>,
+	Time: UnixTime,
+	Timeout: Get<u64>,
+{
+	fn spawn(
+		from: &AccountId,
+		origin_network: NetworkId,
+		origin_user_id: &[u8],
+		network: NetworkId,
+		salt: Vec<u8>,
+		assets: Vec<(AssetId, LocalAssetId, Balance)>,
+		program: XCVMProgram,
+	) -> DispatchResult {
+		let (port_id, channel_id) =
+			Channels::convert(network).ok_or(DispatchError::Other("no channel to the network"))?;
+		let escrow = Escrow::get();
+		let mut escrowed = Vec::with_capacity(assets.len());
+		for (asset_id, asset, amount) in assets {
+			Assets::transfer(asset, from, &escrow, amount, false)?;
+			escrowed.push((asset_id, amount.into()));
+		}
+		let packet = XCVMPacket {
+			origin_network_id: origin_network,
+			origin_user_id: origin_user_id.to_vec(),
+			salt,
+			program: program.encode_binary(),
+			assets: escrowed,
+		};
+		let now: u64 = Time::now().as_nanos().saturated_into();
+		Ibc::send_packet(SendPacketData {
+			data: packet.encode(),
+			revision_number: None,
+			// Timed out by timestamp only
+			timeout_height: 0,
+			timeout_timestamp: now.saturating_add(Timeout::get()),
+			port_id,
+			channel_id,
+		})
+		.map_err(|_| DispatchError::Other("failed to send the packet"))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn setup_benchmark(network: NetworkId) -> DispatchResult {
+		use ibc::core::ics24_host::identifier::ConnectionId;
+		use ibc_trait::{channel_id_from_bytes, port_id_from_bytes};
+
+		let (port_id, channel_id) =
+			Channels::convert(network).ok_or(DispatchError::Other("no channel to the network"))?;
+		let port_id =
+			port_id_from_bytes(port_id).map_err(|_| DispatchError::Other("invalid port"))?;
+		let channel_id = channel_id_from_bytes(channel_id)
+			.map_err(|_| DispatchError::Other("invalid channel"))?;
+		let connection_id = ConnectionId::new(0);
+		Ibc::create_client()
+			.and_then(|client_id| Ibc::create_connection(client_id, connection_id.clone()))
+			.and_then(|_| Ibc::create_channel(port_id, channel_id, connection_id))
+			.map_err(|_| DispatchError::Other("failed to open the channel"))
+	}
+}
@@ -0,0 +1,77 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn execute(n: u32, ) -> Weight;
+	fn transfer(n: u32, ) -> Weight;
+	fn call() -> Weight;
+	fn spawn(n: u32, ) -> Weight;
+	fn predicate() -> Weight;
+}
+
+/// Weights estimated from the storage accessed by each instruction, spawns being priced for the
+/// `IbcTransport`.
+// TODO: not benchmarked yet, run the benchmark CLI for `pallet_xcvm` and replace these
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn execute(n: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn transfer(n: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn call() -> Weight {
+		(10_000_000 as Weight)
+	}
+	fn spawn(n: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((20_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn predicate() -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn execute(n: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn transfer(n: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn call() -> Weight {
+		(10_000_000 as Weight)
+	}
+	fn spawn(n: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((20_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn predicate() -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+	}
+}
@@ -22,7 +22,8 @@ use crate::{
 	runtime::{
 		assets::ExtendWithAssetsApi, cosmwasm::ExtendWithCosmwasmApi,
 		crowdloan_rewards::ExtendWithCrowdloanRewardsApi, ibc::ExtendWithIbcApi,
-		lending::ExtendWithLendingApi, pablo::ExtendWithPabloApi, BaseHostRuntimeApis,
+		lending::ExtendWithLendingApi, mosaic::ExtendWithMosaicApi, pablo::ExtendWithPabloApi,
+		vault::ExtendWithVaultApi, BaseHostRuntimeApis,
 	},
 };
 
@@ -37,6 +38,8 @@ pub struct FullDeps<Client, Pool> {
 	pub deny_unsafe: DenyUnsafe,
 	/// Chain properties
 	pub chain_props: sc_chain_spec::Properties,
+	/// Executor of the RPC subscription tasks
+	pub subscription_executor: sc_rpc::SubscriptionTaskExecutor,
 }
 
 /// Instantiate all full RPC extensions.
@@ -67,7 +70,9 @@ where
 			+ ExtendWithPabloApi<RuntimeApi, Executor>
 			+ ExtendWithLendingApi<RuntimeApi, Executor>
 			+ ExtendWithIbcApi<RuntimeApi, Executor>
-			+ ExtendWithCosmwasmApi<RuntimeApi, Executor>,
+			+ ExtendWithCosmwasmApi<RuntimeApi, Executor>
+			+ ExtendWithVaultApi<RuntimeApi, Executor>
+			+ ExtendWithMosaicApi<RuntimeApi, Executor>,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
@@ -104,6 +109,16 @@ where
 	)?;
 
 	<FullClient<RuntimeApi, Executor> as ProvideRuntimeApi<OpaqueBlock>>::Api::extend_with_cosmwasm_api(
+		&mut io,
+		deps.clone(),
+	)?;
+
+	<FullClient<RuntimeApi, Executor> as ProvideRuntimeApi<OpaqueBlock>>::Api::extend_with_vault_api(
+		&mut io,
+		deps.clone(),
+	)?;
+
+	<FullClient<RuntimeApi, Executor> as ProvideRuntimeApi<OpaqueBlock>>::Api::extend_with_mosaic_api(
 		&mut io, deps,
 	)?;
 
@@ -5,6 +5,7 @@ use crowdloan_rewards_rpc::{CrowdloanRewards, CrowdloanRewardsApiServer};
 use cumulus_primitives_core::CollectCollationInfo;
 use ibc_rpc::{IbcApiServer, IbcRpcHandler};
 use lending_rpc::{Lending, LendingApiServer};
+use mosaic_rpc::{Mosaic, MosaicApiServer};
 use pablo_rpc::{Pablo, PabloApiServer};
 use pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi;
 use sp_api::{ApiExt, Metadata, StateBackend};
@@ -15,6 +16,7 @@ use sp_runtime::traits::BlakeTwo256;
 use sp_session::SessionKeys;
 use sp_transaction_pool::runtime_api::TaggedTransactionQueue;
 use substrate_frame_rpc_system::AccountNonceApi;
+use vault_rpc::{Vault, VaultApiServer};
 
 /// Consider this a trait alias.
 pub trait BaseHostRuntimeApis:
@@ -238,7 +240,10 @@ define_trait! {
 		#[cfg(feature = "dali")]
 		impl for dali_runtime {
 			fn (io, deps) {
-				io.merge(IbcRpcHandler::new(deps.client, deps.chain_props).into_rpc())
+				io.merge(
+					IbcRpcHandler::new(deps.client, deps.chain_props, deps.subscription_executor)
+						.into_rpc(),
+				)
 			}
 		}
 	}
@@ -260,4 +265,40 @@ define_trait! {
 			}
 		}
 	}
+
+	mod vault {
+		pub trait ExtendWithVaultApi {
+			fn extend_with_vault_api(io, deps);
+		}
+
+		#[cfg(feature = "composable")]
+		impl for composable_runtime {}
+
+		impl for picasso_runtime {}
+
+		#[cfg(feature = "dali")]
+		impl for dali_runtime {
+			fn (io, deps) {
+				io.merge(Vault::new(deps.client).into_rpc())
+			}
+		}
+	}
+
+	mod mosaic {
+		pub trait ExtendWithMosaicApi {
+			fn extend_with_mosaic_api(io, deps);
+		}
+
+		#[cfg(feature = "composable")]
+		impl for composable_runtime {}
+
+		impl for picasso_runtime {}
+
+		#[cfg(feature = "dali")]
+		impl for dali_runtime {
+			fn (io, deps) {
+				io.merge(Mosaic::new(deps.client).into_rpc())
+			}
+		}
+	}
 }
@@ -32,7 +32,8 @@ use crate::{
 	runtime::{
 		assets::ExtendWithAssetsApi, cosmwasm::ExtendWithCosmwasmApi,
 		crowdloan_rewards::ExtendWithCrowdloanRewardsApi, ibc::ExtendWithIbcApi,
-		lending::ExtendWithLendingApi, pablo::ExtendWithPabloApi, BaseHostRuntimeApis,
+		lending::ExtendWithLendingApi, mosaic::ExtendWithMosaicApi, pablo::ExtendWithPabloApi,
+		vault::ExtendWithVaultApi, BaseHostRuntimeApis,
 	},
 };
 
@@ -288,7 +289,9 @@ where
 		+ ExtendWithPabloApi<RuntimeApi, Executor>
 		+ ExtendWithLendingApi<RuntimeApi, Executor>
 		+ ExtendWithIbcApi<RuntimeApi, Executor>
-		+ ExtendWithCosmwasmApi<RuntimeApi, Executor>,
+		+ ExtendWithCosmwasmApi<RuntimeApi, Executor>
+		+ ExtendWithVaultApi<RuntimeApi, Executor>
+		+ ExtendWithMosaicApi<RuntimeApi, Executor>,
 	StateBackendFor<FullBackend, OpaqueBlock>: StateBackend<BlakeTwo256>,
 	Executor: NativeExecutionDispatch + 'static,
 {
@@ -367,12 +370,13 @@ where
 		let client = client.clone();
 		let transaction_pool = transaction_pool.clone();
 		let chain_props = parachain_config.chain_spec.properties();
-		Box::new(move |deny_unsafe, _| {
+		Box::new(move |deny_unsafe, subscription_executor| {
 			let deps = rpc::FullDeps {
 				client: client.clone(),
 				pool: transaction_pool.clone(),
 				deny_unsafe,
 				chain_props: chain_props.clone(),
+				subscription_executor,
 			};
 
 			Ok(rpc::create(deps).expect("RPC failed to initialize"))
@@ -50,6 +50,8 @@ use composable_traits::{
 	assets::Asset,
 	defi::Rate,
 	dex::{Amm, PriceAggregate, RemoveLiquiditySimulationResult},
+	mosaic::{BudgetStatus, PendingTransfer, PendingTransfers, RelayerStatus},
+	vault::{Deposit, RentStatus, StrategyOverview},
 	xcm::assets::RemoteAssetRegistryInspect,
 };
 use primitives::currency::{CurrencyId, ValidateCurrencyId};
@@ -99,10 +101,11 @@ pub use sp_runtime::{FixedPointNumber, Perbill, Permill, Perquintill};
 use sp_std::{collections::btree_map::BTreeMap, fmt::Debug, vec::Vec};
 use system::{
 	limits::{BlockLength, BlockWeights},
-	EnsureRoot,
+	EnsureRoot, EnsureSignedBy,
 };
 use transaction_payment::{Multiplier, TargetedFeeAdjustment};
 pub use xcmp::XcmConfig;
+use xcvm_core::StaticNetwork;
 
 use crate::{governance::PreimageByteDeposit, xcmp::XcmRouter};
 
@@ -891,6 +894,8 @@ parameter_types! {
 	pub const PicaStakeFinancialNftCollectionId: CurrencyId = CurrencyId::PICA_STAKE_FNFT_COLLECTION;
 	pub const PbloStakeFinancialNftCollectionId: CurrencyId = CurrencyId::PBLO_STAKE_FNFT_COLLECTION;
 	pub const StakingRewardsLockId: LockIdentifier = *b"stk_lock";
+	pub const StakingRewardsGaugeEpochLength: BlockNumber = 7 * DAYS;
+	pub const MaxStakingRewardsGauges: u32 = 32;
 }
 
 impl pallet_staking_rewards::Config for Runtime {
@@ -918,6 +923,8 @@ impl pallet_staking_rewards::Config for Runtime {
 	type LockId = StakingRewardsLockId;
 	type TreasuryAccount = TreasuryAccount;
 	type ExistentialDeposits = MultiExistentialDeposits;
+	type GaugeEpochLength = StakingRewardsGaugeEpochLength;
+	type MaxGauges = MaxStakingRewardsGauges;
 }
 
 /// The calls we permit to be executed by extrinsics
@@ -932,7 +939,7 @@ impl Contains<Call> for BaseCallFilter {
 				Call::Tokens(_) |
 					Call::Indices(_) | Call::Treasury(_) |
 					Call::IbcPing(_) | Call::Transfer(_) |
-					Call::Ibc(_)
+					Call::Ibc(_) | Call::Ica(_)
 			))
 	}
 }
@@ -1010,6 +1017,10 @@ parameter_types! {
 	pub const MosaicId: PalletId = PalletId(*b"plmosaic");
 	pub const MinimumTTL: BlockNumber = 10;
 	pub const MinimumTimeLockPeriod: BlockNumber = 20;
+	pub const MosaicMaxRelayers: u32 = 16;
+	pub const MosaicRelayerBondAssetId: CurrencyId = CurrencyId::PICA;
+	pub MosaicRelayerBond: Balance = 1000 * CurrencyId::unit::<Balance>();
+	pub const MosaicAttestationTTL: BlockNumber = DAYS;
 }
 
 impl mosaic::Config for Runtime {
@@ -1025,6 +1036,10 @@ impl mosaic::Config for Runtime {
 	type WeightInfo = weights::mosaic::WeightInfo<Runtime>;
 	type RemoteAmmId = u128; // TODO: Swap to U256?
 	type AmmMinimumAmountOut = u128;
+	type MaxRelayers = MosaicMaxRelayers;
+	type RelayerBondAssetId = MosaicRelayerBondAssetId;
+	type RelayerBond = MosaicRelayerBond;
+	type AttestationTTL = MosaicAttestationTTL;
 }
 
 pub type LiquidationStrategyId = u32;
@@ -1044,7 +1059,9 @@ impl liquidations::Config for Runtime {
 	type PalletId = LiquidationsPalletId;
 	type CanModifyStrategies = EnsureRootOrHalfNativeCouncil;
 	type XcmSender = XcmRouter;
+	type Dex = DexRouter;
 	type MaxLiquidationStrategiesAmount = ConstU32<10>;
+	type MaxTimeoutStepsPerBlock = ConstU32<100>;
 }
 
 parameter_types! {
@@ -1179,6 +1196,13 @@ where
 
 parameter_types! {
 	pub TransferPalletID: PalletId = PalletId(*b"transfer");
+	/// One hour, in nanoseconds
+	pub const IbcRefundTimeout: u64 = 60 * 60 * 1_000_000_000;
+	/// Cosmos denoms are usually in micro units
+	pub const IbcAssetDecimals: composable_traits::currency::Exponent = 6;
+	pub const IbcAssetExistentialDeposit: Balance = 1;
+	// TODO: set once IBC assets are accepted for fees
+	pub const IbcAssetFeeRatio: Option<composable_traits::defi::Ratio> = None;
 }
 
 impl ibc_transfer::Config for Runtime {
@@ -1190,6 +1214,11 @@ impl ibc_transfer::Config for Runtime {
 	type CurrencyFactory = CurrencyFactory;
 	type AdminOrigin = EnsureRoot<AccountId>;
 	type PalletId = TransferPalletID;
+	type RefundTimeout = IbcRefundTimeout;
+	type IbcAssetDecimals = IbcAssetDecimals;
+	type IbcAssetExistentialDeposit = IbcAssetExistentialDeposit;
+	type IbcAssetFeeRatio = IbcAssetFeeRatio;
+	type UnixTime = Timestamp;
 	type WeightInfo = crate::weights::ibc_transfer::WeightInfo<Self>;
 }
 
@@ -1203,6 +1232,7 @@ impl pallet_ibc::Config for Runtime {
 	type ExpectedBlockTime = ExpectedBlockTime;
 	type WeightInfo = crate::weights::pallet_ibc::WeightInfo<Self>;
 	type AdminOrigin = EnsureRoot<AccountId>;
+	type Router = cosmwasm::ibc::Router<Runtime>;
 }
 
 impl pallet_ibc_ping::Config for Runtime {
@@ -1210,6 +1240,20 @@ impl pallet_ibc_ping::Config for Runtime {
 	type IbcHandler = Ibc;
 }
 
+parameter_types! {
+	pub IcaPalletID: PalletId = PalletId(*b"pica/ica");
+}
+
+impl pallet_ibc_ica::Config for Runtime {
+	type Event = Event;
+	type IbcHandler = Ibc;
+	type Call = Call;
+	type PalletId = IcaPalletID;
+	type AdminOrigin = EnsureRootOrOneThirdNativeTechnical;
+	type MaxStringSize = MaxStringSize;
+	type WeightInfo = pallet_ibc_ica::SubstrateWeight<Runtime>;
+}
+
 /// Native <-> Cosmwasm account mapping
 /// TODO(hussein-aitlahcen): Probably nicer to have SS58 representation here.
 pub struct AccountToAddr;
@@ -1264,6 +1308,7 @@ parameter_types! {
   pub const CodeStorageByteDeposit: u32 = 1;
   pub const ContractStorageByteReadPrice: u32 = 1;
   pub const ContractStorageByteWritePrice: u32 = 1;
+  pub const CosmwasmIbcCallbackGas: u64 = 100_000_000;
 }
 
 impl cosmwasm::Config for Runtime {
@@ -1296,6 +1341,75 @@ impl cosmwasm::Config for Runtime {
 	type UnixTime = Timestamp;
 	// TODO: proper weights
 	type WeightInfo = cosmwasm::weights::SubstrateWeight<Runtime>;
+	type IbcHandler = Ibc;
+	type IbcTransfer = Transfer;
+	type IbcCallbackGas = CosmwasmIbcCallbackGas;
+}
+
+parameter_types! {
+	pub const XcvmPalletId: PalletId = PalletId(*b"picaxcvm");
+	pub const XcvmNetworkId: xcvm_core::NetworkId = xcvm_core::Picasso::ID;
+	pub const XcvmMaxCallWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 10;
+}
+
+/// Programs can only dispatch asset transfers and swaps. Calls dispatching other calls, such as
+/// the `Utility`, `Proxy` or `Multisig` ones, are left out so that they can't bypass the filter.
+pub struct XcvmCallFilter;
+impl Contains<Call> for XcvmCallFilter {
+	fn contains(call: &Call) -> bool {
+		matches!(
+			call,
+			Call::Assets(
+				assets::Call::transfer { .. } |
+					assets::Call::transfer_native { .. } |
+					assets::Call::transfer_all { .. } |
+					assets::Call::transfer_all_native { .. }
+			) | Call::Pablo(
+				pablo::Call::buy { .. } |
+					pablo::Call::swap { .. } |
+					pablo::Call::add_liquidity { .. } |
+					pablo::Call::remove_liquidity { .. }
+			) | Call::DexRouter(
+				dex_router::Call::buy { .. } |
+					dex_router::Call::swap { .. } |
+					dex_router::Call::add_liquidity { .. } |
+					dex_router::Call::remove_liquidity { .. }
+			)
+		)
+	}
+}
+
+/// XCVM <-> Native asset mapping
+pub struct XcvmAssetIdConvert;
+impl Convert<xcvm_core::AssetId, Option<CurrencyId>> for XcvmAssetIdConvert {
+	fn convert(asset_id: xcvm_core::AssetId) -> Option<CurrencyId> {
+		use xcvm_core::{Asset, PICA, USDC, USDT};
+		match asset_id {
+			PICA::ID => Some(CurrencyId::PICA),
+			USDT::ID => Some(CurrencyId::USDT),
+			USDC::ID => Some(CurrencyId::USDC),
+			_ => None,
+		}
+	}
+}
+
+impl pallet_xcvm::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type CallFilter = XcvmCallFilter;
+	type AssetId = CurrencyId;
+	type Balance = Balance;
+	type Assets = Assets;
+	type AssetIdConvert = XcvmAssetIdConvert;
+	// TODO: use the `IbcTransport` once an IBC module handles the packets of the xcvm port,
+	// executing the received programs and refunding the failed ones
+	type Transport = ();
+	// Remote programs are relayed by the technical committee until a bridge executes them
+	type RemoteOrigin = EnsureSignedBy<TechnicalMembership, AccountId>;
+	type NetworkId = XcvmNetworkId;
+	type MaxCallWeight = XcvmMaxCallWeight;
+	type PalletId = XcvmPalletId;
+	type WeightInfo = pallet_xcvm::weights::SubstrateWeight<Runtime>;
 }
 
 construct_runtime!(
@@ -1376,9 +1490,13 @@ construct_runtime!(
 		IbcPing: pallet_ibc_ping = 151,
 		Transfer: ibc_transfer = 152,
 		Ibc: pallet_ibc = 153,
+		Ica: pallet_ibc_ica = 154,
 
 	  // Cosmwasm support
-	  Cosmwasm: cosmwasm = 180
+	  Cosmwasm: cosmwasm = 180,
+
+		// XCVM support
+		Xcvm: pallet_xcvm = 190,
 	}
 );
 
@@ -1455,6 +1573,9 @@ mod benches {
 		[pallet_account_proxy, Proxy]
 		[dex_router, DexRouter]
 		[cosmwasm, Cosmwasm]
+		[pallet_ibc_ica, Ica]
+		// TODO: benchmark once a transport is wired, `spawn` needs one
+		// [pallet_xcvm, Xcvm]
 	// TODO: Broken
 		// [pallet_ibc, Ibc]
 		// [ibc_transfer, Transfer]
@@ -1564,6 +1685,114 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl vault_runtime_api::VaultRuntimeApi<Block, AccountId, u64, Balance, BlockNumber> for Runtime {
+		fn preview_deposit(
+			vault_id: SafeRpcWrapper<u64>,
+			asset_amount: SafeRpcWrapper<Balance>,
+		) -> Result<SafeRpcWrapper<Balance>, sp_runtime::DispatchError> {
+			vault::Pallet::<Runtime>::preview_deposit(&vault_id.0, asset_amount.0).map(SafeRpcWrapper)
+		}
+
+		fn preview_withdraw(
+			vault_id: SafeRpcWrapper<u64>,
+			lp_amount: SafeRpcWrapper<Balance>,
+		) -> Result<SafeRpcWrapper<Balance>, sp_runtime::DispatchError> {
+			vault::Pallet::<Runtime>::preview_withdraw(&vault_id.0, lp_amount.0).map(SafeRpcWrapper)
+		}
+
+		fn total_assets(
+			vault_id: SafeRpcWrapper<u64>,
+		) -> Result<SafeRpcWrapper<Balance>, sp_runtime::DispatchError> {
+			vault::Pallet::<Runtime>::total_assets(&vault_id.0).map(SafeRpcWrapper)
+		}
+
+		fn strategies(
+			vault_id: SafeRpcWrapper<u64>,
+		) -> Result<Vec<(SafeRpcWrapper<AccountId>, StrategyOverview<SafeRpcWrapper<Balance>>)>, sp_runtime::DispatchError> {
+			let strategies = vault::Pallet::<Runtime>::strategies(&vault_id.0)?;
+			Ok(strategies
+				.into_iter()
+				.map(|(account, overview)| {
+					(
+						SafeRpcWrapper(account),
+						StrategyOverview {
+							allocation: overview.allocation,
+							balance: SafeRpcWrapper(overview.balance),
+							lifetime_withdrawn: SafeRpcWrapper(overview.lifetime_withdrawn),
+							lifetime_deposited: SafeRpcWrapper(overview.lifetime_deposited),
+						},
+					)
+				})
+				.collect())
+		}
+
+		fn rent_status(
+			vault_id: SafeRpcWrapper<u64>,
+		) -> Result<RentStatus<SafeRpcWrapper<Balance>, SafeRpcWrapper<BlockNumber>>, sp_runtime::DispatchError> {
+			let status = vault::Pallet::<Runtime>::rent_status(&vault_id.0)?;
+			let deposit = match status.deposit {
+				Deposit::Existential => Deposit::Existential,
+				Deposit::Rent { amount, at } =>
+					Deposit::Rent { amount: SafeRpcWrapper(amount), at: SafeRpcWrapper(at) },
+			};
+			Ok(RentStatus {
+				deposit,
+				tombstoned: status.tombstoned,
+				blocks_until_eviction: status.blocks_until_eviction.map(SafeRpcWrapper),
+				blocks_until_deletion: status.blocks_until_deletion.map(SafeRpcWrapper),
+			})
+		}
+	}
+
+	impl mosaic_runtime_api::MosaicRuntimeApi<Block, AccountId, CurrencyId, u32, u128, Balance, BlockNumber> for Runtime {
+		fn pending_transfers(
+			account: SafeRpcWrapper<AccountId>,
+		) -> PendingTransfers<SafeRpcWrapper<CurrencyId>, SafeRpcWrapper<Balance>, SafeRpcWrapper<BlockNumber>> {
+			let wrap = |transfers: Vec<PendingTransfer<CurrencyId, Balance, BlockNumber>>| {
+				transfers
+					.into_iter()
+					.map(|transfer| PendingTransfer {
+						asset_id: SafeRpcWrapper(transfer.asset_id),
+						amount: SafeRpcWrapper(transfer.amount),
+						unlock_block: SafeRpcWrapper(transfer.unlock_block),
+					})
+					.collect()
+			};
+			let transfers = mosaic::Pallet::<Runtime>::pending_transfers(account.0);
+			PendingTransfers { incoming: wrap(transfers.incoming), outgoing: wrap(transfers.outgoing) }
+		}
+
+		fn budgets() -> Vec<BudgetStatus<SafeRpcWrapper<CurrencyId>, SafeRpcWrapper<u32>, SafeRpcWrapper<Balance>>> {
+			mosaic::Pallet::<Runtime>::budgets()
+				.into_iter()
+				.map(|status| BudgetStatus {
+					asset_id: SafeRpcWrapper(status.asset_id),
+					network_id: status.network_id.map(SafeRpcWrapper),
+					budget: SafeRpcWrapper(status.budget),
+					penalty: SafeRpcWrapper(status.penalty),
+					remaining: SafeRpcWrapper(status.remaining),
+				})
+				.collect()
+		}
+
+		fn relayer() -> Option<RelayerStatus<SafeRpcWrapper<AccountId>, SafeRpcWrapper<BlockNumber>>> {
+			mosaic::Pallet::<Runtime>::relayer_status().map(|status| RelayerStatus {
+				current: SafeRpcWrapper(status.current),
+				next: status.next.map(SafeRpcWrapper),
+				ttl: status.ttl.map(SafeRpcWrapper),
+			})
+		}
+
+		fn remote_amm_ids() -> Vec<(SafeRpcWrapper<u32>, Vec<SafeRpcWrapper<u128>>)> {
+			mosaic::Pallet::<Runtime>::remote_amm_ids()
+				.into_iter()
+				.map(|(network_id, amm_ids)| {
+					(SafeRpcWrapper(network_id), amm_ids.into_iter().map(SafeRpcWrapper).collect())
+				})
+				.collect()
+		}
+	}
+
 	impl cosmwasm_runtime_api::CosmwasmRuntimeApi<Block, AccountId, CurrencyId, Balance, Vec<u8>> for Runtime {
 		fn query(
 			contract: AccountId,
@@ -1820,6 +2049,14 @@ impl_runtime_apis! {
 			Ibc::packet_acknowledgements(channel_id, port_id).ok()
 		}
 
+		fn packet_commitments_batch(channel_id: Vec<u8>, port_id: Vec<u8>, seqs: Vec<u64>) -> Option<ibc_primitives::QueryPacketStatesResponse> {
+			Ibc::packet_commitments_batch(channel_id, port_id, seqs).ok()
+		}
+
+		fn packet_acknowledgements_batch(channel_id: Vec<u8>, port_id: Vec<u8>, seqs: Vec<u64>) -> Option<ibc_primitives::QueryPacketStatesResponse> {
+			Ibc::packet_acknowledgements_batch(channel_id, port_id, seqs).ok()
+		}
+
 		fn unreceived_packets(channel_id: Vec<u8>, port_id: Vec<u8>, seqs: Vec<u64>) -> Option<Vec<u64>> {
 			Ibc::unreceived_packets(channel_id, port_id, seqs).ok()
 		}
@@ -69,6 +69,17 @@ impl<T: frame_system::Config> ibc_transfer::WeightInfo for WeightInfo<T> {
 		(21_112_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// TODO: not benchmarked yet, run the benchmark CLI for the rate limit extrinsics
+	fn set_rate_limit() -> Weight {
+		<() as ibc_transfer::WeightInfo>::set_rate_limit()
+	}
+	fn remove_rate_limit() -> Weight {
+		<() as ibc_transfer::WeightInfo>::remove_rate_limit()
+	}
+	// TODO: not benchmarked yet, run the benchmark CLI for the asset metadata extrinsic
+	fn set_asset_metadata() -> Weight {
+		<() as ibc_transfer::WeightInfo>::set_asset_metadata()
+	}
 	fn on_chan_open_init() -> Weight {
 		(654_000 as Weight)
 	}
@@ -52,4 +52,8 @@ impl<T: frame_system::Config> liquidations::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(x as Weight)))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// TODO: not benchmarked yet, run the benchmark CLI for `on_initialize`
+	fn on_initialize(x: u32, ) -> Weight {
+		<liquidations::weights::SubstrateWeight<T> as liquidations::WeightInfo>::on_initialize(x)
+	}
 }
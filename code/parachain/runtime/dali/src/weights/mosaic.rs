@@ -133,4 +133,18 @@ impl<T: frame_system::Config> mosaic::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// TODO: not benchmarked yet, run the benchmark CLI for the relayer committee extrinsics
+	fn set_relayer_committee(m: u32, ) -> Weight {
+		<() as mosaic::WeightInfo>::set_relayer_committee(m)
+	}
+	fn slash_relayer() -> Weight {
+		<() as mosaic::WeightInfo>::slash_relayer()
+	}
+	// TODO: not benchmarked yet, run the benchmark CLI for the per-network budget extrinsics
+	fn set_network_budget() -> Weight {
+		<() as mosaic::WeightInfo>::set_network_budget()
+	}
+	fn set_asset_network() -> Weight {
+		<() as mosaic::WeightInfo>::set_asset_network()
+	}
 }
@@ -154,4 +154,20 @@ impl<T: frame_system::Config> pallet_staking_rewards::WeightInfo for WeightInfo<
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// TODO: not benchmarked yet, run the benchmark CLI for the gauge extrinsics
+	fn register_gauge() -> Weight {
+		<() as pallet_staking_rewards::WeightInfo>::register_gauge()
+	}
+	fn remove_gauge() -> Weight {
+		<() as pallet_staking_rewards::WeightInfo>::remove_gauge()
+	}
+	fn set_gauge_emission(g: u32, ) -> Weight {
+		<() as pallet_staking_rewards::WeightInfo>::set_gauge_emission(g)
+	}
+	fn vote_gauges(g: u32, ) -> Weight {
+		<() as pallet_staking_rewards::WeightInfo>::vote_gauges(g)
+	}
+	fn apply_gauge_votes(g: u32, ) -> Weight {
+		<() as pallet_staking_rewards::WeightInfo>::apply_gauge_votes(g)
+	}
 }
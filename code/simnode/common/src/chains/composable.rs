@@ -47,6 +47,7 @@ impl substrate_simnode::ChainInfo for ChainInfo {
 			pool: deps.pool,
 			deny_unsafe: deps.deny_unsafe,
 			chain_props: Default::default(),
+			subscription_executor: Arc::new(sp_core::testing::TaskExecutor::new()),
 		};
 		node::rpc::create::<composable_runtime::RuntimeApi, ExecutorDispatch>(full_deps)
 			.expect("Rpc to be initialized")
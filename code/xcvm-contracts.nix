@@ -12,7 +12,9 @@
     in {
       packages = {
         xcvm-contract-asset-registry = mkXcvmContract "xcvm-asset-registry";
+        xcvm-contract-network-registry = mkXcvmContract "xcvm-network-registry";
         xcvm-contract-router = mkXcvmContract "xcvm-router";
+        xcvm-contract-gateway = mkXcvmContract "xcvm-gateway";
         xcvm-contract-interpreter = mkXcvmContract "xcvm-interpreter";
         # TODO: inherit and provide script to run all stuff
       };
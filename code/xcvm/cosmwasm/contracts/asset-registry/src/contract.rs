@@ -1,28 +1,36 @@
 use crate::{
 	error::ContractError,
-	msg::{ExecuteMsg, GetAssetContractResponse, InstantiateMsg, MigrateMsg, QueryMsg},
-	state::{XcvmAssetId, ASSETS},
+	msg::{
+		AssetReference, ExecuteMsg, GetAdminResponse, GetAssetContractResponse,
+		GetAssetMappingResponse, InstantiateMsg, ListAssetsResponse, MigrateMsg, QueryMsg,
+	},
+	state::{XcvmAssetId, ADMIN, ASSETS, LEGACY_ASSETS, LOCAL_ASSETS, REMOTE_ASSETS},
 };
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-	to_binary, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult,
+	to_binary, Binary, Deps, DepsMut, Env, Event, MessageInfo, Order, Response, StdResult,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Bound;
 use cw_utils::ensure_from_older_version;
 use std::collections::BTreeMap;
+use xcvm_core::NetworkId;
 
 const CONTRACT_NAME: &str = "composable:xcvm-asset-registry";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
 	deps: DepsMut,
 	_env: Env,
-	_info: MessageInfo,
+	info: MessageInfo,
 	_msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
 	set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+	ADMIN.save(deps.storage, &info.sender)?;
 	Ok(Response::default().add_event(Event::new("xcvm.registry.instantiated")))
 }
 
@@ -30,53 +38,201 @@ pub fn instantiate(
 pub fn execute(
 	deps: DepsMut,
 	_env: Env,
-	_info: MessageInfo,
+	info: MessageInfo,
 	msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+	if ADMIN.load(deps.storage)? != info.sender {
+		return Err(ContractError::NotAuthorized)
+	}
 	match msg {
 		ExecuteMsg::SetAssets(asset) => handle_set_assets(deps, asset),
+		ExecuteMsg::RegisterAsset { asset_id, reference } =>
+			handle_register_asset(deps, asset_id, reference),
+		ExecuteMsg::UnregisterAsset { asset_id } => handle_unregister_asset(deps, asset_id),
+		ExecuteMsg::RegisterAssetMapping { asset_id, network_id, remote_asset_id } =>
+			handle_register_asset_mapping(deps, asset_id, network_id, remote_asset_id),
+		ExecuteMsg::UnregisterAssetMapping { asset_id, network_id } =>
+			handle_unregister_asset_mapping(deps, asset_id, network_id),
+		ExecuteMsg::SetAdmin { admin } => {
+			let admin = deps.api.addr_validate(&admin)?;
+			ADMIN.save(deps.storage, &admin)?;
+			Ok(Response::new()
+				.add_event(Event::new("xcvm.registry.admin_updated").add_attribute("admin", admin)))
+		},
 	}
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
 	let _ = ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+	migrate_legacy_assets(deps.branch())?;
+	match msg.admin {
+		Some(admin) => ADMIN.save(deps.storage, &deps.api.addr_validate(&admin)?)?,
+		// A registry instantiated before it was owned must be given an admin.
+		None if ADMIN.may_load(deps.storage)?.is_none() => return Err(ContractError::MissingAdmin),
+		None => {},
+	}
 	Ok(Response::default())
 }
 
+/// Rewrite the cw20 contract addresses registered before native denoms were supported as
+/// asset references.
+fn migrate_legacy_assets(deps: DepsMut) -> StdResult<()> {
+	for asset_id in LEGACY_ASSETS
+		.keys(deps.storage, None, None, Order::Ascending)
+		.collect::<StdResult<Vec<_>>>()?
+	{
+		if ASSETS.load(deps.storage, asset_id).is_err() {
+			let cw20_address = LEGACY_ASSETS.load(deps.storage, asset_id)?;
+			ASSETS.save(deps.storage, asset_id, &AssetReference::Virtual { cw20_address })?;
+		}
+	}
+	Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 	match msg {
 		QueryMsg::GetAssetContract(token_id) => to_binary(&query_asset_contract(deps, token_id)?),
+		QueryMsg::ListAssets { start_after, limit } =>
+			to_binary(&query_list_assets(deps, start_after, limit)?),
+		QueryMsg::GetRemoteAsset { asset_id, network_id } => to_binary(&GetAssetMappingResponse {
+			asset_id: REMOTE_ASSETS.may_load(deps.storage, (asset_id, network_id.0))?,
+		}),
+		QueryMsg::GetLocalAsset { network_id, remote_asset_id } =>
+			to_binary(&GetAssetMappingResponse {
+				asset_id: LOCAL_ASSETS.may_load(deps.storage, (network_id.0, remote_asset_id))?,
+			}),
+		QueryMsg::GetAdmin {} => to_binary(&GetAdminResponse { admin: ADMIN.load(deps.storage)? }),
 	}
 }
 
 pub fn handle_set_assets(
-	deps: DepsMut,
-	assets: BTreeMap<String, String>,
+	mut deps: DepsMut,
+	assets: BTreeMap<String, AssetReference>,
 ) -> Result<Response, ContractError> {
 	// Remove all keys
 	for key in ASSETS
-		.keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+		.keys(deps.storage, None, None, Order::Ascending)
 		.collect::<Result<Vec<_>, _>>()?
 	{
 		ASSETS.remove(deps.storage, key);
 	}
 
-	for (asset_id, contract_addr) in assets {
-		let addr = deps.api.addr_validate(&contract_addr)?;
-		ASSETS.save(deps.storage, asset_id.parse::<XcvmAssetId>().unwrap(), &addr)?;
+	for (asset_id, reference) in assets {
+		let asset_id =
+			asset_id.parse::<XcvmAssetId>().map_err(|_| ContractError::InvalidAssetId)?;
+		save_asset(deps.branch(), asset_id, &reference)?;
 	}
 
 	Ok(Response::new().add_event(Event::new("xcvm.registry.updated")))
 }
 
+pub fn handle_register_asset(
+	deps: DepsMut,
+	asset_id: XcvmAssetId,
+	reference: AssetReference,
+) -> Result<Response, ContractError> {
+	save_asset(deps, asset_id, &reference)?;
+	Ok(Response::new().add_event(
+		Event::new("xcvm.registry.asset_registered")
+			.add_attribute("asset_id", asset_id.to_string()),
+	))
+}
+
+/// Remove `asset_id` along with its mappings to the other networks.
+pub fn handle_unregister_asset(
+	deps: DepsMut,
+	asset_id: XcvmAssetId,
+) -> Result<Response, ContractError> {
+	ASSETS.remove(deps.storage, asset_id);
+	for (network_id, remote_asset_id) in REMOTE_ASSETS
+		.prefix(asset_id)
+		.range(deps.storage, None, None, Order::Ascending)
+		.collect::<StdResult<Vec<_>>>()?
+	{
+		REMOTE_ASSETS.remove(deps.storage, (asset_id, network_id));
+		LOCAL_ASSETS.remove(deps.storage, (network_id, remote_asset_id));
+	}
+	Ok(Response::new().add_event(
+		Event::new("xcvm.registry.asset_unregistered")
+			.add_attribute("asset_id", asset_id.to_string()),
+	))
+}
+
+fn save_asset(
+	deps: DepsMut,
+	asset_id: XcvmAssetId,
+	reference: &AssetReference,
+) -> Result<(), ContractError> {
+	if let AssetReference::Virtual { cw20_address } = reference {
+		deps.api.addr_validate(cw20_address.as_str())?;
+	}
+	ASSETS.save(deps.storage, asset_id, reference)?;
+	Ok(())
+}
+
+pub fn handle_register_asset_mapping(
+	deps: DepsMut,
+	asset_id: XcvmAssetId,
+	network_id: NetworkId,
+	remote_asset_id: XcvmAssetId,
+) -> Result<Response, ContractError> {
+	// The mapping must be one to one for packets to be translated back and forth.
+	match LOCAL_ASSETS.may_load(deps.storage, (network_id.0, remote_asset_id))? {
+		Some(local_asset_id) if local_asset_id != asset_id =>
+			return Err(ContractError::AlreadyMapped),
+		_ => {},
+	}
+	if let Some(previous) = REMOTE_ASSETS.may_load(deps.storage, (asset_id, network_id.0))? {
+		LOCAL_ASSETS.remove(deps.storage, (network_id.0, previous));
+	}
+	REMOTE_ASSETS.save(deps.storage, (asset_id, network_id.0), &remote_asset_id)?;
+	LOCAL_ASSETS.save(deps.storage, (network_id.0, remote_asset_id), &asset_id)?;
+	Ok(Response::new().add_event(
+		Event::new("xcvm.registry.mapping_registered")
+			.add_attribute("asset_id", asset_id.to_string())
+			.add_attribute("network_id", network_id.0.to_string())
+			.add_attribute("remote_asset_id", remote_asset_id.to_string()),
+	))
+}
+
+pub fn handle_unregister_asset_mapping(
+	deps: DepsMut,
+	asset_id: XcvmAssetId,
+	network_id: NetworkId,
+) -> Result<Response, ContractError> {
+	if let Some(remote_asset_id) = REMOTE_ASSETS.may_load(deps.storage, (asset_id, network_id.0))? {
+		REMOTE_ASSETS.remove(deps.storage, (asset_id, network_id.0));
+		LOCAL_ASSETS.remove(deps.storage, (network_id.0, remote_asset_id));
+	}
+	Ok(Response::new().add_event(
+		Event::new("xcvm.registry.mapping_unregistered")
+			.add_attribute("asset_id", asset_id.to_string())
+			.add_attribute("network_id", network_id.0.to_string()),
+	))
+}
+
 pub fn query_asset_contract(
 	deps: Deps,
 	token_id: XcvmAssetId,
 ) -> StdResult<GetAssetContractResponse> {
-	let contract_addr = ASSETS.load(deps.storage, token_id)?;
-	Ok(GetAssetContractResponse { addr: contract_addr })
+	let reference = ASSETS.load(deps.storage, token_id)?;
+	Ok(GetAssetContractResponse { reference })
+}
+
+pub fn query_list_assets(
+	deps: Deps,
+	start_after: Option<XcvmAssetId>,
+	limit: Option<u32>,
+) -> StdResult<ListAssetsResponse> {
+	let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+	let start = start_after.map(Bound::exclusive);
+	let assets = ASSETS
+		.range(deps.storage, start, None, Order::Ascending)
+		.take(limit)
+		.collect::<StdResult<Vec<_>>>()?;
+	Ok(ListAssetsResponse { assets })
 }
 
 #[cfg(test)]
@@ -85,9 +241,13 @@ mod tests {
 	use cosmwasm_std::{
 		from_binary,
 		testing::{mock_dependencies, mock_env, mock_info},
-		Addr, Order,
+		Addr,
 	};
 
+	fn cw20(addr: &str) -> AssetReference {
+		AssetReference::Virtual { cw20_address: Addr::unchecked(addr) }
+	}
+
 	#[test]
 	fn proper_instantiation() {
 		let mut deps = mock_dependencies();
@@ -109,20 +269,23 @@ mod tests {
 		let _ = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
 		let mut assets = BTreeMap::new();
-		assets.insert("1".into(), "addr1".into());
-		assets.insert("2".into(), "addr2".into());
+		assets.insert("1".into(), cw20("addr1"));
+		assets.insert("2".into(), AssetReference::Native { denom: "upica".into() });
 
 		let res =
 			execute(deps.as_mut(), mock_env(), info.clone(), ExecuteMsg::SetAssets(assets.clone()))
 				.unwrap();
 		assert_eq!(res.attributes.len(), 0);
 
-		assert_eq!(ASSETS.load(&deps.storage, 1).unwrap(), Addr::unchecked("addr1"));
-		assert_eq!(ASSETS.load(&deps.storage, 2).unwrap(), Addr::unchecked("addr2"));
+		assert_eq!(ASSETS.load(&deps.storage, 1).unwrap(), cw20("addr1"));
+		assert_eq!(
+			ASSETS.load(&deps.storage, 2).unwrap(),
+			AssetReference::Native { denom: "upica".into() }
+		);
 
 		let mut assets = BTreeMap::new();
-		assets.insert("3".into(), "addr3".into());
-		assets.insert("4".into(), "addr4".into());
+		assets.insert("3".into(), cw20("addr3"));
+		assets.insert("4".into(), cw20("addr4"));
 
 		let _ = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::SetAssets(assets.clone()))
 			.unwrap();
@@ -130,8 +293,8 @@ mod tests {
 		// Make sure that set removes the previous elements
 		assert!(ASSETS.load(&deps.storage, 1).is_err());
 		assert!(ASSETS.load(&deps.storage, 2).is_err());
-		assert_eq!(ASSETS.load(&deps.storage, 3).unwrap(), Addr::unchecked("addr3"));
-		assert_eq!(ASSETS.load(&deps.storage, 4).unwrap(), Addr::unchecked("addr4"));
+		assert_eq!(ASSETS.load(&deps.storage, 3).unwrap(), cw20("addr3"));
+		assert_eq!(ASSETS.load(&deps.storage, 4).unwrap(), cw20("addr4"));
 
 		// Finally make sure that there are two elements in the assets storage
 		assert_eq!(
@@ -153,7 +316,7 @@ mod tests {
 		let _ = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
 		let mut assets = BTreeMap::new();
-		assets.insert("1".into(), "addr1".into());
+		assets.insert("1".into(), cw20("addr1"));
 
 		let _ =
 			execute(deps.as_mut(), mock_env(), info.clone(), ExecuteMsg::SetAssets(assets.clone()))
@@ -164,9 +327,232 @@ mod tests {
 				.unwrap();
 
 		// Query should return the corresponding address
-		assert_eq!(res, GetAssetContractResponse { addr: Addr::unchecked("addr1") });
+		assert_eq!(res, GetAssetContractResponse { reference: cw20("addr1") });
 
 		// This should fail since there the asset doesn't exist
 		assert!(query(deps.as_ref(), mock_env(), QueryMsg::GetAssetContract(2)).is_err());
 	}
+
+	#[test]
+	fn register_assets() {
+		let mut deps = mock_dependencies();
+		let info = mock_info("admin", &vec![]);
+		instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+
+		// Only the admin can update the registry
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info("sender", &vec![]),
+				ExecuteMsg::RegisterAsset { asset_id: 1, reference: cw20("addr1") },
+			),
+			Err(ContractError::NotAuthorized)
+		));
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				info.clone(),
+				ExecuteMsg::SetAssets(BTreeMap::from([("pica".into(), cw20("addr1"))])),
+			),
+			Err(ContractError::InvalidAssetId)
+		));
+
+		for asset_id in [3, 1, 2] {
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				info.clone(),
+				ExecuteMsg::RegisterAsset {
+					asset_id,
+					reference: cw20(&format!("addr{}", asset_id)),
+				},
+			)
+			.unwrap();
+		}
+		execute(deps.as_mut(), mock_env(), info, ExecuteMsg::UnregisterAsset { asset_id: 2 })
+			.unwrap();
+
+		let res: ListAssetsResponse = from_binary(
+			&query(
+				deps.as_ref(),
+				mock_env(),
+				QueryMsg::ListAssets { start_after: None, limit: None },
+			)
+			.unwrap(),
+		)
+		.unwrap();
+		assert_eq!(res.assets, vec![(1, cw20("addr1")), (3, cw20("addr3"))]);
+
+		let res: ListAssetsResponse = from_binary(
+			&query(
+				deps.as_ref(),
+				mock_env(),
+				QueryMsg::ListAssets { start_after: Some(1), limit: Some(1) },
+			)
+			.unwrap(),
+		)
+		.unwrap();
+		assert_eq!(res.assets, vec![(3, cw20("addr3"))]);
+
+		// The ownership can be transferred
+		execute(
+			deps.as_mut(),
+			mock_env(),
+			mock_info("admin", &vec![]),
+			ExecuteMsg::SetAdmin { admin: "new_admin".into() },
+		)
+		.unwrap();
+		let res: GetAdminResponse =
+			from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetAdmin {}).unwrap()).unwrap();
+		assert_eq!(res.admin, Addr::unchecked("new_admin"));
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info("admin", &vec![]),
+				ExecuteMsg::UnregisterAsset { asset_id: 1 },
+			),
+			Err(ContractError::NotAuthorized)
+		));
+	}
+
+	#[test]
+	fn migrate_cw20_assets() {
+		let mut deps = mock_dependencies();
+		set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.1.0").unwrap();
+		LEGACY_ASSETS.save(deps.as_mut().storage, 1, &Addr::unchecked("addr1")).unwrap();
+		ASSETS
+			.save(deps.as_mut().storage, 2, &AssetReference::Native { denom: "upica".into() })
+			.unwrap();
+
+		migrate(deps.as_mut(), mock_env(), MigrateMsg { admin: Some("admin".into()) }).unwrap();
+
+		let res: ListAssetsResponse = from_binary(
+			&query(
+				deps.as_ref(),
+				mock_env(),
+				QueryMsg::ListAssets { start_after: None, limit: None },
+			)
+			.unwrap(),
+		)
+		.unwrap();
+		assert_eq!(
+			res.assets,
+			vec![(1, cw20("addr1")), (2, AssetReference::Native { denom: "upica".into() })]
+		);
+	}
+
+	#[test]
+	fn migrate_requires_admin() {
+		let mut deps = mock_dependencies();
+		set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.1.0").unwrap();
+
+		assert!(matches!(
+			migrate(deps.as_mut(), mock_env(), MigrateMsg { admin: None }),
+			Err(ContractError::MissingAdmin)
+		));
+		migrate(deps.as_mut(), mock_env(), MigrateMsg { admin: Some("admin".into()) }).unwrap();
+		// The admin is kept once set
+		migrate(deps.as_mut(), mock_env(), MigrateMsg { admin: None }).unwrap();
+		assert_eq!(ADMIN.load(&deps.storage).unwrap(), Addr::unchecked("admin"));
+	}
+
+	#[test]
+	fn asset_mappings() {
+		let mut deps = mock_dependencies();
+		let info = mock_info("admin", &vec![]);
+		instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+
+		let remote_asset = |deps: Deps, asset_id, network_id| {
+			from_binary::<GetAssetMappingResponse>(
+				&query(deps, mock_env(), QueryMsg::GetRemoteAsset { asset_id, network_id })
+					.unwrap(),
+			)
+			.unwrap()
+			.asset_id
+		};
+		let local_asset = |deps: Deps, network_id, remote_asset_id| {
+			from_binary::<GetAssetMappingResponse>(
+				&query(deps, mock_env(), QueryMsg::GetLocalAsset { network_id, remote_asset_id })
+					.unwrap(),
+			)
+			.unwrap()
+			.asset_id
+		};
+
+		execute(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			ExecuteMsg::RegisterAssetMapping {
+				asset_id: 1,
+				network_id: NetworkId(2),
+				remote_asset_id: 10,
+			},
+		)
+		.unwrap();
+		assert_eq!(remote_asset(deps.as_ref(), 1, NetworkId(2)), Some(10));
+		assert_eq!(local_asset(deps.as_ref(), NetworkId(2), 10), Some(1));
+		assert_eq!(remote_asset(deps.as_ref(), 1, NetworkId(3)), None);
+
+		// A remote asset can only be mapped to a single local asset
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				info.clone(),
+				ExecuteMsg::RegisterAssetMapping {
+					asset_id: 2,
+					network_id: NetworkId(2),
+					remote_asset_id: 10,
+				},
+			),
+			Err(ContractError::AlreadyMapped)
+		));
+
+		// Updating a mapping removes the previous one
+		execute(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			ExecuteMsg::RegisterAssetMapping {
+				asset_id: 1,
+				network_id: NetworkId(2),
+				remote_asset_id: 11,
+			},
+		)
+		.unwrap();
+		assert_eq!(remote_asset(deps.as_ref(), 1, NetworkId(2)), Some(11));
+		assert_eq!(local_asset(deps.as_ref(), NetworkId(2), 10), None);
+		assert_eq!(local_asset(deps.as_ref(), NetworkId(2), 11), Some(1));
+
+		execute(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			ExecuteMsg::UnregisterAssetMapping { asset_id: 1, network_id: NetworkId(2) },
+		)
+		.unwrap();
+		assert_eq!(remote_asset(deps.as_ref(), 1, NetworkId(2)), None);
+		assert_eq!(local_asset(deps.as_ref(), NetworkId(2), 11), None);
+
+		// Unregistering an asset removes its mappings
+		for (network_id, remote_asset_id) in [(NetworkId(2), 10), (NetworkId(3), 20)] {
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				info.clone(),
+				ExecuteMsg::RegisterAssetMapping { asset_id: 1, network_id, remote_asset_id },
+			)
+			.unwrap();
+		}
+		execute(deps.as_mut(), mock_env(), info, ExecuteMsg::UnregisterAsset { asset_id: 1 })
+			.unwrap();
+		assert_eq!(remote_asset(deps.as_ref(), 1, NetworkId(2)), None);
+		assert_eq!(remote_asset(deps.as_ref(), 1, NetworkId(3)), None);
+		assert_eq!(local_asset(deps.as_ref(), NetworkId(2), 10), None);
+		assert_eq!(local_asset(deps.as_ref(), NetworkId(3), 20), None);
+	}
 }
@@ -5,4 +5,16 @@ use thiserror::Error;
 pub enum ContractError {
 	#[error("{0}")]
 	Std(#[from] StdError),
+
+	#[error("Caller is not authorized to take this action")]
+	NotAuthorized,
+
+	#[error("Asset ID is not a valid u128 integer")]
+	InvalidAssetId,
+
+	#[error("An admin must be given to migrate a registry without admin")]
+	MissingAdmin,
+
+	#[error("Asset is already mapped on this network")]
+	AlreadyMapped,
 }
@@ -1,9 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{to_binary, Addr, CosmosMsg, StdResult, WasmMsg};
+use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, QuerierWrapper, StdResult, WasmMsg};
+use cw20::{BalanceResponse, Cw20Contract, Cw20ExecuteMsg, Cw20QueryMsg};
 
-use crate::msg::ExecuteMsg;
+use crate::msg::{AssetReference, ExecuteMsg};
 
 /// AssetRegistryContract is a wrapper around Addr that provides helpers
 /// for working with this as a library.
@@ -20,3 +21,50 @@ impl AssetRegistryContract {
 		Ok(WasmMsg::Execute { contract_addr: self.addr().into(), msg, funds: vec![] }.into())
 	}
 }
+
+impl AssetReference {
+	/// Message transferring `amount` of the asset from the sender to `recipient`.
+	pub fn transfer_msg(&self, recipient: impl Into<String>, amount: u128) -> StdResult<CosmosMsg> {
+		match self {
+			AssetReference::Native { denom } => Ok(BankMsg::Send {
+				to_address: recipient.into(),
+				amount: vec![Coin::new(amount, denom)],
+			}
+			.into()),
+			AssetReference::Virtual { cw20_address } =>
+				Cw20Contract(cw20_address.clone()).call(Cw20ExecuteMsg::Transfer {
+					recipient: recipient.into(),
+					amount: amount.into(),
+				}),
+		}
+	}
+
+	/// Message burning `amount` of the asset from the sender.
+	pub fn burn_msg(&self, amount: u128) -> StdResult<CosmosMsg> {
+		match self {
+			AssetReference::Native { denom } =>
+				Ok(BankMsg::Burn { amount: vec![Coin::new(amount, denom)] }.into()),
+			AssetReference::Virtual { cw20_address } => Cw20Contract(cw20_address.clone())
+				.call(Cw20ExecuteMsg::Burn { amount: amount.into() }),
+		}
+	}
+
+	/// Balance of the asset held by `address`.
+	pub fn query_balance(
+		&self,
+		querier: &QuerierWrapper,
+		address: impl Into<String>,
+	) -> StdResult<u128> {
+		match self {
+			AssetReference::Native { denom } =>
+				Ok(querier.query_balance(address, denom)?.amount.into()),
+			AssetReference::Virtual { cw20_address } => {
+				let response: BalanceResponse = querier.query_wasm_smart(
+					cw20_address,
+					&Cw20QueryMsg::Balance { address: address.into() },
+				)?;
+				Ok(response.balance.into())
+			},
+		}
+	}
+}
@@ -5,26 +5,97 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::state::XcvmAssetId;
+use xcvm_core::NetworkId;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {}
 
+/// Local representation of an XCVM asset.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetReference {
+	/// Native denom of the bank module.
+	Native { denom: String },
+	/// CW20 token contract.
+	Virtual { cw20_address: Addr },
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-	SetAssets(BTreeMap<String, String>),
+	/// Replace all the registered assets.
+	SetAssets(BTreeMap<String, AssetReference>),
+	/// Register an asset or update its reference.
+	RegisterAsset {
+		asset_id: XcvmAssetId,
+		reference: AssetReference,
+	},
+	UnregisterAsset {
+		asset_id: XcvmAssetId,
+	},
+	/// Register that the local `asset_id` is `remote_asset_id` on `network_id`.
+	RegisterAssetMapping {
+		asset_id: XcvmAssetId,
+		network_id: NetworkId,
+		remote_asset_id: XcvmAssetId,
+	},
+	UnregisterAssetMapping {
+		asset_id: XcvmAssetId,
+		network_id: NetworkId,
+	},
+	/// Transfer the ownership of the registry.
+	SetAdmin {
+		admin: String,
+	},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+	/// Admin of a registry instantiated before the registry was owned, required for such
+	/// registries. Replaces the current admin otherwise.
+	pub admin: Option<String>,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
 	GetAssetContract(XcvmAssetId),
+	/// List the registered assets in ascending order of their ID.
+	ListAssets {
+		start_after: Option<XcvmAssetId>,
+		limit: Option<u32>,
+	},
+	/// ID on `network_id` of the local `asset_id`.
+	GetRemoteAsset {
+		asset_id: XcvmAssetId,
+		network_id: NetworkId,
+	},
+	/// Local ID of `remote_asset_id` from `network_id`.
+	GetLocalAsset {
+		network_id: NetworkId,
+		remote_asset_id: XcvmAssetId,
+	},
+	GetAdmin {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct GetAssetContractResponse {
-	pub addr: Addr,
+	pub reference: AssetReference,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListAssetsResponse {
+	pub assets: Vec<(XcvmAssetId, AssetReference)>,
+}
+
+/// Mapped asset ID, `None` if the asset isn't mapped in which case it has the same ID on both
+/// networks.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetAssetMappingResponse {
+	pub asset_id: Option<XcvmAssetId>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetAdminResponse {
+	pub admin: Addr,
 }
@@ -1,6 +1,19 @@
+use crate::msg::AssetReference;
 use cosmwasm_std::Addr;
-use cw_storage_plus::Map;
+use cw_storage_plus::{Item, Map};
 
 pub type XcvmAssetId = u128;
 
-pub const ASSETS: Map<XcvmAssetId, Addr> = Map::new("assets");
+pub const ADMIN: Item<Addr> = Item::new("admin");
+
+pub const ASSETS: Map<XcvmAssetId, AssetReference> = Map::new("assets");
+
+/// Assets registered before native denoms were supported, all of them being cw20 contracts.
+/// Rewritten to [`ASSETS`] on migration.
+pub const LEGACY_ASSETS: Map<XcvmAssetId, Addr> = Map::new("assets");
+
+/// Local asset and network to the asset ID on that network.
+pub const REMOTE_ASSETS: Map<(XcvmAssetId, u32), XcvmAssetId> = Map::new("remote_assets");
+
+/// Network and asset ID on that network to the local asset.
+pub const LOCAL_ASSETS: Map<(u32, XcvmAssetId), XcvmAssetId> = Map::new("local_assets");
@@ -0,0 +1,932 @@
+use crate::{
+	error::ContractError,
+	msg::{
+		BridgeType, ConfigResponse, ExecuteMsg, GetBridgeTypeResponse, InstantiateMsg, MigrateMsg,
+		Packet, QueryMsg,
+	},
+	state::{
+		Config, BRIDGES, BRIDGE_TYPES, CONFIG, OUTGOING_PACKETS, PACKET_SEQUENCE, PENDING_PACKET,
+		ROUTER,
+	},
+};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+	to_binary, wasm_execute, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, Event,
+	MessageInfo, Reply, Response, StdError, StdResult, SubMsg, SubMsgResult, WasmMsg, WasmQuery,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20Contract, Cw20ExecuteMsg};
+use cw_utils::ensure_from_older_version;
+use xcvm_asset_registry::msg::{
+	AssetReference, GetAssetContractResponse, GetAssetMappingResponse,
+	QueryMsg as AssetRegistryQueryMsg,
+};
+use xcvm_core::{AssetId, Displayed, Funds, NetworkId};
+use xcvm_interpreter::msg::{
+	ConfigResponse as InterpreterConfigResponse, ExecuteMsg as InterpreterExecuteMsg,
+	QueryMsg as InterpreterQueryMsg, XCVMProgram,
+};
+use xcvm_network_registry::msg::{GetNetworkResponse, QueryMsg as NetworkRegistryQueryMsg};
+use xcvm_router::msg::{
+	ExecuteMsg as RouterExecuteMsg, GetInterpreterResponse, InstantiateMsg as RouterInstantiateMsg,
+	QueryMsg as RouterQueryMsg,
+};
+
+const CONTRACT_NAME: &str = "composable:xcvm-gateway";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const INSTANTIATE_REPLY_ID: u64 = 1;
+const DISPATCH_REPLY_ID: u64 = 2;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+	deps: DepsMut,
+	env: Env,
+	info: MessageInfo,
+	msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+	set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+	let registry_address = deps.api.addr_validate(&msg.registry_address)?;
+	let network_registry_address = deps.api.addr_validate(&msg.network_registry_address)?;
+	let admin = info.sender;
+	CONFIG.save(
+		deps.storage,
+		&Config {
+			admin: admin.clone(),
+			registry_address: registry_address.clone(),
+			network_registry_address,
+			network_id: msg.network_id,
+		},
+	)?;
+
+	let instantiate_msg: CosmosMsg = WasmMsg::Instantiate {
+		admin: Some(env.contract.address.clone().into_string()),
+		code_id: msg.router_code_id,
+		msg: to_binary(&RouterInstantiateMsg {
+			admin: admin.into_string(),
+			registry_address: registry_address.into_string(),
+			gateway_address: env.contract.address.into_string(),
+			interpreter_code_id: msg.interpreter_code_id,
+		})?,
+		funds: vec![],
+		label: format!("xcvm-router-{}", msg.network_id.0),
+	}
+	.into();
+
+	Ok(Response::new()
+		.add_submessage(SubMsg::reply_on_success(instantiate_msg, INSTANTIATE_REPLY_ID))
+		.add_event(Event::new("xcvm.gateway.instantiated")))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+	deps: DepsMut,
+	env: Env,
+	info: MessageInfo,
+	msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+	match msg {
+		ExecuteMsg::Spawn { network, salt, assets, program } =>
+			handle_spawn(deps, env, info, network, salt, assets, program),
+		ExecuteMsg::Receive { relayer, packet } => handle_receive(deps, env, info, relayer, packet),
+		ExecuteMsg::Refund { packet } => handle_refund(deps, info, packet),
+		ExecuteMsg::Dispatch { relayer, packet } =>
+			handle_dispatch(deps, env, info, relayer, packet),
+		ExecuteMsg::RegisterBridge { address, network_id } => {
+			ensure_admin(deps.as_ref(), &info)?;
+			let address = deps.api.addr_validate(&address)?;
+			BRIDGES.save(deps.storage, address, &network_id)?;
+			Ok(Response::new().add_event(Event::new("xcvm.gateway.bridge_registered")))
+		},
+		ExecuteMsg::UnregisterBridge { address } => {
+			ensure_admin(deps.as_ref(), &info)?;
+			BRIDGES.remove(deps.storage, deps.api.addr_validate(&address)?);
+			Ok(Response::new().add_event(Event::new("xcvm.gateway.bridge_unregistered")))
+		},
+		ExecuteMsg::SetBridgeType { asset_id, bridge_type } => {
+			ensure_admin(deps.as_ref(), &info)?;
+			BRIDGE_TYPES.save(deps.storage, asset_id.into(), &bridge_type)?;
+			Ok(Response::new().add_event(Event::new("xcvm.gateway.bridge_type_updated")))
+		},
+	}
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+	let _ = ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+	Ok(Response::default())
+}
+
+fn ensure_admin(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+	if CONFIG.load(deps.storage)?.admin != info.sender {
+		return Err(ContractError::NotAuthorized)
+	}
+	Ok(())
+}
+
+/// Network the calling bridge is connected to.
+fn ensure_bridge(deps: Deps, info: &MessageInfo) -> Result<NetworkId, ContractError> {
+	BRIDGES
+		.may_load(deps.storage, info.sender.clone())?
+		.ok_or(ContractError::NotAuthorized)
+}
+
+/// Collect the assets of an interpreter to escrow or burn them, then emit the packet to be
+/// relayed.
+pub fn handle_spawn(
+	deps: DepsMut,
+	env: Env,
+	info: MessageInfo,
+	network: NetworkId,
+	salt: Vec<u8>,
+	assets: Funds<Displayed<u128>>,
+	program: XCVMProgram,
+) -> Result<Response, ContractError> {
+	// Only the interpreters instantiated by our router can spawn programs on behalf of their
+	// owner.
+	let interpreter: InterpreterConfigResponse = deps
+		.querier
+		.query_wasm_smart(info.sender.clone(), &InterpreterQueryMsg::Config {})
+		.map_err(|_| ContractError::NotAuthorized)?;
+	let interpreter_address =
+		query_interpreter(deps.as_ref(), interpreter.network_id, interpreter.user_id.clone())
+			.map_err(|_| ContractError::NotAuthorized)?;
+	if interpreter_address != info.sender {
+		return Err(ContractError::NotAuthorized)
+	}
+
+	let network_registry_address = CONFIG.load(deps.storage)?.network_registry_address;
+	let _: GetNetworkResponse = deps
+		.querier
+		.query_wasm_smart(
+			network_registry_address,
+			&NetworkRegistryQueryMsg::GetNetwork { network_id: network },
+		)
+		.map_err(|_| ContractError::UnsupportedNetwork)?;
+
+	// The native assets must be attached to the message, the cw20 ones are pulled from the
+	// allowance of the interpreter. The amounts relayed are then the ones actually collected.
+	let mut response = Response::new();
+	let mut native_assets = 0;
+	for (asset_id, amount) in &assets.0 {
+		let reference = query_asset_reference(deps.as_ref(), *asset_id)?;
+		let owner = info.sender.clone().into_string();
+		let msg = match (bridge_type(deps.as_ref(), *asset_id)?, reference) {
+			(bridge_type, AssetReference::Native { denom }) => {
+				let coin = Coin::new(amount.0, denom);
+				if !info.funds.contains(&coin) {
+					return Err(ContractError::InvalidFunds)
+				}
+				native_assets += 1;
+				match bridge_type {
+					BridgeType::Escrow => None,
+					BridgeType::Burn => Some(BankMsg::Burn { amount: vec![coin] }.into()),
+				}
+			},
+			(BridgeType::Escrow, AssetReference::Virtual { cw20_address }) =>
+				Some(Cw20Contract(cw20_address).call(Cw20ExecuteMsg::TransferFrom {
+					owner,
+					recipient: env.contract.address.clone().into_string(),
+					amount: amount.0.into(),
+				})?),
+			(BridgeType::Burn, AssetReference::Virtual { cw20_address }) => Some(
+				Cw20Contract(cw20_address)
+					.call(Cw20ExecuteMsg::BurnFrom { owner, amount: amount.0.into() })?,
+			),
+		};
+		response = response.add_messages(msg);
+	}
+	if native_assets != info.funds.len() {
+		return Err(ContractError::InvalidFunds)
+	}
+
+	// The assets are relayed under their ID on the target network.
+	let assets = Funds(
+		assets
+			.0
+			.into_iter()
+			.map(|(asset_id, amount)| {
+				Ok((query_remote_asset(deps.as_ref(), asset_id, network)?, amount))
+			})
+			.collect::<StdResult<_>>()?,
+	);
+	let sequence = PACKET_SEQUENCE.may_load(deps.storage)?.unwrap_or_default();
+	PACKET_SEQUENCE.save(deps.storage, &(sequence + 1))?;
+	let packet = Packet {
+		origin_network_id: interpreter.network_id,
+		origin_user_id: interpreter.user_id,
+		sequence,
+		salt,
+		program,
+		assets,
+	};
+	// Kept until refunded, the packet is otherwise considered executed by the target network.
+	OUTGOING_PACKETS.save(deps.storage, (network.0, sequence), &packet)?;
+	Ok(response.add_event(packet_event("xcvm.gateway.packet", network, &packet)?))
+}
+
+/// Dispatch a received packet, a refund packet is emitted if its execution fails.
+pub fn handle_receive(
+	deps: DepsMut,
+	env: Env,
+	info: MessageInfo,
+	relayer: Addr,
+	packet: Packet,
+) -> Result<Response, ContractError> {
+	let network_id = ensure_bridge(deps.as_ref(), &info)?;
+	// A bridge only relays the programs of the users of its network.
+	if packet.origin_network_id != network_id {
+		return Err(ContractError::InvalidOriginNetwork)
+	}
+	PENDING_PACKET.save(deps.storage, &(network_id, packet.clone()))?;
+	// Funds sent along, such as the router execution fee, are forwarded down to the router.
+	let dispatch_msg =
+		wasm_execute(env.contract.address, &ExecuteMsg::Dispatch { relayer, packet }, info.funds)?;
+	Ok(Response::new().add_submessage(SubMsg::reply_always(dispatch_msg, DISPATCH_REPLY_ID)))
+}
+
+pub fn handle_dispatch(
+	deps: DepsMut,
+	env: Env,
+	info: MessageInfo,
+	relayer: Addr,
+	packet: Packet,
+) -> Result<Response, ContractError> {
+	if info.sender != env.contract.address {
+		return Err(ContractError::NotAuthorized)
+	}
+	let (network_id, _) = PENDING_PACKET.load(deps.storage)?;
+	if packet.origin_network_id != network_id {
+		return Err(ContractError::InvalidOriginNetwork)
+	}
+
+	let router_address = ROUTER.load(deps.storage)?;
+	let mut response = Response::new();
+	for (asset_id, amount) in &packet.assets.0 {
+		response = response.add_message(credit_msg(
+			deps.as_ref(),
+			*asset_id,
+			router_address.clone(),
+			amount.0,
+		)?);
+	}
+
+	Ok(response.add_message(wasm_execute(
+		router_address,
+		&RouterExecuteMsg::Run {
+			network_id: packet.origin_network_id,
+			user_id: packet.origin_user_id,
+			interpreter_execute_msg: InterpreterExecuteMsg::Execute {
+				relayer,
+				program: packet.program,
+			},
+			funds: packet.assets,
+		},
+		info.funds,
+	)?))
+}
+
+/// Give back the assets of a packet to the interpreter that spawned it.
+pub fn handle_refund(
+	deps: DepsMut,
+	info: MessageInfo,
+	packet: Packet,
+) -> Result<Response, ContractError> {
+	let network_id = ensure_bridge(deps.as_ref(), &info)?;
+	// Only the packets sent to the network of the bridge are refunded, as they were stored.
+	let key = (network_id.0, packet.sequence);
+	let packet = OUTGOING_PACKETS
+		.may_load(deps.storage, key)?
+		.ok_or(ContractError::UnknownPacket)?;
+	OUTGOING_PACKETS.remove(deps.storage, key);
+	let interpreter_address =
+		query_interpreter(deps.as_ref(), packet.origin_network_id, packet.origin_user_id)?;
+	let mut response = Response::new();
+	for (asset_id, amount) in packet.assets.0 {
+		// The assets were relayed under their ID on the network of the bridge.
+		response = response.add_message(credit_msg(
+			deps.as_ref(),
+			query_local_asset(deps.as_ref(), network_id, asset_id)?,
+			interpreter_address.clone(),
+			amount.0,
+		)?);
+	}
+	Ok(response.add_event(Event::new("xcvm.gateway.refunded")))
+}
+
+/// Message releasing or minting `amount` of `asset_id` to `recipient`.
+fn credit_msg(
+	deps: Deps,
+	asset_id: AssetId,
+	recipient: Addr,
+	amount: u128,
+) -> Result<CosmosMsg, ContractError> {
+	let reference = query_asset_reference(deps, asset_id)?;
+	match (bridge_type(deps, asset_id)?, reference) {
+		(BridgeType::Escrow, reference) => Ok(reference.transfer_msg(recipient, amount)?),
+		(BridgeType::Burn, AssetReference::Virtual { cw20_address }) =>
+			Ok(Cw20Contract(cw20_address).call(Cw20ExecuteMsg::Mint {
+				recipient: recipient.into_string(),
+				amount: amount.into(),
+			})?),
+		(BridgeType::Burn, AssetReference::Native { .. }) =>
+			Err(ContractError::CannotMintNativeAsset),
+	}
+}
+
+fn bridge_type(deps: Deps, asset_id: AssetId) -> Result<BridgeType, ContractError> {
+	BRIDGE_TYPES
+		.may_load(deps.storage, asset_id.into())?
+		.ok_or(ContractError::UnsupportedAsset)
+}
+
+fn query_asset_reference(deps: Deps, asset_id: AssetId) -> StdResult<AssetReference> {
+	let registry_address = CONFIG.load(deps.storage)?.registry_address.into_string();
+	let response: GetAssetContractResponse = deps.querier.query(
+		&WasmQuery::Smart {
+			contract_addr: registry_address,
+			msg: to_binary(&AssetRegistryQueryMsg::GetAssetContract(asset_id.into()))?,
+		}
+		.into(),
+	)?;
+	Ok(response.reference)
+}
+
+/// ID of the local `asset_id` on `network_id`, the same ID if the asset isn't mapped.
+fn query_remote_asset(deps: Deps, asset_id: AssetId, network_id: NetworkId) -> StdResult<AssetId> {
+	let registry_address = CONFIG.load(deps.storage)?.registry_address;
+	let response: GetAssetMappingResponse = deps.querier.query_wasm_smart(
+		registry_address,
+		&AssetRegistryQueryMsg::GetRemoteAsset { asset_id: asset_id.into(), network_id },
+	)?;
+	Ok(response.asset_id.map(AssetId).unwrap_or(asset_id))
+}
+
+/// Local ID of `remote_asset_id` from `network_id`, the same ID if the asset isn't mapped.
+fn query_local_asset(
+	deps: Deps,
+	network_id: NetworkId,
+	remote_asset_id: AssetId,
+) -> StdResult<AssetId> {
+	let registry_address = CONFIG.load(deps.storage)?.registry_address;
+	let response: GetAssetMappingResponse = deps.querier.query_wasm_smart(
+		registry_address,
+		&AssetRegistryQueryMsg::GetLocalAsset {
+			network_id,
+			remote_asset_id: remote_asset_id.into(),
+		},
+	)?;
+	Ok(response.asset_id.map(AssetId).unwrap_or(remote_asset_id))
+}
+
+fn query_interpreter(deps: Deps, network_id: NetworkId, user_id: Vec<u8>) -> StdResult<Addr> {
+	let response: GetInterpreterResponse = deps.querier.query_wasm_smart(
+		ROUTER.load(deps.storage)?,
+		&RouterQueryMsg::GetInterpreter { network_id, user_id },
+	)?;
+	Ok(response.addr)
+}
+
+fn packet_event(ty: &str, network: NetworkId, packet: &Packet) -> Result<Event, ContractError> {
+	Ok(Event::new(ty).add_attribute("network_id", network.0.to_string()).add_attribute(
+		"packet",
+		serde_json_wasm::to_string(packet).map_err(|_| ContractError::DataSerializationError)?,
+	))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+	match msg.id {
+		INSTANTIATE_REPLY_ID => handle_instantiate_reply(deps, msg),
+		DISPATCH_REPLY_ID => handle_dispatch_reply(deps, msg),
+		id => Err(StdError::generic_err(format!("Unknown reply id: {}", id)).into()),
+	}
+}
+
+fn handle_instantiate_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+	let response = msg.result.into_result().map_err(StdError::generic_err)?;
+	let router_address = {
+		let instantiate_event = response
+			.events
+			.iter()
+			.find(|event| event.ty == "instantiate")
+			.ok_or(StdError::not_found("instantiate event not found"))?;
+		deps.api.addr_validate(
+			&instantiate_event
+				.attributes
+				.iter()
+				.find(|attr| &attr.key == "_contract_address")
+				.ok_or(StdError::not_found("_contract_address attribute not found"))?
+				.value,
+		)?
+	};
+	ROUTER.save(deps.storage, &router_address)?;
+	Ok(Response::new())
+}
+
+/// Emit a refund packet back to the origin network if the dispatch failed. The dispatch is
+/// reverted, so the assets of the packet are neither released nor minted.
+fn handle_dispatch_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+	let (network_id, packet) = PENDING_PACKET.load(deps.storage)?;
+	PENDING_PACKET.remove(deps.storage);
+	match msg.result {
+		SubMsgResult::Ok(_) => Ok(Response::new()),
+		SubMsgResult::Err(error) => Ok(Response::new().add_event(
+			packet_event("xcvm.gateway.refund", network_id, &packet)?.add_attribute("error", error),
+		)),
+	}
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+	match msg {
+		QueryMsg::Config {} => to_binary(&query_config(deps)?),
+		QueryMsg::GetBridgeType { asset_id } => to_binary(&GetBridgeTypeResponse {
+			bridge_type: BRIDGE_TYPES.load(deps.storage, asset_id.into())?,
+		}),
+	}
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+	let Config { admin, registry_address, network_registry_address, network_id } =
+		CONFIG.load(deps.storage)?;
+	Ok(ConfigResponse {
+		admin,
+		registry_address,
+		network_registry_address,
+		router_address: ROUTER.may_load(deps.storage)?,
+		network_id,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cosmwasm_std::{
+		from_binary,
+		testing::{mock_dependencies, mock_env, mock_info, MockQuerier, MOCK_CONTRACT_ADDR},
+		ContractResult, QuerierResult, SubMsgResponse, SystemResult,
+	};
+	use xcvm_core::{Amount, CallEncoding, Ethereum, NetworkInfo, Picasso, ETH, PICA, USDC, USDT};
+	use xcvm_interpreter::msg::XCVMInstruction;
+
+	const ADMIN_ADDR: &str = "admin";
+	const BRIDGE_ADDR: &str = "bridge";
+	const CW20_ADDR: &str = "cw20addr";
+	const INTERPRETER_ADDR: &str = "interpreter";
+	const REGISTRY_ADDR: &str = "registryaddr";
+	const NETWORK_REGISTRY_ADDR: &str = "networkregistryaddr";
+	const ROUTER_ADDR: &str = "routeraddr";
+	const USDC_DENOM: &str = "uusdc";
+
+	fn wasm_querier(query: &WasmQuery) -> QuerierResult {
+		let response = match query {
+			WasmQuery::Smart { contract_addr, msg } if contract_addr.as_str() == REGISTRY_ADDR =>
+				match from_binary(msg).unwrap() {
+					AssetRegistryQueryMsg::GetAssetContract(asset_id)
+						if AssetId(asset_id) == USDC.into() =>
+						to_binary(&GetAssetContractResponse {
+							reference: AssetReference::Native { denom: USDC_DENOM.into() },
+						}),
+					AssetRegistryQueryMsg::GetAssetContract(_) =>
+						to_binary(&GetAssetContractResponse {
+							reference: AssetReference::Virtual {
+								cw20_address: Addr::unchecked(CW20_ADDR),
+							},
+						}),
+					// ETH is known as USDT on Picasso
+					AssetRegistryQueryMsg::GetRemoteAsset { asset_id, network_id } =>
+						to_binary(&GetAssetMappingResponse {
+							asset_id: (AssetId(asset_id) == ETH.into() &&
+								network_id == Picasso.into())
+							.then(|| AssetId::from(USDT).0),
+						}),
+					AssetRegistryQueryMsg::GetLocalAsset { network_id, remote_asset_id } =>
+						to_binary(&GetAssetMappingResponse {
+							asset_id: (AssetId(remote_asset_id) == USDT.into() &&
+								network_id == Picasso.into())
+							.then(|| AssetId::from(ETH).0),
+						}),
+					_ => panic!("Unhandled registry query"),
+				},
+			WasmQuery::Smart { contract_addr, msg }
+				if contract_addr.as_str() == NETWORK_REGISTRY_ADDR =>
+				match from_binary(msg).unwrap() {
+					NetworkRegistryQueryMsg::GetNetwork { network_id }
+						if network_id == Picasso.into() =>
+						to_binary(&GetNetworkResponse {
+							info: NetworkInfo {
+								name: "picasso".into(),
+								call_encoding: CallEncoding::Scale,
+								gateway_address: "gateway".into(),
+							},
+						}),
+					_ => return SystemResult::Ok(ContractResult::Err("network not found".into())),
+				},
+			WasmQuery::Smart { contract_addr, .. } if contract_addr.as_str() == ROUTER_ADDR =>
+				to_binary(&GetInterpreterResponse { addr: Addr::unchecked(INTERPRETER_ADDR) }),
+			WasmQuery::Smart { contract_addr, .. }
+				if contract_addr.as_str() == INTERPRETER_ADDR =>
+				to_binary(&InterpreterConfigResponse {
+					registry_address: Addr::unchecked(REGISTRY_ADDR),
+					gateway_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+					network_id: Picasso.into(),
+					user_id: vec![1],
+				}),
+			_ => panic!("Unhandled query"),
+		};
+		SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+	}
+
+	fn setup(deps: DepsMut) {
+		instantiate(
+			deps,
+			mock_env(),
+			mock_info(ADMIN_ADDR, &vec![]),
+			InstantiateMsg {
+				registry_address: REGISTRY_ADDR.into(),
+				network_registry_address: NETWORK_REGISTRY_ADDR.into(),
+				router_code_id: 1,
+				interpreter_code_id: 2,
+				network_id: Picasso.into(),
+			},
+		)
+		.unwrap();
+	}
+
+	fn setup_bridge(mut deps: DepsMut) {
+		ROUTER.save(deps.storage, &Addr::unchecked(ROUTER_ADDR)).unwrap();
+		let info = mock_info(ADMIN_ADDR, &vec![]);
+		for msg in [
+			ExecuteMsg::RegisterBridge { address: BRIDGE_ADDR.into(), network_id: Picasso.into() },
+			ExecuteMsg::SetBridgeType { asset_id: PICA.into(), bridge_type: BridgeType::Escrow },
+			ExecuteMsg::SetBridgeType { asset_id: ETH.into(), bridge_type: BridgeType::Burn },
+			ExecuteMsg::SetBridgeType { asset_id: USDC.into(), bridge_type: BridgeType::Escrow },
+		] {
+			execute(deps.branch(), mock_env(), info.clone(), msg).unwrap();
+		}
+	}
+
+	fn packet() -> Packet {
+		Packet {
+			origin_network_id: Picasso.into(),
+			origin_user_id: vec![1],
+			sequence: 0,
+			salt: vec![],
+			program: XCVMProgram {
+				tag: vec![],
+				instructions: vec![XCVMInstruction::Transfer {
+					to: "user".into(),
+					assets: Funds::from([(Into::<AssetId>::into(PICA), Amount::absolute(1))]),
+				}]
+				.into(),
+			},
+			assets: Funds::from([
+				(Into::<AssetId>::into(PICA), Displayed(1000_u128)),
+				(Into::<AssetId>::into(ETH), Displayed(2000_u128)),
+			]),
+		}
+	}
+
+	/// Packet with ETH mapped to USDT on Picasso.
+	fn mapped_packet() -> Packet {
+		Packet {
+			assets: Funds::from([
+				(Into::<AssetId>::into(PICA), Displayed(1000_u128)),
+				(Into::<AssetId>::into(USDT), Displayed(2000_u128)),
+			]),
+			..packet()
+		}
+	}
+
+	#[test]
+	fn proper_instantiation() {
+		let mut deps = mock_dependencies();
+
+		let res = instantiate(
+			deps.as_mut(),
+			mock_env(),
+			mock_info(ADMIN_ADDR, &vec![]),
+			InstantiateMsg {
+				registry_address: REGISTRY_ADDR.into(),
+				network_registry_address: NETWORK_REGISTRY_ADDR.into(),
+				router_code_id: 1,
+				interpreter_code_id: 2,
+				network_id: Picasso.into(),
+			},
+		)
+		.unwrap();
+
+		let instantiate_msg = WasmMsg::Instantiate {
+			admin: Some(MOCK_CONTRACT_ADDR.to_string()),
+			code_id: 1,
+			msg: to_binary(&RouterInstantiateMsg {
+				admin: ADMIN_ADDR.to_string(),
+				registry_address: REGISTRY_ADDR.to_string(),
+				gateway_address: MOCK_CONTRACT_ADDR.to_string(),
+				interpreter_code_id: 2,
+			})
+			.unwrap(),
+			funds: vec![],
+			label: "xcvm-router-1".to_string(),
+		};
+		assert_eq!(res.messages[0].msg, instantiate_msg.into());
+		assert_eq!(res.messages[0].id, INSTANTIATE_REPLY_ID);
+
+		reply(
+			deps.as_mut(),
+			mock_env(),
+			Reply {
+				id: INSTANTIATE_REPLY_ID,
+				result: SubMsgResult::Ok(SubMsgResponse {
+					events: vec![
+						Event::new("instantiate").add_attribute("_contract_address", ROUTER_ADDR)
+					],
+					data: None,
+				}),
+			},
+		)
+		.unwrap();
+		assert_eq!(
+			query_config(deps.as_ref()).unwrap(),
+			ConfigResponse {
+				admin: Addr::unchecked(ADMIN_ADDR),
+				registry_address: Addr::unchecked(REGISTRY_ADDR),
+				network_registry_address: Addr::unchecked(NETWORK_REGISTRY_ADDR),
+				router_address: Some(Addr::unchecked(ROUTER_ADDR)),
+				network_id: Picasso.into(),
+			}
+		);
+	}
+
+	#[test]
+	fn spawn() {
+		let mut deps = mock_dependencies();
+		let mut querier = MockQuerier::default();
+		querier.update_wasm(wasm_querier);
+		deps.querier = querier;
+		setup(deps.as_mut());
+		setup_bridge(deps.as_mut());
+
+		// Programs can only be spawned on registered networks
+		let Packet { salt, program, assets, .. } = packet();
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info(INTERPRETER_ADDR, &vec![]),
+				ExecuteMsg::Spawn { network: Ethereum.into(), salt, program, assets },
+			),
+			Err(ContractError::UnsupportedNetwork)
+		));
+
+		let Packet { salt, program, assets, .. } = packet();
+		let spawn_msg = ExecuteMsg::Spawn { network: Picasso.into(), salt, program, assets };
+
+		// Only the interpreter of the user can spawn its programs
+		assert!(matches!(
+			execute(deps.as_mut(), mock_env(), mock_info(ROUTER_ADDR, &vec![]), spawn_msg.clone()),
+			Err(ContractError::NotAuthorized)
+		));
+
+		let res =
+			execute(deps.as_mut(), mock_env(), mock_info(INTERPRETER_ADDR, &vec![]), spawn_msg)
+				.unwrap();
+
+		// The escrowed asset is collected by the gateway while the other one is burnt
+		let cw20_contract = Cw20Contract(Addr::unchecked(CW20_ADDR));
+		assert_eq!(
+			res.messages.iter().map(|msg| msg.msg.clone()).collect::<Vec<_>>(),
+			vec![
+				cw20_contract
+					.call(Cw20ExecuteMsg::TransferFrom {
+						owner: INTERPRETER_ADDR.into(),
+						recipient: MOCK_CONTRACT_ADDR.into(),
+						amount: 1000_u128.into(),
+					})
+					.unwrap(),
+				cw20_contract
+					.call(Cw20ExecuteMsg::BurnFrom {
+						owner: INTERPRETER_ADDR.into(),
+						amount: 2000_u128.into(),
+					})
+					.unwrap(),
+			]
+		);
+		// The mapped asset is relayed under its ID on the target network
+		assert_eq!(
+			res.events,
+			vec![packet_event("xcvm.gateway.packet", Picasso.into(), &mapped_packet()).unwrap()]
+		);
+		assert_eq!(
+			OUTGOING_PACKETS.load(&deps.storage, (NetworkId::from(Picasso).0, 0)).unwrap(),
+			mapped_packet()
+		);
+	}
+
+	#[test]
+	fn spawn_requires_the_native_assets() {
+		let mut deps = mock_dependencies();
+		let mut querier = MockQuerier::default();
+		querier.update_wasm(wasm_querier);
+		deps.querier = querier;
+		setup(deps.as_mut());
+		setup_bridge(deps.as_mut());
+
+		let Packet { salt, program, .. } = packet();
+		let spawn_msg = ExecuteMsg::Spawn {
+			network: Picasso.into(),
+			salt,
+			program,
+			assets: Funds::from([(Into::<AssetId>::into(USDC), Displayed(500_u128))]),
+		};
+		for funds in [
+			vec![],
+			vec![Coin::new(400, USDC_DENOM)],
+			vec![Coin::new(500, USDC_DENOM), Coin::new(1, "upica")],
+		] {
+			assert!(matches!(
+				execute(
+					deps.as_mut(),
+					mock_env(),
+					mock_info(INTERPRETER_ADDR, &funds),
+					spawn_msg.clone()
+				),
+				Err(ContractError::InvalidFunds)
+			));
+		}
+
+		// The attached asset is escrowed by the gateway
+		let res = execute(
+			deps.as_mut(),
+			mock_env(),
+			mock_info(INTERPRETER_ADDR, &[Coin::new(500, USDC_DENOM)]),
+			spawn_msg,
+		)
+		.unwrap();
+		assert!(res.messages.is_empty());
+	}
+
+	#[test]
+	fn receive_and_dispatch() {
+		let mut deps = mock_dependencies();
+		let mut querier = MockQuerier::default();
+		querier.update_wasm(wasm_querier);
+		deps.querier = querier;
+		setup(deps.as_mut());
+		setup_bridge(deps.as_mut());
+
+		let relayer = Addr::unchecked("relayer");
+		let receive_msg = ExecuteMsg::Receive { relayer: relayer.clone(), packet: packet() };
+		assert!(matches!(
+			execute(deps.as_mut(), mock_env(), mock_info("sender", &vec![]), receive_msg.clone()),
+			Err(ContractError::NotAuthorized)
+		));
+
+		// The bridge cannot relay the programs of another network
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info(BRIDGE_ADDR, &vec![]),
+				ExecuteMsg::Receive {
+					relayer: relayer.clone(),
+					packet: Packet { origin_network_id: Ethereum.into(), ..packet() },
+				},
+			),
+			Err(ContractError::InvalidOriginNetwork)
+		));
+
+		let res = execute(deps.as_mut(), mock_env(), mock_info(BRIDGE_ADDR, &vec![]), receive_msg)
+			.unwrap();
+		let dispatch_msg = ExecuteMsg::Dispatch { relayer: relayer.clone(), packet: packet() };
+		assert_eq!(
+			res.messages,
+			vec![SubMsg::reply_always(
+				wasm_execute(MOCK_CONTRACT_ADDR, &dispatch_msg, vec![]).unwrap(),
+				DISPATCH_REPLY_ID
+			)]
+		);
+
+		let res = execute(
+			deps.as_mut(),
+			mock_env(),
+			mock_info(MOCK_CONTRACT_ADDR, &vec![]),
+			dispatch_msg,
+		)
+		.unwrap();
+		let cw20_contract = Cw20Contract(Addr::unchecked(CW20_ADDR));
+		let Packet { program, assets, .. } = packet();
+		assert_eq!(
+			res.messages.iter().map(|msg| msg.msg.clone()).collect::<Vec<_>>(),
+			vec![
+				cw20_contract
+					.call(Cw20ExecuteMsg::Transfer {
+						recipient: ROUTER_ADDR.into(),
+						amount: 1000_u128.into(),
+					})
+					.unwrap(),
+				cw20_contract
+					.call(Cw20ExecuteMsg::Mint {
+						recipient: ROUTER_ADDR.into(),
+						amount: 2000_u128.into(),
+					})
+					.unwrap(),
+				wasm_execute(
+					ROUTER_ADDR,
+					&RouterExecuteMsg::Run {
+						network_id: Picasso.into(),
+						user_id: vec![1],
+						interpreter_execute_msg: InterpreterExecuteMsg::Execute {
+							relayer,
+							program
+						},
+						funds: assets,
+					},
+					vec![],
+				)
+				.unwrap()
+				.into(),
+			]
+		);
+	}
+
+	#[test]
+	fn refund() {
+		let mut deps = mock_dependencies();
+		let mut querier = MockQuerier::default();
+		querier.update_wasm(wasm_querier);
+		deps.querier = querier;
+		setup(deps.as_mut());
+		setup_bridge(deps.as_mut());
+
+		execute(
+			deps.as_mut(),
+			mock_env(),
+			mock_info(BRIDGE_ADDR, &vec![]),
+			ExecuteMsg::Receive { relayer: Addr::unchecked("relayer"), packet: packet() },
+		)
+		.unwrap();
+
+		// A failed dispatch emits a refund packet back to the network of the bridge
+		let res = reply(
+			deps.as_mut(),
+			mock_env(),
+			Reply { id: DISPATCH_REPLY_ID, result: SubMsgResult::Err("failed".into()) },
+		)
+		.unwrap();
+		assert_eq!(
+			res.events,
+			vec![packet_event("xcvm.gateway.refund", Picasso.into(), &packet())
+				.unwrap()
+				.add_attribute("error", "failed")]
+		);
+		assert!(PENDING_PACKET.may_load(&deps.storage).unwrap().is_none());
+
+		// Only the packets sent can be refunded
+		let refund_msg = ExecuteMsg::Refund { packet: mapped_packet() };
+		assert!(matches!(
+			execute(deps.as_mut(), mock_env(), mock_info(BRIDGE_ADDR, &vec![]), refund_msg.clone()),
+			Err(ContractError::UnknownPacket)
+		));
+
+		let Packet { salt, program, assets, .. } = packet();
+		execute(
+			deps.as_mut(),
+			mock_env(),
+			mock_info(INTERPRETER_ADDR, &vec![]),
+			ExecuteMsg::Spawn { network: Picasso.into(), salt, program, assets },
+		)
+		.unwrap();
+
+		// The refund packet gives back the assets to the interpreter that spawned them, under
+		// their local ID
+		let res =
+			execute(deps.as_mut(), mock_env(), mock_info(BRIDGE_ADDR, &vec![]), refund_msg.clone())
+				.unwrap();
+		let cw20_contract = Cw20Contract(Addr::unchecked(CW20_ADDR));
+		assert_eq!(
+			res.messages.iter().map(|msg| msg.msg.clone()).collect::<Vec<_>>(),
+			vec![
+				cw20_contract
+					.call(Cw20ExecuteMsg::Transfer {
+						recipient: INTERPRETER_ADDR.into(),
+						amount: 1000_u128.into(),
+					})
+					.unwrap(),
+				cw20_contract
+					.call(Cw20ExecuteMsg::Mint {
+						recipient: INTERPRETER_ADDR.into(),
+						amount: 2000_u128.into(),
+					})
+					.unwrap(),
+			]
+		);
+
+		// A packet is refunded once
+		assert!(matches!(
+			execute(deps.as_mut(), mock_env(), mock_info(BRIDGE_ADDR, &vec![]), refund_msg),
+			Err(ContractError::UnknownPacket)
+		));
+	}
+}
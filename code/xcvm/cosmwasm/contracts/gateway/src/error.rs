@@ -0,0 +1,32 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+	#[error("{0}")]
+	Std(#[from] StdError),
+
+	#[error("Caller is not authorized")]
+	NotAuthorized,
+
+	#[error("Asset has no bridge type")]
+	UnsupportedAsset,
+
+	#[error("Network is not registered")]
+	UnsupportedNetwork,
+
+	#[error("Native assets cannot be minted")]
+	CannotMintNativeAsset,
+
+	#[error("Data cannot be serialized")]
+	DataSerializationError,
+
+	#[error("Funds do not match the assets of the program")]
+	InvalidFunds,
+
+	#[error("Packet does not originate from the network of the bridge")]
+	InvalidOriginNetwork,
+
+	#[error("Packet is unknown or already refunded")]
+	UnknownPacket,
+}
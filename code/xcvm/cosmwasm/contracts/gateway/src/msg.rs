@@ -0,0 +1,106 @@
+use cosmwasm_std::Addr;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use xcvm_core::{AssetId, Displayed, Funds, NetworkId};
+use xcvm_interpreter::msg::{UserId, XCVMProgram};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+	pub registry_address: String,
+	pub network_registry_address: String,
+	pub router_code_id: u64,
+	pub interpreter_code_id: u64,
+	pub network_id: NetworkId,
+}
+
+/// How an asset leaves and enters the network.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeType {
+	/// The asset is native to the network. It is escrowed by the gateway when sent and released
+	/// when received.
+	Escrow,
+	/// The asset represents a remote asset. It is burnt when sent and minted by the gateway when
+	/// received.
+	Burn,
+}
+
+/// Program relayed between the gateways of two networks.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Packet {
+	/// Network of the user owning the interpreter that spawned the program.
+	pub origin_network_id: NetworkId,
+	/// User owning the interpreter that spawned the program.
+	pub origin_user_id: UserId,
+	/// Sequence of the packet on the gateway that sent it, identifying it for refunds.
+	pub sequence: u64,
+	pub salt: Vec<u8>,
+	pub program: XCVMProgram,
+	pub assets: Funds<Displayed<u128>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+	/// Relay a program to the target `network`. Only callable by the interpreters, with the
+	/// native `assets` attached and an allowance for the cw20 ones.
+	Spawn {
+		network: NetworkId,
+		salt: Vec<u8>,
+		assets: Funds<Displayed<u128>>,
+		program: XCVMProgram,
+	},
+	/// Execute a packet received by a bridge.
+	Receive {
+		relayer: Addr,
+		packet: Packet,
+	},
+	/// Give back the assets of a packet that failed on the target network. Only callable by the
+	/// bridge of that network, once per packet sent.
+	Refund {
+		packet: Packet,
+	},
+	/// Credit the assets of a packet to the router and run its program. Only callable by the
+	/// gateway itself.
+	Dispatch {
+		relayer: Addr,
+		packet: Packet,
+	},
+	/// Allow `address` to relay packets from and to `network_id`.
+	RegisterBridge {
+		address: String,
+		network_id: NetworkId,
+	},
+	UnregisterBridge {
+		address: String,
+	},
+	SetBridgeType {
+		asset_id: AssetId,
+		bridge_type: BridgeType,
+	},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+	Config {},
+	GetBridgeType { asset_id: AssetId },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+	pub admin: Addr,
+	pub registry_address: Addr,
+	pub network_registry_address: Addr,
+	/// `None` until the router is instantiated.
+	pub router_address: Option<Addr>,
+	pub network_id: NetworkId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetBridgeTypeResponse {
+	pub bridge_type: BridgeType,
+}
@@ -0,0 +1,29 @@
+use crate::msg::{BridgeType, Packet};
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use xcvm_core::NetworkId;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+	pub admin: Addr,
+	pub registry_address: Addr,
+	pub network_registry_address: Addr,
+	pub network_id: NetworkId,
+}
+
+pub type XcvmAssetId = u128;
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// Router instantiated by the gateway.
+pub const ROUTER: Item<Addr> = Item::new("router");
+/// Bridges allowed to relay packets, with the network they are connected to.
+pub const BRIDGES: Map<Addr, NetworkId> = Map::new("bridges");
+pub const BRIDGE_TYPES: Map<XcvmAssetId, BridgeType> = Map::new("bridge_types");
+/// Sequence of the next packet sent.
+pub const PACKET_SEQUENCE: Item<u64> = Item::new("packet_sequence");
+/// Packets sent and not refunded, by target network and sequence.
+pub const OUTGOING_PACKETS: Map<(u32, u64), Packet> = Map::new("outgoing_packets");
+/// Packet being dispatched along the network it has been received from.
+pub const PENDING_PACKET: Item<(NetworkId, Packet)> = Item::new("pending_packet");
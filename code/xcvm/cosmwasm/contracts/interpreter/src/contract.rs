@@ -1,25 +1,35 @@
 use crate::{
 	error::ContractError,
-	msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, XCVMInstruction, XCVMProgram},
-	state::{Config, CONFIG},
+	msg::{
+		ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ResultResponse,
+		XCVMInstruction, XCVMProgram,
+	},
+	state::{CallResult, Config, ProgramResult, CONFIG, RESULT_REGISTER},
 };
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-	to_binary, wasm_execute, Binary, CosmosMsg, Deps, DepsMut, Env, Event, MessageInfo,
-	QueryRequest, Response, StdError, StdResult, WasmQuery,
+	to_binary, wasm_execute, Addr, Binary, Coin, ContractInfoResponse, ContractResult, CosmosMsg,
+	Deps, DepsMut, Env, Event, MessageInfo, Reply, Response, StdError, StdResult, SubMsg,
+	SystemResult, WasmQuery,
 };
 use cw2::set_contract_version;
-use cw20::{BalanceResponse, Cw20Contract, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw20::{Cw20Contract, Cw20ExecuteMsg};
 use cw_utils::ensure_from_older_version;
 use num::Zero;
 use serde::Serialize;
 use std::collections::VecDeque;
-use xcvm_asset_registry::msg::{GetAssetContractResponse, QueryMsg as AssetRegistryQueryMsg};
-use xcvm_core::{Displayed, Funds, Instruction, NetworkId};
+use xcvm_asset_registry::msg::{
+	AssetReference, GetAssetContractResponse, QueryMsg as AssetRegistryQueryMsg,
+};
+use xcvm_core::{
+	expanded_len, AssetId, BindingValue, Bindings, Displayed, Funds, Instruction, NetworkId,
+	Predicate, MAX_EXPANDED_INSTRUCTIONS, MAX_REPEAT,
+};
 
 const CONTRACT_NAME: &str = "composable:xcvm-interpreter";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const CALL_REPLY_ID: u64 = 1;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -31,8 +41,13 @@ pub fn instantiate(
 	set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
 	let registry_address = deps.api.addr_validate(&msg.registry_address)?;
-	let config =
-		Config { registry_address, network_id: msg.network_id, user_id: msg.user_id.clone() };
+	let gateway_address = deps.api.addr_validate(&msg.gateway_address)?;
+	let config = Config {
+		registry_address,
+		gateway_address,
+		network_id: msg.network_id,
+		user_id: msg.user_id.clone(),
+	};
 	CONFIG.save(deps.storage, &config)?;
 
 	Ok(Response::new().add_event(
@@ -50,9 +65,32 @@ pub fn execute(
 	info: MessageInfo,
 	msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+	ensure_router(deps.as_ref(), &env, &info)?;
 	match msg {
-		ExecuteMsg::Execute { program } => interpret_program(deps, env, info, program),
+		ExecuteMsg::Execute { relayer, program } =>
+			interpret_program(deps, env, info, relayer, program),
+		ExecuteMsg::ExecuteBinary { relayer, program } => {
+			let program = XCVMProgram::decode_binary(program.as_slice())
+				.map_err(|_| ContractError::InvalidProgramEncoding)?;
+			interpret_program(deps, env, info, relayer, program)
+		},
+	}
+}
+
+/// Programs are only executed on behalf of the router, the admin of the interpreter, and
+/// continued by the interpreter itself.
+fn ensure_router(deps: Deps, env: &Env, info: &MessageInfo) -> Result<(), ContractError> {
+	if info.sender == env.contract.address {
+		return Ok(())
+	}
+	let contract_info: ContractInfoResponse = deps.querier.query(
+		&WasmQuery::ContractInfo { contract_addr: env.contract.address.clone().into_string() }
+			.into(),
+	)?;
+	if contract_info.admin.as_deref() != Some(info.sender.as_str()) {
+		return Err(ContractError::NotAuthorized)
 	}
+	Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -64,34 +102,72 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 pub fn interpret_program(
 	mut deps: DepsMut,
 	env: Env,
-	_info: MessageInfo,
+	info: MessageInfo,
+	relayer: Addr,
 	program: XCVMProgram,
 ) -> Result<Response, ContractError> {
+	// A program is continued by the interpreter itself after each `Call`.
+	let mut result = if info.sender == env.contract.address {
+		RESULT_REGISTER.load(deps.storage)?
+	} else {
+		ProgramResult::new(program.tag.clone())
+	};
+	if expanded_len(&program.instructions) > MAX_EXPANDED_INSTRUCTIONS {
+		return Err(ContractError::ProgramTooLarge)
+	}
+
 	let mut response = Response::new();
-	let instruction_len = program.instructions.len();
-	let mut instruction_iter = program.instructions.into_iter().enumerate();
-	while let Some((index, instruction)) = instruction_iter.next() {
+	let mut instructions = program.instructions;
+	while let Some(instruction) = instructions.pop_front() {
+		result.instruction_pointer += 1;
 		response = match instruction {
-			Instruction::Call { encoded } =>
-				if index >= instruction_len - 1 {
+			Instruction::Call { encoded, bindings } => {
+				let encoded =
+					apply_bindings(deps.as_ref(), &env, &relayer, &result, encoded, bindings)?;
+				if instructions.is_empty() {
 					interpret_call(encoded, response)?
 				} else {
 					let response = interpret_call(encoded, response)?;
-					let instructions: VecDeque<XCVMInstruction> =
-						instruction_iter.map(|(_, instr)| instr).collect();
+					RESULT_REGISTER.save(deps.storage, &result)?;
 					let program = XCVMProgram { tag: program.tag, instructions };
 					return Ok(response.add_message(wasm_execute(
 						env.contract.address,
-						&ExecuteMsg::Execute { program },
+						&ExecuteMsg::Execute { relayer, program },
 						vec![],
 					)?))
-				},
+				}
+			},
 			Instruction::Spawn { network, salt, assets, program } =>
 				interpret_spawn(&deps, &env, network, salt, assets, program, response)?,
 			Instruction::Transfer { to, assets } =>
 				interpret_transfer(&mut deps, &env, to, assets, response)?,
+			Instruction::Assert { predicate } =>
+				if interpret_predicate(deps.as_ref(), &env, &predicate)? {
+					response
+				} else {
+					return Err(ContractError::AssertionFailed)
+				},
+			Instruction::IfElse { predicate, then, otherwise } => {
+				let branch = if interpret_predicate(deps.as_ref(), &env, &predicate)? {
+					then
+				} else {
+					otherwise
+				};
+				prepend(&mut instructions, branch);
+				response
+			},
+			Instruction::Repeat { times, instructions: body } => {
+				if times > MAX_REPEAT {
+					return Err(ContractError::RepeatTooLarge)
+				}
+				for _ in 0..times {
+					prepend(&mut instructions, body.clone());
+				}
+				response
+			},
 		};
 	}
+	RESULT_REGISTER.save(deps.storage, &result)?;
 
 	Ok(response.add_event(Event::new("xcvm.interpreter.executed").add_attribute(
 		"program",
@@ -99,13 +175,104 @@ pub fn interpret_program(
 	)))
 }
 
+/// Schedule `prefix` to be executed before the remaining `instructions`.
+fn prepend(instructions: &mut VecDeque<XCVMInstruction>, prefix: VecDeque<XCVMInstruction>) {
+	for instruction in prefix.into_iter().rev() {
+		instructions.push_front(instruction);
+	}
+}
+
+pub fn interpret_predicate(
+	deps: Deps,
+	env: &Env,
+	predicate: &Predicate<Vec<u8>>,
+) -> Result<bool, ContractError> {
+	match predicate {
+		Predicate::BalanceAtLeast { asset, amount } =>
+			Ok(query_balance(deps, env, *asset)? >= amount.0),
+		Predicate::BalanceAtMost { asset, amount } =>
+			Ok(query_balance(deps, env, *asset)? <= amount.0),
+		Predicate::QueryEq { query, expected } => match deps.querier.raw_query(query) {
+			SystemResult::Ok(ContractResult::Ok(value)) =>
+				Ok(value.as_slice() == expected.as_slice()),
+			_ => Err(ContractError::PredicateQueryFailed),
+		},
+	}
+}
+
+/// Interpolate the `bindings` in the `payload` of a `Call`.
+pub fn apply_bindings(
+	deps: Deps,
+	env: &Env,
+	relayer: &Addr,
+	result: &ProgramResult,
+	payload: Vec<u8>,
+	bindings: Bindings,
+) -> Result<Vec<u8>, ContractError> {
+	if bindings.is_empty() {
+		return Ok(payload)
+	}
+	let mut bound = Vec::with_capacity(payload.len());
+	let mut position = 0;
+	for (offset, binding) in bindings {
+		let offset = usize::from(offset);
+		if offset < position || offset > payload.len() {
+			return Err(ContractError::InvalidBindings)
+		}
+		bound.extend_from_slice(&payload[position..offset]);
+		position = offset;
+		match binding {
+			BindingValue::Self_ => bound.extend_from_slice(env.contract.address.as_bytes()),
+			BindingValue::Relayer => bound.extend_from_slice(relayer.as_bytes()),
+			BindingValue::Result => bound.extend_from_slice(
+				result
+					.call_results
+					.last()
+					.and_then(|call_result| call_result.data.as_ref())
+					.ok_or(ContractError::MissingCallResult)?
+					.as_slice(),
+			),
+			BindingValue::Balance(asset_id) =>
+				bound.extend_from_slice(query_balance(deps, env, asset_id)?.to_string().as_bytes()),
+			BindingValue::AssetId(asset_id) => match query_asset_reference(deps, asset_id)? {
+				AssetReference::Native { denom } => bound.extend_from_slice(denom.as_bytes()),
+				AssetReference::Virtual { cw20_address } =>
+					bound.extend_from_slice(cw20_address.as_bytes()),
+			},
+		}
+	}
+	bound.extend_from_slice(&payload[position..]);
+	Ok(bound)
+}
+
+/// Local reference of `asset_id`, either a native denom or a cw20 contract.
+fn query_asset_reference(deps: Deps, asset_id: AssetId) -> Result<AssetReference, ContractError> {
+	let config = CONFIG.load(deps.storage)?;
+	let query_msg = AssetRegistryQueryMsg::GetAssetContract(asset_id.into());
+	let response: GetAssetContractResponse = deps.querier.query(
+		&WasmQuery::Smart {
+			contract_addr: config.registry_address.into_string(),
+			msg: to_binary(&query_msg)?,
+		}
+		.into(),
+	)?;
+	Ok(response.reference)
+}
+
+/// Balance of the interpreter of `asset_id`.
+fn query_balance(deps: Deps, env: &Env, asset_id: AssetId) -> Result<u128, ContractError> {
+	Ok(query_asset_reference(deps, asset_id)?
+		.query_balance(&deps.querier, env.contract.address.clone())?)
+}
+
 pub fn interpret_call(encoded: Vec<u8>, response: Response) -> Result<Response, ContractError> {
 	let cosmos_msg: CosmosMsg =
 		serde_json_wasm::from_slice(&encoded).map_err(|_| ContractError::InvalidCallPayload)?;
 
-	Ok(response.add_message(cosmos_msg))
+	Ok(response.add_submessage(SubMsg::reply_on_success(cosmos_msg, CALL_REPLY_ID)))
 }
 
+/// Send the `assets` to the gateway, which relays the `program` to the target `network`.
 pub fn interpret_spawn(
 	deps: &DepsMut,
 	env: &Env,
@@ -116,16 +283,21 @@ pub fn interpret_spawn(
 	mut response: Response,
 ) -> Result<Response, ContractError> {
 	#[derive(Serialize)]
-	struct SpawnEvent {
-		network: NetworkId,
-		salt: Vec<u8>,
-		assets: Funds<Displayed<u128>>,
-		program: XCVMProgram,
+	#[serde(rename_all = "snake_case")]
+	enum GatewayExecuteMsg {
+		Spawn {
+			network: NetworkId,
+			salt: Vec<u8>,
+			assets: Funds<Displayed<u128>>,
+			program: XCVMProgram,
+		},
 	}
 
 	let config = CONFIG.load(deps.storage)?;
-	let registry_addr = config.registry_address.into_string();
 	let mut normalized_funds = Funds::<Displayed<u128>>::empty();
+	// The gateway collects the assets itself: native denoms are attached to the message while
+	// the cw20 tokens are pulled through an allowance.
+	let mut native_funds = Vec::new();
 
 	for (asset_id, amount) in assets.0 {
 		if amount.is_zero() {
@@ -134,66 +306,33 @@ pub fn interpret_spawn(
 		}
 
 		let amount = if amount.slope.0 == 0 {
-			// No need to get balance from cw20 contract
+			// No need to get the balance of the asset
 			amount.intercept
 		} else {
-			let query_msg = AssetRegistryQueryMsg::GetAssetContract(asset_id.into());
-
-			let cw20_address: GetAssetContractResponse = deps.querier.query(
-				&WasmQuery::Smart {
-					contract_addr: registry_addr.clone(),
-					msg: to_binary(&query_msg)?,
-				}
-				.into(),
-			)?;
-			let response =
-				deps.querier.query::<BalanceResponse>(&QueryRequest::Wasm(WasmQuery::Smart {
-					contract_addr: cw20_address.addr.clone().into_string(),
-					msg: to_binary(&Cw20QueryMsg::Balance {
-						address: env.contract.address.clone().into_string(),
-					})?,
-				}))?;
-			amount.apply(response.balance.into()).into()
+			amount.apply(query_balance(deps.as_ref(), env, asset_id)?).into()
 		};
 
 		if amount.0 > 0 {
+			match query_asset_reference(deps.as_ref(), asset_id)? {
+				AssetReference::Native { denom } => native_funds.push(Coin::new(amount.0, denom)),
+				AssetReference::Virtual { cw20_address } =>
+					response = response.add_message(Cw20Contract(cw20_address).call(
+						Cw20ExecuteMsg::IncreaseAllowance {
+							spender: config.gateway_address.clone().into_string(),
+							amount: amount.0.into(),
+							expires: None,
+						},
+					)?),
+			}
 			normalized_funds.0.insert(asset_id, amount.into());
 		}
 	}
 
-	// TODO(probably call the router via a Cw20 `send` to spawn the program and do w/e required with
-	// the funds)
-	for (asset_id, amount) in normalized_funds.clone().0 {
-		let query_msg = AssetRegistryQueryMsg::GetAssetContract(asset_id.into());
-		let cw20_address: GetAssetContractResponse = deps.querier.query(
-			&WasmQuery::Smart { contract_addr: registry_addr.clone(), msg: to_binary(&query_msg)? }
-				.into(),
-		)?;
-		let contract = Cw20Contract(cw20_address.addr);
-		response =
-			response.add_message(contract.call(Cw20ExecuteMsg::Burn { amount: amount.0.into() })?);
-	}
-
-	let data = SpawnEvent { network, salt, assets: normalized_funds, program };
-
-	Ok(response.add_event(
-		Event::new("xcvm.interpreter.spawn")
-			.add_attribute(
-				"origin_network_id",
-				serde_json_wasm::to_string(&config.network_id.0)
-					.map_err(|_| ContractError::DataSerializationError)?,
-			)
-			.add_attribute(
-				"origin_user_id",
-				serde_json_wasm::to_string(&config.user_id)
-					.map_err(|_| ContractError::DataSerializationError)?,
-			)
-			.add_attribute(
-				"program",
-				serde_json_wasm::to_string(&data)
-					.map_err(|_| ContractError::DataSerializationError)?,
-			),
-	))
+	Ok(response.add_message(wasm_execute(
+		config.gateway_address,
+		&GatewayExecuteMsg::Spawn { network, salt, assets: normalized_funds, program },
+		native_funds,
+	)?))
 }
 
 pub fn interpret_transfer(
@@ -203,69 +342,87 @@ pub fn interpret_transfer(
 	assets: Funds,
 	mut response: Response,
 ) -> Result<Response, ContractError> {
-	let config = CONFIG.load(deps.storage)?;
-	let registry_addr = config.registry_address.into_string();
-
 	for (asset_id, amount) in assets.0 {
-		let query_msg = AssetRegistryQueryMsg::GetAssetContract(asset_id.into());
-
-		let cw20_address: GetAssetContractResponse = deps.querier.query(
-			&WasmQuery::Smart { contract_addr: registry_addr.clone(), msg: to_binary(&query_msg)? }
-				.into(),
-		)?;
-		let contract = Cw20Contract(cw20_address.addr.clone());
-
 		if amount.is_zero() {
 			continue
 		}
 
-		let transfer_amount = {
-			let response =
-				deps.querier.query::<BalanceResponse>(&QueryRequest::Wasm(WasmQuery::Smart {
-					contract_addr: cw20_address.addr.clone().into_string(),
-					msg: to_binary(&Cw20QueryMsg::Balance {
-						address: env.contract.address.clone().into_string(),
-					})?,
-				}))?;
-			amount.apply(response.balance.into())
-		};
-
-		response = response.add_message(contract.call(Cw20ExecuteMsg::Transfer {
-			recipient: to.clone(),
-			amount: transfer_amount.into(),
-		})?);
+		let reference = query_asset_reference(deps.as_ref(), asset_id)?;
+		let transfer_amount =
+			amount.apply(reference.query_balance(&deps.querier, env.contract.address.clone())?);
+		response = response.add_message(reference.transfer_msg(to.clone(), transfer_amount)?);
 	}
 
 	Ok(response)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(_deps: Deps, _env: Env, _msg: QueryMsg) -> StdResult<Binary> {
-	Err(StdError::generic_err("not implemented"))
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+	match msg.id {
+		CALL_REPLY_ID => handle_call_reply(deps, msg),
+		id => Err(StdError::generic_err(format!("Unknown reply id: {}", id))),
+	}
+}
+
+/// Store the data returned by a successful `Call`. A failing `Call` is not replied to and reverts
+/// the whole program.
+fn handle_call_reply(deps: DepsMut, msg: Reply) -> StdResult<Response> {
+	let response = msg.result.into_result().map_err(StdError::generic_err)?;
+	let mut result = RESULT_REGISTER.load(deps.storage)?;
+	result.call_results.push(CallResult {
+		instruction: result.instruction_pointer.saturating_sub(1),
+		data: response.data,
+	});
+	RESULT_REGISTER.save(deps.storage, &result)?;
+	Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+	match msg {
+		QueryMsg::Config {} => to_binary(&query_config(deps)?),
+		QueryMsg::Result {} => to_binary(&query_result(deps)?),
+	}
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+	let Config { registry_address, gateway_address, network_id, user_id } =
+		CONFIG.load(deps.storage)?;
+	Ok(ConfigResponse { registry_address, gateway_address, network_id, user_id })
+}
+
+pub fn query_result(deps: Deps) -> StdResult<ResultResponse> {
+	Ok(ResultResponse { result: RESULT_REGISTER.may_load(deps.storage)? })
 }
 
 #[cfg(test)]
 mod tests {
-	use std::collections::BTreeMap;
-
 	use crate::msg::XCVMInstruction;
 
 	use super::*;
 	use cosmwasm_std::{
-		testing::{mock_dependencies, mock_env, mock_info, MockQuerier},
-		wasm_execute, Addr, ContractResult, QuerierResult, SystemResult,
+		from_binary,
+		testing::{mock_dependencies, mock_env, mock_info, MockQuerier, MOCK_CONTRACT_ADDR},
+		wasm_execute, Addr, BankMsg, Coin, ContractResult, QuerierResult, ReplyOn, SubMsgResponse,
+		SubMsgResult, SystemResult, WasmMsg,
 	};
-	use xcvm_core::{Amount, AssetId, Picasso, ETH, PICA};
+	use xcvm_core::{Amount, AssetId, Picasso, ETH, MAX_PARTS, PICA};
 
 	const CW20_ADDR: &str = "cw20addr";
 	const REGISTRY_ADDR: &str = "registryaddr";
+	const RELAYER_ADDR: &str = "relayeraddr";
+	const GATEWAY_ADDR: &str = "gatewayaddr";
 
 	#[test]
 	fn proper_instantiation() {
 		let mut deps = mock_dependencies();
+		let mut querier = MockQuerier::default();
+		querier.update_wasm(wasm_querier);
+		deps.querier = querier;
 
 		let msg = InstantiateMsg {
 			registry_address: "addr".to_string(),
+			gateway_address: GATEWAY_ADDR.to_string(),
 			network_id: Picasso.into(),
 			user_id: vec![],
 		};
@@ -279,14 +436,24 @@ mod tests {
 			CONFIG.load(&deps.storage).unwrap(),
 			Config {
 				registry_address: Addr::unchecked("addr"),
+				gateway_address: Addr::unchecked(GATEWAY_ADDR),
 				network_id: Picasso.into(),
 				user_id: vec![]
 			}
 		);
 	}
 
+	/// The interpreter is administrated by the router, `sender` in the tests.
+	fn contract_info_querier(contract_addr: &str) -> QuerierResult {
+		assert_eq!(contract_addr, MOCK_CONTRACT_ADDR);
+		let mut contract_info = ContractInfoResponse::new(1, "sender");
+		contract_info.admin = Some("sender".into());
+		SystemResult::Ok(ContractResult::Ok(to_binary(&contract_info).unwrap()))
+	}
+
 	fn wasm_querier(query: &WasmQuery) -> QuerierResult {
 		match query {
+			WasmQuery::ContractInfo { contract_addr } => contract_info_querier(contract_addr),
 			WasmQuery::Smart { contract_addr, .. } if contract_addr.as_str() == CW20_ADDR =>
 				SystemResult::Ok(ContractResult::Ok(
 					to_binary(&cw20::BalanceResponse { balance: 100000_u128.into() }).unwrap(),
@@ -294,7 +461,9 @@ mod tests {
 			WasmQuery::Smart { contract_addr, .. } if contract_addr.as_str() == REGISTRY_ADDR =>
 				SystemResult::Ok(ContractResult::Ok(
 					to_binary(&xcvm_asset_registry::msg::GetAssetContractResponse {
-						addr: Addr::unchecked(CW20_ADDR),
+						reference: AssetReference::Virtual {
+							cw20_address: Addr::unchecked(CW20_ADDR),
+						},
 					})
 					.unwrap(),
 				))
@@ -317,6 +486,7 @@ mod tests {
 			info.clone(),
 			InstantiateMsg {
 				registry_address: REGISTRY_ADDR.into(),
+				gateway_address: GATEWAY_ADDR.into(),
 				network_id: Picasso.into(),
 				user_id: vec![],
 			},
@@ -335,8 +505,30 @@ mod tests {
 			.into(),
 		};
 
-		let res = execute(deps.as_mut(), mock_env(), info.clone(), ExecuteMsg::Execute { program })
-			.unwrap();
+		let res = execute(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			ExecuteMsg::Execute {
+				relayer: Addr::unchecked(RELAYER_ADDR),
+				program: program.clone(),
+			},
+		)
+		.unwrap();
+		// Only the router can execute programs
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info("attacker", &vec![]),
+				ExecuteMsg::Execute {
+					relayer: Addr::unchecked(RELAYER_ADDR),
+					program: program.clone(),
+				},
+			),
+			Err(ContractError::NotAuthorized)
+		));
+
 		let contract = Cw20Contract(Addr::unchecked(CW20_ADDR));
 		let messages = vec![
 			contract
@@ -348,11 +540,228 @@ mod tests {
 		];
 
 		assert_eq!(res.messages.iter().map(|msg| msg.msg.clone()).collect::<Vec<_>>(), messages);
+
+		// The binary encoded program is executed the same
+		let res = execute(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			ExecuteMsg::ExecuteBinary {
+				relayer: Addr::unchecked(RELAYER_ADDR),
+				program: program.encode_binary().into(),
+			},
+		)
+		.unwrap();
+		assert_eq!(res.messages.iter().map(|msg| msg.msg.clone()).collect::<Vec<_>>(), messages);
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				info,
+				ExecuteMsg::ExecuteBinary {
+					relayer: Addr::unchecked(RELAYER_ADDR),
+					program: vec![0xFF].into(),
+				},
+			),
+			Err(ContractError::InvalidProgramEncoding)
+		));
+	}
+
+	#[test]
+	fn execute_transfer_native() {
+		let mut deps = mock_dependencies();
+		let mut querier = MockQuerier::new(&[(MOCK_CONTRACT_ADDR, &[Coin::new(1000, "upica")])]);
+		querier.update_wasm(|query| match query {
+			WasmQuery::ContractInfo { contract_addr } => contract_info_querier(contract_addr),
+			WasmQuery::Smart { contract_addr, .. } if contract_addr.as_str() == REGISTRY_ADDR =>
+				SystemResult::Ok(ContractResult::Ok(
+					to_binary(&GetAssetContractResponse {
+						reference: AssetReference::Native { denom: "upica".into() },
+					})
+					.unwrap(),
+				)),
+			_ => panic!("Unhandled query"),
+		});
+		deps.querier = querier;
+
+		let info = mock_info("sender", &vec![]);
+		let _ = instantiate(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			InstantiateMsg {
+				registry_address: REGISTRY_ADDR.into(),
+				gateway_address: GATEWAY_ADDR.into(),
+				network_id: Picasso.into(),
+				user_id: vec![],
+			},
+		)
+		.unwrap();
+
+		let program = XCVMProgram {
+			tag: vec![],
+			instructions: vec![XCVMInstruction::Transfer {
+				to: "asset".into(),
+				assets: Funds::from([(Into::<AssetId>::into(PICA), Amount::ratio(MAX_PARTS / 2))]),
+			}]
+			.into(),
+		};
+
+		let res = execute(
+			deps.as_mut(),
+			mock_env(),
+			info,
+			ExecuteMsg::Execute { relayer: Addr::unchecked(RELAYER_ADDR), program },
+		)
+		.unwrap();
+		assert_eq!(
+			res.messages.iter().map(|msg| msg.msg.clone()).collect::<Vec<_>>(),
+			vec![CosmosMsg::Bank(BankMsg::Send {
+				to_address: "asset".into(),
+				amount: vec![Coin::new(500, "upica")],
+			})]
+		);
+	}
+
+	#[test]
+	fn execute_control_flow() {
+		let mut deps = mock_dependencies();
+		let mut querier = MockQuerier::default();
+		querier.update_wasm(wasm_querier);
+		deps.querier = querier;
+
+		let info = mock_info("sender", &vec![]);
+		let _ = instantiate(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			InstantiateMsg {
+				registry_address: REGISTRY_ADDR.into(),
+				gateway_address: GATEWAY_ADDR.into(),
+				network_id: Picasso.into(),
+				user_id: vec![],
+			},
+		)
+		.unwrap();
+
+		let transfer = |amount: u128| XCVMInstruction::Transfer {
+			to: "asset".into(),
+			assets: Funds::from([(Into::<AssetId>::into(PICA), Amount::absolute(amount))]),
+		};
+		let program = XCVMProgram {
+			tag: vec![],
+			instructions: vec![
+				XCVMInstruction::Assert {
+					predicate: Predicate::BalanceAtLeast {
+						asset: PICA.into(),
+						amount: Displayed(100000),
+					},
+				},
+				XCVMInstruction::IfElse {
+					predicate: Predicate::BalanceAtMost {
+						asset: PICA.into(),
+						amount: Displayed(1),
+					},
+					then: vec![transfer(1)].into(),
+					otherwise: vec![transfer(2)].into(),
+				},
+				XCVMInstruction::Repeat { times: 2, instructions: vec![transfer(3)].into() },
+			]
+			.into(),
+		};
+
+		let res = execute(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			ExecuteMsg::Execute { relayer: Addr::unchecked(RELAYER_ADDR), program },
+		)
+		.unwrap();
+		let contract = Cw20Contract(Addr::unchecked(CW20_ADDR));
+		let messages = [2_u128, 3, 3]
+			.into_iter()
+			.map(|amount| {
+				contract
+					.call(Cw20ExecuteMsg::Transfer {
+						recipient: "asset".into(),
+						amount: amount.into(),
+					})
+					.unwrap()
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(res.messages.iter().map(|msg| msg.msg.clone()).collect::<Vec<_>>(), messages);
+
+		let failing = XCVMProgram {
+			tag: vec![],
+			instructions: vec![
+				XCVMInstruction::Assert {
+					predicate: Predicate::BalanceAtLeast {
+						asset: PICA.into(),
+						amount: Displayed(100001),
+					},
+				},
+				transfer(1),
+			]
+			.into(),
+		};
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				info.clone(),
+				ExecuteMsg::Execute { relayer: Addr::unchecked(RELAYER_ADDR), program: failing }
+			),
+			Err(ContractError::AssertionFailed)
+		));
+
+		let too_large = XCVMProgram {
+			tag: vec![],
+			instructions: vec![XCVMInstruction::Repeat {
+				times: MAX_REPEAT + 1,
+				instructions: vec![transfer(1)].into(),
+			}]
+			.into(),
+		};
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				info,
+				ExecuteMsg::Execute { relayer: Addr::unchecked(RELAYER_ADDR), program: too_large }
+			),
+			Err(ContractError::RepeatTooLarge)
+		));
+
+		// Nested repeats are bounded as a whole
+		let nested = XCVMProgram {
+			tag: vec![],
+			instructions: vec![XCVMInstruction::Repeat {
+				times: MAX_REPEAT,
+				instructions: vec![XCVMInstruction::Repeat {
+					times: MAX_REPEAT,
+					instructions: vec![transfer(1)].into(),
+				}]
+				.into(),
+			}]
+			.into(),
+		};
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info("sender", &vec![]),
+				ExecuteMsg::Execute { relayer: Addr::unchecked(RELAYER_ADDR), program: nested }
+			),
+			Err(ContractError::ProgramTooLarge)
+		));
 	}
 
 	#[test]
 	fn execute_call() {
 		let mut deps = mock_dependencies();
+		let mut querier = MockQuerier::default();
+		querier.update_wasm(wasm_querier);
+		deps.querier = querier;
 
 		let info = mock_info("sender", &vec![]);
 		let _ = instantiate(
@@ -361,6 +770,7 @@ mod tests {
 			info.clone(),
 			InstantiateMsg {
 				registry_address: "addr".into(),
+				gateway_address: GATEWAY_ADDR.into(),
 				network_id: Picasso.into(),
 				user_id: vec![],
 			},
@@ -371,9 +781,9 @@ mod tests {
 			wasm_execute("1234", &"hello world".to_string(), vec![]).unwrap().into();
 		let msg = serde_json_wasm::to_string(&out_msg_1).unwrap();
 		let instructions = vec![
-			XCVMInstruction::Call { encoded: msg.as_bytes().into() },
+			XCVMInstruction::Call { encoded: msg.as_bytes().into(), bindings: vec![] },
 			XCVMInstruction::Transfer { to: "1234".into(), assets: Funds::empty() },
-			XCVMInstruction::Call { encoded: msg.as_bytes().into() },
+			XCVMInstruction::Call { encoded: msg.as_bytes().into(), bindings: vec![] },
 			XCVMInstruction::Spawn {
 				network: Picasso.into(),
 				salt: vec![],
@@ -386,6 +796,7 @@ mod tests {
 		let out_msg_2: CosmosMsg = wasm_execute(
 			"cosmos2contract",
 			&ExecuteMsg::Execute {
+				relayer: Addr::unchecked(RELAYER_ADDR),
 				program: XCVMProgram {
 					tag: vec![],
 					instructions: instructions[1..].to_owned().into(),
@@ -396,16 +807,93 @@ mod tests {
 		.unwrap()
 		.into();
 
-		let res = execute(deps.as_mut(), mock_env(), info.clone(), ExecuteMsg::Execute { program })
-			.unwrap();
+		let res = execute(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			ExecuteMsg::Execute { relayer: Addr::unchecked(RELAYER_ADDR), program },
+		)
+		.unwrap();
 		assert_eq!(res.messages[0].msg, out_msg_1);
 		assert_eq!(res.messages[1].msg, out_msg_2);
 		assert_eq!(res.messages.len(), 2);
 	}
 
+	#[test]
+	fn execute_call_with_bindings() {
+		let mut deps = mock_dependencies();
+		let mut querier = MockQuerier::default();
+		querier.update_wasm(wasm_querier);
+		deps.querier = querier;
+
+		let info = mock_info("sender", &vec![]);
+		let _ = instantiate(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			InstantiateMsg {
+				registry_address: REGISTRY_ADDR.into(),
+				gateway_address: GATEWAY_ADDR.into(),
+				network_id: Picasso.into(),
+				user_id: vec![],
+			},
+		)
+		.unwrap();
+
+		let payload = r#"{"bank":{"send":{"to_address":"","amount":[{"denom":"","amount":""}]}}}"#;
+		let offset = |pattern: &str| (payload.find(pattern).unwrap() + pattern.len()) as u16;
+		let bindings = vec![
+			(offset(r#""to_address":""#), BindingValue::Relayer),
+			(offset(r#""denom":""#), BindingValue::AssetId(PICA.into())),
+			(offset(r#""amount":""#), BindingValue::Balance(PICA.into())),
+		];
+		let program = XCVMProgram {
+			tag: vec![],
+			instructions: vec![XCVMInstruction::Call { encoded: payload.into(), bindings }].into(),
+		};
+		let res = execute(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			ExecuteMsg::Execute { relayer: Addr::unchecked(RELAYER_ADDR), program },
+		)
+		.unwrap();
+		assert_eq!(
+			res.messages[0].msg,
+			CosmosMsg::Bank(BankMsg::Send {
+				to_address: RELAYER_ADDR.into(),
+				amount: vec![Coin::new(100000, CW20_ADDR)],
+			})
+		);
+
+		let program = XCVMProgram {
+			tag: vec![],
+			instructions: vec![XCVMInstruction::Call {
+				encoded: payload.into(),
+				bindings: vec![
+					(offset(r#""denom":""#), BindingValue::Self_),
+					(0, BindingValue::Result),
+				],
+			}]
+			.into(),
+		};
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				info,
+				ExecuteMsg::Execute { relayer: Addr::unchecked(RELAYER_ADDR), program },
+			),
+			Err(ContractError::InvalidBindings)
+		));
+	}
+
 	#[test]
 	fn execute_spawn() {
 		let mut deps = mock_dependencies();
+		let mut querier = MockQuerier::default();
+		querier.update_wasm(wasm_querier);
+		deps.querier = querier;
 
 		let info = mock_info("sender", &vec![]);
 		let _ = instantiate(
@@ -413,7 +901,8 @@ mod tests {
 			mock_env(),
 			info.clone(),
 			InstantiateMsg {
-				registry_address: "addr".into(),
+				registry_address: REGISTRY_ADDR.into(),
+				gateway_address: GATEWAY_ADDR.into(),
 				network_id: Picasso.into(),
 				user_id: vec![],
 			},
@@ -425,17 +914,133 @@ mod tests {
 			instructions: vec![XCVMInstruction::Spawn {
 				network: Picasso.into(),
 				salt: vec![],
-				assets: Funds(BTreeMap::new()),
+				assets: Funds::from([(Into::<AssetId>::into(PICA), Amount::absolute(1000))]),
 				program: XCVMProgram {
 					tag: vec![],
-					instructions: vec![XCVMInstruction::Call { encoded: vec![] }].into(),
+					instructions: vec![XCVMInstruction::Call { encoded: vec![], bindings: vec![] }]
+						.into(),
 				},
 			}]
 			.into(),
 		};
 
-		let res = execute(deps.as_mut(), mock_env(), info.clone(), ExecuteMsg::Execute { program })
-			.unwrap();
-		assert_eq!(res.events[0], Event::new("xcvm.interpreter.spawn").add_attribute("origin_network_id", "1").add_attribute("origin_user_id", "[]").add_attribute("program", r#"{"network":1,"salt":[],"assets":{},"program":{"tag":[],"instructions":[{"call":{"encoded":[]}}]}}"#.to_string()));
+		let res = execute(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			ExecuteMsg::Execute { relayer: Addr::unchecked(RELAYER_ADDR), program },
+		)
+		.unwrap();
+
+		// The gateway is allowed to collect the assets along the program
+		assert_eq!(
+			res.messages[0].msg,
+			Cw20Contract(Addr::unchecked(CW20_ADDR))
+				.call(Cw20ExecuteMsg::IncreaseAllowance {
+					spender: GATEWAY_ADDR.into(),
+					amount: 1000_u128.into(),
+					expires: None,
+				})
+				.unwrap()
+		);
+		match &res.messages[1].msg {
+			CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) => {
+				assert_eq!(contract_addr, GATEWAY_ADDR);
+				assert!(funds.is_empty());
+				let msg: serde_json::Value = serde_json::from_slice(msg.as_slice()).unwrap();
+				assert_eq!(msg["spawn"]["network"], 1);
+				assert_eq!(
+					msg["spawn"]["program"]["instructions"][0],
+					serde_json::json!({"call":{"encoded":[],"bindings":[]}})
+				);
+			},
+			msg => panic!("Unexpected message {:?}", msg),
+		}
+	}
+
+	#[test]
+	fn query_result_register() {
+		let mut deps = mock_dependencies();
+		let mut querier = MockQuerier::default();
+		querier.update_wasm(wasm_querier);
+		deps.querier = querier;
+
+		let info = mock_info("sender", &vec![]);
+		let _ = instantiate(
+			deps.as_mut(),
+			mock_env(),
+			info.clone(),
+			InstantiateMsg {
+				registry_address: "addr".into(),
+				gateway_address: GATEWAY_ADDR.into(),
+				network_id: Picasso.into(),
+				user_id: vec![1],
+			},
+		)
+		.unwrap();
+
+		let config: ConfigResponse =
+			from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+		assert_eq!(
+			config,
+			ConfigResponse {
+				registry_address: Addr::unchecked("addr"),
+				gateway_address: Addr::unchecked(GATEWAY_ADDR),
+				network_id: Picasso.into(),
+				user_id: vec![1]
+			}
+		);
+		let result: ResultResponse =
+			from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Result {}).unwrap()).unwrap();
+		assert_eq!(result, ResultResponse { result: None });
+
+		let call: CosmosMsg =
+			wasm_execute("1234", &"hello world".to_string(), vec![]).unwrap().into();
+		let call = XCVMInstruction::Call {
+			encoded: serde_json_wasm::to_string(&call).unwrap().as_bytes().into(),
+			bindings: vec![],
+		};
+		let program = XCVMProgram {
+			tag: b"tag".to_vec(),
+			instructions: vec![
+				XCVMInstruction::Transfer { to: "1234".into(), assets: Funds::empty() },
+				call.clone(),
+				call,
+			]
+			.into(),
+		};
+		let res = execute(
+			deps.as_mut(),
+			mock_env(),
+			info,
+			ExecuteMsg::Execute { relayer: Addr::unchecked(RELAYER_ADDR), program },
+		)
+		.unwrap();
+		assert_eq!(res.messages[0].id, CALL_REPLY_ID);
+		let reply_ok = |data: &[u8]| Reply {
+			id: CALL_REPLY_ID,
+			result: SubMsgResult::Ok(SubMsgResponse {
+				events: vec![],
+				data: Some(Binary::from(data)),
+			}),
+		};
+		reply(deps.as_mut(), mock_env(), reply_ok(b"first")).unwrap();
+
+		let result: ResultResponse =
+			from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Result {}).unwrap()).unwrap();
+		assert_eq!(
+			result.result,
+			Some(ProgramResult {
+				tag: b"tag".to_vec(),
+				instruction_pointer: 2,
+				call_results: vec![CallResult {
+					instruction: 1,
+					data: Some(Binary::from(b"first"))
+				}],
+			})
+		);
+
+		// A failing call is not replied to, reverting the whole program
+		assert_eq!(res.messages[0].reply_on, ReplyOn::Success);
 	}
 }
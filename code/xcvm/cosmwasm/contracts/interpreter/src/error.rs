@@ -6,12 +6,36 @@ pub enum ContractError {
 	#[error("{0}")]
 	Std(#[from] StdError),
 
+	#[error("Caller is not authorized to take this action")]
+	NotAuthorized,
+
 	#[error("Invalid call payload")]
 	InvalidCallPayload,
 
 	#[error("Data cannot be serialized")]
 	DataSerializationError,
 
+	#[error("Program is not a valid binary encoded program")]
+	InvalidProgramEncoding,
+
 	#[error("A program tag must be a correct utf8 encoded string")]
 	InvalidProgramTag,
+
+	#[error("Assertion failed")]
+	AssertionFailed,
+
+	#[error("Repeat instruction exceeds the maximum number of iterations")]
+	RepeatTooLarge,
+
+	#[error("Program exceeds the maximum number of executed instructions")]
+	ProgramTooLarge,
+
+	#[error("Predicate query failed")]
+	PredicateQueryFailed,
+
+	#[error("Bindings must be sorted by offset and within the payload")]
+	InvalidBindings,
+
+	#[error("No call result to bind")]
+	MissingCallResult,
 }
@@ -1,3 +1,5 @@
+use crate::state::ProgramResult;
+use cosmwasm_std::{Addr, Binary};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
@@ -10,6 +12,7 @@ pub type UserId = Vec<u8>;
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
 	pub registry_address: String,
+	pub gateway_address: String,
 	pub network_id: NetworkId,
 	pub user_id: UserId,
 }
@@ -17,11 +20,37 @@ pub struct InstantiateMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-	Execute { program: XCVMProgram },
+	Execute {
+		/// Address of the relayer that submitted the program, bound by `BindingValue::Relayer`.
+		relayer: Addr,
+		program: XCVMProgram,
+	},
+	/// Same as `Execute` with the program in the compact binary encoding of `xcvm-core`.
+	ExecuteBinary { relayer: Addr, program: Binary },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MigrateMsg {}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub enum QueryMsg {}
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+	/// Get the owner of the interpreter and the registry it uses.
+	Config {},
+	/// Get the result register of the last executed program.
+	Result {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+	pub registry_address: Addr,
+	pub gateway_address: Addr,
+	pub network_id: NetworkId,
+	pub user_id: UserId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ResultResponse {
+	/// `None` if no program has been executed yet.
+	pub result: Option<ProgramResult>,
+}
@@ -2,15 +2,43 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::msg::UserId;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary};
 use cw_storage_plus::Item;
 use xcvm_core::NetworkId;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
 	pub registry_address: Addr,
+	pub gateway_address: Addr,
 	pub network_id: NetworkId,
 	pub user_id: UserId,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Data returned by a `Call` instruction.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CallResult {
+	/// Index of the `Call` in the executed instructions.
+	pub instruction: u32,
+	pub data: Option<Binary>,
+}
+
+/// Result register of a program, updated as the program is executed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProgramResult {
+	/// Tag of the program.
+	pub tag: Vec<u8>,
+	/// Number of instructions executed so far, i.e. the index of the next instruction.
+	pub instruction_pointer: u32,
+	/// Replies of the executed `Call` instructions, in execution order.
+	pub call_results: Vec<CallResult>,
+}
+
+impl ProgramResult {
+	pub fn new(tag: Vec<u8>) -> Self {
+		ProgramResult { tag, instruction_pointer: 0, call_results: vec![] }
+	}
+}
+
+pub const RESULT_REGISTER: Item<ProgramResult> = Item::new("result_register");
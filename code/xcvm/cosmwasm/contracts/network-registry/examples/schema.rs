@@ -0,0 +1,16 @@
+use std::{env::current_dir, fs::create_dir_all};
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+
+use xcvm_network_registry::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+fn main() {
+	let mut out_dir = current_dir().unwrap();
+	out_dir.push("schema");
+	create_dir_all(&out_dir).unwrap();
+	remove_schemas(&out_dir).unwrap();
+
+	export_schema(&schema_for!(InstantiateMsg), &out_dir);
+	export_schema(&schema_for!(ExecuteMsg), &out_dir);
+	export_schema(&schema_for!(QueryMsg), &out_dir);
+}
@@ -0,0 +1,218 @@
+use crate::{
+	error::ContractError,
+	msg::{
+		ExecuteMsg, GetAdminResponse, GetNetworkResponse, InstantiateMsg, ListNetworksResponse,
+		MigrateMsg, QueryMsg,
+	},
+	state::{ADMIN, NETWORKS},
+};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+	to_binary, Binary, Deps, DepsMut, Env, Event, MessageInfo, Order, Response, StdResult,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use cw_utils::ensure_from_older_version;
+use xcvm_core::{NetworkId, NetworkInfo};
+
+const CONTRACT_NAME: &str = "composable:xcvm-network-registry";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+	deps: DepsMut,
+	_env: Env,
+	info: MessageInfo,
+	_msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+	set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+	ADMIN.save(deps.storage, &info.sender)?;
+	Ok(Response::default().add_event(Event::new("xcvm.network_registry.instantiated")))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+	deps: DepsMut,
+	_env: Env,
+	info: MessageInfo,
+	msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+	if ADMIN.load(deps.storage)? != info.sender {
+		return Err(ContractError::NotAuthorized)
+	}
+	match msg {
+		ExecuteMsg::RegisterNetwork { network_id, info } =>
+			handle_register_network(deps, network_id, info),
+		ExecuteMsg::UnregisterNetwork { network_id } => {
+			NETWORKS.remove(deps.storage, network_id.0);
+			Ok(Response::new().add_event(
+				Event::new("xcvm.network_registry.unregistered")
+					.add_attribute("network_id", network_id.0.to_string()),
+			))
+		},
+		ExecuteMsg::SetAdmin { admin } => {
+			let admin = deps.api.addr_validate(&admin)?;
+			ADMIN.save(deps.storage, &admin)?;
+			Ok(Response::new().add_event(
+				Event::new("xcvm.network_registry.admin_updated").add_attribute("admin", admin),
+			))
+		},
+	}
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+	let _ = ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+	Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+	match msg {
+		QueryMsg::GetNetwork { network_id } => to_binary(&query_network(deps, network_id)?),
+		QueryMsg::ListNetworks { start_after, limit } =>
+			to_binary(&query_list_networks(deps, start_after, limit)?),
+		QueryMsg::GetAdmin {} => to_binary(&GetAdminResponse { admin: ADMIN.load(deps.storage)? }),
+	}
+}
+
+pub fn handle_register_network(
+	deps: DepsMut,
+	network_id: NetworkId,
+	info: NetworkInfo,
+) -> Result<Response, ContractError> {
+	NETWORKS.save(deps.storage, network_id.0, &info)?;
+	Ok(Response::new().add_event(
+		Event::new("xcvm.network_registry.registered")
+			.add_attribute("network_id", network_id.0.to_string()),
+	))
+}
+
+pub fn query_network(deps: Deps, network_id: NetworkId) -> StdResult<GetNetworkResponse> {
+	let info = NETWORKS.load(deps.storage, network_id.0)?;
+	Ok(GetNetworkResponse { info })
+}
+
+pub fn query_list_networks(
+	deps: Deps,
+	start_after: Option<NetworkId>,
+	limit: Option<u32>,
+) -> StdResult<ListNetworksResponse> {
+	let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+	let start = start_after.map(|network_id| Bound::exclusive(network_id.0));
+	let networks = NETWORKS
+		.range(deps.storage, start, None, Order::Ascending)
+		.take(limit)
+		.map(|item| item.map(|(network_id, info)| (NetworkId(network_id), info)))
+		.collect::<StdResult<Vec<_>>>()?;
+	Ok(ListNetworksResponse { networks })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cosmwasm_std::{
+		from_binary,
+		testing::{mock_dependencies, mock_env, mock_info},
+		Addr,
+	};
+	use xcvm_core::CallEncoding;
+
+	fn network_info(name: &str) -> NetworkInfo {
+		NetworkInfo {
+			name: name.into(),
+			call_encoding: CallEncoding::Json,
+			gateway_address: "gateway".into(),
+		}
+	}
+
+	#[test]
+	fn register_networks() {
+		let mut deps = mock_dependencies();
+		let info = mock_info("admin", &vec![]);
+		instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+
+		// Only the admin can register networks
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info("sender", &vec![]),
+				ExecuteMsg::RegisterNetwork { network_id: NetworkId(1), info: network_info("a") },
+			),
+			Err(ContractError::NotAuthorized)
+		));
+
+		for (network_id, name) in [(300, "c"), (1, "a"), (2, "b")] {
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				info.clone(),
+				ExecuteMsg::RegisterNetwork {
+					network_id: NetworkId(network_id),
+					info: network_info(name),
+				},
+			)
+			.unwrap();
+		}
+
+		let res: GetNetworkResponse = from_binary(
+			&query(deps.as_ref(), mock_env(), QueryMsg::GetNetwork { network_id: NetworkId(300) })
+				.unwrap(),
+		)
+		.unwrap();
+		assert_eq!(res, GetNetworkResponse { info: network_info("c") });
+
+		let res: ListNetworksResponse = from_binary(
+			&query(
+				deps.as_ref(),
+				mock_env(),
+				QueryMsg::ListNetworks { start_after: Some(NetworkId(1)), limit: None },
+			)
+			.unwrap(),
+		)
+		.unwrap();
+		assert_eq!(
+			res.networks,
+			vec![(NetworkId(2), network_info("b")), (NetworkId(300), network_info("c"))]
+		);
+
+		execute(
+			deps.as_mut(),
+			mock_env(),
+			info,
+			ExecuteMsg::UnregisterNetwork { network_id: NetworkId(300) },
+		)
+		.unwrap();
+		assert!(query(
+			deps.as_ref(),
+			mock_env(),
+			QueryMsg::GetNetwork { network_id: NetworkId(300) }
+		)
+		.is_err());
+
+		// The ownership can be transferred
+		execute(
+			deps.as_mut(),
+			mock_env(),
+			mock_info("admin", &vec![]),
+			ExecuteMsg::SetAdmin { admin: "new_admin".into() },
+		)
+		.unwrap();
+		let res: GetAdminResponse =
+			from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetAdmin {}).unwrap()).unwrap();
+		assert_eq!(res.admin, Addr::unchecked("new_admin"));
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info("admin", &vec![]),
+				ExecuteMsg::UnregisterNetwork { network_id: NetworkId(1) },
+			),
+			Err(ContractError::NotAuthorized)
+		));
+	}
+}
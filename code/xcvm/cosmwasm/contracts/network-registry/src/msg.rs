@@ -0,0 +1,56 @@
+use cosmwasm_std::Addr;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use xcvm_core::{NetworkId, NetworkInfo};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+	/// Register a network or update its metadata.
+	RegisterNetwork {
+		network_id: NetworkId,
+		info: NetworkInfo,
+	},
+	UnregisterNetwork {
+		network_id: NetworkId,
+	},
+	/// Transfer the ownership of the registry.
+	SetAdmin {
+		admin: String,
+	},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+	GetNetwork {
+		network_id: NetworkId,
+	},
+	/// List the registered networks in ascending order of their ID.
+	ListNetworks {
+		start_after: Option<NetworkId>,
+		limit: Option<u32>,
+	},
+	GetAdmin {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetNetworkResponse {
+	pub info: NetworkInfo,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListNetworksResponse {
+	pub networks: Vec<(NetworkId, NetworkInfo)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetAdminResponse {
+	pub admin: Addr,
+}
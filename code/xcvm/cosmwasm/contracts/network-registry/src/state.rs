@@ -0,0 +1,6 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+use xcvm_core::NetworkInfo;
+
+pub const ADMIN: Item<Addr> = Item::new("admin");
+pub const NETWORKS: Map<u32, NetworkInfo> = Map::new("networks");
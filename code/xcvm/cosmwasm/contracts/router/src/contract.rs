@@ -1,21 +1,28 @@
 use crate::{
 	error::ContractError,
-	msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-	state::{Config, UserId, CONFIG, INTERPRETERS},
+	msg::{
+		ConfigResponse, ExecuteMsg, GetInterpreterResponse, InstantiateMsg, IsBridgeResponse,
+		MigrateMsg, QueryMsg,
+	},
+	state::{
+		Config, ExecutionFee, LegacyConfig, UserId, BRIDGES, CONFIG, INTERPRETERS, LEGACY_BRIDGES,
+		LEGACY_CONFIG, LEGACY_INTERPRETERS,
+	},
 };
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-	from_binary, to_binary, wasm_execute, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-	Reply, Response, StdError, StdResult, SubMsg, WasmMsg, WasmQuery,
+	from_binary, to_binary, wasm_execute, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Empty,
+	Env, Event, MessageInfo, Order, Reply, Response, StdError, StdResult, SubMsg, WasmMsg,
+	WasmQuery,
 };
 use cw2::set_contract_version;
-use cw20::{Cw20Contract, Cw20ExecuteMsg};
 use cw_utils::ensure_from_older_version;
 use xcvm_asset_registry::msg::{GetAssetContractResponse, QueryMsg as AssetRegistryQueryMsg};
 use xcvm_core::{Displayed, Funds, NetworkId};
 use xcvm_interpreter::msg::{
 	ExecuteMsg as InterpreterExecuteMsg, InstantiateMsg as InterpreterInstantiateMsg,
+	MigrateMsg as InterpreterMigrateMsg,
 };
 
 const CONTRACT_NAME: &str = "composable:xcvm-router";
@@ -30,10 +37,18 @@ pub fn instantiate(
 	msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
 	set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-	let addr = deps.api.addr_validate(&msg.registry_address)?;
+	let admin = deps.api.addr_validate(&msg.admin)?;
+	let registry_address = deps.api.addr_validate(&msg.registry_address)?;
+	let gateway_address = deps.api.addr_validate(&msg.gateway_address)?;
 	CONFIG.save(
 		deps.storage,
-		&Config { registry_address: addr, interpreter_code_id: msg.interpreter_code_id },
+		&Config {
+			admin,
+			registry_address,
+			gateway_address,
+			interpreter_code_id: msg.interpreter_code_id,
+			execution_fee: None,
+		},
 	)?;
 	Ok(Response::default())
 }
@@ -42,21 +57,154 @@ pub fn instantiate(
 pub fn execute(
 	deps: DepsMut,
 	env: Env,
-	_info: MessageInfo,
+	info: MessageInfo,
 	msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
 	match msg {
-		ExecuteMsg::Run { network_id, user_id, interpreter_execute_msg, funds } =>
-			handle_run(deps, env, network_id, user_id, interpreter_execute_msg, funds),
+		ExecuteMsg::Run { network_id, user_id, interpreter_execute_msg, funds } => {
+			let response = ensure_authorized_run(deps.as_ref(), &env, &info, network_id)?;
+			let run_response =
+				handle_run(deps, env, network_id, user_id, interpreter_execute_msg, funds)?;
+			Ok(response.add_submessages(run_response.messages).add_events(run_response.events))
+		},
+		ExecuteMsg::RegisterBridge { network_id, address } => {
+			ensure_admin(deps.as_ref(), &info)?;
+			let address = deps.api.addr_validate(&address)?;
+			BRIDGES.save(deps.storage, (network_id.0, address), &Empty {})?;
+			Ok(Response::new().add_event(Event::new("xcvm.router.bridge_registered")))
+		},
+		ExecuteMsg::UnregisterBridge { network_id, address } => {
+			ensure_admin(deps.as_ref(), &info)?;
+			let address = deps.api.addr_validate(&address)?;
+			BRIDGES.remove(deps.storage, (network_id.0, address));
+			Ok(Response::new().add_event(Event::new("xcvm.router.bridge_unregistered")))
+		},
+		ExecuteMsg::SetExecutionFee { execution_fee } => {
+			ensure_admin(deps.as_ref(), &info)?;
+			let execution_fee = execution_fee
+				.map(|ExecutionFee { amount, recipient }| -> StdResult<_> {
+					Ok(ExecutionFee {
+						amount,
+						recipient: deps.api.addr_validate(recipient.as_str())?,
+					})
+				})
+				.transpose()?;
+			CONFIG.update(deps.storage, |config| -> StdResult<_> {
+				Ok(Config { execution_fee, ..config })
+			})?;
+			Ok(Response::new().add_event(Event::new("xcvm.router.execution_fee_updated")))
+		},
+		ExecuteMsg::SetInterpreterCodeId { code_id } => {
+			ensure_admin(deps.as_ref(), &info)?;
+			CONFIG.update(deps.storage, |config| -> StdResult<_> {
+				Ok(Config { interpreter_code_id: code_id, ..config })
+			})?;
+			Ok(Response::new().add_event(Event::new("xcvm.router.interpreter_code_id_updated")))
+		},
+		ExecuteMsg::MigrateInterpreter { network_id, user_id } => {
+			ensure_admin(deps.as_ref(), &info)?;
+			handle_migrate_interpreter(deps, network_id, user_id)
+		},
 	}
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
 	let _ = ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+	let admin = msg.admin.map(|admin| deps.api.addr_validate(&admin)).transpose()?;
+	let config = match (CONFIG.load(deps.storage), admin) {
+		(Ok(config), Some(admin)) => Config { admin, ..config },
+		(Ok(config), None) => config,
+		// Routers instantiated before they had an admin must be given one.
+		(Err(_), admin) => {
+			let LegacyConfig { registry_address, gateway_address, interpreter_code_id } =
+				LEGACY_CONFIG.load(deps.storage)?;
+			Config {
+				admin: admin.ok_or(ContractError::MissingAdmin)?,
+				registry_address,
+				gateway_address,
+				interpreter_code_id,
+				execution_fee: None,
+			}
+		},
+	};
+	CONFIG.save(deps.storage, &config)?;
+	migrate_legacy_network_ids(deps)?;
 	Ok(Response::default())
 }
 
+/// Rewrite the interpreters and bridges registered with single byte network IDs, which would
+/// otherwise be unreachable.
+fn migrate_legacy_network_ids(deps: DepsMut) -> StdResult<()> {
+	// Entries keyed by `u32` network IDs fail to decode and are skipped.
+	let interpreters = LEGACY_INTERPRETERS
+		.range(deps.storage, None, None, Order::Ascending)
+		.filter_map(Result::ok)
+		.collect::<Vec<_>>();
+	for ((network_id, user_id), interpreter_address) in interpreters {
+		LEGACY_INTERPRETERS.remove(deps.storage, (network_id, user_id.clone()));
+		INTERPRETERS.save(deps.storage, (network_id.into(), user_id), &interpreter_address)?;
+	}
+	let bridges = LEGACY_BRIDGES
+		.keys(deps.storage, None, None, Order::Ascending)
+		.filter_map(Result::ok)
+		.collect::<Vec<_>>();
+	for (network_id, address) in bridges {
+		LEGACY_BRIDGES.remove(deps.storage, (network_id, address.clone()));
+		BRIDGES.save(deps.storage, (network_id.into(), address), &Empty {})?;
+	}
+	Ok(())
+}
+
+fn ensure_admin(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+	if CONFIG.load(deps.storage)?.admin != info.sender {
+		return Err(ContractError::NotAuthorized)
+	}
+	Ok(())
+}
+
+/// Only the gateway and the bridges of `network_id` can run programs on behalf of its users. The
+/// execution fee, if any, must be sent along and is forwarded to its recipient. Any other funds
+/// sent along are refunded to the sender.
+fn ensure_authorized_run(
+	deps: Deps,
+	env: &Env,
+	info: &MessageInfo,
+	network_id: NetworkId,
+) -> Result<Response, ContractError> {
+	// The router calls itself back once the interpreter is instantiated.
+	if info.sender == env.contract.address {
+		return Ok(Response::new())
+	}
+
+	let Config { gateway_address, execution_fee, .. } = CONFIG.load(deps.storage)?;
+	if info.sender != gateway_address &&
+		!BRIDGES.has(deps.storage, (network_id.0, info.sender.clone()))
+	{
+		return Err(ContractError::NotAuthorized)
+	}
+
+	let mut response = Response::new();
+	let mut excess = info.funds.clone();
+	if let Some(ExecutionFee { amount, recipient }) = execution_fee {
+		let paid = excess
+			.iter_mut()
+			.find(|coin| coin.denom == amount.denom && coin.amount >= amount.amount)
+			.ok_or(ContractError::InsufficientFee)?;
+		paid.amount -= amount.amount;
+		response = response.add_message(BankMsg::Send {
+			to_address: recipient.into_string(),
+			amount: vec![amount],
+		});
+	}
+	excess.retain(|coin| !coin.amount.is_zero());
+	if !excess.is_empty() {
+		response = response
+			.add_message(BankMsg::Send { to_address: info.sender.to_string(), amount: excess });
+	}
+	Ok(response)
+}
+
 pub fn handle_run(
 	deps: DepsMut,
 	env: Env,
@@ -73,12 +221,14 @@ pub fn handle_run(
 			Ok(response.add_message(wasm_msg))
 		},
 		Err(_) => {
-			let Config { registry_address, interpreter_code_id } = CONFIG.load(deps.storage)?;
+			let Config { registry_address, gateway_address, interpreter_code_id, .. } =
+				CONFIG.load(deps.storage)?;
 			let instantiate_msg: CosmosMsg = WasmMsg::Instantiate {
 				admin: Some(env.contract.address.clone().into_string()),
 				code_id: interpreter_code_id,
 				msg: to_binary(&InterpreterInstantiateMsg {
 					registry_address: registry_address.into_string(),
+					gateway_address: gateway_address.into_string(),
 					network_id,
 					user_id: user_id.clone(),
 				})?,
@@ -99,6 +249,22 @@ pub fn handle_run(
 	}
 }
 
+pub fn handle_migrate_interpreter(
+	deps: DepsMut,
+	network_id: NetworkId,
+	user_id: UserId,
+) -> Result<Response, ContractError> {
+	let interpreter_address = INTERPRETERS.load(deps.storage, (network_id.0, user_id))?;
+	let Config { interpreter_code_id, .. } = CONFIG.load(deps.storage)?;
+	Ok(Response::new()
+		.add_message(WasmMsg::Migrate {
+			contract_addr: interpreter_address.into_string(),
+			new_code_id: interpreter_code_id,
+			msg: to_binary(&InterpreterMigrateMsg {})?,
+		})
+		.add_event(Event::new("xcvm.router.interpreter_migrated")))
+}
+
 fn send_funds_to_interpreter(
 	deps: Deps,
 	interpreter_address: Addr,
@@ -109,23 +275,20 @@ fn send_funds_to_interpreter(
 	let interpreter_address = interpreter_address.into_string();
 	for (asset_id, amount) in funds.0 {
 		let query_msg = AssetRegistryQueryMsg::GetAssetContract(asset_id.into());
-		let cw20_address: GetAssetContractResponse = deps.querier.query(
+		let asset: GetAssetContractResponse = deps.querier.query(
 			&WasmQuery::Smart {
 				contract_addr: registry_address.clone(),
 				msg: to_binary(&query_msg)?,
 			}
 			.into(),
 		)?;
-		let contract = Cw20Contract(cw20_address.addr.clone());
 
 		if amount.0 == 0 {
 			continue
 		}
 
-		response = response.add_message(contract.call(Cw20ExecuteMsg::Transfer {
-			recipient: interpreter_address.clone(),
-			amount: amount.0.into(),
-		})?);
+		response = response
+			.add_message(asset.reference.transfer_msg(interpreter_address.clone(), amount.0)?);
 	}
 	Ok(response)
 }
@@ -139,8 +302,39 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(_deps: Deps, _env: Env, _msg: QueryMsg) -> StdResult<Binary> {
-	Err(StdError::generic_err("not implemented"))
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+	match msg {
+		QueryMsg::Config {} => to_binary(&query_config(deps)?),
+		QueryMsg::GetInterpreter { network_id, user_id } =>
+			to_binary(&query_interpreter(deps, network_id, user_id)?),
+		QueryMsg::IsBridge { network_id, address } => {
+			let address = deps.api.addr_validate(&address)?;
+			to_binary(&IsBridgeResponse {
+				is_bridge: BRIDGES.has(deps.storage, (network_id.0, address)),
+			})
+		},
+	}
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+	let Config { admin, registry_address, gateway_address, interpreter_code_id, execution_fee } =
+		CONFIG.load(deps.storage)?;
+	Ok(ConfigResponse {
+		admin,
+		registry_address,
+		gateway_address,
+		interpreter_code_id,
+		execution_fee,
+	})
+}
+
+pub fn query_interpreter(
+	deps: Deps,
+	network_id: NetworkId,
+	user_id: UserId,
+) -> StdResult<GetInterpreterResponse> {
+	let addr = INTERPRETERS.load(deps.storage, (network_id.0, user_id))?;
+	Ok(GetInterpreterResponse { addr })
 }
 
 fn handle_instantiate_reply(deps: DepsMut, msg: Reply) -> StdResult<Response> {
@@ -168,7 +362,7 @@ fn handle_instantiate_reply(deps: DepsMut, msg: Reply) -> StdResult<Response> {
 			.find(|event| event.ty == "wasm-xcvm.interpreter.instantiated")
 			.ok_or(StdError::not_found("interpreter event not found"))?;
 
-		from_binary::<(u8, UserId)>(&Binary::from_base64(
+		from_binary::<(u32, UserId)>(&Binary::from_base64(
 			interpreter_event
 				.attributes
 				.iter()
@@ -188,20 +382,30 @@ fn handle_instantiate_reply(deps: DepsMut, msg: Reply) -> StdResult<Response> {
 mod tests {
 	use super::*;
 	use cosmwasm_std::{
+		coin,
 		testing::{mock_dependencies, mock_env, mock_info, MockQuerier, MOCK_CONTRACT_ADDR},
 		wasm_execute, Addr, ContractResult, QuerierResult, SystemResult,
 	};
-	use xcvm_core::{Amount, AssetId, Picasso, ETH, PICA};
+	use cw20::{Cw20Contract, Cw20ExecuteMsg};
+	use xcvm_asset_registry::msg::AssetReference;
+	use xcvm_core::{Amount, AssetId, Ethereum, Picasso, ETH, PICA};
 	use xcvm_interpreter::msg::{XCVMInstruction, XCVMProgram};
 
 	const CW20_ADDR: &str = "cw20addr";
 	const REGISTRY_ADDR: &str = "registryaddr";
+	const GATEWAY_ADDR: &str = "gatewayaddr";
+	const ADMIN_ADDR: &str = "admin";
 
 	#[test]
 	fn proper_instantiation() {
 		let mut deps = mock_dependencies();
 
-		let msg = InstantiateMsg { registry_address: "addr".to_string(), interpreter_code_id: 1 };
+		let msg = InstantiateMsg {
+			admin: ADMIN_ADDR.to_string(),
+			registry_address: "addr".to_string(),
+			gateway_address: GATEWAY_ADDR.to_string(),
+			interpreter_code_id: 1,
+		};
 		let info = mock_info("sender", &vec![]);
 
 		let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -210,7 +414,13 @@ mod tests {
 		// Make sure that the storage is empty
 		assert_eq!(
 			CONFIG.load(&deps.storage).unwrap(),
-			Config { registry_address: Addr::unchecked("addr"), interpreter_code_id: 1 }
+			Config {
+				admin: Addr::unchecked(ADMIN_ADDR),
+				registry_address: Addr::unchecked("addr"),
+				gateway_address: Addr::unchecked(GATEWAY_ADDR),
+				interpreter_code_id: 1,
+				execution_fee: None,
+			}
 		);
 	}
 
@@ -223,7 +433,9 @@ mod tests {
 			WasmQuery::Smart { contract_addr, .. } if contract_addr.as_str() == REGISTRY_ADDR =>
 				SystemResult::Ok(ContractResult::Ok(
 					to_binary(&xcvm_asset_registry::msg::GetAssetContractResponse {
-						addr: Addr::unchecked(CW20_ADDR),
+						reference: AssetReference::Virtual {
+							cw20_address: Addr::unchecked(CW20_ADDR),
+						},
 					})
 					.unwrap(),
 				))
@@ -239,12 +451,17 @@ mod tests {
 		querier.update_wasm(wasm_querier);
 		deps.querier = querier;
 
-		let info = mock_info("sender", &vec![]);
+		let info = mock_info(GATEWAY_ADDR, &vec![]);
 		let _ = instantiate(
 			deps.as_mut(),
 			mock_env(),
 			info.clone(),
-			InstantiateMsg { registry_address: REGISTRY_ADDR.into(), interpreter_code_id: 1 },
+			InstantiateMsg {
+				admin: ADMIN_ADDR.into(),
+				registry_address: REGISTRY_ADDR.into(),
+				gateway_address: GATEWAY_ADDR.into(),
+				interpreter_code_id: 1,
+			},
 		)
 		.unwrap();
 
@@ -259,7 +476,8 @@ mod tests {
 			}]
 			.into(),
 		};
-		let interpreter_execute_msg = InterpreterExecuteMsg::Execute { program };
+		let interpreter_execute_msg =
+			InterpreterExecuteMsg::Execute { relayer: Addr::unchecked("relayer"), program };
 
 		let funds =
 			Funds::<Displayed<u128>>::from([(Into::<AssetId>::into(PICA), Displayed(1000_u128))]);
@@ -278,6 +496,7 @@ mod tests {
 			code_id: 1,
 			msg: to_binary(&InterpreterInstantiateMsg {
 				registry_address: REGISTRY_ADDR.to_string(),
+				gateway_address: GATEWAY_ADDR.to_string(),
 				network_id: Picasso.into(),
 				user_id: vec![1],
 			})
@@ -303,12 +522,17 @@ mod tests {
 		querier.update_wasm(wasm_querier);
 		deps.querier = querier;
 
-		let info = mock_info("sender", &vec![]);
+		let info = mock_info(GATEWAY_ADDR, &vec![]);
 		let _ = instantiate(
 			deps.as_mut(),
 			mock_env(),
 			info.clone(),
-			InstantiateMsg { registry_address: REGISTRY_ADDR.into(), interpreter_code_id: 1 },
+			InstantiateMsg {
+				admin: ADMIN_ADDR.into(),
+				registry_address: REGISTRY_ADDR.into(),
+				gateway_address: GATEWAY_ADDR.into(),
+				interpreter_code_id: 1,
+			},
 		)
 		.unwrap();
 
@@ -331,7 +555,10 @@ mod tests {
 			}]
 			.into(),
 		};
-		let interpreter_execute_msg = InterpreterExecuteMsg::Execute { program };
+		let interpreter_execute_msg = InterpreterExecuteMsg::Execute {
+			relayer: Addr::unchecked("relayer"),
+			program: program.clone(),
+		};
 
 		let funds = Funds::<Displayed<u128>>::from([
 			(Into::<AssetId>::into(PICA), Displayed(1000_u128)),
@@ -366,7 +593,24 @@ mod tests {
 
 		messages.into_iter().enumerate().for_each(|(i, msg)| {
 			assert_eq!(res.messages[i].msg, msg);
-		})
+		});
+
+		// The binary encoded program is forwarded as is to the interpreter
+		let interpreter_execute_msg = InterpreterExecuteMsg::ExecuteBinary {
+			relayer: Addr::unchecked("relayer"),
+			program: program.encode_binary().into(),
+		};
+		let run_msg = ExecuteMsg::Run {
+			network_id: Picasso.into(),
+			user_id: vec![],
+			interpreter_execute_msg: interpreter_execute_msg.clone(),
+			funds,
+		};
+		let res = execute(deps.as_mut(), mock_env(), info.clone(), run_msg).unwrap();
+		assert_eq!(
+			res.messages.last().map(|msg| msg.msg.clone()),
+			Some(wasm_execute("interpreter", &interpreter_execute_msg, vec![]).unwrap().into())
+		);
 
 		/*
 		let execute_msg = WasmMsg::Execute {
@@ -376,4 +620,292 @@ mod tests {
 		};
 		*/
 	}
+
+	fn setup_with_interpreter(deps: DepsMut) {
+		instantiate(
+			deps,
+			mock_env(),
+			mock_info("sender", &vec![]),
+			InstantiateMsg {
+				admin: ADMIN_ADDR.into(),
+				registry_address: REGISTRY_ADDR.into(),
+				gateway_address: GATEWAY_ADDR.into(),
+				interpreter_code_id: 1,
+			},
+		)
+		.unwrap();
+	}
+
+	fn run_msg(network_id: NetworkId) -> ExecuteMsg {
+		ExecuteMsg::Run {
+			network_id,
+			user_id: vec![],
+			interpreter_execute_msg: InterpreterExecuteMsg::Execute {
+				relayer: Addr::unchecked("relayer"),
+				program: XCVMProgram { tag: vec![], instructions: vec![].into() },
+			},
+			funds: Funds::empty(),
+		}
+	}
+
+	#[test]
+	fn execute_run_access_control() {
+		let mut deps = mock_dependencies();
+		setup_with_interpreter(deps.as_mut());
+		INTERPRETERS
+			.save(
+				&mut deps.storage,
+				(Into::<NetworkId>::into(Picasso).0, vec![]),
+				&Addr::unchecked("interpreter"),
+			)
+			.unwrap();
+
+		// Anyone else than the gateway or a registered bridge is rejected
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info("bridge", &vec![]),
+				run_msg(Picasso.into())
+			),
+			Err(ContractError::NotAuthorized)
+		));
+
+		// Only the admin can register bridges
+		let register_msg =
+			ExecuteMsg::RegisterBridge { network_id: Picasso.into(), address: "bridge".into() };
+		assert!(matches!(
+			execute(deps.as_mut(), mock_env(), mock_info("bridge", &vec![]), register_msg.clone()),
+			Err(ContractError::NotAuthorized)
+		));
+		execute(deps.as_mut(), mock_env(), mock_info(ADMIN_ADDR, &vec![]), register_msg).unwrap();
+		assert_eq!(
+			from_binary::<IsBridgeResponse>(
+				&query(
+					deps.as_ref(),
+					mock_env(),
+					QueryMsg::IsBridge { network_id: Picasso.into(), address: "bridge".into() }
+				)
+				.unwrap()
+			)
+			.unwrap(),
+			IsBridgeResponse { is_bridge: true }
+		);
+		execute(deps.as_mut(), mock_env(), mock_info("bridge", &vec![]), run_msg(Picasso.into()))
+			.unwrap();
+
+		// A bridge is only authorized for the network it is registered for
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info("bridge", &vec![]),
+				run_msg(Ethereum.into())
+			),
+			Err(ContractError::NotAuthorized)
+		));
+
+		execute(
+			deps.as_mut(),
+			mock_env(),
+			mock_info(ADMIN_ADDR, &vec![]),
+			ExecuteMsg::UnregisterBridge { network_id: Picasso.into(), address: "bridge".into() },
+		)
+		.unwrap();
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info("bridge", &vec![]),
+				run_msg(Picasso.into())
+			),
+			Err(ContractError::NotAuthorized)
+		));
+	}
+
+	#[test]
+	fn execute_run_with_fee() {
+		let mut deps = mock_dependencies();
+		setup_with_interpreter(deps.as_mut());
+		INTERPRETERS
+			.save(
+				&mut deps.storage,
+				(Into::<NetworkId>::into(Picasso).0, vec![]),
+				&Addr::unchecked("interpreter"),
+			)
+			.unwrap();
+
+		let execution_fee =
+			ExecutionFee { amount: coin(10, "upica"), recipient: Addr::unchecked("treasury") };
+		execute(
+			deps.as_mut(),
+			mock_env(),
+			mock_info(ADMIN_ADDR, &vec![]),
+			ExecuteMsg::SetExecutionFee { execution_fee: Some(execution_fee.clone()) },
+		)
+		.unwrap();
+		assert_eq!(query_config(deps.as_ref()).unwrap().execution_fee, Some(execution_fee));
+
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info(GATEWAY_ADDR, &vec![]),
+				run_msg(Picasso.into())
+			),
+			Err(ContractError::InsufficientFee)
+		));
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info(GATEWAY_ADDR, &[coin(9, "upica")]),
+				run_msg(Picasso.into())
+			),
+			Err(ContractError::InsufficientFee)
+		));
+
+		let res = execute(
+			deps.as_mut(),
+			mock_env(),
+			mock_info(GATEWAY_ADDR, &[coin(10, "upica")]),
+			run_msg(Picasso.into()),
+		)
+		.unwrap();
+		assert_eq!(
+			res.messages[0].msg,
+			BankMsg::Send { to_address: "treasury".into(), amount: vec![coin(10, "upica")] }.into()
+		);
+		assert_eq!(res.messages.len(), 2);
+
+		// The excess is refunded
+		let res = execute(
+			deps.as_mut(),
+			mock_env(),
+			mock_info(GATEWAY_ADDR, &[coin(15, "upica"), coin(3, "uatom")]),
+			run_msg(Picasso.into()),
+		)
+		.unwrap();
+		assert_eq!(
+			res.messages[1].msg,
+			BankMsg::Send {
+				to_address: GATEWAY_ADDR.into(),
+				amount: vec![coin(5, "upica"), coin(3, "uatom")]
+			}
+			.into()
+		);
+	}
+
+	#[test]
+	fn migrate_legacy_config() {
+		let mut deps = mock_dependencies();
+		set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.1.0").unwrap();
+		LEGACY_CONFIG
+			.save(
+				deps.as_mut().storage,
+				&LegacyConfig {
+					registry_address: Addr::unchecked(REGISTRY_ADDR),
+					gateway_address: Addr::unchecked(GATEWAY_ADDR),
+					interpreter_code_id: 1,
+				},
+			)
+			.unwrap();
+
+		assert!(matches!(
+			migrate(deps.as_mut(), mock_env(), MigrateMsg { admin: None }),
+			Err(ContractError::MissingAdmin)
+		));
+		migrate(deps.as_mut(), mock_env(), MigrateMsg { admin: Some(ADMIN_ADDR.into()) }).unwrap();
+		assert_eq!(
+			CONFIG.load(&deps.storage).unwrap(),
+			Config {
+				admin: Addr::unchecked(ADMIN_ADDR),
+				registry_address: Addr::unchecked(REGISTRY_ADDR),
+				gateway_address: Addr::unchecked(GATEWAY_ADDR),
+				interpreter_code_id: 1,
+				execution_fee: None,
+			}
+		);
+
+		// Migrating again keeps the configuration
+		migrate(deps.as_mut(), mock_env(), MigrateMsg { admin: None }).unwrap();
+		assert_eq!(CONFIG.load(&deps.storage).unwrap().admin, Addr::unchecked(ADMIN_ADDR));
+	}
+
+	#[test]
+	fn migrate_legacy_network_ids() {
+		let mut deps = mock_dependencies();
+		setup_with_interpreter(deps.as_mut());
+		let storage = deps.as_mut().storage;
+		LEGACY_INTERPRETERS
+			.save(storage, (1, vec![1]), &Addr::unchecked("legacy_interpreter"))
+			.unwrap();
+		INTERPRETERS
+			.save(storage, (1, vec![2]), &Addr::unchecked("interpreter"))
+			.unwrap();
+		LEGACY_BRIDGES.save(storage, (2, Addr::unchecked("bridge")), &Empty {}).unwrap();
+
+		migrate(deps.as_mut(), mock_env(), MigrateMsg { admin: None }).unwrap();
+
+		assert_eq!(
+			query_interpreter(deps.as_ref(), Picasso.into(), vec![1]).unwrap().addr,
+			Addr::unchecked("legacy_interpreter")
+		);
+		assert_eq!(
+			query_interpreter(deps.as_ref(), Picasso.into(), vec![2]).unwrap().addr,
+			Addr::unchecked("interpreter")
+		);
+		assert!(BRIDGES.has(&deps.storage, (2, Addr::unchecked("bridge"))));
+		assert_eq!(
+			LEGACY_INTERPRETERS
+				.keys(&deps.storage, None, None, Order::Ascending)
+				.filter(Result::is_ok)
+				.count(),
+			0
+		);
+	}
+
+	#[test]
+	fn migrate_interpreter() {
+		let mut deps = mock_dependencies();
+		setup_with_interpreter(deps.as_mut());
+		INTERPRETERS
+			.save(
+				&mut deps.storage,
+				(Into::<NetworkId>::into(Picasso).0, vec![]),
+				&Addr::unchecked("interpreter"),
+			)
+			.unwrap();
+
+		let migrate_msg =
+			ExecuteMsg::MigrateInterpreter { network_id: Picasso.into(), user_id: vec![] };
+		assert!(matches!(
+			execute(
+				deps.as_mut(),
+				mock_env(),
+				mock_info(GATEWAY_ADDR, &vec![]),
+				migrate_msg.clone()
+			),
+			Err(ContractError::NotAuthorized)
+		));
+
+		execute(
+			deps.as_mut(),
+			mock_env(),
+			mock_info(ADMIN_ADDR, &vec![]),
+			ExecuteMsg::SetInterpreterCodeId { code_id: 2 },
+		)
+		.unwrap();
+		let res = execute(deps.as_mut(), mock_env(), mock_info(ADMIN_ADDR, &vec![]), migrate_msg)
+			.unwrap();
+		assert_eq!(
+			res.messages[0].msg,
+			WasmMsg::Migrate {
+				contract_addr: "interpreter".into(),
+				new_code_id: 2,
+				msg: to_binary(&InterpreterMigrateMsg {}).unwrap(),
+			}
+			.into()
+		);
+	}
 }
@@ -5,4 +5,13 @@ use thiserror::Error;
 pub enum ContractError {
 	#[error("{0}")]
 	Std(#[from] StdError),
+
+	#[error("Caller is not authorized to take this action")]
+	NotAuthorized,
+
+	#[error("An admin must be given to migrate a router without admin")]
+	MissingAdmin,
+
+	#[error("Execution fee is not paid")]
+	InsufficientFee,
 }
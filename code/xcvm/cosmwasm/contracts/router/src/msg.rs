@@ -1,4 +1,5 @@
-use crate::state::UserId;
+use crate::state::{ExecutionFee, UserId};
+use cosmwasm_std::Addr;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use xcvm_core::{Displayed, Funds, NetworkId};
@@ -6,23 +7,80 @@ use xcvm_interpreter::msg::ExecuteMsg as InterpreterExecuteMsg;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
+	pub admin: String,
 	pub registry_address: String,
+	pub gateway_address: String,
 	pub interpreter_code_id: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
+	/// Execute a program on the interpreter of `(network_id, user_id)`, instantiating it if
+	/// needed. Only callable by the gateway or a bridge registered for `network_id`.
 	Run {
 		network_id: NetworkId,
 		user_id: UserId,
 		interpreter_execute_msg: InterpreterExecuteMsg,
 		funds: Funds<Displayed<u128>>,
 	},
+	/// Allow `address` to run programs on behalf of the users of `network_id`.
+	RegisterBridge {
+		network_id: NetworkId,
+		address: String,
+	},
+	UnregisterBridge {
+		network_id: NetworkId,
+		address: String,
+	},
+	/// Set or remove the fee charged for each `Run`.
+	SetExecutionFee {
+		execution_fee: Option<ExecutionFee>,
+	},
+	/// Code used for the interpreters instantiated from now on.
+	SetInterpreterCodeId {
+		code_id: u64,
+	},
+	/// Migrate an existing interpreter to the current interpreter code.
+	MigrateInterpreter {
+		network_id: NetworkId,
+		user_id: UserId,
+	},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+	/// Admin of a router instantiated before it had one, required for such routers. Replaces the
+	/// current admin otherwise.
+	pub admin: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+	/// Get the configuration of the router.
+	Config {},
+	/// Get the interpreter of a `(network_id, user_id)` pair.
+	GetInterpreter { network_id: NetworkId, user_id: UserId },
+	/// Whether `address` is a bridge registered for `network_id`.
+	IsBridge { network_id: NetworkId, address: String },
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub enum QueryMsg {}
+pub struct ConfigResponse {
+	pub admin: Addr,
+	pub registry_address: Addr,
+	pub gateway_address: Addr,
+	pub interpreter_code_id: u64,
+	pub execution_fee: Option<ExecutionFee>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetInterpreterResponse {
+	pub addr: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IsBridgeResponse {
+	pub is_bridge: bool,
+}
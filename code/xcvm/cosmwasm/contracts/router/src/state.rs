@@ -1,15 +1,43 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Coin, Empty};
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
+	pub admin: Addr,
 	pub registry_address: Addr,
+	pub gateway_address: Addr,
 	pub interpreter_code_id: u64,
+	pub execution_fee: Option<ExecutionFee>,
+}
+
+/// Configuration of the routers instantiated before they had an admin and an execution fee.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+	pub registry_address: Addr,
+	pub gateway_address: Addr,
+	pub interpreter_code_id: u64,
+}
+
+/// Fee charged for each `Run` and sent to `recipient`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExecutionFee {
+	pub amount: Coin,
+	pub recipient: Addr,
 }
 
 pub type UserId = Vec<u8>;
 
-pub const INTERPRETERS: Map<(u8, UserId), Addr> = Map::new("interpreters");
+pub const INTERPRETERS: Map<(u32, UserId), Addr> = Map::new("interpreters");
+/// Bridges allowed to run programs on behalf of the users of a network.
+pub const BRIDGES: Map<(u32, Addr), Empty> = Map::new("bridges");
+
+/// [`INTERPRETERS`] and [`BRIDGES`] keyed by the former single byte network IDs, rewritten on
+/// migration. Keys of both encodings can't be mistaken for one another as the network ID is
+/// length prefixed.
+pub const LEGACY_INTERPRETERS: Map<(u8, UserId), Addr> = Map::new("interpreters");
+pub const LEGACY_BRIDGES: Map<(u8, Addr), Empty> = Map::new("bridges");
 pub const CONFIG: Item<Config> = Item::new("config");
+/// [`CONFIG`] of the routers instantiated before they had an admin, rewritten on migration.
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
@@ -0,0 +1,582 @@
+//! Compact binary encoding of programs, following the protobuf schema of `proto/xcvm.proto`.
+//!
+//! Amounts are encoded as varints rather than strings as in JSON, making bridge payloads
+//! significantly smaller.
+
+use crate::{
+	Amount, AssetId, BindingValue, Displayed, Funds, Instruction, NetworkId, Predicate, Program,
+};
+use alloc::{collections::VecDeque, string::String, vec::Vec};
+use prost::Message;
+
+/// Version of the binary encoding, increased on every backward incompatible change.
+pub const BINARY_ENCODING_VERSION: u32 = 1;
+
+/// Instruction in its binary encodable form.
+pub type BinaryInstruction<Account> = Instruction<NetworkId, Vec<u8>, Account, Funds>;
+
+/// Program in its binary encodable form.
+pub type BinaryProgram<Account> = Program<VecDeque<BinaryInstruction<Account>>>;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BinaryError {
+	/// The bytes are not a protobuf encoded `VersionedProgram`.
+	InvalidEncoding,
+	/// The program has been encoded with an unknown version.
+	UnsupportedVersion(u32),
+	/// A required field is missing.
+	MissingField,
+	/// A value is out of the range of its type.
+	InvalidValue,
+}
+
+/// Account that can be represented as raw bytes.
+pub trait BinaryAccount: Sized {
+	fn to_bytes(&self) -> Vec<u8>;
+	fn from_bytes(bytes: Vec<u8>) -> Option<Self>;
+}
+
+impl BinaryAccount for Vec<u8> {
+	fn to_bytes(&self) -> Vec<u8> {
+		self.clone()
+	}
+
+	fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+		Some(bytes)
+	}
+}
+
+impl BinaryAccount for String {
+	fn to_bytes(&self) -> Vec<u8> {
+		self.as_bytes().to_vec()
+	}
+
+	fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+		String::from_utf8(bytes).ok()
+	}
+}
+
+impl BinaryAccount for () {
+	fn to_bytes(&self) -> Vec<u8> {
+		Vec::new()
+	}
+
+	fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+		bytes.is_empty().then_some(())
+	}
+}
+
+impl<Account: BinaryAccount> BinaryProgram<Account> {
+	/// Encode the program along the version of the encoding.
+	pub fn encode_binary(&self) -> Vec<u8> {
+		proto::VersionedProgram {
+			version: BINARY_ENCODING_VERSION,
+			program: Some(encode_program(self)),
+		}
+		.encode_to_vec()
+	}
+
+	/// Decode a program encoded by [`Self::encode_binary`].
+	pub fn decode_binary(bytes: &[u8]) -> Result<Self, BinaryError> {
+		let proto::VersionedProgram { version, program } =
+			proto::VersionedProgram::decode(bytes).map_err(|_| BinaryError::InvalidEncoding)?;
+		if version != BINARY_ENCODING_VERSION {
+			return Err(BinaryError::UnsupportedVersion(version))
+		}
+		decode_program(program.ok_or(BinaryError::MissingField)?)
+	}
+}
+
+fn encode_program<Account: BinaryAccount>(program: &BinaryProgram<Account>) -> proto::Program {
+	proto::Program {
+		tag: program.tag.clone(),
+		instructions: encode_instructions(&program.instructions),
+	}
+}
+
+fn decode_program<Account: BinaryAccount>(
+	program: proto::Program,
+) -> Result<BinaryProgram<Account>, BinaryError> {
+	Ok(Program { tag: program.tag, instructions: decode_instructions(program.instructions)? })
+}
+
+fn encode_instructions<Account: BinaryAccount>(
+	instructions: &VecDeque<BinaryInstruction<Account>>,
+) -> Vec<proto::Instruction> {
+	instructions.iter().map(encode_instruction).collect()
+}
+
+fn decode_instructions<Account: BinaryAccount>(
+	instructions: Vec<proto::Instruction>,
+) -> Result<VecDeque<BinaryInstruction<Account>>, BinaryError> {
+	instructions.into_iter().map(decode_instruction).collect()
+}
+
+fn encode_instruction<Account: BinaryAccount>(
+	instruction: &BinaryInstruction<Account>,
+) -> proto::Instruction {
+	use proto::instruction::Instruction as I;
+	let instruction = match instruction {
+		Instruction::Transfer { to, assets } =>
+			I::Transfer(proto::Transfer { to: to.to_bytes(), assets: encode_funds(assets) }),
+		Instruction::Call { encoded, bindings } => I::Call(proto::Call {
+			encoded: encoded.clone(),
+			bindings: bindings
+				.iter()
+				.map(|(offset, value)| proto::Binding {
+					offset: (*offset).into(),
+					value: Some(encode_binding_value(value)),
+				})
+				.collect(),
+		}),
+		Instruction::Spawn { network, salt, assets, program } => I::Spawn(proto::Spawn {
+			network: network.0,
+			salt: salt.clone(),
+			assets: encode_funds(assets),
+			program: Some(encode_program(program)),
+		}),
+		Instruction::Assert { predicate } =>
+			I::Assert(proto::Assert { predicate: Some(encode_predicate(predicate)) }),
+		Instruction::IfElse { predicate, then, otherwise } => I::IfElse(proto::IfElse {
+			predicate: Some(encode_predicate(predicate)),
+			then: encode_instructions(then),
+			otherwise: encode_instructions(otherwise),
+		}),
+		Instruction::Repeat { times, instructions } => I::Repeat(proto::Repeat {
+			times: *times,
+			instructions: encode_instructions(instructions),
+		}),
+	};
+	proto::Instruction { instruction: Some(instruction) }
+}
+
+fn decode_instruction<Account: BinaryAccount>(
+	instruction: proto::Instruction,
+) -> Result<BinaryInstruction<Account>, BinaryError> {
+	use proto::instruction::Instruction as I;
+	Ok(match instruction.instruction.ok_or(BinaryError::MissingField)? {
+		I::Transfer(proto::Transfer { to, assets }) => Instruction::Transfer {
+			to: Account::from_bytes(to).ok_or(BinaryError::InvalidValue)?,
+			assets: decode_funds(assets)?,
+		},
+		I::Call(proto::Call { encoded, bindings }) => Instruction::Call {
+			encoded,
+			bindings: bindings
+				.into_iter()
+				.map(|proto::Binding { offset, value }| {
+					Ok((
+						u16::try_from(offset).map_err(|_| BinaryError::InvalidValue)?,
+						decode_binding_value(value.ok_or(BinaryError::MissingField)?)?,
+					))
+				})
+				.collect::<Result<_, _>>()?,
+		},
+		I::Spawn(proto::Spawn { network, salt, assets, program }) => Instruction::Spawn {
+			network: NetworkId(network),
+			salt,
+			assets: decode_funds(assets)?,
+			program: decode_program(program.ok_or(BinaryError::MissingField)?)?,
+		},
+		I::Assert(proto::Assert { predicate }) => Instruction::Assert {
+			predicate: decode_predicate(predicate.ok_or(BinaryError::MissingField)?)?,
+		},
+		I::IfElse(proto::IfElse { predicate, then, otherwise }) => Instruction::IfElse {
+			predicate: decode_predicate(predicate.ok_or(BinaryError::MissingField)?)?,
+			then: decode_instructions(then)?,
+			otherwise: decode_instructions(otherwise)?,
+		},
+		I::Repeat(proto::Repeat { times, instructions }) =>
+			Instruction::Repeat { times, instructions: decode_instructions(instructions)? },
+	})
+}
+
+fn encode_funds(Funds(funds): &Funds) -> Vec<proto::Asset> {
+	funds
+		.iter()
+		.map(|(asset_id, amount)| proto::Asset {
+			asset_id: Some(encode_u128(asset_id.0)),
+			amount: Some(proto::Amount {
+				intercept: Some(encode_u128(amount.intercept.0)),
+				slope: Some(encode_u128(amount.slope.0)),
+			}),
+		})
+		.collect()
+}
+
+fn decode_funds(assets: Vec<proto::Asset>) -> Result<Funds, BinaryError> {
+	Ok(Funds(
+		assets
+			.into_iter()
+			.map(|proto::Asset { asset_id, amount }| {
+				let proto::Amount { intercept, slope } = amount.ok_or(BinaryError::MissingField)?;
+				Ok((
+					AssetId(decode_u128(asset_id)?),
+					Amount {
+						intercept: Displayed(decode_u128(intercept)?),
+						slope: Displayed(decode_u128(slope)?),
+					},
+				))
+			})
+			.collect::<Result<_, _>>()?,
+	))
+}
+
+fn encode_binding_value(value: &BindingValue) -> proto::BindingValue {
+	use proto::binding_value::Value as V;
+	let value = match value {
+		BindingValue::Self_ => V::Self_(proto::Empty {}),
+		BindingValue::Relayer => V::Relayer(proto::Empty {}),
+		BindingValue::Result => V::Result(proto::Empty {}),
+		BindingValue::Balance(asset_id) => V::Balance(encode_u128(asset_id.0)),
+		BindingValue::AssetId(asset_id) => V::AssetId(encode_u128(asset_id.0)),
+	};
+	proto::BindingValue { value: Some(value) }
+}
+
+fn decode_binding_value(value: proto::BindingValue) -> Result<BindingValue, BinaryError> {
+	use proto::binding_value::Value as V;
+	Ok(match value.value.ok_or(BinaryError::MissingField)? {
+		V::Self_(_) => BindingValue::Self_,
+		V::Relayer(_) => BindingValue::Relayer,
+		V::Result(_) => BindingValue::Result,
+		V::Balance(asset_id) => BindingValue::Balance(AssetId(decode_u128(Some(asset_id))?)),
+		V::AssetId(asset_id) => BindingValue::AssetId(AssetId(decode_u128(Some(asset_id))?)),
+	})
+}
+
+fn encode_predicate(predicate: &Predicate<Vec<u8>>) -> proto::Predicate {
+	use proto::predicate::Predicate as P;
+	let balance = |asset: &AssetId, amount: &Displayed<u128>| proto::BalancePredicate {
+		asset_id: Some(encode_u128(asset.0)),
+		amount: Some(encode_u128(amount.0)),
+	};
+	let predicate = match predicate {
+		Predicate::BalanceAtLeast { asset, amount } => P::BalanceAtLeast(balance(asset, amount)),
+		Predicate::BalanceAtMost { asset, amount } => P::BalanceAtMost(balance(asset, amount)),
+		Predicate::QueryEq { query, expected } =>
+			P::QueryEq(proto::QueryEq { query: query.clone(), expected: expected.clone() }),
+	};
+	proto::Predicate { predicate: Some(predicate) }
+}
+
+fn decode_predicate(predicate: proto::Predicate) -> Result<Predicate<Vec<u8>>, BinaryError> {
+	use proto::predicate::Predicate as P;
+	let balance = |proto::BalancePredicate { asset_id, amount }| -> Result<_, BinaryError> {
+		Ok((AssetId(decode_u128(asset_id)?), Displayed(decode_u128(amount)?)))
+	};
+	Ok(match predicate.predicate.ok_or(BinaryError::MissingField)? {
+		P::BalanceAtLeast(predicate) => {
+			let (asset, amount) = balance(predicate)?;
+			Predicate::BalanceAtLeast { asset, amount }
+		},
+		P::BalanceAtMost(predicate) => {
+			let (asset, amount) = balance(predicate)?;
+			Predicate::BalanceAtMost { asset, amount }
+		},
+		P::QueryEq(proto::QueryEq { query, expected }) => Predicate::QueryEq { query, expected },
+	})
+}
+
+fn encode_u128(x: u128) -> proto::Uint128 {
+	proto::Uint128 { high_bits: (x >> 64) as u64, low_bits: x as u64 }
+}
+
+fn decode_u128(x: Option<proto::Uint128>) -> Result<u128, BinaryError> {
+	let proto::Uint128 { high_bits, low_bits } = x.ok_or(BinaryError::MissingField)?;
+	Ok((u128::from(high_bits) << 64) | u128::from(low_bits))
+}
+
+/// Messages of `proto/xcvm.proto`, as generated by `prost-build`.
+pub mod proto {
+	use alloc::vec::Vec;
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct VersionedProgram {
+		#[prost(uint32, tag = "1")]
+		pub version: u32,
+		#[prost(message, optional, tag = "2")]
+		pub program: Option<Program>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Program {
+		#[prost(bytes = "vec", tag = "1")]
+		pub tag: Vec<u8>,
+		#[prost(message, repeated, tag = "2")]
+		pub instructions: Vec<Instruction>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Instruction {
+		#[prost(oneof = "instruction::Instruction", tags = "1, 2, 3, 4, 5, 6")]
+		pub instruction: Option<instruction::Instruction>,
+	}
+
+	pub mod instruction {
+		#[derive(Clone, PartialEq, ::prost::Oneof)]
+		pub enum Instruction {
+			#[prost(message, tag = "1")]
+			Transfer(super::Transfer),
+			#[prost(message, tag = "2")]
+			Call(super::Call),
+			#[prost(message, tag = "3")]
+			Spawn(super::Spawn),
+			#[prost(message, tag = "4")]
+			Assert(super::Assert),
+			#[prost(message, tag = "5")]
+			IfElse(super::IfElse),
+			#[prost(message, tag = "6")]
+			Repeat(super::Repeat),
+		}
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Transfer {
+		#[prost(bytes = "vec", tag = "1")]
+		pub to: Vec<u8>,
+		#[prost(message, repeated, tag = "2")]
+		pub assets: Vec<Asset>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Call {
+		#[prost(bytes = "vec", tag = "1")]
+		pub encoded: Vec<u8>,
+		#[prost(message, repeated, tag = "2")]
+		pub bindings: Vec<Binding>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Spawn {
+		#[prost(uint32, tag = "1")]
+		pub network: u32,
+		#[prost(bytes = "vec", tag = "2")]
+		pub salt: Vec<u8>,
+		#[prost(message, repeated, tag = "3")]
+		pub assets: Vec<Asset>,
+		#[prost(message, optional, tag = "4")]
+		pub program: Option<Program>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Assert {
+		#[prost(message, optional, tag = "1")]
+		pub predicate: Option<Predicate>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct IfElse {
+		#[prost(message, optional, tag = "1")]
+		pub predicate: Option<Predicate>,
+		#[prost(message, repeated, tag = "2")]
+		pub then: Vec<Instruction>,
+		#[prost(message, repeated, tag = "3")]
+		pub otherwise: Vec<Instruction>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Repeat {
+		#[prost(uint32, tag = "1")]
+		pub times: u32,
+		#[prost(message, repeated, tag = "2")]
+		pub instructions: Vec<Instruction>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Uint128 {
+		#[prost(uint64, tag = "1")]
+		pub high_bits: u64,
+		#[prost(uint64, tag = "2")]
+		pub low_bits: u64,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Amount {
+		#[prost(message, optional, tag = "1")]
+		pub intercept: Option<Uint128>,
+		#[prost(message, optional, tag = "2")]
+		pub slope: Option<Uint128>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Asset {
+		#[prost(message, optional, tag = "1")]
+		pub asset_id: Option<Uint128>,
+		#[prost(message, optional, tag = "2")]
+		pub amount: Option<Amount>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Binding {
+		#[prost(uint32, tag = "1")]
+		pub offset: u32,
+		#[prost(message, optional, tag = "2")]
+		pub value: Option<BindingValue>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct BindingValue {
+		#[prost(oneof = "binding_value::Value", tags = "1, 2, 3, 4, 5")]
+		pub value: Option<binding_value::Value>,
+	}
+
+	pub mod binding_value {
+		#[derive(Clone, PartialEq, ::prost::Oneof)]
+		pub enum Value {
+			#[prost(message, tag = "1")]
+			Self_(super::Empty),
+			#[prost(message, tag = "2")]
+			Relayer(super::Empty),
+			#[prost(message, tag = "3")]
+			Result(super::Empty),
+			#[prost(message, tag = "4")]
+			Balance(super::Uint128),
+			#[prost(message, tag = "5")]
+			AssetId(super::Uint128),
+		}
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Empty {}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct BalancePredicate {
+		#[prost(message, optional, tag = "1")]
+		pub asset_id: Option<Uint128>,
+		#[prost(message, optional, tag = "2")]
+		pub amount: Option<Uint128>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct QueryEq {
+		#[prost(bytes = "vec", tag = "1")]
+		pub query: Vec<u8>,
+		#[prost(bytes = "vec", tag = "2")]
+		pub expected: Vec<u8>,
+	}
+
+	#[derive(Clone, PartialEq, ::prost::Message)]
+	pub struct Predicate {
+		#[prost(oneof = "predicate::Predicate", tags = "1, 2, 3")]
+		pub predicate: Option<predicate::Predicate>,
+	}
+
+	pub mod predicate {
+		#[derive(Clone, PartialEq, ::prost::Oneof)]
+		pub enum Predicate {
+			#[prost(message, tag = "1")]
+			BalanceAtLeast(super::BalancePredicate),
+			#[prost(message, tag = "2")]
+			BalanceAtMost(super::BalancePredicate),
+			#[prost(message, tag = "3")]
+			QueryEq(super::QueryEq),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		Asset, Juno, Network, Picasso, ProgramBuilder, Protocol, RepeatTooLarge, ETH, PICA, USDT,
+	};
+	use alloc::{string::ToString, vec};
+	use codec::{Decode, Encode};
+
+	#[derive(Debug)]
+	struct BuildError;
+	impl From<RepeatTooLarge> for BuildError {
+		fn from(_: RepeatTooLarge) -> Self {
+			BuildError
+		}
+	}
+
+	struct DummyProtocol;
+	impl Protocol<Picasso> for DummyProtocol {
+		type Error = BuildError;
+		fn serialize(&self) -> Result<<Picasso as Network>::EncodedCall, Self::Error> {
+			Ok(vec![0xCA, 0xFE, 0xBE, 0xEF])
+		}
+	}
+
+	fn program() -> BinaryProgram<String> {
+		let predicate = Predicate::BalanceAtLeast { asset: PICA::ID, amount: Displayed(1_000_000) };
+		ProgramBuilder::<Picasso, String, Funds>::new(b"swap".to_vec())
+			.call(DummyProtocol)
+			.and_then(|builder| {
+				builder.if_else::<_, _, BuildError>(
+					predicate.clone(),
+					|then| {
+						Ok(then.call_raw_with_bindings(
+							vec![0x01, 0x02],
+							vec![(1, BindingValue::Self_), (2, BindingValue::Balance(USDT::ID))],
+						))
+					},
+					|otherwise| {
+						Ok(otherwise.assert(Predicate::QueryEq {
+							query: b"query".to_vec(),
+							expected: b"expected".to_vec(),
+						}))
+					},
+				)
+			})
+			.and_then(|builder| {
+				builder.spawn::<Juno, Juno, BuildError, _>(
+					b"juno".to_vec(),
+					b"salt".to_vec(),
+					Funds::from([(ETH::ID, Amount::ratio(u128::MAX)), (USDT::ID, 1_000.into())]),
+					|child| {
+						child.repeat(2, |body| {
+							Ok(body.transfer("juno1user".to_string(), Funds::from([(PICA::ID, 1)])))
+						})
+					},
+				)
+			})
+			.map(|builder| builder.assert(predicate))
+			.expect("valid program")
+			.build()
+	}
+
+	#[test]
+	fn binary_round_trip() {
+		let program = program();
+		let encoded = program.encode_binary();
+		assert_eq!(BinaryProgram::<String>::decode_binary(&encoded), Ok(program.clone()));
+
+		// The program decoded from JSON and SCALE has the same binary encoding
+		let json = serde_json::to_vec(&program).expect("json encodable");
+		let from_json: BinaryProgram<String> =
+			serde_json::from_slice(&json).expect("json decodable");
+		assert_eq!(from_json.encode_binary(), encoded);
+		let scale = program.encode();
+		let from_scale =
+			BinaryProgram::<String>::decode(&mut scale.as_slice()).expect("scale decodable");
+		assert_eq!(from_scale.encode_binary(), encoded);
+
+		// Amounts are varints instead of strings
+		assert!(encoded.len() < json.len() / 2);
+	}
+
+	#[test]
+	fn binary_versioning() {
+		let program = proto::VersionedProgram {
+			version: BINARY_ENCODING_VERSION + 1,
+			program: Some(encode_program(&program())),
+		};
+		assert_eq!(
+			BinaryProgram::<String>::decode_binary(&program.encode_to_vec()),
+			Err(BinaryError::UnsupportedVersion(BINARY_ENCODING_VERSION + 1))
+		);
+		assert_eq!(
+			BinaryProgram::<String>::decode_binary(&[0xFF, 0xFF]),
+			Err(BinaryError::InvalidEncoding)
+		);
+		assert_eq!(
+			BinaryProgram::<String>::decode_binary(
+				&proto::VersionedProgram { version: BINARY_ENCODING_VERSION, program: None }
+					.encode_to_vec()
+			),
+			Err(BinaryError::MissingField)
+		);
+	}
+}
@@ -1,4 +1,4 @@
-use crate::Program;
+use crate::{AssetId, Displayed, Program};
 use alloc::{collections::VecDeque, vec::Vec};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
@@ -23,12 +23,102 @@ pub enum Instruction<Network, Payload, Account, Assets> {
 	/// Depending on the network, the payload might be more structured than the base call.
 	/// For most of the network in fact, we need to provide the target address along the payload,
 	/// which can be encoded inside this single payload.
+	///
+	/// The [`Bindings`] are interpolated in the payload by the interpreter before executing it.
 	#[serde(rename_all = "snake_case")]
-	Call { encoded: Payload },
+	Call { encoded: Payload, bindings: Bindings },
 	/// Spawn a sub-program on the target `network`.
 	///
 	/// The program will be spawned with the desired [`Assets`].
 	/// The salt is used to track the program when events are dispatched in the network.
 	#[serde(rename_all = "snake_case")]
 	Spawn { network: Network, salt: Vec<u8>, assets: Assets, program: Program<VecDeque<Self>> },
+	/// Abort the program if the [`Predicate`] does not hold.
+	///
+	/// Predicates are evaluated against the state of the current network at the time the
+	/// instruction is reached, e.g. after the previous `Call` has been executed.
+	#[serde(rename_all = "snake_case")]
+	Assert { predicate: Predicate<Payload> },
+	/// Execute the `then` instructions if the [`Predicate`] holds, the `otherwise` instructions if
+	/// not. The branch is executed in place of this instruction, before the remaining ones.
+	#[serde(rename_all = "snake_case")]
+	IfElse { predicate: Predicate<Payload>, then: VecDeque<Self>, otherwise: VecDeque<Self> },
+	/// Execute the `instructions` `times` times in a row, in place of this instruction.
+	///
+	/// `times` must not exceed [`MAX_REPEAT`], the program is aborted otherwise.
+	#[serde(rename_all = "snake_case")]
+	Repeat { times: u32, instructions: VecDeque<Self> },
+}
+
+/// Values interpolated in a `Call` payload, as `(offset, value)` pairs sorted by offset.
+///
+/// Each value is inserted at its byte offset in the original payload.
+pub type Bindings = Vec<(u16, BindingValue)>;
+
+/// Value only known by the interpreter executing the program.
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingValue {
+	/// Address of the interpreter.
+	#[serde(rename = "self")]
+	Self_,
+	/// Address of the relayer that submitted the program.
+	Relayer,
+	/// Data returned by the last executed `Call`.
+	Result,
+	/// Balance of the interpreter of the asset.
+	Balance(AssetId),
+	/// Local reference of the asset, e.g. its cw20 contract address on cosmos.
+	AssetId(AssetId),
+}
+
+/// Maximum number of iterations of a `Repeat` instruction.
+pub const MAX_REPEAT: u32 = 64;
+
+/// Maximum number of instructions a program may execute once its `Repeat` and `IfElse`
+/// instructions are expanded. Bounds nested `Repeat` instructions, each of them being limited to
+/// [`MAX_REPEAT`] iterations only.
+pub const MAX_EXPANDED_INSTRUCTIONS: u32 = 1024;
+
+impl<Network, Payload, Account, Assets> Instruction<Network, Payload, Account, Assets> {
+	/// Number of instructions executed in place of this one, taking the longest branch of an
+	/// `IfElse`. The instructions of a spawned program are executed on the other network and are
+	/// not accounted for.
+	pub fn expanded_len(&self) -> u32 {
+		match self {
+			Instruction::IfElse { then, otherwise, .. } =>
+				expanded_len(then).max(expanded_len(otherwise)).saturating_add(1),
+			Instruction::Repeat { times, instructions } =>
+				times.saturating_mul(expanded_len(instructions)).saturating_add(1),
+			_ => 1,
+		}
+	}
+}
+
+/// Number of instructions executed by `instructions`, see [`Instruction::expanded_len`].
+pub fn expanded_len<Network, Payload, Account, Assets>(
+	instructions: &VecDeque<Instruction<Network, Payload, Account, Assets>>,
+) -> u32 {
+	instructions
+		.iter()
+		.fold(0_u32, |total, instruction| total.saturating_add(instruction.expanded_len()))
+}
+
+/// Condition checked by the `Assert` and `IfElse` instructions.
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Predicate<Payload> {
+	/// The balance of the interpreter is at least `amount` of `asset`.
+	#[serde(rename_all = "snake_case")]
+	BalanceAtLeast { asset: AssetId, amount: Displayed<u128> },
+	/// The balance of the interpreter is at most `amount` of `asset`.
+	#[serde(rename_all = "snake_case")]
+	BalanceAtMost { asset: AssetId, amount: Displayed<u128> },
+	/// The raw `query` inside the current network returns `expected`.
+	///
+	/// On cosmos, the query is a json `QueryRequest` and `expected` the raw response.
+	#[serde(rename_all = "snake_case")]
+	QueryEq { query: Payload, expected: Payload },
 }
@@ -4,15 +4,20 @@ extern crate alloc;
 
 mod abstraction;
 mod asset;
+mod binary;
 mod instruction;
 mod network;
 mod program;
 mod protocol;
 
-pub use crate::{asset::*, instruction::*, network::*, program::*, protocol::*};
+pub use crate::{asset::*, binary::*, instruction::*, network::*, program::*, protocol::*};
 use alloc::{collections::VecDeque, vec::Vec};
 use core::marker::PhantomData;
 
+/// A `Repeat` instruction exceeds [`MAX_REPEAT`] iterations.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct RepeatTooLarge;
+
 /// Strongly typed network builder originating on `CurrentNetwork` network.
 #[derive(Clone)]
 pub struct ProgramBuilder<CurrentNetwork: Network, Account, Assets> {
@@ -49,7 +54,7 @@ where
 		F: FnOnce(
 			ProgramBuilder<SpawningNetwork, Account, Assets>,
 		) -> Result<ProgramBuilder<FinalNetwork, Account, Assets>, E>,
-		SpawningNetwork: Network,
+		SpawningNetwork: StaticNetwork,
 		SpawningNetwork::EncodedCall: Into<Vec<u8>>,
 		FinalNetwork: Network,
 		FinalNetwork::EncodedCall: Into<Vec<u8>>,
@@ -66,9 +71,45 @@ where
 		Ok(builder)
 	}
 
+	/// Spawn a program on `network`, only known at runtime. As its encoding isn't known at compile
+	/// time, the spawned program can only execute raw calls.
+	#[inline]
+	pub fn spawn_on<E, F>(
+		mut self,
+		network: NetworkId,
+		tag: Vec<u8>,
+		salt: Vec<u8>,
+		assets: Assets,
+		f: F,
+	) -> Result<Self, E>
+	where
+		F: FnOnce(
+			ProgramBuilder<AnyNetwork, Account, Assets>,
+		) -> Result<ProgramBuilder<AnyNetwork, Account, Assets>, E>,
+	{
+		self.instructions.push_back(Instruction::Spawn {
+			salt,
+			assets,
+			network,
+			program: f(ProgramBuilder::<AnyNetwork, Account, Assets>::new(tag))?.build(),
+		});
+		Ok(self)
+	}
+
+	#[inline]
+	pub fn call_raw(self, encoded: CurrentNetwork::EncodedCall) -> Self {
+		self.call_raw_with_bindings(encoded, Vec::new())
+	}
+
+	/// Call `encoded` once the `bindings` have been interpolated by the interpreter.
 	#[inline]
-	pub fn call_raw(mut self, encoded: CurrentNetwork::EncodedCall) -> Self {
-		self.instructions.push_back(Instruction::Call { encoded: encoded.into() });
+	pub fn call_raw_with_bindings(
+		mut self,
+		encoded: CurrentNetwork::EncodedCall,
+		bindings: Bindings,
+	) -> Self {
+		self.instructions
+			.push_back(Instruction::Call { encoded: encoded.into(), bindings });
 		self
 	}
 
@@ -80,6 +121,47 @@ where
 		protocol.serialize().map(|encoded_call| self.call_raw(encoded_call))
 	}
 
+	/// Abort the program unless `predicate` holds when reached.
+	#[inline]
+	pub fn assert(mut self, predicate: Predicate<Vec<u8>>) -> Self {
+		self.instructions.push_back(Instruction::Assert { predicate });
+		self
+	}
+
+	/// Execute the instructions built by `then` if `predicate` holds when reached, the ones built
+	/// by `otherwise` if not.
+	#[inline]
+	pub fn if_else<F, G, E>(
+		mut self,
+		predicate: Predicate<Vec<u8>>,
+		then: F,
+		otherwise: G,
+	) -> Result<Self, E>
+	where
+		F: FnOnce(Self) -> Result<Self, E>,
+		G: FnOnce(Self) -> Result<Self, E>,
+	{
+		let then = then(Self::new(Vec::new()))?.instructions;
+		let otherwise = otherwise(Self::new(Vec::new()))?.instructions;
+		self.instructions.push_back(Instruction::IfElse { predicate, then, otherwise });
+		Ok(self)
+	}
+
+	/// Execute the instructions built by `f` `times` times in a row, at most [`MAX_REPEAT`].
+	#[inline]
+	pub fn repeat<F, E>(mut self, times: u32, f: F) -> Result<Self, E>
+	where
+		F: FnOnce(Self) -> Result<Self, E>,
+		E: From<RepeatTooLarge>,
+	{
+		if times > MAX_REPEAT {
+			return Err(RepeatTooLarge.into())
+		}
+		let instructions = f(Self::new(Vec::new()))?.instructions;
+		self.instructions.push_back(Instruction::Repeat { times, instructions });
+		Ok(self)
+	}
+
 	#[inline]
 	pub fn build(self) -> Program<VecDeque<Instruction<NetworkId, Vec<u8>, Account, Assets>>> {
 		Program { tag: self.tag, instructions: self.instructions }
@@ -127,6 +209,12 @@ mod tests {
 	enum ProgramBuildError {
 		DummyProtocol1(DummyProtocol1Error),
 		DummyProtocol2(DummyProtocol2Error),
+		RepeatTooLarge,
+	}
+	impl From<RepeatTooLarge> for ProgramBuildError {
+		fn from(_: RepeatTooLarge) -> Self {
+			ProgramBuildError::RepeatTooLarge
+		}
 	}
 	impl From<DummyProtocol1Error> for ProgramBuildError {
 		fn from(x: DummyProtocol1Error) -> Self {
@@ -165,7 +253,7 @@ mod tests {
 				tag: "Main program".as_bytes().to_vec(),
 				instructions: VecDeque::from([
 					// Protocol 1 on picasso
-					Instruction::Call { encoded: vec![202, 254, 190, 239] },
+					Instruction::Call { encoded: vec![202, 254, 190, 239], bindings: Vec::new() },
 					// Move to ethereum
 					Instruction::Spawn {
 						network: Ethereum::ID,
@@ -175,9 +263,15 @@ mod tests {
 							tag: Default::default(),
 							instructions: VecDeque::from([
 								// Protocol 2 on eth
-								Instruction::Call { encoded: vec![222, 173, 192, 222] },
+								Instruction::Call {
+									encoded: vec![222, 173, 192, 222],
+									bindings: Vec::new()
+								},
 								// Protocol 1 on eth, different encoding than on previous network
-								Instruction::Call { encoded: vec![192, 222, 192, 222] },
+								Instruction::Call {
+									encoded: vec![192, 222, 192, 222],
+									bindings: Vec::new()
+								},
 								Instruction::Transfer {
 									to: (),
 									assets: Funds::from(BTreeMap::from([(PICA::ID, u128::MAX)]))
@@ -189,4 +283,105 @@ mod tests {
 			},
 		);
 	}
+
+	#[test]
+	fn can_build_runtime_network() {
+		let network = NetworkId(1024);
+		let program = ProgramBuilder::<Picasso, (), Funds>::new(Vec::new())
+			.spawn_on::<ProgramBuildError, _>(
+				network,
+				Vec::new(),
+				Vec::new(),
+				Funds::empty(),
+				|child| Ok(child.call_raw(vec![0xCA, 0xFE])),
+			)
+			.expect("valid program")
+			.build();
+
+		assert_eq!(
+			program.instructions,
+			VecDeque::from([Instruction::Spawn {
+				network,
+				salt: Vec::new(),
+				assets: Funds::empty(),
+				program: Program {
+					tag: Vec::new(),
+					instructions: VecDeque::from([Instruction::Call {
+						encoded: vec![0xCA, 0xFE],
+						bindings: Vec::new()
+					}])
+				}
+			}])
+		);
+	}
+
+	#[test]
+	fn nested_repeat_expanded_len() {
+		let call = || Instruction::<NetworkId, Vec<u8>, (), Funds>::Call {
+			encoded: vec![],
+			bindings: Vec::new(),
+		};
+		let repeat = |instructions| Instruction::Repeat { times: MAX_REPEAT, instructions };
+		let nested = repeat(VecDeque::from([repeat(VecDeque::from([call()]))]));
+		assert_eq!(nested.expanded_len(), MAX_REPEAT * (MAX_REPEAT + 1) + 1);
+		assert!(expanded_len(&VecDeque::from([nested])) > MAX_EXPANDED_INSTRUCTIONS);
+
+		let if_else = Instruction::IfElse {
+			predicate: Predicate::BalanceAtLeast { asset: PICA::ID, amount: Displayed(1) },
+			then: VecDeque::from([call(), call()]),
+			otherwise: VecDeque::from([call()]),
+		};
+		assert_eq!(expanded_len(&VecDeque::from([call(), if_else])), 4);
+	}
+
+	#[test]
+	fn can_build_control_flow() {
+		let predicate = Predicate::BalanceAtLeast { asset: PICA::ID, amount: Displayed(1_000_000) };
+		let program = ProgramBuilder::<Picasso, (), Funds>::new("Swap".as_bytes().to_vec())
+			.call(DummyProtocol1)
+			.expect("valid call")
+			.assert(predicate.clone())
+			.if_else::<_, _, ProgramBuildError>(
+				predicate.clone(),
+				|then| Ok(then.transfer((), Funds::from([(PICA::ID, u128::MAX)]))),
+				|otherwise| Ok(otherwise.call(DummyProtocol2)?),
+			)
+			.and_then(|builder| {
+				builder.repeat::<_, ProgramBuildError>(2, |body| Ok(body.call(DummyProtocol1)?))
+			})
+			.expect("valid program")
+			.build();
+
+		assert_eq!(
+			program.instructions,
+			VecDeque::from([
+				Instruction::Call { encoded: vec![202, 254, 190, 239], bindings: Vec::new() },
+				Instruction::Assert { predicate: predicate.clone() },
+				Instruction::IfElse {
+					predicate,
+					then: VecDeque::from([Instruction::Transfer {
+						to: (),
+						assets: Funds::from([(PICA::ID, u128::MAX)])
+					}]),
+					otherwise: VecDeque::from([Instruction::Call {
+						encoded: vec![202, 254, 186, 190],
+						bindings: Vec::new(),
+					}]),
+				},
+				Instruction::Repeat {
+					times: 2,
+					instructions: VecDeque::from([Instruction::Call {
+						encoded: vec![202, 254, 190, 239],
+						bindings: Vec::new(),
+					}]),
+				},
+			])
+		);
+
+		assert!(matches!(
+			ProgramBuilder::<Picasso, (), Funds>::new(Vec::new())
+				.repeat::<_, ProgramBuildError>(MAX_REPEAT + 1, Ok),
+			Err(ProgramBuildError::RepeatTooLarge)
+		));
+	}
 }
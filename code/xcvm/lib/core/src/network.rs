@@ -1,5 +1,5 @@
 use crate::abstraction::IndexOf;
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
@@ -7,6 +7,11 @@ use serde::{Deserialize, Serialize};
 /// Newtype for XCVM networks ID. Must be unique for each network and must never change.
 /// This ID is an opaque, arbitrary type from the XCVM protocol and no assumption must be made on
 /// how it is computed.
+/// Networks known at compile time are listed in [`Networks`], the others are registered at
+/// runtime along their [`NetworkInfo`].
+///
+/// Network IDs used to be a `u8`. The SCALE encoding of the programs has changed with the `u32`,
+/// programs encoded with the former IDs must be encoded again.
 #[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
 #[derive(
 	Copy,
@@ -23,10 +28,10 @@ use serde::{Deserialize, Serialize};
 	Deserialize,
 )]
 #[repr(transparent)]
-pub struct NetworkId(pub u8);
+pub struct NetworkId(pub u32);
 
-impl From<u8> for NetworkId {
-	fn from(x: u8) -> Self {
+impl From<u32> for NetworkId {
+	fn from(x: u32) -> Self {
 		NetworkId(x)
 	}
 }
@@ -63,34 +68,80 @@ pub struct Juno;
 // /!\ The order matters and must not be changed, adding a network on the right is safe.
 pub type Networks = (InvalidNetwork, (Picasso, (Ethereum, (Juno, ()))));
 
-/// Type implement network must be part of [`Networks`], otherwise invalid.
+/// Network only known at runtime, its calls are raw bytes encoded as described by its
+/// [`NetworkInfo`].
+pub struct AnyNetwork;
+
+/// Network on which calls are encoded as `EncodedCall`.
 pub trait Network {
-	const ID: NetworkId;
 	type EncodedCall;
 }
 
+/// Type implement static network must be part of [`Networks`], otherwise invalid.
+pub trait StaticNetwork: Network {
+	const ID: NetworkId;
+}
+
+impl Network for AnyNetwork {
+	type EncodedCall = Vec<u8>;
+}
+
 impl Network for Picasso {
-	const ID: NetworkId = NetworkId(<Networks as IndexOf<Self, _>>::INDEX);
 	type EncodedCall = Vec<u8>;
 }
 
+impl StaticNetwork for Picasso {
+	const ID: NetworkId = NetworkId(<Networks as IndexOf<Self, _>>::INDEX as u32);
+}
+
 impl Network for Ethereum {
-	const ID: NetworkId = NetworkId(<Networks as IndexOf<Self, _>>::INDEX);
 	type EncodedCall = Vec<u8>;
 }
 
+impl StaticNetwork for Ethereum {
+	const ID: NetworkId = NetworkId(<Networks as IndexOf<Self, _>>::INDEX as u32);
+}
+
 impl Network for Juno {
-	const ID: NetworkId = NetworkId(<Networks as IndexOf<Self, _>>::INDEX);
 	type EncodedCall = Vec<u8>;
 }
 
+impl StaticNetwork for Juno {
+	const ID: NetworkId = NetworkId(<Networks as IndexOf<Self, _>>::INDEX as u32);
+}
+
+/// Encoding of the calls of a network.
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallEncoding {
+	/// SCALE encoded calls of Substrate based networks.
+	Scale,
+	/// JSON encoded messages of CosmWasm based networks.
+	Json,
+	/// ABI encoded calls of EVM based networks.
+	Abi,
+}
+
+/// Metadata of a network registered at runtime.
+#[cfg_attr(feature = "std", derive(schemars::JsonSchema))]
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+pub struct NetworkInfo {
+	/// Human readable name of the network.
+	pub name: String,
+	/// Encoding of the calls of the programs spawned on the network.
+	pub call_encoding: CallEncoding,
+	/// Address of the XCVM gateway of the network, in the format of the network.
+	pub gateway_address: String,
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	#[test]
 	fn network_ids() {
-		assert_eq!(Picasso::ID, NetworkId(1u8));
-		assert_eq!(Ethereum::ID, NetworkId(2u8));
-		assert_eq!(Juno::ID, NetworkId(3u8));
+		assert_eq!(Picasso::ID, NetworkId(1));
+		assert_eq!(Ethereum::ID, NetworkId(2));
+		assert_eq!(Juno::ID, NetworkId(3));
 	}
 }
@@ -0,0 +1,119 @@
+use crate::ProtocolError;
+use alloc::{string::String, vec, vec::Vec};
+use serde::Serialize;
+use xcvm_core::{Displayed, Juno, Network, Protocol};
+
+/// Token of a Junoswap pool.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+pub enum TokenSelect {
+	Token1,
+	Token2,
+}
+
+/// Swap `input_amount` of the `input_token` for at least `min_output` of the other token of the
+/// Junoswap pool at `pool_address`. The input token must be the native `input_denom`, sent along
+/// the call, as a CW20 input would have to be approved to the pool in a separate call.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct JunoswapSwap {
+	pub pool_address: String,
+	pub input_token: TokenSelect,
+	pub input_denom: String,
+	pub input_amount: u128,
+	pub min_output: u128,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum JunoswapExecuteMsg {
+	Swap {
+		input_token: TokenSelect,
+		input_amount: Displayed<u128>,
+		min_output: Displayed<u128>,
+		expiration: Option<()>,
+	},
+}
+
+/// Subset of the `CosmosMsg` of CosmWasm, executed by the interpreter.
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CosmosMsg {
+	Wasm(WasmMsg),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum WasmMsg {
+	Execute {
+		contract_addr: String,
+		/// Base64 encoded JSON message.
+		msg: String,
+		funds: Vec<Coin>,
+	},
+}
+
+#[derive(Serialize)]
+struct Coin {
+	denom: String,
+	amount: Displayed<u128>,
+}
+
+impl Protocol<Juno> for JunoswapSwap {
+	type Error = ProtocolError;
+	fn serialize(&self) -> Result<<Juno as Network>::EncodedCall, Self::Error> {
+		let msg = serde_json::to_vec(&JunoswapExecuteMsg::Swap {
+			input_token: self.input_token,
+			input_amount: Displayed(self.input_amount),
+			min_output: Displayed(self.min_output),
+			expiration: None,
+		})
+		.map_err(|_| ProtocolError::SerializationFailed)?;
+		serde_json::to_vec(&CosmosMsg::Wasm(WasmMsg::Execute {
+			contract_addr: self.pool_address.clone(),
+			msg: base64::encode(msg),
+			funds: vec![Coin {
+				denom: self.input_denom.clone(),
+				amount: Displayed(self.input_amount),
+			}],
+		}))
+		.map_err(|_| ProtocolError::SerializationFailed)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cosmwasm_std::{coins, from_slice};
+
+	#[test]
+	fn junoswap_swap() {
+		let swap = JunoswapSwap {
+			pool_address: "pool".into(),
+			input_token: TokenSelect::Token1,
+			input_denom: "ujuno".into(),
+			input_amount: 1_000,
+			min_output: 900,
+		};
+
+		// The call must be a valid `CosmosMsg` for the interpreter
+		match from_slice(&swap.serialize().unwrap()).unwrap() {
+			cosmwasm_std::CosmosMsg::<cosmwasm_std::Empty>::Wasm(
+				cosmwasm_std::WasmMsg::Execute { contract_addr, msg, funds },
+			) => {
+				assert_eq!(contract_addr, "pool");
+				assert_eq!(funds, coins(1_000, "ujuno"));
+				assert_eq!(
+					serde_json::from_slice::<serde_json::Value>(msg.as_slice()).unwrap(),
+					serde_json::json!({
+						"swap": {
+							"input_token": "Token1",
+							"input_amount": "1000",
+							"min_output": "900",
+							"expiration": null,
+						}
+					})
+				);
+			},
+			_ => panic!("Expected a wasm execute message"),
+		}
+	}
+}
@@ -0,0 +1,71 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod juno;
+mod picasso;
+
+pub use crate::{juno::*, picasso::*};
+
+/// Error raised while encoding the call of a protocol.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ProtocolError {
+	/// The call could not be serialized to the encoding of the network.
+	SerializationFailed,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::{collections::VecDeque, vec, vec::Vec};
+	use xcvm_core::{Asset, Funds, Instruction, Juno, Picasso, ProgramBuilder, Protocol, PICA};
+
+	#[test]
+	fn can_build_cross_chain_swap() {
+		let pablo_swap = PabloSwap {
+			pallet: PabloPallet::DALI,
+			pool_id: 1,
+			in_asset: PabloAssetAmount { asset_id: 1, amount: 1_000 },
+			min_receive: PabloAssetAmount { asset_id: 4, amount: 900 },
+			keep_alive: true,
+		};
+		let junoswap_swap = JunoswapSwap {
+			pool_address: "pool".into(),
+			input_token: TokenSelect::Token2,
+			input_denom: "ibc/usdc".into(),
+			input_amount: 900,
+			min_output: 800,
+		};
+
+		let program = ProgramBuilder::<Picasso, (), Funds>::new(Vec::new())
+			.call(pablo_swap)
+			.and_then(|builder| {
+				builder.spawn::<Juno, Juno, ProtocolError, _>(
+					Vec::new(),
+					Vec::new(),
+					Funds::from([(PICA::ID, 900_u128)]),
+					|child| child.call(junoswap_swap.clone()),
+				)
+			})
+			.expect("valid program")
+			.build();
+
+		assert_eq!(
+			program.instructions[0],
+			Instruction::Call {
+				encoded: Protocol::<Picasso>::serialize(&pablo_swap).unwrap(),
+				bindings: Vec::new()
+			}
+		);
+		match &program.instructions[1] {
+			Instruction::Spawn { program, .. } => assert_eq!(
+				program.instructions,
+				VecDeque::from([Instruction::Call {
+					encoded: Protocol::<Juno>::serialize(&junoswap_swap).unwrap(),
+					bindings: vec![]
+				}])
+			),
+			_ => panic!("Expected a spawn instruction"),
+		}
+	}
+}
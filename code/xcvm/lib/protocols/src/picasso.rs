@@ -0,0 +1,114 @@
+use crate::ProtocolError;
+use alloc::collections::BTreeMap;
+use codec::Encode;
+use xcvm_core::{Network, Picasso, Protocol};
+
+/// Location of the Pablo pallet and of its calls in a runtime, which differs across runtimes.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct PabloPallet {
+	/// Index of the pallet in the `construct_runtime!` of the runtime.
+	pub pallet_index: u8,
+	pub swap_call_index: u8,
+	pub add_liquidity_call_index: u8,
+}
+
+impl PabloPallet {
+	/// Pablo pallet of the Dali runtime, Picasso doesn't include Pablo yet.
+	pub const DALI: PabloPallet =
+		PabloPallet { pallet_index: 65, swap_call_index: 2, add_liquidity_call_index: 3 };
+}
+
+/// Amount of a local asset of the runtime, SCALE compatible with
+/// `composable_traits::dex::AssetAmount`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Encode)]
+pub struct PabloAssetAmount {
+	pub asset_id: u128,
+	pub amount: u128,
+}
+
+/// Swap `in_asset` for at least `min_receive` on the Pablo pool `pool_id`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct PabloSwap {
+	pub pallet: PabloPallet,
+	pub pool_id: u128,
+	pub in_asset: PabloAssetAmount,
+	pub min_receive: PabloAssetAmount,
+	pub keep_alive: bool,
+}
+
+/// Provide the `assets` to the Pablo pool `pool_id`, minting at least `min_mint_amount` LP tokens.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PabloAddLiquidity {
+	pub pallet: PabloPallet,
+	pub pool_id: u128,
+	pub assets: BTreeMap<u128, u128>,
+	pub min_mint_amount: u128,
+	pub keep_alive: bool,
+}
+
+impl Protocol<Picasso> for PabloSwap {
+	type Error = ProtocolError;
+	fn serialize(&self) -> Result<<Picasso as Network>::EncodedCall, Self::Error> {
+		let PabloSwap { pallet, pool_id, in_asset, min_receive, keep_alive } = *self;
+		Ok((
+			pallet.pallet_index,
+			pallet.swap_call_index,
+			pool_id,
+			in_asset,
+			min_receive,
+			keep_alive,
+		)
+			.encode())
+	}
+}
+
+impl Protocol<Picasso> for PabloAddLiquidity {
+	type Error = ProtocolError;
+	fn serialize(&self) -> Result<<Picasso as Network>::EncodedCall, Self::Error> {
+		let PabloAddLiquidity { pallet, pool_id, assets, min_mint_amount, keep_alive } = self;
+		Ok((
+			pallet.pallet_index,
+			pallet.add_liquidity_call_index,
+			pool_id,
+			assets,
+			min_mint_amount,
+			keep_alive,
+		)
+			.encode())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn pablo_calls() {
+		let in_asset = PabloAssetAmount { asset_id: 1, amount: 1_000 };
+		let min_receive = PabloAssetAmount { asset_id: 4, amount: 900 };
+		assert_eq!(
+			PabloSwap {
+				pallet: PabloPallet::DALI,
+				pool_id: 7,
+				in_asset,
+				min_receive,
+				keep_alive: true
+			}
+			.serialize(),
+			Ok((65_u8, 2_u8, 7_u128, (1_u128, 1_000_u128), (4_u128, 900_u128), true).encode())
+		);
+
+		let assets = BTreeMap::from([(1, 1_000), (4, 2_000)]);
+		assert_eq!(
+			PabloAddLiquidity {
+				pallet: PabloPallet::DALI,
+				pool_id: 7,
+				assets: assets.clone(),
+				min_mint_amount: 10,
+				keep_alive: false
+			}
+			.serialize(),
+			Ok((65_u8, 3_u8, 7_u128, assets, 10_u128, false).encode())
+		);
+	}
+}